@@ -24,5 +24,8 @@ fn main() {
         x11_platform: { all(feature = "x11", free_unix, not(wasm), not(redox)) },
         wayland_platform: { all(feature = "wayland", free_unix, not(wasm), not(redox)) },
         orbital_platform: { redox },
+
+        // Features.
+        automation: { all(feature = "automation", unix) },
     }
 }
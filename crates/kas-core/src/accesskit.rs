@@ -7,9 +7,12 @@
 
 use crate::cast::Cast;
 use crate::geom::Offset;
+use crate::text::CursorRange;
 use crate::window::{POPUP_INNER_INDEX, WindowErased};
-use crate::{Role, RoleCx, TextOrSource, Tile, TileExt};
-use accesskit::{Action, Node, NodeId};
+use crate::{Id, Live, Relation, Role, RoleCx, TextOrSource, TextRun, Tile, TileExt};
+use accesskit::{Action, Node, NodeId, TextDirection, TextPosition, TextSelection};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use unicode_segmentation::UnicodeSegmentation;
 
 pub(crate) fn apply_scroll_props_to_node(offset: Offset, max_offset: Offset, node: &mut Node) {
     if offset.1 < max_offset.1 {
@@ -38,6 +41,13 @@ pub(crate) fn apply_scroll_props_to_node(offset: Offset, max_offset: Offset, nod
 struct WalkCx {
     label: Option<String>,
     labelled_by: Option<NodeId>,
+    description: Option<String>,
+    described_by: Vec<NodeId>,
+    controls: Vec<NodeId>,
+    owns: Vec<NodeId>,
+    error_message: Option<NodeId>,
+    live: Option<Live>,
+    text_runs: Vec<TextRun>,
 }
 
 impl WalkCx {
@@ -47,6 +57,24 @@ impl WalkCx {
         } else if let Some(id) = self.labelled_by.take() {
             node.set_labelled_by(vec![id]);
         }
+        if let Some(description) = self.description.take() {
+            node.set_description(description);
+        }
+        if !self.described_by.is_empty() {
+            node.set_described_by(self.described_by);
+        }
+        if !self.controls.is_empty() {
+            node.set_controls(self.controls);
+        }
+        if !self.owns.is_empty() {
+            node.set_owns(self.owns);
+        }
+        if let Some(id) = self.error_message {
+            node.set_error_message(id);
+        }
+        if let Some(live) = self.live {
+            node.set_live(live.into());
+        }
     }
 }
 
@@ -58,6 +86,162 @@ impl RoleCx for WalkCx {
             TextOrSource::Source(id) => self.labelled_by = Some(id.into()),
         }
     }
+
+    fn set_description_impl(&mut self, description: TextOrSource<'_>) {
+        match description {
+            TextOrSource::Borrowed(s) => self.description = Some(s.to_string()),
+            TextOrSource::Owned(s) => self.description = Some(s),
+            TextOrSource::Source(id) => self.described_by.push(id.into()),
+        }
+    }
+
+    fn add_relation(&mut self, relation: Relation, target: Id) {
+        let id = target.into();
+        match relation {
+            Relation::DescribedBy => self.described_by.push(id),
+            Relation::Controls => self.controls.push(id),
+            Relation::Owns => self.owns.push(id),
+            Relation::ErrorMessage => self.error_message = Some(id),
+        }
+    }
+
+    fn set_live(&mut self, live: Live) {
+        self.live = Some(live);
+    }
+
+    fn set_text_runs(&mut self, runs: Vec<TextRun>) {
+        self.text_runs = runs;
+    }
+}
+
+/// Kinds of node which are not widgets (see [`derived_node_id`])
+#[derive(Clone, Copy, Hash)]
+enum Derived {
+    TextRun,
+    AnnouncementRegion,
+    Announcement,
+}
+
+/// Get the [`NodeId`] of a node which is not a widget
+///
+/// The result is a hash of `id`, `kind` and `index` with the low two bits
+/// clear. Such values are never the result of [`Id::to_nzu64`], thus cannot
+/// collide with the node of a widget. Unlike [`Id::make_child`], this does not
+/// allocate.
+fn derived_node_id(id: &Id, kind: Derived, index: usize) -> NodeId {
+    let mut hasher = DefaultHasher::new();
+    (id.to_nzu64(), kind, index).hash(&mut hasher);
+    NodeId((hasher.finish() & !3).max(4))
+}
+
+/// Get the [`NodeId`] of text run `index` of `tile`
+fn text_run_id(tile: &dyn Tile, index: usize) -> NodeId {
+    derived_node_id(tile.id_ref(), Derived::TextRun, index)
+}
+
+/// Find the position of text `index` within `runs`
+fn text_position(run_ids: &[NodeId], runs: &[TextRun], text: &str, index: usize) -> TextPosition {
+    // Where a run ends at index, the next run starts there: prefer the latter
+    let i = runs
+        .iter()
+        .rposition(|run| run.range.start <= index)
+        .unwrap_or(0);
+    let range = &runs[i].range;
+    let end = index.clamp(range.start, range.end);
+    let character_index = text
+        .get(range.start..end)
+        .map(|s| s.chars().count())
+        .unwrap_or(0);
+    TextPosition {
+        node: run_ids[i],
+        character_index,
+    }
+}
+
+/// Push a node for each of `runs`, returning their identifiers
+///
+/// This also sets the text selection on `node`.
+fn push_text_runs(
+    tile: &dyn Tile,
+    text: &str,
+    cursor: CursorRange,
+    runs: &[TextRun],
+    node: &mut Node,
+    nodes: &mut Vec<(NodeId, Node)>,
+) -> Vec<NodeId> {
+    let run_ids: Vec<NodeId> = (0..runs.len()).map(|i| text_run_id(tile, i)).collect();
+
+    for (run, id) in runs.iter().zip(run_ids.iter()) {
+        let Some(value) = text.get(run.range.clone()) else {
+            continue;
+        };
+
+        let mut run_node = Node::new(accesskit::Role::TextRun);
+        run_node.set_bounds(run.rect.cast());
+        run_node.set_value(value);
+        let lengths: Vec<u8> = value.chars().map(|c| c.len_utf8().cast()).collect();
+        run_node.set_character_lengths(lengths);
+        run_node.set_character_positions(run.positions.clone());
+        run_node.set_character_widths(run.widths.clone());
+
+        let mut words: Vec<u8> = vec![];
+        for word in value.split_word_bounds() {
+            let mut len = word.chars().count();
+            while len > 0 {
+                let n = len.min(u8::MAX.into());
+                words.push(n.cast());
+                len -= n;
+            }
+        }
+        run_node.set_word_lengths(words);
+
+        run_node.set_text_direction(match run.rtl {
+            false => TextDirection::LeftToRight,
+            true => TextDirection::RightToLeft,
+        });
+        nodes.push((*id, run_node));
+    }
+
+    node.set_text_selection(TextSelection {
+        anchor: text_position(&run_ids, runs, text, cursor.sel_index()),
+        focus: text_position(&run_ids, runs, text, cursor.edit_index()),
+    });
+
+    run_ids
+}
+
+/// Resolve an AccessKit text selection over a [`Role::TextInput`] `tile`
+pub(crate) fn text_selection_range(
+    tile: &dyn Tile,
+    selection: &TextSelection,
+) -> Option<CursorRange> {
+    let mut cx = WalkCx::default();
+    let Role::TextInput {
+        text,
+        protected: false,
+        ..
+    } = tile.role(&mut cx)
+    else {
+        return None;
+    };
+    let runs = cx.text_runs;
+
+    let index = |pos: &TextPosition| -> Option<usize> {
+        let i = (0..runs.len()).find(|i| text_run_id(tile, *i) == pos.node)?;
+        let range = runs[i].range.clone();
+        let run_text = text.get(range.clone())?;
+        Some(
+            run_text
+                .char_indices()
+                .nth(pos.character_index)
+                .map(|(i, _)| range.start + i)
+                .unwrap_or(range.end),
+        )
+    };
+
+    let sel_index = index(&selection.anchor)?;
+    let edit_index = index(&selection.focus)?;
+    Some(CursorRange::new(sel_index, edit_index))
 }
 
 fn push_child(
@@ -71,16 +255,34 @@ fn push_child(
         let role = child.role(&mut cx);
         let child_is_scrollable = matches!(role, Role::ScrollRegion { .. });
         parent.role_child_properties(&mut cx, index);
+        if cx.description.is_none()
+            && let Some(tooltip) = child.tooltip()
+        {
+            cx.description = Some(tooltip.to_string());
+        }
 
         let mut node = role.as_accesskit_node(child);
+        let runs = std::mem::take(&mut cx.text_runs);
+        let mut children = match role {
+            Role::TextInput {
+                text,
+                protected: false,
+                cursor,
+                ..
+            } if !runs.is_empty() => push_text_runs(child, text, cursor, &runs, &mut node, nodes),
+            _ => vec![],
+        };
         cx.apply_to_node(&mut node);
 
         if has_scrollable_parent {
             node.add_action(Action::ScrollIntoView);
         }
 
-        let children =
-            push_all_children(child, nodes, has_scrollable_parent || child_is_scrollable);
+        children.extend(push_all_children(
+            child,
+            nodes,
+            has_scrollable_parent || child_is_scrollable,
+        ));
         if !children.is_empty() {
             node.set_children(children);
         }
@@ -127,3 +329,65 @@ pub(crate) fn window_nodes(root: &dyn WindowErased) -> (Vec<(NodeId, Node)>, Nod
     nodes.push((root_id, node));
     (nodes, root_id)
 }
+
+/// Set the live property on nodes in `live_regions`
+pub(crate) fn apply_live_regions<'a>(
+    nodes: &mut [(NodeId, Node)],
+    live_regions: impl Iterator<Item = (&'a Id, &'a Live)>,
+) {
+    for (id, live) in live_regions {
+        let id = NodeId::from(id);
+        if let Some((_, node)) = nodes.iter_mut().find(|(node_id, _)| *node_id == id) {
+            node.set_live((*live).into());
+        }
+    }
+}
+
+/// Push a live region containing `text` as a child of the window
+///
+/// A new node is used for each `serial` number, thus repeated announcement of
+/// the same `text` is not ignored.
+pub(crate) fn push_announcement(
+    window: &Id,
+    serial: usize,
+    text: &str,
+    live: Live,
+    nodes: &mut Vec<(NodeId, Node)>,
+) {
+    let region_id = derived_node_id(window, Derived::AnnouncementRegion, 0);
+    let text_id = derived_node_id(window, Derived::Announcement, serial);
+    let mut node = Node::new(accesskit::Role::Label);
+    node.set_value(text);
+    nodes.push((text_id, node));
+
+    let mut node = Node::new(accesskit::Role::GenericContainer);
+    node.set_live(live.into());
+    node.set_children(vec![text_id]);
+    nodes.push((region_id, node));
+
+    let window_id = NodeId::from(window);
+    if let Some((_, node)) = nodes.iter_mut().find(|(id, _)| *id == window_id) {
+        node.push_child(region_id);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn derived_node_ids() {
+        let id = Id::ROOT.make_child(3).make_child(1);
+        let a = derived_node_id(&id, Derived::TextRun, 0);
+        let b = derived_node_id(&id, Derived::TextRun, 1);
+        let c = derived_node_id(&id, Derived::Announcement, 0);
+        assert_eq!(a, derived_node_id(&id, Derived::TextRun, 0));
+        assert!(a != b && a != c && b != c);
+
+        for node in [a, b, c] {
+            assert_eq!(node.0 & 3, 0);
+            assert!(Id::try_from_u64(node.0).is_none());
+            assert_ne!(node, NodeId::from(&id));
+        }
+    }
+}
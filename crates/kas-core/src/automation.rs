@@ -0,0 +1,680 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! UI automation over a local socket
+//!
+//! This module requires feature `automation` and is only available on Unix
+//! platforms. It is intended for end-to-end testing and scripting.
+//!
+//! When the `KAS_AUTOMATION_SOCKET` environment variable is set, the runner
+//! listens on a Unix domain socket at this path. The socket is accessible only
+//! by the current user. Clients may connect and send requests, one [`Request`]
+//! per line encoded as JSON; each request is answered by a single line
+//! containing a JSON-encoded [`Response`]. Clients are served one at a time:
+//! further connections wait until the current client disconnects.
+//!
+//! Widgets are identified by the [`Display`](std::fmt::Display) form of their
+//! [`Id`] (e.g. `"#1a"`) and described by [`WidgetInfo`], including their
+//! [`Role`] and its properties. Messages supported by a widget are documented
+//! on its [`Role`].
+//!
+//! Example session (`→` is a request and `←` a response):
+//! ```json
+//! → {"op":"query","role":"Button","label":"Save"}
+//! ← {"Widgets":[{"window":1,"id":"#1211","role":"Button",...}]}
+//! → {"op":"send","id":"#1211","message":"Activate"}
+//! ← "Ok"
+//! → {"op":"input","input":{"Click":{"x":40.0,"y":20.0}}}
+//! ← "Ok"
+//! ```
+
+use crate::event::{Command, EventState};
+use crate::geom::Offset;
+use crate::messages::{self, Erased};
+use crate::runner::Proxy;
+use crate::window::{POPUP_INNER_INDEX, WindowErased, WindowId};
+use crate::{Id, Role, RoleCx, TextOrSource, Tile, TileExt};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value, json};
+use std::io::{self, BufRead, BufReader, Write};
+use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+
+/// Environment variable giving the path of the socket
+pub const SOCKET_VAR: &str = "KAS_AUTOMATION_SOCKET";
+
+/// An automation request
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum Request {
+    /// List open windows
+    ///
+    /// Response: [`Response::Windows`]
+    Windows,
+    /// Find widgets matching all given criteria
+    ///
+    /// Response: [`Response::Widgets`]
+    Query {
+        /// Only search this window
+        window: Option<u32>,
+        /// Match the name of the [`Role`] (case insensitive), e.g. `"Button"`
+        role: Option<String>,
+        /// Match the widget's label or text exactly
+        label: Option<String>,
+        /// Match the widget's [`Id`]
+        id: Option<String>,
+    },
+    /// Send a message to widget `id`
+    ///
+    /// Response: [`Response::Ok`] if the widget was found.
+    Send {
+        /// Target widget
+        id: String,
+        /// The message
+        message: Message,
+    },
+    /// Synthesize input to a window
+    ///
+    /// Response: [`Response::Ok`] if the window was found.
+    Input {
+        /// Target window (default: the first open window)
+        window: Option<u32>,
+        /// The input
+        input: Input,
+    },
+}
+
+/// A message, from the standard set of [`kas::messages`](crate::messages)
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum Message {
+    /// [`messages::Activate`]
+    Activate,
+    /// [`messages::IncrementStep`]
+    IncrementStep,
+    /// [`messages::DecrementStep`]
+    DecrementStep,
+    /// [`messages::SetValueF64`]
+    SetValueF64(f64),
+    /// [`messages::SetValueText`]
+    SetValueText(String),
+    /// [`messages::ReplaceSelectedText`]
+    ReplaceSelectedText(String),
+    /// [`messages::SetIndex`]
+    SetIndex(usize),
+    /// [`messages::Expand`]
+    Expand,
+    /// [`messages::Collapse`]
+    Collapse,
+    /// [`messages::Select`]
+    Select,
+    /// [`messages::SetScrollOffset`]
+    SetScrollOffset(i32, i32),
+}
+
+impl Message {
+    pub(crate) fn into_erased(self) -> Erased {
+        match self {
+            Message::Activate => Erased::new(messages::Activate(None)),
+            Message::IncrementStep => Erased::new(messages::IncrementStep),
+            Message::DecrementStep => Erased::new(messages::DecrementStep),
+            Message::SetValueF64(value) => Erased::new(messages::SetValueF64(value)),
+            Message::SetValueText(text) => Erased::new(messages::SetValueText(text)),
+            Message::ReplaceSelectedText(text) => Erased::new(messages::ReplaceSelectedText(text)),
+            Message::SetIndex(index) => Erased::new(messages::SetIndex(index)),
+            Message::Expand => Erased::new(messages::Expand),
+            Message::Collapse => Erased::new(messages::Collapse),
+            Message::Select => Erased::new(messages::Select),
+            Message::SetScrollOffset(x, y) => Erased::new(messages::SetScrollOffset(Offset(x, y))),
+        }
+    }
+}
+
+/// A mouse button
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Button {
+    /// The primary button
+    #[default]
+    Left,
+    /// The secondary button
+    Right,
+    /// The middle button
+    Middle,
+}
+
+impl From<Button> for winit::event::MouseButton {
+    fn from(button: Button) -> Self {
+        match button {
+            Button::Left => winit::event::MouseButton::Left,
+            Button::Right => winit::event::MouseButton::Right,
+            Button::Middle => winit::event::MouseButton::Middle,
+        }
+    }
+}
+
+/// Synthesized input
+///
+/// Coordinates are physical pixels relative to the window's top-left corner.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum Input {
+    /// Move the mouse pointer
+    PointerMove {
+        /// Horizontal position
+        x: f64,
+        /// Vertical position
+        y: f64,
+    },
+    /// Press or release a mouse button at the current pointer position
+    PointerButton {
+        /// The button
+        button: Button,
+        /// True to press, false to release
+        pressed: bool,
+    },
+    /// Move the mouse pointer then click the left button
+    Click {
+        /// Horizontal position
+        x: f64,
+        /// Vertical position
+        y: f64,
+    },
+    /// Scroll under the mouse pointer by the given number of lines
+    Scroll {
+        /// Horizontal lines
+        x: f32,
+        /// Vertical lines
+        y: f32,
+    },
+    /// A command, as if triggered by its keyboard shortcut
+    Command(Command),
+    /// Type text into the widget with selection or navigation focus
+    Text(String),
+}
+
+/// An automation response
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum Response {
+    /// The request was handled
+    Ok,
+    /// Open windows
+    Windows(Vec<WindowInfo>),
+    /// Matching widgets, in depth-first order
+    Widgets(Vec<WidgetInfo>),
+    /// The request failed
+    Error(String),
+}
+
+/// Description of a window
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WindowInfo {
+    /// Window identifier
+    pub id: u32,
+    /// Window title
+    pub title: String,
+}
+
+/// Description of a widget
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WidgetInfo {
+    /// Window identifier
+    pub window: u32,
+    /// Widget identifier
+    pub id: String,
+    /// Name of the widget's [`Role`] (e.g. `"Button"`)
+    pub role: String,
+    /// Properties of the widget's [`Role`] (fields, by name)
+    pub properties: Map<String, Value>,
+    /// The widget's label, if set or inferred from children
+    pub label: Option<String>,
+    /// The widget's description, if any
+    pub description: Option<String>,
+    /// Widget rect as `(x, y, w, h)` in window coordinates
+    pub rect: (i32, i32, i32, i32),
+    /// True if the widget is disabled
+    pub disabled: bool,
+    /// True if the widget has navigation focus
+    pub nav_focus: bool,
+}
+
+impl WidgetInfo {
+    /// Get the widget's text (as in [`Role::Label`]), if any
+    fn text(&self) -> Option<&str> {
+        self.properties.get("text").and_then(Value::as_str)
+    }
+
+    pub(crate) fn matches(
+        &self,
+        role: Option<&str>,
+        label: Option<&str>,
+        id: Option<&str>,
+    ) -> bool {
+        role.is_none_or(|role| self.role.eq_ignore_ascii_case(role))
+            && label.is_none_or(|label| {
+                self.label.as_deref() == Some(label) || self.text() == Some(label)
+            })
+            && id.is_none_or(|id| self.id == id)
+    }
+}
+
+#[derive(Default)]
+struct WalkCx {
+    label: Option<String>,
+    labelled_by: Option<Id>,
+    description: Option<String>,
+}
+
+impl RoleCx for WalkCx {
+    fn set_label_impl(&mut self, label: TextOrSource<'_>) {
+        match label {
+            TextOrSource::Borrowed(s) => self.label = Some(s.to_string()),
+            TextOrSource::Owned(s) => self.label = Some(s),
+            TextOrSource::Source(id) => self.labelled_by = Some(id),
+        }
+    }
+
+    fn set_description_impl(&mut self, description: TextOrSource<'_>) {
+        match description {
+            TextOrSource::Borrowed(s) => self.description = Some(s.to_string()),
+            TextOrSource::Owned(s) => self.description = Some(s),
+            TextOrSource::Source(_) => (),
+        }
+    }
+}
+
+/// Get the name of a [`Role`] variant
+fn role_name(role: &Role) -> String {
+    let mut name = format!("{role:?}");
+    if let Some(index) = name.find(|c: char| !c.is_alphanumeric()) {
+        name.truncate(index);
+    }
+    name
+}
+
+/// Get the fields of a [`Role`] variant
+///
+/// The text of protected inputs is omitted.
+fn role_properties(role: &Role) -> Map<String, Value> {
+    let value = match *role {
+        Role::Label(text) => json!({ "text": text }),
+        Role::AccessLabel(text, ref key) => json!({ "text": text, "access_key": key.to_text() }),
+        Role::CheckBox(checked) | Role::RadioButton(checked) => json!({ "checked": checked }),
+        Role::ScrollRegion { offset, max_offset } => json!({
+            "offset": [offset.0, offset.1],
+            "max_offset": [max_offset.0, max_offset.1],
+        }),
+        Role::ScrollBar {
+            direction,
+            value,
+            max_value,
+        } => json!({
+            "direction": format!("{direction:?}"),
+            "value": value,
+            "max_value": max_value,
+        }),
+        Role::TextLabel {
+            text,
+            cursor,
+            sel_index,
+        } => json!({ "text": text, "cursor": cursor, "sel_index": sel_index }),
+        Role::TextInput {
+            text,
+            multi_line,
+            protected,
+            cursor,
+        } => {
+            let mut value = json!({ "multi_line": multi_line, "protected": protected });
+            if !protected {
+                value["text"] = text.into();
+                value["cursor"] = cursor.edit_index().into();
+                value["sel_index"] = cursor.sel_index().into();
+            }
+            value
+        }
+        Role::Slider {
+            min,
+            max,
+            step,
+            value,
+            direction,
+        } => json!({
+            "min": min,
+            "max": max,
+            "step": step,
+            "value": value,
+            "direction": format!("{direction:?}"),
+        }),
+        Role::SpinButton {
+            min,
+            max,
+            step,
+            value,
+        } => json!({ "min": min, "max": max, "step": step, "value": value }),
+        Role::ProgressBar {
+            fraction,
+            direction,
+        } => json!({ "fraction": fraction, "direction": format!("{direction:?}") }),
+        Role::OptionList { len, direction } => {
+            json!({ "len": len, "direction": format!("{direction:?}") })
+        }
+        Role::OptionListItem { index, selected } => {
+            json!({ "index": index, "selected": selected })
+        }
+        Role::Grid { columns, rows } => json!({ "columns": columns, "rows": rows }),
+        Role::GridCell { info, selected } => json!({
+            "cell": info.map(|info| [info.col, info.last_col, info.row, info.last_row]),
+            "selected": selected,
+        }),
+        Role::ColumnHeader { info } | Role::RowHeader { info } => json!({
+            "cell": info.map(|info| [info.col, info.last_col, info.row, info.last_row]),
+        }),
+        Role::TreeItem {
+            level,
+            expanded,
+            selected,
+        } => json!({ "level": level, "expanded": expanded, "selected": selected }),
+        Role::Heading { level } => json!({ "level": level }),
+        Role::Link { url } => json!({ "url": url }),
+        Role::Menu { expanded } | Role::Disclosure { expanded } => {
+            json!({ "expanded": expanded })
+        }
+        Role::ComboBox {
+            active,
+            text,
+            expanded,
+        } => json!({ "active": active, "text": text, "expanded": expanded }),
+        Role::Notification { urgent } => json!({ "urgent": urgent }),
+        _ => return Map::new(),
+    };
+
+    match value {
+        Value::Object(map) => map,
+        _ => unreachable!(),
+    }
+}
+
+/// Get the text of label-like roles
+fn role_text<'a>(role: &Role<'a>) -> Option<&'a str> {
+    match *role {
+        Role::Label(text) | Role::AccessLabel(text, _) | Role::TextLabel { text, .. } => Some(text),
+        _ => None,
+    }
+}
+
+/// True for roles which infer a label from their children
+fn infers_label(role: &Role) -> bool {
+    matches!(
+        role,
+        Role::Button
+            | Role::CheckBox(_)
+            | Role::RadioButton(_)
+            | Role::Tab
+            | Role::Link { .. }
+            | Role::Menu { .. }
+            | Role::Disclosure { .. }
+            | Role::OptionListItem { .. }
+            | Role::GridCell { .. }
+            | Role::TreeItem { .. }
+            | Role::Heading { .. }
+    )
+}
+
+struct Walk<'a> {
+    window: u32,
+    ev_state: &'a EventState,
+    widgets: Vec<WidgetInfo>,
+    labelled_by: Vec<(usize, Id)>,
+}
+
+impl Walk<'_> {
+    /// Push `tile` and its descendants, returning the first text found
+    fn push(
+        &mut self,
+        tile: &dyn Tile,
+        parent: Option<(&dyn Tile, usize)>,
+        translation: Offset,
+    ) -> Option<String> {
+        let mut cx = WalkCx::default();
+        let role = tile.role(&mut cx);
+        if let Some((parent, index)) = parent {
+            parent.role_child_properties(&mut cx, index);
+        }
+        if cx.description.is_none()
+            && let Some(tooltip) = tile.tooltip()
+        {
+            cx.description = Some(tooltip.to_string());
+        }
+
+        let id = tile.id();
+        let rect = tile.rect() - translation;
+        let index = self.widgets.len();
+        if let Some(source) = cx.labelled_by {
+            self.labelled_by.push((index, source));
+        }
+        self.widgets.push(WidgetInfo {
+            window: self.window,
+            id: id.to_string(),
+            role: role_name(&role),
+            properties: role_properties(&role),
+            label: cx.label,
+            description: cx.description,
+            rect: (rect.pos.0, rect.pos.1, rect.size.0, rect.size.1),
+            disabled: self.ev_state.is_disabled(&id),
+            nav_focus: self.ev_state.has_nav_focus(&id),
+        });
+
+        let mut text = role_text(&role).map(|text| text.to_string());
+        for child_index in tile.child_indices() {
+            if let Some(child) = tile.get_child(child_index) {
+                let translation = translation + tile.translation(child_index);
+                let child_text = self.push(child, Some((tile, child_index)), translation);
+                text = text.or(child_text);
+            }
+        }
+
+        if infers_label(&role) && self.widgets[index].label.is_none() {
+            self.widgets[index].label = text.clone();
+        }
+        text
+    }
+
+    /// Resolve labels given by [`TextOrSource::Source`]
+    fn resolve_labels(&mut self) {
+        for (index, source) in std::mem::take(&mut self.labelled_by) {
+            let source = source.to_string();
+            let label = self
+                .widgets
+                .iter()
+                .find(|w| w.id == source)
+                .and_then(|w| w.text().or(w.label.as_deref()))
+                .map(|text| text.to_string());
+            self.widgets[index].label = label;
+        }
+    }
+}
+
+/// Push descriptions of all widgets of the `root` window to `widgets`
+pub(crate) fn window_widgets(
+    root: &dyn WindowErased,
+    ev_state: &EventState,
+    window: WindowId,
+    widgets: &mut Vec<WidgetInfo>,
+) {
+    let mut walk = Walk {
+        window: window.get(),
+        ev_state,
+        widgets: vec![],
+        labelled_by: vec![],
+    };
+    walk.push(root.as_tile(), None, Offset::ZERO);
+
+    for popup in root.iter_popups() {
+        if let Some((_, translation)) = root.find_tile_rect(&popup.id)
+            && let Some(tile) = root.find_tile(&popup.id)
+            && let Some(inner) = tile.get_child(POPUP_INNER_INDEX)
+        {
+            let translation = translation + tile.translation(POPUP_INNER_INDEX);
+            walk.push(inner, Some((tile, POPUP_INNER_INDEX)), translation);
+        }
+    }
+
+    walk.resolve_labels();
+    widgets.append(&mut walk.widgets);
+}
+
+/// Find the widget of the `root` window with the given `id`
+pub(crate) fn find_id(root: &dyn WindowErased, id: &str) -> Option<Id> {
+    fn find(tile: &dyn Tile, id: &str) -> Option<Id> {
+        if *tile.id_ref() == id {
+            return Some(tile.id());
+        }
+        tile.children().find_map(|child| find(child, id))
+    }
+
+    find(root.as_tile(), id).or_else(|| {
+        root.iter_popups().find_map(|popup| {
+            let tile = root.find_tile(&popup.id)?;
+            find(tile.get_child(POPUP_INNER_INDEX)?, id)
+        })
+    })
+}
+
+/// Start the automation server if [`SOCKET_VAR`] is set
+pub(crate) fn serve_from_env(proxy: Proxy) {
+    let Some(path) = std::env::var_os(SOCKET_VAR) else {
+        return;
+    };
+    let path = PathBuf::from(path);
+    if let Err(err) = serve(&path, proxy) {
+        log::error!("automation: unable to listen on {}: {err}", path.display());
+    }
+}
+
+fn serve(path: &Path, proxy: Proxy) -> io::Result<()> {
+    // Remove a stale socket (but nothing else) from a previous run
+    if let Ok(meta) = std::fs::symlink_metadata(path)
+        && meta.file_type().is_socket()
+    {
+        std::fs::remove_file(path)?;
+    }
+
+    let listener = UnixListener::bind(path)?;
+    // Only the current user may connect
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+    log::info!("automation: listening on {}", path.display());
+
+    std::thread::Builder::new()
+        .name("kas-automation".to_string())
+        .spawn(move || {
+            // Clients are handled one at a time
+            for stream in listener.incoming() {
+                let result = stream.and_then(|stream| handle_client(stream, &proxy));
+                if let Err(err) = result {
+                    log::warn!("automation: client error: {err}");
+                }
+            }
+        })?;
+    Ok(())
+}
+
+fn handle_client(stream: UnixStream, proxy: &Proxy) -> io::Result<()> {
+    let mut writer = stream.try_clone()?;
+    for line in BufReader::new(stream).lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let response = match serde_json::from_str::<Request>(&line) {
+            Ok(request) => match proxy.automation(request) {
+                Ok(response) => response,
+                Err(_) => return Ok(()), // the UI has exited
+            },
+            Err(err) => Response::Error(format!("bad request: {err}")),
+        };
+        serde_json::to_writer(&mut writer, &response)?;
+        writer.write_all(b"\n")?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn widget(role: &str, label: Option<&str>, text: Option<&str>) -> WidgetInfo {
+        let mut properties = Map::new();
+        if let Some(text) = text {
+            properties.insert("text".to_string(), text.into());
+        }
+        WidgetInfo {
+            window: 1,
+            id: "#1211".to_string(),
+            role: role.to_string(),
+            properties,
+            label: label.map(|s| s.to_string()),
+            description: None,
+            rect: (0, 0, 80, 20),
+            disabled: false,
+            nav_focus: false,
+        }
+    }
+
+    #[test]
+    fn parse_requests() {
+        let request = r#"{"op":"query","role":"Button","label":"Save"}"#;
+        let Ok(Request::Query {
+            window: None,
+            role: Some(role),
+            label: Some(label),
+            id: None,
+        }) = serde_json::from_str(request)
+        else {
+            panic!("failed to parse query");
+        };
+        assert_eq!((role.as_str(), label.as_str()), ("Button", "Save"));
+
+        let request = r##"{"op":"send","id":"#1211","message":"Activate"}"##;
+        let Ok(Request::Send { id, message }) = serde_json::from_str(request) else {
+            panic!("failed to parse send");
+        };
+        assert_eq!(id, "#1211");
+        assert!(matches!(message, Message::Activate));
+
+        let request = r#"{"op":"input","input":{"Click":{"x":40.0,"y":20.0}}}"#;
+        let Ok(Request::Input {
+            window: None,
+            input: Input::Click { x, y },
+        }) = serde_json::from_str(request)
+        else {
+            panic!("failed to parse input");
+        };
+        assert_eq!((x, y), (40.0, 20.0));
+
+        let request = r#"{"op":"input","input":{"Command":"Tab"}}"#;
+        let Ok(Request::Input {
+            input: Input::Command(Command::Tab),
+            ..
+        }) = serde_json::from_str(request)
+        else {
+            panic!("failed to parse command");
+        };
+
+        assert!(serde_json::from_str::<Request>(r#"{"op":"frobnicate"}"#).is_err());
+    }
+
+    #[test]
+    fn widget_matches() {
+        let button = widget("Button", Some("Save"), None);
+        assert!(button.matches(None, None, None));
+        assert!(button.matches(Some("button"), Some("Save"), Some("#1211")));
+        assert!(!button.matches(Some("Label"), None, None));
+        assert!(!button.matches(None, Some("save"), None));
+        assert!(!button.matches(None, None, Some("#12")));
+
+        let label = widget("Label", None, Some("Hello"));
+        assert!(label.matches(Some("Label"), Some("Hello"), None));
+        assert!(!label.matches(None, Some("Hell"), None));
+    }
+}
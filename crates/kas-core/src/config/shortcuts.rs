@@ -236,6 +236,101 @@ impl Shortcuts {
         }
         None
     }
+
+    /// Find a key binding for `command`
+    ///
+    /// Where multiple bindings exist, the one with the fewest modifiers is
+    /// preferred.
+    pub fn find_binding(&self, command: Command) -> Option<(ModifiersState, Key)> {
+        self.map
+            .iter()
+            .flat_map(|(modifiers, map)| {
+                map.iter()
+                    .filter(move |(_, cmd)| **cmd == command)
+                    .map(move |(key, _)| (*modifiers, key))
+            })
+            .filter_map(|(modifiers, key)| {
+                let text = binding_text(modifiers, key)?;
+                Some((modifiers.bits().count_ones(), text, modifiers, key))
+            })
+            .min_by(|a, b| (a.0, &a.1).cmp(&(b.0, &b.1)))
+            .map(|(_, _, modifiers, key)| (modifiers, key.clone()))
+    }
+
+    /// Describe the key binding for `command`, if any
+    ///
+    /// The result is intended for display, e.g. `"Ctrl+S"`.
+    pub fn describe_binding(&self, command: Command) -> Option<String> {
+        let (modifiers, key) = self.find_binding(command)?;
+        binding_text(modifiers, &key)
+    }
+}
+
+/// Format a key binding for display
+///
+/// Returns `None` for keys which cannot be displayed.
+fn binding_text(modifiers: ModifiersState, key: &Key) -> Option<String> {
+    let names = if cfg!(target_os = "macos") {
+        ["Ctrl", "Option", "Shift", "Cmd"]
+    } else {
+        ["Ctrl", "Alt", "Shift", "Super"]
+    };
+    let flags = [
+        ModifiersState::CONTROL,
+        ModifiersState::ALT,
+        ModifiersState::SHIFT,
+        ModifiersState::META,
+    ];
+
+    let mut text = String::new();
+    for (flag, name) in flags.into_iter().zip(names) {
+        if modifiers.contains(flag) {
+            text.push_str(name);
+            text.push('+');
+        }
+    }
+    match key {
+        Key::Character(c) => text.push_str(&c.to_uppercase()),
+        Key::Named(named) => text.push_str(named_key_text(*named)?),
+        _ => return None,
+    }
+    Some(text)
+}
+
+/// Display name of a named key
+///
+/// Returns `None` for keys not usually bound to commands.
+fn named_key_text(key: NamedKey) -> Option<&'static str> {
+    Some(match key {
+        NamedKey::Enter => "Enter",
+        NamedKey::Tab => "Tab",
+        NamedKey::Space => "Space",
+        NamedKey::Backspace => "Backspace",
+        NamedKey::Delete => "Del",
+        NamedKey::Insert => "Ins",
+        NamedKey::Escape => "Esc",
+        NamedKey::ArrowLeft => "Left",
+        NamedKey::ArrowRight => "Right",
+        NamedKey::ArrowUp => "Up",
+        NamedKey::ArrowDown => "Down",
+        NamedKey::Home => "Home",
+        NamedKey::End => "End",
+        NamedKey::PageUp => "PgUp",
+        NamedKey::PageDown => "PgDn",
+        NamedKey::F1 => "F1",
+        NamedKey::F2 => "F2",
+        NamedKey::F3 => "F3",
+        NamedKey::F4 => "F4",
+        NamedKey::F5 => "F5",
+        NamedKey::F6 => "F6",
+        NamedKey::F7 => "F7",
+        NamedKey::F8 => "F8",
+        NamedKey::F9 => "F9",
+        NamedKey::F10 => "F10",
+        NamedKey::F11 => "F11",
+        NamedKey::F12 => "F12",
+        _ => return None,
+    })
 }
 
 #[cfg(feature = "serde")]
@@ -601,3 +696,136 @@ mod deser {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn shortcuts(bindings: &[(ModifiersState, Key, Command)]) -> Shortcuts {
+        let mut s = Shortcuts::empty();
+        for (modifiers, key, command) in bindings {
+            let map = s.map.entry(*modifiers).or_insert_with(Default::default);
+            map.insert(key.clone(), *command);
+        }
+        s
+    }
+
+    fn char_key(c: &str) -> Key {
+        Key::Character(c.into())
+    }
+
+    #[test]
+    fn find_binding_prefers_fewest_modifiers() {
+        let s = shortcuts(&[
+            (
+                ModifiersState::CONTROL | ModifiersState::SHIFT,
+                char_key("s"),
+                Command::Save,
+            ),
+            (ModifiersState::CONTROL, char_key("s"), Command::Save),
+            (ModifiersState::CONTROL, char_key("o"), Command::Open),
+        ]);
+        assert_eq!(
+            s.find_binding(Command::Save),
+            Some((ModifiersState::CONTROL, char_key("s")))
+        );
+        assert_eq!(s.find_binding(Command::New), None);
+    }
+
+    #[test]
+    fn find_binding_orders_by_text() {
+        // With equal modifier counts, the binding with the lowest text wins
+        let s = shortcuts(&[
+            (ModifiersState::SHIFT, char_key("z"), Command::Redo),
+            (ModifiersState::CONTROL, char_key("y"), Command::Redo),
+        ]);
+        assert_eq!(
+            s.find_binding(Command::Redo),
+            Some((ModifiersState::CONTROL, char_key("y")))
+        );
+    }
+
+    #[test]
+    fn find_binding_skips_undisplayable_keys() {
+        let s = shortcuts(&[
+            (
+                ModifiersState::empty(),
+                NamedKey::Copy.into(),
+                Command::Copy,
+            ),
+            (ModifiersState::CONTROL, char_key("c"), Command::Copy),
+        ]);
+        assert_eq!(
+            s.find_binding(Command::Copy),
+            Some((ModifiersState::CONTROL, char_key("c")))
+        );
+
+        let s = shortcuts(&[(
+            ModifiersState::empty(),
+            NamedKey::Copy.into(),
+            Command::Copy,
+        )]);
+        assert_eq!(s.find_binding(Command::Copy), None);
+        assert_eq!(s.describe_binding(Command::Copy), None);
+    }
+
+    #[test]
+    fn describe_binding() {
+        let s = shortcuts(&[
+            (ModifiersState::CONTROL, char_key("s"), Command::Save),
+            (ModifiersState::empty(), NamedKey::F1.into(), Command::Help),
+        ]);
+        assert_eq!(s.describe_binding(Command::Save).as_deref(), Some("Ctrl+S"));
+        assert_eq!(s.describe_binding(Command::Help).as_deref(), Some("F1"));
+        assert_eq!(s.describe_binding(Command::Open), None);
+    }
+
+    #[test]
+    fn binding_text_modifiers() {
+        let key = char_key("a");
+        assert_eq!(
+            binding_text(ModifiersState::empty(), &key).as_deref(),
+            Some("A")
+        );
+        assert_eq!(
+            binding_text(ModifiersState::SHIFT | ModifiersState::CONTROL, &key).as_deref(),
+            Some("Ctrl+Shift+A")
+        );
+
+        let all = ModifiersState::CONTROL
+            | ModifiersState::ALT
+            | ModifiersState::SHIFT
+            | ModifiersState::META;
+        let expected = if cfg!(target_os = "macos") {
+            "Ctrl+Option+Shift+Cmd+A"
+        } else {
+            "Ctrl+Alt+Shift+Super+A"
+        };
+        assert_eq!(binding_text(all, &key).as_deref(), Some(expected));
+    }
+
+    #[test]
+    fn binding_text_keys() {
+        let ctrl = ModifiersState::CONTROL;
+        assert_eq!(
+            binding_text(ctrl, &NamedKey::PageDown.into()).as_deref(),
+            Some("Ctrl+PgDn")
+        );
+        assert_eq!(
+            binding_text(ModifiersState::SHIFT, &NamedKey::Tab.into()).as_deref(),
+            Some("Shift+Tab")
+        );
+        assert_eq!(binding_text(ctrl, &NamedKey::Copy.into()), None);
+        assert_eq!(binding_text(ctrl, &Key::Dead(Some('`'))), None);
+    }
+
+    #[test]
+    fn named_key_text_names() {
+        assert_eq!(named_key_text(NamedKey::Delete), Some("Del"));
+        assert_eq!(named_key_text(NamedKey::Escape), Some("Esc"));
+        assert_eq!(named_key_text(NamedKey::ArrowLeft), Some("Left"));
+        assert_eq!(named_key_text(NamedKey::F12), Some("F12"));
+        assert_eq!(named_key_text(NamedKey::Copy), None);
+        assert_eq!(named_key_text(NamedKey::Shift), None);
+    }
+}
@@ -24,7 +24,8 @@ pub use data::*;
 pub use events::Events;
 pub use layout::*;
 pub use node::Node;
-pub use role::{Role, RoleCx, RoleCxExt, TextOrSource};
+pub(crate) use role::RoleSink;
+pub use role::{Live, Relation, Role, RoleCx, RoleCxExt, TextOrSource, TextRun};
 pub use tile::*;
 pub use widget::*;
 pub use widget_id::*;
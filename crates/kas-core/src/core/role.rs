@@ -9,12 +9,14 @@ use crate::Id;
 use crate::dir::Direction;
 use crate::event::Key;
 #[allow(unused)] use crate::event::{Event, EventState};
-use crate::geom::Offset;
+use crate::geom::{Offset, Rect};
 use crate::layout::GridCellInfo;
 #[allow(unused)]
 use crate::messages::{DecrementStep, IncrementStep, SetValueF64};
 use crate::text::CursorRange;
 #[allow(unused)] use crate::{Layout, Tile};
+use std::fmt;
+use std::ops::Range;
 
 /// Describes a widget's purpose and capabilities
 ///
@@ -26,6 +28,9 @@ use crate::text::CursorRange;
 ///
 /// Some roles of widget are expected to accept specific messages, as outlined
 /// below. See also [`EventState::send`] and related functions.
+///
+/// The [`Debug`](fmt::Debug) representation omits the text of
+/// [`Role::TextInput`] when `protected`.
 #[non_exhaustive]
 pub enum Role<'a> {
     /// The widget does not present any semantics under introspection
@@ -137,6 +142,11 @@ pub enum Role<'a> {
     /// [`kas::messages::SetValueText`] may be used to replace the entire
     /// text. [`kas::messages::ReplaceSelectedText`] may be used to insert text
     /// at `cursor`, replacing all text between `cursor` and `sel_index`.
+    /// [`kas::messages::SetTextSelection`] may be used to set the cursor and
+    /// selection.
+    ///
+    /// Widgets should also report the layout of their text via
+    /// [`RoleCx::set_text_runs`] (unless `protected`).
     TextInput {
         /// Text contents
         ///
@@ -145,6 +155,10 @@ pub enum Role<'a> {
         text: &'a str,
         /// Whether the text input supports multi-line text
         multi_line: bool,
+        /// Whether the text is protected (e.g. a password)
+        ///
+        /// The value of protected text is not exposed to accessibility tools.
+        protected: bool,
         /// The cursor index and selection range
         cursor: CursorRange,
     },
@@ -196,7 +210,9 @@ pub enum Role<'a> {
     /// A progress bar
     ProgressBar {
         /// The reported value should be between `0.0` and `1.0`.
-        fraction: f32,
+        ///
+        /// This is `None` while progress is indeterminate.
+        fraction: Option<f32>,
         /// Orientation (direction of increasing values)
         direction: Direction,
     },
@@ -254,6 +270,51 @@ pub enum Role<'a> {
         /// See also [`accesskit::Node::is_selected`](https://docs.rs/accesskit/latest/accesskit/struct.Node.html#method.is_selected).
         selected: Option<bool>,
     },
+    /// A header cell within a [`Role::Grid`], labelling a column
+    ColumnHeader {
+        /// Grid cell index and span, if known
+        info: Option<GridCellInfo>,
+    },
+    /// A header cell within a [`Role::Grid`], labelling a row
+    RowHeader {
+        /// Grid cell index and span, if known
+        info: Option<GridCellInfo>,
+    },
+    /// A tree of possibly expandable items
+    ///
+    /// Child nodes should (but are not required to) use [`Role::TreeItem`].
+    Tree,
+    /// An item within a tree
+    ///
+    /// ### Messages
+    ///
+    /// [`kas::messages::Expand`] and [`kas::messages::Collapse`] may be used to
+    /// show and hide child items.
+    TreeItem {
+        /// Depth within the tree, starting from 1
+        level: usize,
+        /// Whether child items are shown, if the item has children
+        expanded: Option<bool>,
+        /// Whether the item is currently selected, if applicable.
+        selected: Option<bool>,
+    },
+    /// A heading
+    ///
+    /// The heading text should be provided by a child (e.g. [`Role::Label`])
+    /// or by [`RoleCx::set_label`].
+    Heading {
+        /// Heading level, starting from 1 (most significant)
+        level: usize,
+    },
+    /// A hyperlink
+    ///
+    /// ### Messages
+    ///
+    /// [`kas::messages::Activate`] may be used to follow the link.
+    Link {
+        /// The link target, if known
+        url: Option<&'a str>,
+    },
     /// A menu bar
     MenuBar,
     /// An openable menu
@@ -286,12 +347,211 @@ pub enum Role<'a> {
         /// True if the menu is open
         expanded: bool,
     },
+    /// A control which shows or hides some content (e.g. an expander header)
+    ///
+    /// ### Messages
+    ///
+    /// [`kas::messages::Activate`] may be used to toggle the state.
+    ///
+    /// [`kas::messages::Expand`] and [`kas::messages::Collapse`] may be used to
+    /// show and hide the content.
+    Disclosure {
+        /// True if the content is shown
+        expanded: bool,
+    },
     /// A list of variable-size children with resizing grips
     Splitter,
+    /// A bar of tools (buttons and other controls)
+    ToolBar,
+    /// A bar reporting status, usually at the bottom of a window
+    StatusBar,
+    /// A dialog box
+    ///
+    /// This is the content of a window presenting a message or request.
+    Dialog,
+    /// A dialog box reporting an urgent message (e.g. an error)
+    ///
+    /// Unlike [`Role::Dialog`], this is expected to interrupt the user.
+    AlertDialog,
+    /// The content of a tooltip
+    Tooltip,
     /// A window
     Window,
     /// The special bar at the top of a window titling contents and usually embedding window controls
     TitleBar,
+    /// A transient notification (e.g. a toast)
+    ///
+    /// Notifications are announced by screen readers as a live region.
+    Notification {
+        /// If true, the notification should interrupt the user
+        urgent: bool,
+    },
+}
+
+impl<'a> fmt::Debug for Role<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Role::None => f.write_str("None"),
+            Role::Unknown => f.write_str("Unknown"),
+            Role::Label(text) => f.debug_tuple("Label").field(&text).finish(),
+            Role::AccessLabel(text, ref key) => f
+                .debug_tuple("AccessLabel")
+                .field(&text)
+                .field(key)
+                .finish(),
+            Role::Button => f.write_str("Button"),
+            Role::CheckBox(checked) => f.debug_tuple("CheckBox").field(&checked).finish(),
+            Role::RadioButton(checked) => f.debug_tuple("RadioButton").field(&checked).finish(),
+            Role::Tab => f.write_str("Tab"),
+            Role::TabPage => f.write_str("TabPage"),
+            Role::Border => f.write_str("Border"),
+            Role::ScrollRegion { offset, max_offset } => f
+                .debug_struct("ScrollRegion")
+                .field("offset", &offset)
+                .field("max_offset", &max_offset)
+                .finish(),
+            Role::ScrollBar {
+                direction,
+                value,
+                max_value,
+            } => f
+                .debug_struct("ScrollBar")
+                .field("direction", &direction)
+                .field("value", &value)
+                .field("max_value", &max_value)
+                .finish(),
+            Role::Indicator => f.write_str("Indicator"),
+            Role::Image => f.write_str("Image"),
+            Role::Canvas => f.write_str("Canvas"),
+            Role::TextLabel {
+                text,
+                cursor,
+                sel_index,
+            } => f
+                .debug_struct("TextLabel")
+                .field("text", &text)
+                .field("cursor", &cursor)
+                .field("sel_index", &sel_index)
+                .finish(),
+            Role::TextInput {
+                text,
+                multi_line,
+                protected,
+                ref cursor,
+            } => {
+                let mut s = f.debug_struct("TextInput");
+                if !protected {
+                    s.field("text", &text);
+                }
+                s.field("multi_line", &multi_line)
+                    .field("protected", &protected)
+                    .field("cursor", cursor)
+                    .finish()
+            }
+            Role::Grip => f.write_str("Grip"),
+            Role::Slider {
+                min,
+                max,
+                step,
+                value,
+                direction,
+            } => f
+                .debug_struct("Slider")
+                .field("min", &min)
+                .field("max", &max)
+                .field("step", &step)
+                .field("value", &value)
+                .field("direction", &direction)
+                .finish(),
+            Role::SpinButton {
+                min,
+                max,
+                step,
+                value,
+            } => f
+                .debug_struct("SpinButton")
+                .field("min", &min)
+                .field("max", &max)
+                .field("step", &step)
+                .field("value", &value)
+                .finish(),
+            Role::ProgressBar {
+                fraction,
+                direction,
+            } => f
+                .debug_struct("ProgressBar")
+                .field("fraction", &fraction)
+                .field("direction", &direction)
+                .finish(),
+            Role::OptionList { len, direction } => f
+                .debug_struct("OptionList")
+                .field("len", &len)
+                .field("direction", &direction)
+                .finish(),
+            Role::OptionListItem { index, selected } => f
+                .debug_struct("OptionListItem")
+                .field("index", &index)
+                .field("selected", &selected)
+                .finish(),
+            Role::Grid { columns, rows } => f
+                .debug_struct("Grid")
+                .field("columns", &columns)
+                .field("rows", &rows)
+                .finish(),
+            Role::GridCell { ref info, selected } => f
+                .debug_struct("GridCell")
+                .field("info", info)
+                .field("selected", &selected)
+                .finish(),
+            Role::ColumnHeader { ref info } => {
+                f.debug_struct("ColumnHeader").field("info", info).finish()
+            }
+            Role::RowHeader { ref info } => {
+                f.debug_struct("RowHeader").field("info", info).finish()
+            }
+            Role::Tree => f.write_str("Tree"),
+            Role::TreeItem {
+                level,
+                expanded,
+                selected,
+            } => f
+                .debug_struct("TreeItem")
+                .field("level", &level)
+                .field("expanded", &expanded)
+                .field("selected", &selected)
+                .finish(),
+            Role::Heading { level } => f.debug_struct("Heading").field("level", &level).finish(),
+            Role::Link { url } => f.debug_struct("Link").field("url", &url).finish(),
+            Role::MenuBar => f.write_str("MenuBar"),
+            Role::Menu { expanded } => f.debug_struct("Menu").field("expanded", &expanded).finish(),
+            Role::ComboBox {
+                active,
+                text,
+                expanded,
+            } => f
+                .debug_struct("ComboBox")
+                .field("active", &active)
+                .field("text", &text)
+                .field("expanded", &expanded)
+                .finish(),
+            Role::Disclosure { expanded } => f
+                .debug_struct("Disclosure")
+                .field("expanded", &expanded)
+                .finish(),
+            Role::Splitter => f.write_str("Splitter"),
+            Role::ToolBar => f.write_str("ToolBar"),
+            Role::StatusBar => f.write_str("StatusBar"),
+            Role::Dialog => f.write_str("Dialog"),
+            Role::AlertDialog => f.write_str("AlertDialog"),
+            Role::Tooltip => f.write_str("Tooltip"),
+            Role::Window => f.write_str("Window"),
+            Role::TitleBar => f.write_str("TitleBar"),
+            Role::Notification { urgent } => f
+                .debug_struct("Notification")
+                .field("urgent", &urgent)
+                .finish(),
+        }
+    }
 }
 
 /// A copy-on-write text value or a reference to another source
@@ -328,6 +588,71 @@ impl<'a> From<&'a String> for TextOrSource<'a> {
     }
 }
 
+/// A relationship from one widget to another
+///
+/// See [`RoleCx::add_relation`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Relation {
+    /// The target describes this widget (e.g. help text)
+    DescribedBy,
+    /// This widget controls the target (e.g. a menu controls its pop-up)
+    Controls,
+    /// The target is logically a child of this widget though not a child in
+    /// the widget tree (e.g. a pop-up)
+    Owns,
+    /// The target presents an error message concerning this widget
+    ErrorMessage,
+}
+
+/// Announcement priority of a live region
+///
+/// Changes to the text content of a live region are announced by screen
+/// readers. See [`RoleCx::set_live`] and [`EventState::announce`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Live {
+    /// Changes are not announced
+    #[default]
+    Off,
+    /// Changes are announced when the user is idle
+    Polite,
+    /// Changes are announced immediately, interrupting the user
+    ///
+    /// This should be reserved for urgent messages such as errors.
+    Assertive,
+}
+
+#[cfg(feature = "accesskit")]
+impl From<Live> for accesskit::Live {
+    fn from(live: Live) -> Self {
+        match live {
+            Live::Off => accesskit::Live::Off,
+            Live::Polite => accesskit::Live::Polite,
+            Live::Assertive => accesskit::Live::Assertive,
+        }
+    }
+}
+
+/// A run of text on a single line
+///
+/// This describes the layout of part of the text of a [`Role::TextInput`]
+/// for the benefit of accessibility tools. See [`RoleCx::set_text_runs`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TextRun {
+    /// The range of text covered (byte indices)
+    pub range: Range<usize>,
+    /// The bounds of this run, in the widget's coordinate space
+    pub rect: Rect,
+    /// True if this run is right-to-left
+    pub rtl: bool,
+    /// The position of each `char` relative to the leading edge of `rect`
+    ///
+    /// This has one entry per `char` in `range`.
+    pub positions: Vec<f32>,
+    /// The advance width of each `char` in `range`
+    pub widths: Vec<f32>,
+}
+
 impl From<Id> for TextOrSource<'static> {
     #[inline]
     fn from(id: Id) -> Self {
@@ -355,6 +680,9 @@ impl<'a> Role<'a> {
             Role::Indicator => R::Unknown,
             Role::Image => R::Image,
             Role::Canvas => R::Canvas,
+            Role::TextInput {
+                protected: true, ..
+            } => R::PasswordInput,
             Role::TextInput {
                 multi_line: false, ..
             } => R::TextInput,
@@ -372,9 +700,23 @@ impl<'a> Role<'a> {
             Role::MenuBar => R::MenuBar,
             Role::Menu { .. } => R::Menu,
             Role::ComboBox { .. } => R::ComboBox,
+            Role::Disclosure { .. } => R::DisclosureTriangle,
+            Role::ColumnHeader { .. } => R::ColumnHeader,
+            Role::RowHeader { .. } => R::RowHeader,
+            Role::Tree => R::Tree,
+            Role::TreeItem { .. } => R::TreeItem,
+            Role::Heading { .. } => R::Heading,
+            Role::Link { .. } => R::Link,
+            Role::Dialog => R::Dialog,
+            Role::AlertDialog => R::AlertDialog,
+            Role::Tooltip => R::Tooltip,
             Role::Splitter => R::Splitter,
+            Role::ToolBar => R::Toolbar,
+            Role::StatusBar => R::Status,
             Role::Window => R::Window,
             Role::TitleBar => R::TitleBar,
+            Role::Notification { urgent: true } => R::Alert,
+            Role::Notification { urgent: false } => R::Status,
         }
     }
 
@@ -398,12 +740,30 @@ impl<'a> Role<'a> {
             }
             Role::TabPage => (),
             Role::Indicator | Role::Image | Role::Canvas => (),
-            Role::MenuBar | Role::Window | Role::TitleBar => (),
+            Role::MenuBar | Role::ToolBar | Role::StatusBar => (),
+            Role::Window | Role::TitleBar => (),
+            Role::Tree | Role::Dialog | Role::AlertDialog | Role::Tooltip => (),
+            Role::Heading { level } => node.set_level(level),
+            Role::Link { url } => {
+                node.add_action(Action::Click);
+                if let Some(url) = url {
+                    node.set_url(url);
+                }
+            }
+            Role::Notification { urgent } => {
+                use accesskit::Live;
+                node.set_live(if urgent { Live::Assertive } else { Live::Polite });
+            }
             Role::Label(text) | Role::TextLabel { text, .. } => node.set_value(text),
-            Role::TextInput { text, .. } => {
+            Role::TextInput {
+                text, protected, ..
+            } => {
                 node.add_action(Action::SetValue);
                 node.add_action(Action::ReplaceSelectedText);
-                node.set_value(text)
+                node.add_action(Action::SetTextSelection);
+                if !protected {
+                    node.set_value(text)
+                }
             }
             Role::AccessLabel(text, ref key) => {
                 node.set_value(text);
@@ -462,8 +822,10 @@ impl<'a> Role<'a> {
                 fraction,
                 direction,
             } => {
-                node.set_max_numeric_value(1.0);
-                node.set_numeric_value(fraction.cast());
+                if let Some(fraction) = fraction {
+                    node.set_max_numeric_value(1.0);
+                    node.set_numeric_value(fraction.cast());
+                }
                 node.set_orientation(direction.into());
             }
             Role::OptionList { len, direction } => {
@@ -503,6 +865,33 @@ impl<'a> Role<'a> {
                     node.set_selected(state);
                 }
             }
+            Role::ColumnHeader { info } | Role::RowHeader { info } => {
+                if let Some(info) = info {
+                    node.set_column_index(info.col.cast());
+                    node.set_row_index(info.row.cast());
+                }
+            }
+            Role::TreeItem {
+                level,
+                expanded,
+                selected,
+            } => {
+                node.set_level(level);
+                if let Some(expanded) = expanded {
+                    node.add_action(Action::Expand);
+                    node.add_action(Action::Collapse);
+                    node.set_expanded(expanded);
+                }
+                if let Some(state) = selected {
+                    node.set_selected(state);
+                }
+            }
+            Role::Disclosure { expanded } => {
+                node.add_action(Action::Click);
+                node.add_action(Action::Expand);
+                node.add_action(Action::Collapse);
+                node.set_expanded(expanded);
+            }
             Role::ComboBox { expanded, .. } | Role::Menu { expanded } => {
                 node.add_action(Action::Expand);
                 node.add_action(Action::Collapse);
@@ -526,6 +915,54 @@ pub trait RoleCx {
     /// not the primary value, for example an image's alternate text or a label
     /// next to a control.
     fn set_label_impl(&mut self, label: TextOrSource<'_>);
+
+    /// Attach a description
+    ///
+    /// This is supplementary information, for example a tooltip or help text.
+    ///
+    /// By default this does nothing.
+    fn set_description_impl(&mut self, description: TextOrSource<'_>) {
+        let _ = description;
+    }
+
+    /// Add a relationship to another widget
+    ///
+    /// This may be called multiple times. Where a relationship supports only
+    /// a single target (e.g. [`Relation::ErrorMessage`]), the last is used.
+    ///
+    /// By default this does nothing.
+    fn add_relation(&mut self, relation: Relation, target: Id) {
+        let _ = (relation, target);
+    }
+
+    /// Mark this widget as a live region
+    ///
+    /// Changes to the text content of this widget and its descendants will
+    /// be announced by screen readers according to `live`. See also
+    /// [`EventState::set_live_region`].
+    ///
+    /// By default this does nothing.
+    fn set_live(&mut self, live: Live) {
+        let _ = live;
+    }
+
+    /// Attach text layout information
+    ///
+    /// This should be used by [`Role::TextInput`] widgets to describe the
+    /// position of each line of text. Runs should be in order and not
+    /// overlap. This should not be used for protected text.
+    ///
+    /// By default this does nothing.
+    fn set_text_runs(&mut self, runs: Vec<TextRun>) {
+        let _ = runs;
+    }
+}
+
+/// A [`RoleCx`] discarding all properties
+pub(crate) struct RoleSink;
+
+impl RoleCx for RoleSink {
+    fn set_label_impl(&mut self, _: TextOrSource<'_>) {}
 }
 
 /// Convenience methods over a [`RoleCx`]
@@ -539,6 +976,15 @@ pub trait RoleCxExt: RoleCx {
     fn set_label<'a>(&mut self, label: impl Into<TextOrSource<'a>>) {
         self.set_label_impl(label.into());
     }
+
+    /// Attach a description
+    ///
+    /// This is supplementary information, for example a tooltip or help text.
+    /// Use [`TextOrSource::Source`] to reference a widget presenting the
+    /// description.
+    fn set_description<'a>(&mut self, description: impl Into<TextOrSource<'a>>) {
+        self.set_description_impl(description.into());
+    }
 }
 
 impl<C: RoleCx + ?Sized> RoleCxExt for C {}
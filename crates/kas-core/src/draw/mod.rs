@@ -119,12 +119,12 @@ impl WindowCommon {
         self.dur_text += dur;
     }
 
-    /// Check whether immediate redraw is required, and if so clear it
-    pub fn immediate_redraw(&mut self) -> bool {
+    /// Check whether immediate redraw is required at time `now`, and if so clear it
+    pub fn immediate_redraw(&mut self, now: Instant) -> bool {
         match self.anim {
             AnimationState::None => return false,
             // AnimationState::Timed(_) => return false,
-            AnimationState::Timed(when) if when > Instant::now() => return false,
+            AnimationState::Timed(when) if when > now => return false,
             _ => (),
         }
         self.anim = AnimationState::None;
@@ -27,7 +27,7 @@ pub struct KineticStart {
 #[derive(Clone, Debug)]
 pub struct Kinetic {
     press: Option<PressSource>,
-    t_step: Instant,
+    t_step: Option<Instant>,
     vel: Vec2,
     rest: Vec2,
 }
@@ -35,11 +35,9 @@ pub struct Kinetic {
 impl Default for Kinetic {
     #[inline]
     fn default() -> Self {
-        let now = Instant::now();
-
         Kinetic {
             press: None,
-            t_step: now,
+            t_step: None,
             vel: Vec2::ZERO,
             rest: Vec2::ZERO,
         }
@@ -63,7 +61,7 @@ impl Kinetic {
     ///
     /// Returns true when a frame timer ([`EventState::request_frame_timer`])
     /// should be requested (see [`Self::step`]).
-    pub fn press_end(&mut self, press: PressSource, vel: Vec2) -> bool {
+    pub fn press_end(&mut self, cx: &EventState, press: PressSource, vel: Vec2) -> bool {
         if self.press != Some(press) {
             return false;
         }
@@ -74,7 +72,7 @@ impl Kinetic {
             self.stop();
             false
         } else {
-            self.t_step = Instant::now();
+            self.t_step = Some(cx.now());
             true
         }
     }
@@ -82,12 +80,12 @@ impl Kinetic {
     /// Call on [`Scroll::Kinetic`] to immediately start (or accelerate) scrolling
     ///
     /// Returns any offset which should be applied immediately.
-    pub fn start(&mut self, start: KineticStart) -> Offset {
+    pub fn start(&mut self, cx: &EventState, start: KineticStart) -> Offset {
         self.vel += start.vel;
         let d = self.rest + start.rest;
         let delta = Offset::conv_trunc(d);
         self.rest = d - Vec2::conv(delta);
-        self.t_step = Instant::now();
+        self.t_step = Some(cx.now());
         delta
     }
 
@@ -98,9 +96,12 @@ impl Kinetic {
     /// and used to call this method.
     pub fn step(&mut self, cx: &EventState) -> Option<Offset> {
         let evc = cx.config().event();
-        let now = Instant::now();
-        let dur = (now - self.t_step).as_secs_f32();
-        self.t_step = now;
+        let now = cx.now();
+        let dur = self
+            .t_step
+            .map(|t| (now - t).as_secs_f32())
+            .unwrap_or_default();
+        self.t_step = Some(now);
 
         if let Some(source) = self.press {
             let decay_sub = evc.kinetic_grab_sub();
@@ -272,7 +273,7 @@ impl ScrollComponent {
                 self.scroll_by_delta(cx, delta);
             }
             Scroll::Kinetic(start) => {
-                let delta = self.kinetic.start(start);
+                let delta = self.kinetic.start(cx, start);
                 let delta = self.scroll_self_by_delta(cx, delta);
                 if delta == Offset::ZERO {
                     cx.set_scroll(Scroll::Scrolled);
@@ -388,7 +389,7 @@ impl ScrollComponent {
                 if self.max_offset != Offset::ZERO && cx.config_enable_pan(*press) =>
             {
                 if let Some(velocity) = cx.press_velocity(press.source)
-                    && self.kinetic.press_end(press.source, velocity)
+                    && self.kinetic.press_end(cx, press.source, velocity)
                 {
                     cx.request_frame_timer(id, TIMER_KINETIC);
                 }
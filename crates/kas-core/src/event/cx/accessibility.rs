@@ -25,7 +25,14 @@ impl EventState {
     ) -> accesskit::TreeUpdate {
         self.accesskit_is_enabled = true;
 
-        let (nodes, root_id) = crate::accesskit::window_nodes(root);
+        let (mut nodes, root_id) = crate::accesskit::window_nodes(root);
+        let live_regions = self.live_regions.iter().map(|(id, (live, _))| (id, live));
+        crate::accesskit::apply_live_regions(&mut nodes, live_regions);
+        // The announcement is sent once
+        if let Some((text, live)) = self.announcement.take() {
+            let serial = self.announcement_serial;
+            crate::accesskit::push_announcement(root.id_ref(), serial, &text, live, &mut nodes);
+        }
         let tree = Some(accesskit::Tree::new(root_id));
 
         // AccessKit does not like focus to point at a non-existant node, so we
@@ -42,6 +49,22 @@ impl EventState {
     pub(crate) fn disable_accesskit(&mut self) {
         self.accesskit_is_enabled = false;
     }
+
+    // Mark all live regions stale; regions not set again are later pruned
+    pub(super) fn mark_live_regions_stale(&mut self) {
+        for (_, (_, set)) in self.live_regions.iter_mut() {
+            *set = false;
+        }
+    }
+
+    // Drop live regions not set since the last call to `mark_live_regions_stale`
+    //
+    // Since ids may be reused, stale regions could otherwise apply to an
+    // unrelated widget.
+    pub(super) fn prune_live_regions(&mut self) {
+        let regions = std::mem::take(&mut self.live_regions);
+        self.live_regions = regions.into_iter().filter(|(_, (_, set))| *set).collect();
+    }
 }
 
 impl<'a> EventCx<'a> {
@@ -55,7 +78,9 @@ impl<'a> EventCx<'a> {
         };
 
         // TODO: implement remaining actions
-        use crate::messages::{self, Erased, SetValueF64, SetValueText};
+        use crate::messages::{
+            self, Erased, ReplaceSelectedText, SetTextSelection, SetValueF64, SetValueText,
+        };
         use accesskit::{Action as AKA, ActionData};
         match request.action {
             AKA::Click => {
@@ -63,7 +88,12 @@ impl<'a> EventCx<'a> {
             }
             AKA::Focus => self.set_nav_focus(id, FocusSource::Synthetic),
             AKA::Blur => (),
-            AKA::Collapse | AKA::Expand => (), // TODO: open/close menus
+            AKA::Collapse => {
+                self.send_or_replay(widget, id, Erased::new(messages::Collapse));
+            }
+            AKA::Expand => {
+                self.send_or_replay(widget, id, Erased::new(messages::Expand));
+            }
             AKA::CustomAction => (),
             AKA::Decrement => {
                 self.send_or_replay(widget, id, Erased::new(messages::DecrementStep));
@@ -72,7 +102,12 @@ impl<'a> EventCx<'a> {
                 self.send_or_replay(widget, id, Erased::new(messages::IncrementStep));
             }
             AKA::HideTooltip | AKA::ShowTooltip => (),
-            AKA::ReplaceSelectedText => (),
+            AKA::ReplaceSelectedText => {
+                if let Some(ActionData::Value(text)) = request.data {
+                    let msg = ReplaceSelectedText(text.into());
+                    self.send_or_replay(widget, id, Erased::new(msg));
+                }
+            }
             AKA::ScrollDown | AKA::ScrollLeft | AKA::ScrollRight | AKA::ScrollUp => {
                 let delta = match request.action {
                     AKA::ScrollDown => ScrollDelta::Lines(0.0, 1.0),
@@ -114,7 +149,18 @@ impl<'a> EventCx<'a> {
                     self.send_or_replay(widget, id, Erased::new(msg));
                 }
             }
-            AKA::SetTextSelection => (),
+            AKA::SetTextSelection => {
+                if let Some(ActionData::SetTextSelection(selection)) = request.data {
+                    let Some(range) = widget
+                        .as_tile()
+                        .find_tile(&id)
+                        .and_then(|tile| crate::accesskit::text_selection_range(tile, &selection))
+                    else {
+                        return;
+                    };
+                    self.send_or_replay(widget, id, Erased::new(SetTextSelection(range)));
+                }
+            }
             AKA::SetSequentialFocusNavigationStartingPoint => (),
             AKA::SetValue => {
                 let msg = match request.data {
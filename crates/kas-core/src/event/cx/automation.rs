@@ -0,0 +1,67 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Event manager — UI automation
+
+use super::EventCx;
+use crate::automation::{Button, Input};
+use crate::event::{Command, FocusSource};
+use crate::geom::DVec2;
+use crate::messages::{Erased, ReplaceSelectedText};
+use crate::window::WindowWidget;
+use crate::{Id, Node};
+use winit::event::{ElementState, MouseScrollDelta};
+
+impl<'a> EventCx<'a> {
+    /// Send a message to `id`, as with an AccessKit action
+    pub(crate) fn handle_automation_message(&mut self, widget: Node<'_>, id: Id, msg: Erased) {
+        self.send_or_replay(widget, id, msg);
+    }
+
+    /// Handle synthesized input, as with the equivalent winit event
+    pub(crate) fn handle_automation_input<A>(
+        &mut self,
+        win: &mut dyn WindowWidget<Data = A>,
+        data: &A,
+        input: Input,
+    ) {
+        log::trace!(target: "kas_core::event", "handle_automation_input: {input:?}");
+        match input {
+            Input::PointerMove { x, y } => self.handle_pointer_moved(win, data, DVec2(x, y)),
+            Input::PointerButton { button, pressed } => {
+                let state = match pressed {
+                    false => ElementState::Released,
+                    true => ElementState::Pressed,
+                };
+                self.handle_mouse_input(win.as_node(data), state, button.into());
+            }
+            Input::Click { x, y } => {
+                self.handle_pointer_moved(win, data, DVec2(x, y));
+                for state in [ElementState::Pressed, ElementState::Released] {
+                    self.handle_mouse_input(win.as_node(data), state, Button::Left.into());
+                }
+            }
+            Input::Scroll { x, y } => {
+                let delta = MouseScrollDelta::LineDelta(x, y);
+                self.handle_mouse_wheel(win.as_node(data), delta);
+            }
+            Input::Command(cmd) => {
+                if !self.send_command(win.as_node(data), cmd, None)
+                    && self.config.nav_focus
+                    && cmd == Command::Tab
+                {
+                    self.next_nav_focus(None, false, FocusSource::Synthetic);
+                }
+            }
+            Input::Text(text) => {
+                let target = self.input.sel_focus().or(self.nav_focus()).cloned();
+                if let Some(id) = target {
+                    let msg = Erased::new(ReplaceSelectedText(text));
+                    self.send_or_replay(win.as_node(data), id, msg);
+                }
+            }
+        }
+    }
+}
@@ -11,6 +11,7 @@ use crate::HasId;
 use crate::event::{Command, Event, FocusSource};
 use crate::geom::{Rect, Size};
 use crate::util::WidgetHierarchy;
+use crate::window::{Inspector, Window};
 use crate::{Id, Node, Tile, TileExt};
 use winit::dpi::{LogicalPosition, LogicalSize};
 use winit::event::{ElementState, Ime, KeyEvent};
@@ -421,60 +422,10 @@ impl<'a> EventCx<'a> {
             .shortcuts()
             .try_match_event(self.modifiers, event);
 
-        if Some(Command::Exit) == opt_cmd {
-            self.runner.exit();
-            return;
-        } else if Some(Command::Close) == opt_cmd {
-            self.handle_close();
+        if let Some(cmd) = opt_cmd
+            && self.send_command(widget.re(), cmd, Some(event.physical_key))
+        {
             return;
-        } else if let Some(cmd) = opt_cmd {
-            let mut targets = vec![];
-            let mut send = |_self: &mut Self, id: Id, cmd| -> bool {
-                if !targets.contains(&id) {
-                    let event = Event::Command(cmd, Some(event.physical_key));
-                    let used = _self.send_event(widget.re(), id.clone(), event);
-                    targets.push(id);
-                    used
-                } else {
-                    false
-                }
-            };
-
-            if (self.input.key_focus || cmd.suitable_for_sel_focus())
-                && let Some(ref id) = self.input.focus
-                && send(self, id.clone(), cmd)
-            {
-                return;
-            }
-
-            if !self.modifiers.alt_key()
-                && let Some(id) = self.nav_focus().cloned()
-                && send(self, id, cmd)
-            {
-                return;
-            }
-
-            if let Some(id) = self
-                .popups
-                .last()
-                .filter(|popup| popup.is_sized)
-                .map(|popup| popup.desc.id.clone())
-                && send(self, id, cmd)
-            {
-                return;
-            }
-
-            let fallback = self.nav.fallback.clone().unwrap_or(id);
-            if send(self, fallback, cmd) {
-                return;
-            }
-
-            if matches!(cmd, Command::Debug) {
-                let over_id = self.mouse.over_id();
-                let hier = WidgetHierarchy::new(widget.as_tile(), over_id.clone());
-                log::debug!("Widget heirarchy (filter={over_id:?}): {hier}");
-                return;
-            }
         }
 
         // Next priority goes to access keys when Alt is held or alt_bypass is true
@@ -495,6 +446,92 @@ impl<'a> EventCx<'a> {
         }
     }
 
+    /// Send a command to the most appropriate target
+    ///
+    /// The command is sent as if triggered by `key` (if any). Returns true if
+    /// the command was handled.
+    pub(super) fn send_command(
+        &mut self,
+        mut widget: Node<'_>,
+        cmd: Command,
+        key: Option<PhysicalKey>,
+    ) -> bool {
+        if cmd == Command::Exit {
+            self.runner.exit();
+            return true;
+        } else if cmd == Command::Close {
+            // The focus (e.g. a tab) may handle Close before the window does
+            if let Some(id) = self.nav_focus().cloned()
+                && self.send_event(widget.re(), id, Event::Command(cmd, key))
+            {
+                return true;
+            }
+            self.handle_close();
+            return true;
+        }
+
+        let id = widget.id();
+        let mut targets = vec![];
+        let mut send = |_self: &mut Self, widget: Node<'_>, id: Id, cmd| -> bool {
+            if !targets.contains(&id) {
+                let event = Event::Command(cmd, key);
+                let used = _self.send_event(widget, id.clone(), event);
+                targets.push(id);
+                used
+            } else {
+                false
+            }
+        };
+
+        if (self.input.key_focus || cmd.suitable_for_sel_focus())
+            && let Some(ref id) = self.input.focus
+            && send(self, widget.re(), id.clone(), cmd)
+        {
+            return true;
+        }
+
+        if !self.modifiers.alt_key()
+            && let Some(id) = self.nav_focus().cloned()
+            && send(self, widget.re(), id, cmd)
+        {
+            return true;
+        }
+
+        if let Some(id) = self
+            .popups
+            .last()
+            .filter(|popup| popup.is_sized)
+            .map(|popup| popup.desc.id.clone())
+            && send(self, widget.re(), id, cmd)
+        {
+            return true;
+        }
+
+        if self.config.nav_focus
+            && let Some(dir) = cmd.as_direction()
+            && self.spatial_nav(widget.as_tile(), dir)
+        {
+            return true;
+        }
+
+        let fallback = self.nav.fallback.clone().unwrap_or(id);
+        if send(self, widget.re(), fallback, cmd) {
+            return true;
+        }
+
+        if matches!(cmd, Command::Debug) {
+            let over_id = self.mouse.over_id();
+            let hier = WidgetHierarchy::new(widget.as_tile(), over_id.clone());
+            log::debug!("Widget heirarchy (filter={over_id:?}): {hier}");
+
+            let inspector = Inspector::new(self.window_id);
+            self.add_dataless_window(Window::new(inspector, "Widget inspector"), false);
+            return true;
+        }
+
+        false
+    }
+
     pub(super) fn modifiers_changed(&mut self, state: ModifiersState) {
         if state.alt_key() != self.modifiers.alt_key() {
             // This controls drawing of access key indicators
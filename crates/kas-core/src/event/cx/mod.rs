@@ -21,7 +21,7 @@ use crate::config::{ConfigMsg, WindowConfig};
 use crate::draw::DrawShared;
 use crate::geom::{Offset, Vec2};
 use crate::messages::Erased;
-use crate::runner::{Platform, RunnerT, WindowDataErased};
+use crate::runner::{Clock, Platform, RunnerT, WindowDataErased};
 #[allow(unused)] use crate::theme::SizeCx;
 use crate::theme::ThemeSize;
 use crate::window::{PopupDescriptor, WindowId};
@@ -30,6 +30,7 @@ use key::Input;
 use nav::NavFocus;
 
 #[cfg(feature = "accesskit")] mod accessibility;
+#[cfg(automation)] mod automation;
 mod key;
 mod nav;
 mod press;
@@ -46,6 +47,8 @@ struct PopupState {
     id: WindowId,
     desc: PopupDescriptor,
     old_nav_focus: Option<Id>,
+    // Whether the pop-up took navigation focus on opening
+    took_focus: bool,
     is_sized: bool,
 }
 
@@ -74,6 +77,17 @@ pub struct EventState {
     window_has_focus: bool,
     #[cfg(feature = "accesskit")]
     accesskit_is_enabled: bool,
+    // Live regions, each flagged true if set since the last configure
+    #[cfg(feature = "accesskit")]
+    live_regions: LinearMap<Id, (crate::Live, bool)>,
+    #[cfg(feature = "accesskit")]
+    announcement: Option<(String, crate::Live)>,
+    #[cfg(feature = "accesskit")]
+    announcement_serial: usize,
+    // Inspector to notify of the next mouse press, if any
+    pub(crate) inspect_picker: Option<Id>,
+    // Recorded layout while the layout debug overlay is enabled
+    pub(crate) layout_records: Option<Vec<crate::layout::LayoutRecord>>,
     modifiers: ModifiersState,
     input: Input,
     nav: NavFocus,
@@ -83,6 +97,11 @@ pub struct EventState {
     access_keys: HashMap<Key, Id>,
     popups: SmallVec<[PopupState; 16]>,
     popup_removed: SmallVec<[(Id, WindowId); 16]>,
+    // Modal windows opened from this window with the navigation focus to
+    // restore when each closes
+    modal_windows: Vec<(WindowId, Id)>,
+    // Source of time, shared with the runner
+    pub(crate) clock: Clock,
     time_updates: Vec<(Instant, Id, TimerHandle)>,
     frame_updates: LinearSet<(Id, TimerHandle)>,
     need_frame_update: bool,
@@ -108,6 +127,14 @@ impl EventState {
             window_has_focus: false,
             #[cfg(feature = "accesskit")]
             accesskit_is_enabled: false,
+            #[cfg(feature = "accesskit")]
+            live_regions: Default::default(),
+            #[cfg(feature = "accesskit")]
+            announcement: None,
+            #[cfg(feature = "accesskit")]
+            announcement_serial: 0,
+            inspect_picker: None,
+            layout_records: None,
             modifiers: ModifiersState::empty(),
             input: Input::default(),
             nav: NavFocus::default(),
@@ -117,6 +144,8 @@ impl EventState {
             access_keys: Default::default(),
             popups: Default::default(),
             popup_removed: Default::default(),
+            modal_windows: vec![],
+            clock: Clock::default(),
             time_updates: vec![],
             frame_updates: Default::default(),
             need_frame_update: false,
@@ -155,10 +184,17 @@ impl EventState {
 
         // These are recreated during configure:
         self.nav.fallback = None;
+        self.nav.spatial.clear();
+        self.nav.mark_scopes_stale();
+        #[cfg(feature = "accesskit")]
+        self.mark_live_regions_stale();
 
         let mut cx = ConfigCx::new(sizer, self);
         cx.configure(node, id);
         let resize = cx.resize;
+        self.nav.prune_scopes();
+        #[cfg(feature = "accesskit")]
+        self.prune_live_regions();
         // Ignore cx.redraw: we can assume a redraw will happen
         self.action_moved = Some(ActionMoved);
         resize
@@ -200,6 +236,21 @@ impl EventState {
         self.touch.cancel_event_focus(target);
     }
 
+    /// Get the widget under the mouse while an inspector is picking
+    pub(crate) fn inspect_pick_over(&self) -> Option<Id> {
+        self.inspect_picker
+            .as_ref()
+            .and_then(|_| self.mouse.over_id())
+    }
+
+    /// Get whether the layout debug overlay is enabled
+    ///
+    /// See [`SetLayoutOverlay`](crate::messages::SetLayoutOverlay).
+    #[inline]
+    pub fn layout_overlay(&self) -> bool {
+        self.layout_records.is_some()
+    }
+
     /// Check whether a widget is disabled
     ///
     /// A widget is disabled if any ancestor is.
@@ -6,9 +6,12 @@
 //! Event context: navigation focus
 
 use super::{ConfigCx, EventCx, EventState};
+use crate::core::RoleSink;
+use crate::dir::Direction;
+#[allow(unused)] use crate::event::Command;
 use crate::event::{Event, FocusSource};
-use crate::{Id, Node};
-#[allow(unused)] use crate::{Tile, event::Command};
+use crate::geom::{Offset, Rect};
+use crate::{Id, Node, Role, Tile, TileExt};
 
 /// Action of Widget::_nav_next
 #[cfg_attr(not(feature = "internal_doc"), doc(hidden))]
@@ -41,10 +44,26 @@ pub(super) enum PendingNavFocus {
     },
 }
 
+/// State of a focus scope
+struct FocusScope {
+    id: Id,
+    // The last descendant to have navigation focus
+    last_focus: Option<Id>,
+    // Navigation focus to restore on exit
+    restore: Option<Id>,
+    // Whether the scope was registered since the last full configure
+    live: bool,
+}
+
 #[derive(Default)]
 pub(super) struct NavFocus {
     focus: Option<Id>,
     pub(super) fallback: Option<Id>,
+    // Containers with spatial navigation enabled
+    pub(super) spatial: Vec<Id>,
+    // Registered focus scopes. These are retained over reconfigure (in order
+    // to retain state) unless no longer registered; see `prune_scopes`.
+    scopes: Vec<FocusScope>,
     pending_focus: PendingNavFocus,
 }
 
@@ -53,6 +72,81 @@ impl NavFocus {
     pub(super) fn has_pending_changes(&self) -> bool {
         !matches!(self.pending_focus, PendingNavFocus::None)
     }
+
+    fn scope_mut(&mut self, id: &Id) -> Option<&mut FocusScope> {
+        self.scopes.iter_mut().find(|scope| scope.id == *id)
+    }
+
+    // Mark all scopes stale; scopes not registered again are later pruned
+    pub(super) fn mark_scopes_stale(&mut self) {
+        for scope in &mut self.scopes {
+            scope.live = false;
+        }
+    }
+
+    // Drop scopes not registered since the last call to `mark_scopes_stale`
+    //
+    // Since ids may be reused, stale scopes could otherwise direct focus
+    // to an unrelated widget.
+    pub(super) fn prune_scopes(&mut self) {
+        self.scopes.retain(|scope| scope.live);
+    }
+
+    // Register scope `id`, returning true if new
+    fn register_scope(&mut self, id: &Id) -> bool {
+        if let Some(scope) = self.scope_mut(id) {
+            scope.live = true;
+            return false;
+        }
+        self.scopes.push(FocusScope {
+            id: id.clone(),
+            last_focus: None,
+            restore: None,
+            live: true,
+        });
+        true
+    }
+
+    // Enter scope `id`, returning the widget from which to search for a target
+    fn enter_scope(&mut self, id: Id) -> Id {
+        self.register_scope(&id);
+        let focus = self.focus.clone();
+        let scope = self.scope_mut(&id).unwrap();
+        if let Some(focus) = focus.filter(|focus| !id.is_ancestor_of(focus)) {
+            scope.restore = Some(focus);
+        }
+        scope.last_focus.clone().unwrap_or(id)
+    }
+
+    // Exit scope `id`, returning the focus to restore, if any
+    fn exit_scope(&mut self, id: &Id) -> Option<Id> {
+        let focus_in_scope = self.focus.as_ref().is_none_or(|f| id.is_ancestor_of(f));
+        let restore = self.scope_mut(id)?.restore.take();
+        restore.filter(|_| focus_in_scope)
+    }
+
+    // Set focus, updating the last focus of scopes
+    fn set_focus(&mut self, target: Option<Id>) {
+        if let Some(id) = target.as_ref() {
+            for scope in &mut self.scopes {
+                if scope.id.is_ancestor_of(id) {
+                    scope.last_focus = Some(id.clone());
+                }
+            }
+        }
+        self.focus = target;
+    }
+
+    // Get the innermost focus scope containing `id`
+    fn scope_of(&self, id: &Id) -> Option<&Id> {
+        let mut result: Option<&Id> = None;
+        for scope in &self.scopes {
+            if scope.id.is_ancestor_of(id) && result.is_none_or(|r| r.is_ancestor_of(&scope.id)) {
+                result = Some(&scope.id);
+            }
+        }
+        result
+    }
 }
 
 impl EventState {
@@ -74,6 +168,24 @@ impl EventState {
         self.nav.focus.as_ref()
     }
 
+    /// Get whether the navigation focus is a protected text input
+    ///
+    /// This is true when the widget with navigation focus (a descendant of
+    /// `root`) reports [`Role::TextInput`] with `protected: true`.
+    pub(crate) fn nav_focus_is_protected(&self, root: &dyn Tile) -> bool {
+        self.nav_focus()
+            .and_then(|id| root.find_tile(id))
+            .is_some_and(|tile| {
+                matches!(
+                    tile.role(&mut RoleSink),
+                    Role::TextInput {
+                        protected: true,
+                        ..
+                    }
+                )
+            })
+    }
+
     pub(super) fn clear_nav_focus_on(&mut self, target: &Id) {
         if let Some(id) = self.nav.focus.as_ref()
             && target.is_ancestor_of(id)
@@ -84,8 +196,17 @@ impl EventState {
             }
 
             if matches!(self.nav.pending_focus, PendingNavFocus::None) {
+                // If focus is within an entered scope, restore focus as on exit
+                let restore = self.nav.scopes.iter().find_map(|scope| {
+                    let restore = scope.restore.as_ref()?;
+                    (target.is_ancestor_of(&scope.id)
+                        && scope.id.is_ancestor_of(id)
+                        && !target.is_ancestor_of(restore))
+                    .then(|| restore.clone())
+                });
+
                 self.nav.pending_focus = PendingNavFocus::Set {
-                    target: None,
+                    target: restore,
                     source: FocusSource::Synthetic,
                 };
             }
@@ -127,6 +248,153 @@ impl<'a> ConfigCx<'a> {
             self.nav.fallback = Some(id);
         }
     }
+
+    /// Enable spatial navigation within widget `id`
+    ///
+    /// By default, navigation focus moves between widgets in tree order (using
+    /// <kbd>Tab</kbd>) and arrow keys are handled by the focused widget (if at
+    /// all). When spatial navigation is enabled for a container `id` and the
+    /// navigation focus is a descendant of `id`, arrow keys not used by the
+    /// focused widget (or an open pop-up) move navigation focus to the nearest
+    /// [navigable](Tile::navigable) descendant of `id` in that direction, as
+    /// determined from widget positions on screen.
+    ///
+    /// Where containers enabling spatial navigation are nested, the innermost
+    /// container of the navigation focus is used. If no widget lies in the
+    /// given direction, the key is passed on to the
+    /// [fallback recipient](Self::register_nav_fallback) as usual.
+    ///
+    /// This is usually called from [`Events::configure`](crate::Events::configure).
+    pub fn register_spatial_nav(&mut self, id: Id) {
+        if !self.nav.spatial.contains(&id) {
+            log::debug!(target: "kas_core::event", "register_spatial_nav: id={id}");
+            self.nav.spatial.push(id);
+        }
+    }
+
+    /// Make widget `id` a focus scope
+    ///
+    /// While navigation focus is within a focus scope, keyboard navigation
+    /// (see [`EventCx::next_nav_focus`]) is constrained to the scope:
+    /// <kbd>Tab</kbd> cycles through navigable descendants of `id` only. Where
+    /// focus scopes are nested, the innermost scope containing the focus
+    /// applies. This is suitable for modal regions of a window such as inline
+    /// dialogs.
+    ///
+    /// A focus scope remembers which of its descendants last had navigation
+    /// focus. Use [`EventCx::enter_focus_scope`] to move focus into the scope
+    /// and [`EventCx::exit_focus_scope`] to restore focus to where it came
+    /// from.
+    ///
+    /// This should be called from [`Events::configure`](crate::Events::configure):
+    /// a scope not registered again when the window is reconfigured is
+    /// forgotten.
+    pub fn register_focus_scope(&mut self, id: Id) {
+        if self.nav.register_scope(&id) {
+            log::debug!(target: "kas_core::event", "register_focus_scope: id={id}");
+        }
+    }
+}
+
+impl EventState {
+    /// Move navigation focus spatially in direction `dir`
+    ///
+    /// Returns true if a new focus target was found.
+    pub(super) fn spatial_nav(&mut self, root: &dyn Tile, dir: Direction) -> bool {
+        let Some(focus) = self.nav.focus.as_ref() else {
+            return false;
+        };
+
+        // Find the innermost container of focus with spatial navigation
+        let mut container: Option<&Id> = None;
+        for id in &self.nav.spatial {
+            if id.is_ancestor_of(focus) && container.is_none_or(|c| c.is_ancestor_of(id)) {
+                container = Some(id);
+            }
+        }
+        let Some(container) = container else {
+            return false;
+        };
+
+        let Some((rect, translation)) = root.find_tile_rect(focus) else {
+            return false;
+        };
+        let from = rect - translation;
+        let Some((_, translation)) = root.find_tile_rect(container) else {
+            return false;
+        };
+        let Some(tile) = root.find_tile(container) else {
+            return false;
+        };
+
+        let mut candidates = vec![];
+        self.spatial_candidates(tile, translation, &mut candidates);
+
+        let target = candidates
+            .into_iter()
+            .filter(|(id, _)| id != focus)
+            .filter_map(|(id, rect)| spatial_score(from, rect, dir).map(|score| (score, id)))
+            .min_by_key(|(score, _)| *score)
+            .map(|(_, id)| id);
+
+        if let Some(id) = target {
+            log::trace!(target: "kas_core::event", "spatial_nav: dir={dir:?}, target={id}");
+            self.set_nav_focus(id, FocusSource::Key);
+            true
+        } else {
+            false
+        }
+    }
+
+    // Collect navigable descendants of tile (inclusive) with their rects in the
+    // coordinate space of tile's parent, given tile's translation
+    fn spatial_candidates(&self, tile: &dyn Tile, translation: Offset, out: &mut Vec<(Id, Rect)>) {
+        if !tile.status().is_sized() || self.is_disabled(tile.id_ref()) {
+            return;
+        }
+
+        if tile.navigable() {
+            out.push((tile.id(), tile.rect() - translation));
+        }
+
+        for index in tile.child_indices() {
+            if let Some(child) = tile.get_child(index) {
+                let translation = translation + tile.translation(index);
+                self.spatial_candidates(child, translation, out);
+            }
+        }
+    }
+}
+
+/// Score a move from `from` to `to` in direction `dir`; lower is better
+///
+/// Returns `None` if `to` does not lie in direction `dir`. The score prefers
+/// candidates aligned with `from` on the orthogonal axis, then those nearer.
+fn spatial_score(from: Rect, to: Rect, dir: Direction) -> Option<(i32, i32)> {
+    // Project onto intervals on the primary axis (increasing in direction
+    // dir) and the orthogonal axis
+    let project = |rect: Rect| {
+        let (x0, x1) = (rect.pos.0, rect.pos.0 + rect.size.0);
+        let (y0, y1) = (rect.pos.1, rect.pos.1 + rect.size.1);
+        match dir {
+            Direction::Right => ((x0, x1), (y0, y1)),
+            Direction::Left => ((-x1, -x0), (y0, y1)),
+            Direction::Down => ((y0, y1), (x0, x1)),
+            Direction::Up => ((-y1, -y0), (x0, x1)),
+        }
+    };
+    let ((fp0, fp1), (fq0, fq1)) = project(from);
+    let ((tp0, tp1), (tq0, tq1)) = project(to);
+
+    // The near edge of to must be beyond the center of from
+    if 2 * tp0 < fp0 + fp1 || tp0 + tp1 <= fp0 + fp1 {
+        return None;
+    }
+
+    let primary_gap = (tp0 - fp1).max(0);
+    let ortho_gap = (tq0 - fq1).max(fq0 - tq1).max(0);
+    let center_dist = ((tp0 + tp1) - (fp0 + fp1)) / 2 + ((tq0 + tq1) - (fq0 + fq1)).abs() / 2;
+    Some((primary_gap + 2 * ortho_gap, center_dist))
 }
 
 impl<'a> EventCx<'a> {
@@ -153,6 +421,39 @@ impl<'a> EventCx<'a> {
         };
     }
 
+    /// Move navigation focus into focus scope `id`
+    ///
+    /// Navigation focus is given to the descendant of `id` which last had
+    /// focus, if any, otherwise to the first navigable descendant. The current
+    /// navigation focus (if outside of `id`) is remembered and restored by
+    /// [`Self::exit_focus_scope`].
+    ///
+    /// Widget `id` is registered as a focus scope if not already (see
+    /// [`ConfigCx::register_focus_scope`]).
+    pub fn enter_focus_scope(&mut self, id: Id, source: FocusSource) {
+        log::trace!(target: "kas_core::event", "enter_focus_scope: id={id}");
+        let target = self.nav.enter_scope(id);
+
+        self.nav.pending_focus = PendingNavFocus::Next {
+            target: Some(target),
+            advance: NavAdvance::Forward(true),
+            source,
+        };
+    }
+
+    /// Exit focus scope `id`
+    ///
+    /// If navigation focus is within `id` (or there is no navigation focus),
+    /// then focus is restored to the widget which had focus before the last
+    /// call to [`Self::enter_focus_scope`], if any. The scope's last focused
+    /// descendant is still remembered.
+    pub fn exit_focus_scope(&mut self, id: &Id) {
+        log::trace!(target: "kas_core::event", "exit_focus_scope: id={id}");
+        if let Some(restore) = self.nav.exit_scope(id) {
+            self.set_nav_focus(restore, FocusSource::Synthetic);
+        }
+    }
+
     /// Advance the navigation focus
     ///
     /// If `target == Some(id)`, this looks for the next widget from `id`
@@ -229,7 +530,7 @@ impl<'a> EventCx<'a> {
             self.send_event(widget.re(), old, Event::LostNavFocus);
         }
 
-        self.nav.focus = target.clone();
+        self.nav.set_focus(target.clone());
         log::debug!(target: "kas_core::event", "nav_focus = {target:?}");
         if let Some(id) = target {
             self.redraw();
@@ -270,7 +571,17 @@ impl<'a> EventCx<'a> {
             }
         }
 
-        let focus = target.or_else(|| self.nav.focus.clone());
+        let focus = target.clone().or_else(|| self.nav.focus.clone());
+
+        // Constrain navigation to the innermost focus scope containing focus
+        if let Some(scope) = focus.as_ref().and_then(|id| self.nav.scope_of(id)).cloned()
+            && &scope != widget.id_ref()
+            && let Some(r) = widget.find_node(&scope, |node| {
+                self.next_nav_focus_impl(node, target, advance, source)
+            })
+        {
+            return r;
+        }
 
         // Whether to restart from the beginning on failure
         let restart = focus.is_some();
@@ -283,3 +594,115 @@ impl<'a> EventCx<'a> {
         self.set_nav_focus_impl(widget, opt_id, source);
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::geom::{Coord, Size};
+
+    /// Rect of cell `(col, row)` of a grid of 100×50 cells with spacing 10
+    fn cell(col: i32, row: i32) -> Rect {
+        Rect::new(Coord(col * 110, row * 60), Size(100, 50))
+    }
+
+    /// Find the best of `candidates` from `from` in direction `dir`
+    fn best(from: Rect, candidates: &[Rect], dir: Direction) -> Option<Rect> {
+        candidates
+            .iter()
+            .filter_map(|to| spatial_score(from, *to, dir).map(|score| (score, *to)))
+            .min_by_key(|(score, _)| *score)
+            .map(|(_, to)| to)
+    }
+
+    #[test]
+    fn spatial_score_grid() {
+        let grid: Vec<_> = (0..3)
+            .flat_map(|row| (0..3).map(move |col| cell(col, row)))
+            .collect();
+        let from = cell(1, 1);
+        let others: Vec<_> = grid.iter().copied().filter(|r| *r != from).collect();
+
+        assert_eq!(best(from, &others, Direction::Right), Some(cell(2, 1)));
+        assert_eq!(best(from, &others, Direction::Left), Some(cell(0, 1)));
+        assert_eq!(best(from, &others, Direction::Down), Some(cell(1, 2)));
+        assert_eq!(best(from, &others, Direction::Up), Some(cell(1, 0)));
+
+        let corner = cell(2, 2);
+        let others: Vec<_> = grid.iter().copied().filter(|r| *r != corner).collect();
+        assert_eq!(best(corner, &others, Direction::Right), None);
+        assert_eq!(best(corner, &others, Direction::Down), None);
+    }
+
+    #[test]
+    fn spatial_score_prefers_aligned() {
+        let from = cell(0, 0);
+        // Nearer on the primary axis, but far from aligned
+        let offset = Rect::new(Coord(105, 120), Size(100, 50));
+        let aligned = cell(2, 0);
+        assert_eq!(
+            best(from, &[offset, aligned], Direction::Right),
+            Some(aligned)
+        );
+
+        // Behind or overlapping the center of from: not a candidate
+        let overlap = Rect::new(Coord(40, 0), Size(100, 50));
+        assert_eq!(spatial_score(from, overlap, Direction::Right), None);
+        assert_eq!(spatial_score(from, cell(1, 0), Direction::Left), None);
+    }
+
+    #[test]
+    fn scope_confines_tab() {
+        let root = Id::ROOT.make_child(0);
+        let outer = root.make_child(1);
+        let inner = outer.make_child(2);
+        let mut nav = NavFocus::default();
+        nav.register_scope(&outer);
+        nav.register_scope(&inner);
+
+        // Tab navigation is constrained to the innermost scope containing focus
+        assert_eq!(nav.scope_of(&root.make_child(0)), None);
+        assert_eq!(nav.scope_of(&outer.make_child(0)), Some(&outer));
+        assert_eq!(nav.scope_of(&inner.make_child(0)), Some(&inner));
+        assert_eq!(nav.scope_of(&inner), Some(&inner));
+    }
+
+    #[test]
+    fn scope_restore_on_exit() {
+        let root = Id::ROOT.make_child(0);
+        let button = root.make_child(0);
+        let scope = root.make_child(1);
+        let field = scope.make_child(3);
+        let mut nav = NavFocus::default();
+
+        nav.set_focus(Some(button.clone()));
+        assert_eq!(nav.enter_scope(scope.clone()), scope);
+        nav.set_focus(Some(field.clone()));
+        assert_eq!(nav.exit_scope(&scope), Some(button.clone()));
+        nav.set_focus(Some(button.clone()));
+
+        // Re-entry targets the last focus within the scope
+        assert_eq!(nav.enter_scope(scope.clone()), field);
+        // Focus has already left the scope: do not restore
+        nav.set_focus(Some(root.make_child(2)));
+        assert_eq!(nav.exit_scope(&scope), None);
+    }
+
+    #[test]
+    fn prune_stale_scopes() {
+        let root = Id::ROOT.make_child(0);
+        let [a, b] = [root.make_child(0), root.make_child(1)];
+        let mut nav = NavFocus::default();
+        nav.set_focus(Some(root.make_child(2)));
+        nav.enter_scope(a.clone());
+        nav.register_scope(&b);
+
+        nav.mark_scopes_stale();
+        nav.register_scope(&b);
+        nav.prune_scopes();
+
+        // A widget reusing the id of a removed scope does not inherit its state
+        assert_eq!(nav.scope_of(&a.make_child(0)), None);
+        assert_eq!(nav.exit_scope(&a), None);
+        assert_eq!(nav.scope_of(&b.make_child(0)), Some(&b));
+    }
+}
@@ -370,6 +370,14 @@ impl EventState {
                 .is_none_or(|grab| grab.start_id == w_id)
     }
 
+    /// Returns true if the mouse pointer is over `id` or any descendant of `id`
+    pub(crate) fn is_under_mouse_any(&self, id: &Id) -> bool {
+        self.mouse
+            .over
+            .as_ref()
+            .is_some_and(|over| id.is_ancestor_of(over))
+    }
+
     /// Returns true if there is a mouse or touch grab on `id` or any descendant of `id`
     pub fn any_grab_on(&self, id: &Id) -> bool {
         if self
@@ -395,10 +403,11 @@ impl EventState {
     /// mode [`GrabMode::Grab`] are tracked for velocity.
     pub fn press_velocity(&self, source: PressSource) -> Option<Vec2> {
         let evc = self.config().event();
+        let now = self.clock.now();
         if source.is_mouse() {
-            Some(self.mouse.samples.velocity(evc.kinetic_timeout()))
+            Some(self.mouse.samples.velocity(now, evc.kinetic_timeout()))
         } else if let Some(finger_id) = source.finger_id() {
-            self.touch.velocity(finger_id, evc)
+            self.touch.velocity(now, finger_id, evc)
         } else {
             unreachable!()
         }
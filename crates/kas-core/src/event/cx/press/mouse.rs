@@ -10,6 +10,7 @@ use crate::event::{
     Event, EventCx, EventState, FocusSource, NavAdvance, PressStart, ScrollDelta, TimerHandle,
 };
 use crate::geom::{Affine, Coord, DVec2, Vec2};
+use crate::window::InspectPicked;
 use crate::window::WindowErased;
 use crate::window::WindowWidget;
 use crate::{ActionRedraw, Id, Node, Tile, TileExt};
@@ -69,7 +70,7 @@ pub(crate) struct Mouse {
     old_icon: CursorIcon,        // last icon set (grab or non-grab)
     last_click_button: Option<MouseButton>,
     last_click_repetitions: u32,
-    last_click_timeout: Instant,
+    last_click_timeout: Option<Instant>,
     last_pin: Option<(Id, DVec2)>,
     pub(super) grab: Option<MouseGrab>,
     tooltip_source: Option<Id>,
@@ -87,7 +88,7 @@ impl Default for Mouse {
             old_icon: CursorIcon::Default,
             last_click_button: None,
             last_click_repetitions: 0,
-            last_click_timeout: Instant::now(),
+            last_click_timeout: None,
             last_pin: None,
             grab: None,
             tooltip_source: None,
@@ -267,6 +268,9 @@ impl<'a> EventCx<'a> {
             }
             self.mouse.over = w_id.clone();
             self.mouse.icon = Default::default();
+            if self.inspect_picker.is_some() {
+                self.action_redraw = Some(ActionRedraw);
+            }
 
             let tooltip_expiry_started = self.mouse.tooltip_expiry_started;
             let update_timer = if let Some(source) = &mut self.mouse.tooltip_source {
@@ -384,7 +388,7 @@ impl<'a> EventCx<'a> {
     ) {
         let delta: Vec2 = (position - self.mouse.last_position).cast_approx();
         if delta.is_finite() {
-            self.mouse.samples.push_delta(delta);
+            self.mouse.samples.push_delta(self.clock.now(), delta);
         }
         self.mouse.last_position = position;
 
@@ -467,10 +471,23 @@ impl<'a> EventCx<'a> {
         state: ElementState,
         button: MouseButton,
     ) {
+        if state == ElementState::Pressed
+            && let Some(inspector) = self.inspect_picker.take()
+        {
+            // The press is consumed by the inspector's pick mode
+            let id = self.mouse.over.clone();
+            self.send(inspector, InspectPicked(id));
+            self.action_redraw = Some(ActionRedraw);
+            return;
+        }
+
         if state == ElementState::Pressed {
-            let now = Instant::now();
+            let now = self.clock.now();
             if Some(button) != self.mouse.last_click_button
-                || self.mouse.last_click_timeout < now
+                || self
+                    .mouse
+                    .last_click_timeout
+                    .is_none_or(|timeout| timeout < now)
                 || (self.mouse.last_position - self.mouse.last_click_position).distance_l_inf()
                     > self.config.event().double_click_dist_thresh().into()
             {
@@ -478,7 +495,7 @@ impl<'a> EventCx<'a> {
                 self.mouse.last_click_repetitions = 0;
             }
             self.mouse.last_click_repetitions += 1;
-            self.mouse.last_click_timeout = now + DOUBLE_CLICK_TIMEOUT;
+            self.mouse.last_click_timeout = Some(now + DOUBLE_CLICK_TIMEOUT);
             self.mouse.last_click_position = self.mouse.last_position;
         }
 
@@ -12,6 +12,7 @@ use crate::geom::{Affine, DVec2, Vec2};
 use crate::{ActionRedraw, Id, Node};
 use cast::{Cast, CastApprox, CastFloat, Conv};
 use smallvec::SmallVec;
+use std::time::Instant;
 use winit::event::FingerId;
 
 const MAX_TOUCHES: usize = 10;
@@ -215,7 +216,12 @@ impl Touch {
         }
     }
 
-    pub(super) fn velocity(&self, finger_id: FingerId, evc: EventWindowConfig<'_>) -> Option<Vec2> {
+    pub(super) fn velocity(
+        &self,
+        now: Instant,
+        finger_id: FingerId,
+        evc: EventWindowConfig<'_>,
+    ) -> Option<Vec2> {
         let v = self
             .touch_grab
             .iter()
@@ -224,7 +230,7 @@ impl Touch {
             .unwrap_or(u16::MAX);
         self.velocity
             .get(v as usize)
-            .map(|sampler| sampler.velocity(evc.kinetic_timeout()))
+            .map(|sampler| sampler.velocity(now, evc.kinetic_timeout()))
     }
 }
 
@@ -354,7 +360,7 @@ impl<'a> EventCx<'a> {
 
             let vi = self.touch.touch_grab[index].vel_index as usize;
             if vi < VELOCITY_LEN {
-                self.touch.velocity[vi].push_delta(delta);
+                self.touch.velocity[vi].push_delta(self.clock.now(), delta);
             }
 
             let grab = &mut self.touch.touch_grab[index];
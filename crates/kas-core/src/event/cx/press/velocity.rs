@@ -48,9 +48,8 @@ impl Samples {
         self.next = 0;
     }
 
-    /// Push a new sample
-    pub(super) fn push_delta(&mut self, delta: Vec2) {
-        let now = Instant::now();
+    /// Push a new sample taken at time `now`
+    pub(super) fn push_delta(&mut self, now: Instant, delta: Vec2) {
         if self.samples.len() < MAX_SAMPLES {
             self.samples.push((now, delta));
         } else {
@@ -59,11 +58,10 @@ impl Samples {
         }
     }
 
-    /// Calculate average velocity over a given sample `period`
+    /// Calculate average velocity over a given sample `period` ending at `now`
     ///
     /// Units: pixels per second.
-    pub(super) fn velocity(&self, period: Duration) -> Vec2 {
-        let now = Instant::now();
+    pub(super) fn velocity(&self, now: Instant, period: Duration) -> Vec2 {
         let start = now - period; // saturating_sub
 
         let mut delta = Vec2::ZERO;
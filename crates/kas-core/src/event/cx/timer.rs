@@ -37,6 +37,19 @@ impl TimerHandle {
 }
 
 impl EventState {
+    /// Get the current time
+    ///
+    /// This is the time according to the runner's [`Clock`], which is usually
+    /// the system time but may be a virtual time (e.g. when replaying input or
+    /// in tests). Widgets should prefer this to [`Instant::now`] when timing
+    /// animations and delays.
+    ///
+    /// [`Clock`]: crate::runner::Clock
+    #[inline]
+    pub fn now(&self) -> Instant {
+        self.clock.now()
+    }
+
     /// Get the next resume time
     pub(crate) fn next_resume(&self) -> Option<Instant> {
         self.time_updates.last().map(|time| time.0)
@@ -63,7 +76,7 @@ impl EventState {
     /// (see [`TimerHandle`] documentation).
     pub fn request_timer(&mut self, id: Id, handle: TimerHandle, delay: Duration) {
         debug_assert!(id.is_valid());
-        let time = Instant::now() + delay;
+        let time = self.clock.now() + delay;
         if let Some(row) = self
             .time_updates
             .iter_mut()
@@ -126,7 +139,7 @@ impl<'a> EventCx<'a> {
 
     /// Update widgets due to timer
     pub(crate) fn update_timer(&mut self, mut widget: Node<'_>) {
-        let now = Instant::now();
+        let now = self.clock.now();
 
         // assumption: time_updates are sorted in reverse order
         while !self.time_updates.is_empty() {
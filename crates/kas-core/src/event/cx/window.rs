@@ -8,13 +8,15 @@
 use super::{EventCx, EventState, PopupState};
 use crate::cast::Cast;
 use crate::event::{Event, FocusSource};
-use crate::runner::{AppData, Platform, RunnerT, WindowDataErased};
+use crate::geom::DVec2;
+use crate::runner::{AppData, InputEvent, Platform, RunnerT, WindowDataErased};
+use crate::text::format::RichText;
 use crate::theme::ThemeSize;
 #[cfg(all(wayland_platform, feature = "clipboard"))]
 use crate::util::warn_about_error;
-use crate::window::{PopupDescriptor, Window, WindowId, WindowWidget};
-use crate::{ActionRedraw, Id, Node, WindowActions};
-use winit::event::{ButtonSource, ElementState, PointerKind, PointerSource};
+use crate::window::{PopupDescriptor, Toast, Window, WindowId, WindowWidget};
+use crate::{ActionRedraw, Id, Live, Node, WindowActions};
+use winit::event::{ButtonSource, ElementState, PointerSource};
 use winit::window::ResizeDirection;
 
 impl EventState {
@@ -29,6 +31,56 @@ impl EventState {
         self.window_has_focus
     }
 
+    /// Show a toast notification
+    ///
+    /// The `toast` is shown as an overlay in the bottom-right corner of the
+    /// current window, stacked with any other toasts. See [`Toast`].
+    pub fn show_toast(&mut self, toast: Toast) {
+        let id = Id::ROOT.make_child(self.window_id.get().cast());
+        self.send(id, toast);
+    }
+
+    /// Announce a message via screen readers
+    ///
+    /// The `text` is not displayed; it is reported to accessibility tools
+    /// (where the `accesskit` feature is enabled) as an update to a live
+    /// region with the given priority. [`Live::Assertive`] interrupts the user
+    /// thus should be reserved for urgent messages. [`Live::Off`] does nothing.
+    pub fn announce(&mut self, text: impl ToString, live: Live) {
+        #[cfg(feature = "accesskit")]
+        if self.accesskit_is_enabled() && live != Live::Off {
+            self.announcement_serial = self.announcement_serial.wrapping_add(1);
+            self.announcement = Some((text.to_string(), live));
+            self.action_redraw = Some(ActionRedraw);
+        }
+        #[cfg(not(feature = "accesskit"))]
+        let _ = (text, live);
+    }
+
+    /// Mark the widget `id` as a live region
+    ///
+    /// Changes to the text content of a live region and its descendants are
+    /// announced by screen readers according to `live`. Use [`Live::Off`] to
+    /// remove the mark. Widgets may also mark themselves as live regions via
+    /// [`RoleCx::set_live`](crate::RoleCx::set_live).
+    ///
+    /// Marks are cleared when the window is reconfigured, except for those set
+    /// again during configuration; thus this should usually be called from
+    /// [`Events::configure`](crate::Events::configure).
+    pub fn set_live_region(&mut self, id: Id, live: Live) {
+        #[cfg(feature = "accesskit")]
+        {
+            if live == Live::Off {
+                self.live_regions.remove(&id);
+            } else {
+                self.live_regions.insert(id, (live, true));
+            }
+            self.action_redraw = Some(ActionRedraw);
+        }
+        #[cfg(not(feature = "accesskit"))]
+        let _ = (id, live);
+    }
+
     // Remove popup at index and return its [`WindowId`]
     //
     // Panics if `index` is out of bounds.
@@ -42,13 +94,30 @@ impl EventState {
         }
         self.mouse.tooltip_popup_close(&state.desc.parent);
 
-        if let Some(id) = state.old_nav_focus {
+        // Restore focus if taken by the pop-up or moved into it
+        if let Some(id) = state.old_nav_focus
+            && (state.took_focus
+                || self
+                    .nav_focus()
+                    .is_some_and(|f| state.desc.id.is_ancestor_of(f)))
+        {
             self.set_nav_focus(id, FocusSource::Synthetic);
         }
 
         state.id
     }
 
+    /// Handle closure of a window
+    ///
+    /// If `id` is a modal window opened from this window, navigation focus is
+    /// restored to the widget which had focus when it was opened.
+    pub(crate) fn modal_window_closed(&mut self, id: WindowId) {
+        if let Some(index) = self.modal_windows.iter().position(|(w, _)| *w == id) {
+            let (_, focus) = self.modal_windows.remove(index);
+            self.set_nav_focus(focus, FocusSource::Synthetic);
+        }
+    }
+
     pub(crate) fn confirm_popup_is_sized(&mut self, id: WindowId) {
         for popup in &mut self.popups {
             if popup.id == id {
@@ -174,15 +243,15 @@ impl<'a> EventCx<'a> {
 
         let parent_id = self.window.window_id();
         let id = self.runner.add_popup(parent_id, popup.clone());
-        let mut old_nav_focus = None;
+        let old_nav_focus = self.nav_focus().cloned();
         if set_focus {
-            old_nav_focus = self.nav_focus().cloned();
             self.clear_nav_focus();
         }
         self.popups.push(PopupState {
             id,
             desc: popup,
             old_nav_focus,
+            took_focus: set_focus,
             is_sized: false,
         });
         id
@@ -215,13 +284,19 @@ impl<'a> EventCx<'a> {
     /// If `modal`, then the new `window` is considered owned by this window
     /// (the window the calling widget belongs to), preventing interaction with
     /// this window until the new `window` has been closed. **Note:** this is
-    /// mostly unimplemented; see [`Window::set_modal_with_parent`].
+    /// mostly unimplemented; see [`Window::set_modal_with_parent`]. When a
+    /// modal window closes, navigation focus in this window is restored to
+    /// the widget which had focus when it was opened.
     #[inline]
     pub fn add_dataless_window(&mut self, mut window: Window<()>, modal: bool) -> WindowId {
         if modal {
             window.set_modal_with_parent(self.window_id);
         }
-        self.runner.add_dataless_window(window)
+        let id = self.runner.add_dataless_window(window);
+        if modal {
+            self.push_modal_window(id);
+        }
+        id
     }
 
     /// Add a window able to access top-level app data
@@ -240,23 +315,36 @@ impl<'a> EventCx<'a> {
     /// If `modal`, then the new `window` is considered owned by this window
     /// (the window the calling widget belongs to), preventing interaction with
     /// this window until the new `window` has been closed. **Note:** this is
-    /// mostly unimplemented; see [`Window::set_modal_with_parent`].
+    /// mostly unimplemented; see [`Window::set_modal_with_parent`]. When a
+    /// modal window closes, navigation focus in this window is restored to
+    /// the widget which had focus when it was opened.
     #[inline]
     pub fn add_window<Data: AppData>(&mut self, mut window: Window<Data>, modal: bool) -> WindowId {
         if modal {
             window.set_modal_with_parent(self.window_id);
         }
         let data_type_id = std::any::TypeId::of::<Data>();
-        unsafe {
+        let id = unsafe {
             let window: Window<()> = std::mem::transmute(window);
             self.runner.add_window(window, data_type_id)
+        };
+        if modal {
+            self.push_modal_window(id);
+        }
+        id
+    }
+
+    // Remember navigation focus to restore when modal window `id` closes
+    fn push_modal_window(&mut self, id: WindowId) {
+        if let Some(focus) = self.nav_focus().cloned() {
+            self.modal_windows.push((id, focus));
         }
     }
 
     /// Close a window or pop-up
     ///
     /// Navigation focus will return to whichever widget had focus before
-    /// the popup was open.
+    /// the popup (or modal window) was open.
     pub fn close_window(&mut self, mut id: WindowId) {
         for (index, p) in self.popups.iter().enumerate() {
             if p.id == id {
@@ -320,6 +408,27 @@ impl<'a> EventCx<'a> {
         self.runner.set_clipboard(content)
     }
 
+    /// Get rich text contents of the clipboard
+    ///
+    /// The system clipboard holds only plain text. Rich text copied within
+    /// this application (see [`Self::set_rich_clipboard`]) is returned if its
+    /// plain text matches the current clipboard contents.
+    pub fn get_rich_clipboard(&mut self) -> Option<RichText> {
+        let content = self.get_clipboard()?;
+        self.runner
+            .rich_clipboard()
+            .clone()
+            .filter(|text| text.as_str() == content)
+    }
+
+    /// Set rich text contents of the clipboard
+    ///
+    /// This is shared by all windows of the application. The caller should
+    /// also set the plain text contents via [`Self::set_clipboard`].
+    pub fn set_rich_clipboard(&mut self, content: RichText) {
+        *self.runner.rich_clipboard() = Some(content);
+    }
+
     /// True if the primary buffer is enabled
     #[inline]
     pub fn has_primary(&self) -> bool {
@@ -374,9 +483,11 @@ impl<'a> EventCx<'a> {
 
     /// Handle a winit `WindowEvent`.
     ///
-    /// Note that some event types are not handled, since for these
-    /// events the graphics backend must take direct action anyway:
-    /// `Resized(size)`, `RedrawRequested`, `HiDpiFactorChanged(factor)`.
+    /// This handles only events not representable as an [`InputEvent`] (see
+    /// [`Self::handle_input`]); others are ignored. Note that some event types
+    /// are not handled, since for these events the graphics backend must take
+    /// direct action anyway: `Resized(size)`, `RedrawRequested`,
+    /// `HiDpiFactorChanged(factor)`.
     pub(crate) fn handle_winit<A>(
         &mut self,
         win: &mut dyn WindowWidget<Data = A>,
@@ -386,13 +497,45 @@ impl<'a> EventCx<'a> {
         use winit::event::WindowEvent::*;
 
         match event {
-            CloseRequested => self.close_own_window(),
             /* Not yet supported: see #98
             DroppedFile(path) => ,
             HoveredFile(path) => ,
             HoveredFileCancelled => ,
             */
-            Focused(state) => {
+            PointerMoved {
+                position,
+                source: PointerSource::Touch { finger_id, .. },
+                ..
+            } => self.handle_touch_moved(win.as_node(data), finger_id, position.into()),
+            PointerButton {
+                state,
+                position,
+                button: ButtonSource::Touch { finger_id, .. },
+                ..
+            } => match state {
+                ElementState::Pressed => {
+                    self.handle_touch_start(win.as_node(data), finger_id, position.into())
+                }
+                ElementState::Released => {
+                    self.handle_touch_end(win.as_node(data), finger_id, position.into())
+                }
+            },
+            _ => (),
+        }
+    }
+
+    /// Handle an [`InputEvent`]
+    ///
+    /// These are events received from winit or replayed from a recording.
+    pub(crate) fn handle_input<A>(
+        &mut self,
+        win: &mut dyn WindowWidget<Data = A>,
+        data: &A,
+        event: InputEvent,
+    ) {
+        match event {
+            InputEvent::CloseRequested => self.close_own_window(),
+            InputEvent::Focused(state) => {
                 self.window_has_focus = state;
                 if state {
                     // Required to restart theme animations
@@ -404,51 +547,21 @@ impl<'a> EventCx<'a> {
                     }
                 }
             }
-            KeyboardInput {
-                event,
-                is_synthetic,
-                ..
-            } => self.keyboard_input(win.as_node(data), event, is_synthetic),
-            ModifiersChanged(modifiers) => self.modifiers_changed(modifiers.state()),
-            Ime(event) => self.ime_event(win.as_node(data), event),
-            PointerMoved {
-                position, source, ..
-            } => match source {
-                PointerSource::Mouse => self.handle_pointer_moved(win, data, position.into()),
-                PointerSource::Touch { finger_id, .. } => {
-                    self.handle_touch_moved(win.as_node(data), finger_id, position.into())
-                }
-                _ => (),
-            },
-            PointerEntered {
-                kind: PointerKind::Mouse,
-                ..
-            } => self.handle_pointer_entered(),
-            PointerLeft {
-                kind: PointerKind::Mouse,
-                ..
-            } => self.handle_pointer_left(win.as_node(data)),
-            MouseWheel { delta, .. } => self.handle_mouse_wheel(win.as_node(data), delta),
-            PointerButton {
-                state,
-                position,
-                button,
-                ..
-            } => match button {
-                ButtonSource::Mouse(button) => {
-                    self.handle_mouse_input(win.as_node(data), state, button)
-                }
-                ButtonSource::Touch { finger_id, .. } => match state {
-                    ElementState::Pressed => {
-                        self.handle_touch_start(win.as_node(data), finger_id, position.into())
-                    }
-                    ElementState::Released => {
-                        self.handle_touch_end(win.as_node(data), finger_id, position.into())
-                    }
-                },
-                _ => (),
-            },
-            _ => (),
+            InputEvent::Key(input) => {
+                let (event, is_synthetic) = input.into_event();
+                self.keyboard_input(win.as_node(data), event, is_synthetic)
+            }
+            InputEvent::Modifiers(state) => self.modifiers_changed(state),
+            InputEvent::Ime(event) => self.ime_event(win.as_node(data), event),
+            InputEvent::PointerMoved { x, y } => self.handle_pointer_moved(win, data, DVec2(x, y)),
+            InputEvent::PointerEntered => self.handle_pointer_entered(),
+            InputEvent::PointerLeft => self.handle_pointer_left(win.as_node(data)),
+            InputEvent::MouseWheel(delta) => self.handle_mouse_wheel(win.as_node(data), delta),
+            InputEvent::MouseButton { state, button } => {
+                self.handle_mouse_input(win.as_node(data), state, button)
+            }
+            // Handled by the runner:
+            InputEvent::SurfaceResized(..) => (),
         }
     }
 }
@@ -38,6 +38,7 @@
 mod align;
 mod flow_solver;
 mod grid_solver;
+mod overlay;
 mod row_solver;
 mod size_rules;
 mod size_types;
@@ -50,6 +51,7 @@ use crate::dir::{Direction, Directional};
 pub use align::{Align, AlignHints, AlignPair};
 pub use flow_solver::{FlowSetter, FlowSolver, FlowStorage};
 pub use grid_solver::{DefaultWithLen, GridCellInfo, GridDimensions, GridSetter, GridSolver};
+pub(crate) use overlay::{BASELINE_COLOR, LayoutRecord, draw_layout_overlay, record_layout};
 pub use row_solver::{RowPositionSolver, RowSetter, RowSolver};
 pub use size_rules::SizeRules;
 pub use size_types::*;
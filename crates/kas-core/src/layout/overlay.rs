@@ -0,0 +1,101 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Layout debug overlay
+
+use super::{AxisInfo, SizeRules, Stretch};
+use crate::Node;
+use crate::cast::Conv;
+use crate::draw::color::Rgba;
+use crate::geom::{Offset, Quad, Rect, Size, Vec2};
+use crate::theme::{DrawCx, SizeCx};
+
+/// Colour of margin bands
+const MARGIN_COLOR: Rgba = Rgba::rgba(1.0, 0.6, 0.0, 0.25);
+
+/// Colour of text baselines
+pub(crate) const BASELINE_COLOR: Rgba = Rgba::rgb(0.0, 0.7, 0.9);
+
+/// Colour used to outline a rect edge given the [`Stretch`] policy on that axis
+fn stretch_color(stretch: Stretch) -> Rgba {
+    match stretch {
+        Stretch::None => Rgba::rgb(0.5, 0.5, 0.5),
+        Stretch::Filler => Rgba::rgb(0.2, 0.4, 1.0),
+        Stretch::Low => Rgba::rgb(0.1, 0.8, 0.2),
+        Stretch::High => Rgba::rgb(0.9, 0.8, 0.0),
+        Stretch::Maximize => Rgba::rgb(1.0, 0.1, 0.1),
+    }
+}
+
+/// Layout of one widget, as recorded for the overlay
+#[derive(Clone, Debug)]
+pub(crate) struct LayoutRecord {
+    /// Widget rect in window coordinates
+    rect: Rect,
+    horiz: SizeRules,
+    vert: SizeRules,
+}
+
+/// Record the layout of `node` and its sized descendants
+///
+/// This re-evaluates [`SizeRules`] of each widget, thus a full solve must
+/// follow to restore solver state. `translation` is the scroll offset applied
+/// to `node`'s coordinate space.
+pub(crate) fn record_layout(
+    mut node: Node<'_>,
+    cx: &mut SizeCx,
+    translation: Offset,
+    records: &mut Vec<LayoutRecord>,
+) {
+    if !node.as_tile().status().is_sized() {
+        return;
+    }
+
+    let rect = node.rect();
+    let horiz = node.size_rules(cx, AxisInfo::new(false, None));
+    let vert = node.size_rules(cx, AxisInfo::new(true, Some(rect.size.0)));
+    records.push(LayoutRecord {
+        rect: rect - translation,
+        horiz,
+        vert,
+    });
+
+    for index in node.as_tile().child_indices() {
+        let translation = translation + node.as_tile().translation(index);
+        if let Some(child) = node.get_child(index) {
+            record_layout(child, cx, translation, records);
+        }
+    }
+}
+
+/// Draw recorded layout
+///
+/// Each rect is outlined: top and bottom edges are coloured by horizontal
+/// stretch policy, left and right edges by vertical stretch policy. Margins
+/// are drawn as translucent bands outside the rect.
+pub(crate) fn draw_layout_overlay(draw: &mut DrawCx, records: &[LayoutRecord]) {
+    for record in records {
+        let rect = record.rect;
+        let (left, right) = record.horiz.margins_i32();
+        let (top, bottom) = record.vert.margins_i32();
+        let inner = Quad::conv(rect);
+        if left != 0 || right != 0 || top != 0 || bottom != 0 {
+            let outer = Rect::new(
+                rect.pos - Offset(left, top),
+                rect.size + Size(left + right, top + bottom),
+            );
+            draw.draw().frame(Quad::conv(outer), inner, MARGIN_COLOR);
+        }
+
+        let h = stretch_color(record.horiz.stretch());
+        let v = stretch_color(record.vert.stretch());
+        let (a, b) = (inner.a, inner.b);
+        let draw = draw.draw();
+        draw.rect(Quad::from_coords(a, Vec2(b.0, a.1 + 1.0)), h);
+        draw.rect(Quad::from_coords(Vec2(a.0, b.1 - 1.0), b), h);
+        draw.rect(Quad::from_coords(a, Vec2(a.0 + 1.0, b.1)), v);
+        draw.rect(Quad::from_coords(Vec2(b.0 - 1.0, a.1), b), v);
+    }
+}
@@ -35,6 +35,7 @@ pub use kas_macros::{cell_collection, collection, impl_anon, impl_scope, impl_se
 pub use kas_macros::{layout, widget, widget_index};
 
 // public implementations:
+#[cfg(automation)] pub mod automation;
 pub mod config;
 pub mod dir;
 pub mod draw;
@@ -14,6 +14,7 @@
 use crate::event::PhysicalKey;
 #[allow(unused)] use crate::event::{EventCx, EventState};
 use crate::geom::Offset;
+use crate::text::CursorRange;
 use crate::window::icon::Icon;
 use std::any::{Any, TypeId};
 use std::fmt::Debug;
@@ -59,6 +60,12 @@ pub struct SetValueText(pub String);
 #[derive(Clone, Debug)]
 pub struct ReplaceSelectedText(pub String);
 
+/// Set the cursor and selection of a text input
+///
+/// This does not modify the text.
+#[derive(Clone, Debug)]
+pub struct SetTextSelection(pub CursorRange);
+
 /// Set an index
 #[derive(Clone, Debug)]
 pub struct SetIndex(pub usize);
@@ -92,6 +99,14 @@ pub struct SetWindowTitle(pub String);
 #[derive(Clone, Debug)]
 pub struct SetWindowIcon(pub Option<Icon>);
 
+/// Enable or disable the window's layout debug overlay
+///
+/// When enabled, the window draws every widget's rect and margins, colour-coded
+/// by stretch policy (horizontal edges by horizontal stretch, vertical edges by
+/// vertical stretch), plus text baselines. See [`Window`](crate::window::Window).
+#[derive(Clone, Debug)]
+pub struct SetLayoutOverlay(pub bool);
+
 trait AnyDebug: Any + Debug {}
 impl<T: Any + Debug> AnyDebug for T {}
 
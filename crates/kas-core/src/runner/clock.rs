@@ -0,0 +1,177 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Runner clock
+
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use winit::event_loop::EventLoopProxy;
+
+#[derive(Default)]
+struct State {
+    /// Virtual time, if manual
+    time: Option<Instant>,
+    /// Wakes the event loop when virtual time is advanced
+    waker: Option<EventLoopProxy>,
+}
+
+/// A source of the current time
+///
+/// The runner owns a clock which is shared with each window and used for all
+/// timing of timers ([`EventState::request_timer`]), animations
+/// ([`Draw::animate`]), kinetic scrolling and similar. Widgets may read it via
+/// [`EventState::now`].
+///
+/// By default the clock reports the system time. A *manual* clock (see
+/// [`Self::new_manual`]) instead reports a virtual time which only changes
+/// when advanced (by any clone of the clock); this is useful to make tests
+/// involving timers and animations deterministic. Replay of recorded input
+/// (see [`Recording`](super::Recording)) also uses virtual time.
+///
+/// Cloning a clock yields a handle to the same time source. Clones may be sent
+/// to other threads; advancing virtual time wakes the runner's event loop so
+/// that timers which have become due are handled. While using virtual time the
+/// event loop does not wake on its own to handle timers.
+///
+/// [`EventState::request_timer`]: crate::event::EventState::request_timer
+/// [`EventState::now`]: crate::event::EventState::now
+/// [`Draw::animate`]: crate::draw::Draw::animate
+#[derive(Clone, Default)]
+pub struct Clock(Arc<Mutex<State>>);
+
+impl fmt::Debug for Clock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Clock").field(&self.state().time).finish()
+    }
+}
+
+impl Clock {
+    /// Construct a clock reporting the system time
+    ///
+    /// This is the default.
+    #[inline]
+    pub fn new_system() -> Self {
+        Clock::default()
+    }
+
+    /// Construct a manual clock
+    ///
+    /// The clock starts at the current system time and only advances when
+    /// [`Self::advance`] or [`Self::advance_to`] is called.
+    pub fn new_manual() -> Self {
+        let clock = Clock::default();
+        clock.set_virtual(Instant::now());
+        clock
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, State> {
+        // The state is always valid, even if a thread panicked while holding the lock
+        self.0.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// True if this clock reports virtual time
+    #[inline]
+    pub fn is_manual(&self) -> bool {
+        self.state().time.is_some()
+    }
+
+    /// Get the current time
+    #[inline]
+    pub fn now(&self) -> Instant {
+        self.state().time.unwrap_or_else(Instant::now)
+    }
+
+    /// Advance virtual time by `duration`
+    ///
+    /// Does nothing when reporting system time.
+    pub fn advance(&self, duration: Duration) {
+        let mut state = self.state();
+        if let Some(now) = state.time {
+            state.time = Some(now + duration);
+            if let Some(waker) = state.waker.as_ref() {
+                waker.wake_up();
+            }
+        }
+    }
+
+    /// Advance virtual time to `time`
+    ///
+    /// Virtual time never goes backwards. Does nothing when reporting system
+    /// time.
+    pub fn advance_to(&self, time: Instant) {
+        let mut state = self.state();
+        if let Some(now) = state.time
+            && time > now
+        {
+            state.time = Some(time);
+            if let Some(waker) = state.waker.as_ref() {
+                waker.wake_up();
+            }
+        }
+    }
+
+    /// Switch to virtual time, starting from `time`
+    pub(crate) fn set_virtual(&self, time: Instant) {
+        self.state().time = Some(time);
+    }
+
+    /// Switch back to system time
+    pub(crate) fn set_system(&self) {
+        self.state().time = None;
+    }
+
+    /// Set the event loop to wake when virtual time is advanced
+    pub(crate) fn set_waker(&self, waker: EventLoopProxy) {
+        self.state().waker = Some(waker);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn manual_clock() {
+        let clock = Clock::new_manual();
+        assert!(clock.is_manual());
+        let start = clock.now();
+        assert_eq!(clock.now(), start);
+
+        let shared = clock.clone();
+        shared.advance(Duration::from_millis(100));
+        assert_eq!(clock.now(), start + Duration::from_millis(100));
+
+        // Time never goes backwards
+        clock.advance_to(start);
+        assert_eq!(shared.now(), start + Duration::from_millis(100));
+        clock.advance_to(start + Duration::from_secs(1));
+        assert_eq!(shared.now(), start + Duration::from_secs(1));
+
+        clock.set_system();
+        assert!(!shared.is_manual());
+    }
+
+    #[test]
+    fn advance_from_thread() {
+        let clock = Clock::new_manual();
+        let start = clock.now();
+
+        let shared = clock.clone();
+        std::thread::spawn(move || shared.advance(Duration::from_secs(1)))
+            .join()
+            .unwrap();
+        assert_eq!(clock.now(), start + Duration::from_secs(1));
+    }
+
+    #[test]
+    fn system_clock() {
+        let clock = Clock::new_system();
+        assert!(!clock.is_manual());
+        let before = Instant::now();
+        clock.advance(Duration::from_secs(3600));
+        assert!(clock.now() < before + Duration::from_secs(3600));
+    }
+}
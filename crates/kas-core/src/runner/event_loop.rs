@@ -7,6 +7,8 @@
 
 use super::{AppData, GraphicsInstance, Pending, Shared};
 use super::{ProxyAction, Window};
+#[cfg(automation)]
+use crate::automation::{Request, Response};
 use crate::theme::Theme;
 use crate::window::WindowId;
 use std::collections::HashMap;
@@ -47,19 +49,7 @@ where
 
         match cause {
             StartCause::ResumeTimeReached { .. } | StartCause::WaitCancelled { .. } => {
-                let mut first_future = 0;
-                for (i, resume) in self.resumes.iter().enumerate() {
-                    if resume.0 > Instant::now() {
-                        break;
-                    }
-                    first_future = i;
-
-                    if let Some(w) = self.windows.get_mut(&resume.1) {
-                        w.update_timer(&mut self.shared, &self.data, resume.0)
-                    }
-                }
-
-                self.resumes.drain(..first_future);
+                self.update_timers();
             }
             StartCause::Poll => (),
             StartCause::Init => (),
@@ -91,6 +81,10 @@ where
                         window.accesskit_event(&mut self.shared, &self.data, event);
                     }
                 }
+                #[cfg(automation)]
+                ProxyAction::Automation(request, reply) => {
+                    let _ = reply.send(self.automation(request));
+                }
             }
         }
     }
@@ -160,12 +154,20 @@ where
 
         self.resumes.sort_by_key(|item| item.0);
 
+        #[cfg(feature = "serde")]
+        if self.replay_step() {
+            el.set_control_flow(ControlFlow::Poll);
+        }
+
         if self.windows.is_empty() {
             if !self.suspended {
                 self.destroy_surfaces(el);
             }
             el.exit();
         } else if matches!(el.control_flow(), ControlFlow::Poll) {
+        } else if self.shared.clock.is_manual() {
+            // Virtual time does not pass while waiting; Clock::advance wakes us
+            el.set_control_flow(ControlFlow::Wait);
         } else if let Some((instant, _)) = self.resumes.first() {
             el.set_control_flow(ControlFlow::WaitUntil(*instant));
         } else {
@@ -218,6 +220,65 @@ where
         }
     }
 
+    /// Update windows with timers which are due
+    fn update_timers(&mut self) {
+        let now = self.shared.clock.now();
+        let mut first_future = 0;
+        for (i, resume) in self.resumes.iter().enumerate() {
+            if resume.0 > now {
+                break;
+            }
+            first_future = i;
+
+            if let Some(w) = self.windows.get_mut(&resume.1) {
+                w.update_timer(&mut self.shared, &self.data, resume.0)
+            }
+        }
+
+        self.resumes.drain(..first_future);
+    }
+
+    /// Replay the next recorded input event or due timer
+    ///
+    /// Virtual time is advanced to the time of whichever comes first.
+    /// Returns `true` while replay is in progress.
+    #[cfg(feature = "serde")]
+    fn replay_step(&mut self) -> bool {
+        if self.suspended {
+            return false;
+        }
+        let Some(replayer) = self.shared.replayer.as_mut() else {
+            return false;
+        };
+
+        let Some(next) = replayer.next_time() else {
+            log::info!("Replay complete");
+            if replayer.restore_system_time() {
+                self.shared.clock.set_system();
+            }
+            self.shared.replayer = None;
+            return false;
+        };
+
+        if let Some(resume) = self.resumes.first().map(|item| item.0)
+            && resume <= next
+        {
+            self.shared.clock.advance_to(resume);
+            self.update_timers();
+        } else {
+            self.shared.clock.advance_to(next);
+            if let Some((id, event)) = replayer.next_event() {
+                if let Some(window) = self.windows.get_mut(&id) {
+                    window.replay_event(&mut self.shared, &self.data, event);
+                } else {
+                    log::warn!("Replay: window {} not found", id.get());
+                }
+            }
+        }
+
+        true
+    }
+
     fn flush_pending(&mut self, el: &dyn ActiveEventLoop) {
         let mut close_all = false;
         while let Some(pending) = self.shared.pending.pop_front() {
@@ -291,6 +352,7 @@ where
         }
 
         self.resumes.clear();
+        let mut closed_modals = vec![];
         self.windows.retain(|window_id, window| {
             let (actions, resume) = window.flush_pending(&mut self.shared, &self.data);
             if let Some(instant) = resume {
@@ -306,10 +368,69 @@ where
                 let _ = window.flush_pending(&mut self.shared, &self.data);
 
                 self.id_map.retain(|_, v| v != window_id);
+                if let Some(parent) = window.widget.properties().modal_parent {
+                    closed_modals.push((parent, *window_id));
+                }
                 false
             } else {
                 true
             }
         });
+
+        for (parent, id) in closed_modals {
+            if let Some(window) = self.windows.get_mut(&parent) {
+                window.modal_window_closed(id);
+            }
+        }
+    }
+
+    /// Handle an automation request
+    #[cfg(automation)]
+    fn automation(&mut self, request: Request) -> Response {
+        let mut windows: Vec<_> = self.windows.values_mut().collect();
+        windows.sort_by_key(|window| window.window_id().get());
+
+        match request {
+            Request::Windows => {
+                Response::Windows(windows.iter().map(|w| w.automation_info()).collect())
+            }
+            Request::Query {
+                window,
+                role,
+                label,
+                id,
+            } => {
+                let mut widgets = vec![];
+                for w in windows {
+                    if window.is_none_or(|id| id == w.window_id().get()) {
+                        w.automation_widgets(&mut widgets);
+                    }
+                }
+                widgets.retain(|w| w.matches(role.as_deref(), label.as_deref(), id.as_deref()));
+                Response::Widgets(widgets)
+            }
+            Request::Send { id, message } => {
+                for w in windows {
+                    if let Some(target) = w.automation_find(&id) {
+                        let msg = message.into_erased();
+                        w.automation_send(&mut self.shared, &self.data, target, msg);
+                        return Response::Ok;
+                    }
+                }
+                Response::Error(format!("no widget with id {id}"))
+            }
+            Request::Input { window, input } => {
+                let w = match window {
+                    Some(id) => windows.into_iter().find(|w| w.window_id().get() == id),
+                    None => windows.into_iter().next(),
+                };
+                if let Some(w) = w {
+                    w.automation_input(&mut self.shared, &self.data, input);
+                    Response::Ok
+                } else {
+                    Response::Error("no such window".to_string())
+                }
+            }
+        }
     }
 }
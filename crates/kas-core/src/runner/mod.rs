@@ -5,8 +5,10 @@
 
 //! Runner, platforms and backends
 
+mod clock;
 mod common;
 mod event_loop;
+mod record;
 mod runner;
 mod shared;
 mod window;
@@ -22,7 +24,9 @@ use std::fmt::Debug;
 pub use window::Window;
 pub(crate) use window::WindowDataErased;
 
+pub use clock::Clock;
 pub use common::{Error, Platform, Result};
+pub use record::{InputEvent, KeyInput, RecordedEvent, Recording};
 pub use runner::{ClosedError, PreLaunchState, Proxy};
 
 #[cfg_attr(not(feature = "internal_doc"), doc(hidden))]
@@ -223,6 +227,11 @@ enum ProxyAction {
     Message(kas::messages::SendErased),
     #[cfg(feature = "accesskit")]
     AccessKit(winit::window::WindowId, accesskit_winit::WindowEvent),
+    #[cfg(automation)]
+    Automation(
+        crate::automation::Request,
+        std::sync::mpsc::Sender<crate::automation::Response>,
+    ),
 }
 
 #[cfg(feature = "accesskit")]
@@ -0,0 +1,432 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Input recording and replay
+
+use smol_str::SmolStr;
+use winit::event::{ElementState, Ime, KeyEvent, MouseButton, MouseScrollDelta, WindowEvent};
+use winit::keyboard::{Key, KeyLocation, ModifiersState, NativeKeyCode, PhysicalKey};
+#[cfg(feature = "serde")]
+use {
+    super::Clock,
+    crate::config::Format,
+    crate::util::warn_about_error_with_path,
+    crate::window::WindowId,
+    std::path::PathBuf,
+    std::time::{Duration, Instant},
+};
+
+/// Placeholder for redacted characters
+const REDACTED: &str = "*";
+
+/// Replace each `char` of `text` with [`REDACTED`]
+fn redact_str(text: &str) -> String {
+    text.chars().map(|_| REDACTED).collect()
+}
+
+/// A keyboard input event
+///
+/// This is a serializable representation of [`KeyEvent`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KeyInput {
+    /// See [`KeyEvent::physical_key`]
+    pub physical_key: PhysicalKey,
+    /// See [`KeyEvent::logical_key`]
+    pub logical_key: Key,
+    /// See [`KeyEvent::text`]
+    pub text: Option<SmolStr>,
+    /// See [`KeyEvent::location`]
+    pub location: KeyLocation,
+    /// See [`KeyEvent::state`]
+    pub state: ElementState,
+    /// See [`KeyEvent::repeat`]
+    pub repeat: bool,
+    /// See [`KeyEvent::text_with_all_modifiers`]
+    pub text_with_all_modifiers: Option<SmolStr>,
+    /// See [`KeyEvent::key_without_modifiers`]
+    pub key_without_modifiers: Key,
+    /// True if the event was synthesized by the platform
+    pub is_synthetic: bool,
+}
+
+impl KeyInput {
+    fn new(event: KeyEvent, is_synthetic: bool) -> Self {
+        KeyInput {
+            physical_key: event.physical_key,
+            logical_key: event.logical_key,
+            text: event.text,
+            location: event.location,
+            state: event.state,
+            repeat: event.repeat,
+            text_with_all_modifiers: event.text_with_all_modifiers,
+            key_without_modifiers: event.key_without_modifiers,
+            is_synthetic,
+        }
+    }
+
+    /// Replace character keys and text with a placeholder
+    fn redact(&mut self) {
+        let redact_key = |key: &mut Key| {
+            if let Key::Character(_) = key {
+                *key = Key::Character(SmolStr::new_static(REDACTED));
+            }
+        };
+        if let Key::Character(_) = self.logical_key {
+            self.physical_key = PhysicalKey::Unidentified(NativeKeyCode::Unidentified);
+        }
+        redact_key(&mut self.logical_key);
+        redact_key(&mut self.key_without_modifiers);
+        for text in [&mut self.text, &mut self.text_with_all_modifiers] {
+            if let Some(text) = text.as_mut() {
+                *text = redact_str(text).into();
+            }
+        }
+    }
+
+    /// Convert to a [`KeyEvent`] and `is_synthetic` flag
+    pub(crate) fn into_event(self) -> (KeyEvent, bool) {
+        let event = KeyEvent {
+            physical_key: self.physical_key,
+            logical_key: self.logical_key,
+            text: self.text,
+            location: self.location,
+            state: self.state,
+            repeat: self.repeat,
+            text_with_all_modifiers: self.text_with_all_modifiers,
+            key_without_modifiers: self.key_without_modifiers,
+        };
+        (event, self.is_synthetic)
+    }
+}
+
+/// A window input event
+///
+/// This is the subset of winit's `WindowEvent` which may be recorded and
+/// replayed. Positions are in physical pixels.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum InputEvent {
+    /// Request to close the window
+    CloseRequested,
+    /// The window gained (`true`) or lost (`false`) focus
+    Focused(bool),
+    /// Keyboard input
+    Key(KeyInput),
+    /// Keyboard modifiers changed
+    Modifiers(ModifiersState),
+    /// Input method event
+    Ime(Ime),
+    /// The mouse pointer moved
+    PointerMoved {
+        /// Horizontal position
+        x: f64,
+        /// Vertical position
+        y: f64,
+    },
+    /// The mouse pointer entered the window
+    PointerEntered,
+    /// The mouse pointer left the window
+    PointerLeft,
+    /// Mouse wheel or touchpad scroll
+    MouseWheel(MouseScrollDelta),
+    /// A mouse button was pressed or released
+    MouseButton {
+        /// Whether the button was pressed or released
+        state: ElementState,
+        /// The button
+        button: MouseButton,
+    },
+    /// The window's surface was resized
+    ///
+    /// This is handled by the runner: on replay the window requests this size.
+    SurfaceResized(u32, u32),
+}
+
+impl InputEvent {
+    /// True if this event may carry text input
+    pub(crate) fn has_text(&self) -> bool {
+        matches!(self, InputEvent::Key(_) | InputEvent::Ime(_))
+    }
+
+    /// Redact text input
+    ///
+    /// Characters of key and input method events are replaced with a
+    /// placeholder. This is used to avoid recording passwords.
+    pub(crate) fn redacted(mut self) -> Self {
+        match &mut self {
+            InputEvent::Key(key) => key.redact(),
+            InputEvent::Ime(Ime::Preedit(text, cursor)) => {
+                // Cursor positions are byte offsets; adjust to the placeholder
+                let chars = |index: usize| text.get(..index).map_or(0, |s| s.chars().count());
+                *cursor = cursor.map(|(start, end)| (chars(start), chars(end)));
+                *text = redact_str(text);
+            }
+            InputEvent::Ime(Ime::Commit(text)) => *text = redact_str(text),
+            _ => (),
+        }
+        self
+    }
+
+    /// Convert from a winit event
+    ///
+    /// Returns the event unchanged if it has no representation here (e.g.
+    /// touch events).
+    pub(crate) fn from_winit(event: WindowEvent) -> Result<Self, WindowEvent> {
+        use winit::event::{ButtonSource, PointerKind, PointerSource};
+
+        Ok(match event {
+            WindowEvent::CloseRequested => InputEvent::CloseRequested,
+            WindowEvent::Focused(state) => InputEvent::Focused(state),
+            WindowEvent::KeyboardInput {
+                event,
+                is_synthetic,
+                ..
+            } => InputEvent::Key(KeyInput::new(event, is_synthetic)),
+            WindowEvent::ModifiersChanged(modifiers) => InputEvent::Modifiers(modifiers.state()),
+            WindowEvent::Ime(ime) => InputEvent::Ime(ime),
+            WindowEvent::PointerMoved {
+                position,
+                source: PointerSource::Mouse,
+                ..
+            } => InputEvent::PointerMoved {
+                x: position.x,
+                y: position.y,
+            },
+            WindowEvent::PointerEntered {
+                kind: PointerKind::Mouse,
+                ..
+            } => InputEvent::PointerEntered,
+            WindowEvent::PointerLeft {
+                kind: PointerKind::Mouse,
+                ..
+            } => InputEvent::PointerLeft,
+            WindowEvent::MouseWheel { delta, .. } => InputEvent::MouseWheel(delta),
+            WindowEvent::PointerButton {
+                state,
+                button: ButtonSource::Mouse(button),
+                ..
+            } => InputEvent::MouseButton { state, button },
+            event => return Err(event),
+        })
+    }
+}
+
+/// A recorded [`InputEvent`]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RecordedEvent {
+    /// Time since the start of recording, in microseconds
+    pub time: u64,
+    /// Window number
+    ///
+    /// Windows are numbered sequentially from 1 in order of creation.
+    pub window: u32,
+    /// The event
+    pub event: InputEvent,
+}
+
+/// A recording of input events
+///
+/// When the `serde` feature is enabled, the runner supports recording input
+/// to a file and replaying such a file, configured by environment variables:
+///
+/// -   `KAS_RECORD=PATH`: record all input events to `PATH`. The file is
+///     written when the runner exits.
+/// -   `KAS_REPLAY=PATH`: replay input events from `PATH`.
+///
+/// The file format is inferred from the path's extension (see
+/// [`Format`](crate::config::Format)).
+///
+/// During replay the runner uses a virtual clock, advanced to the time of each
+/// recorded event (and to the time of each timer which falls due between
+/// events). Timers (see [`EventState::request_timer`]) are thus delivered in
+/// the same order relative to input as when recording, independent of the
+/// speed of the machine. Once all events have been replayed the clock returns
+/// to system time and input is accepted as normal; until then, input events
+/// other than requests to close a window are ignored.
+///
+/// Touch input is not recorded.
+///
+/// **Warning:** recordings contain all text typed, in plain text. While a
+/// protected text input (e.g. a password field; see [`Role::TextInput`]) has
+/// navigation focus, characters of key and input method events are replaced
+/// with `*` before recording. Other sensitive input is recorded as typed;
+/// treat recordings accordingly.
+///
+/// [`EventState::request_timer`]: crate::event::EventState::request_timer
+/// [`Role::TextInput`]: crate::Role::TextInput
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Recording {
+    /// Events, in order
+    pub events: Vec<RecordedEvent>,
+}
+
+/// Records input events, writing to a file on drop
+#[cfg(feature = "serde")]
+pub(super) struct Recorder {
+    path: PathBuf,
+    start: Instant,
+    recording: Recording,
+}
+
+#[cfg(feature = "serde")]
+impl Recorder {
+    /// Construct from the `KAS_RECORD` environment variable, if set
+    pub(super) fn from_env(clock: &Clock) -> Option<Self> {
+        let path = PathBuf::from(std::env::var_os("KAS_RECORD")?);
+        log::info!("Recording input to {}", path.display());
+        Some(Recorder {
+            path,
+            start: clock.now(),
+            recording: Recording::default(),
+        })
+    }
+
+    /// Record an event at time `now`
+    pub(super) fn push(&mut self, now: Instant, window: WindowId, event: &InputEvent) {
+        let time = (now - self.start).as_micros();
+        self.recording.events.push(RecordedEvent {
+            time: time.try_into().unwrap_or(u64::MAX),
+            window: window.get(),
+            event: event.clone(),
+        });
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Drop for Recorder {
+    fn drop(&mut self) {
+        if let Err(error) = Format::guess_and_write_path(&self.path, &self.recording) {
+            warn_about_error_with_path("Failed to write input recording", &error, &self.path);
+        }
+    }
+}
+
+/// Replays recorded input events
+#[cfg(feature = "serde")]
+pub(super) struct Replayer {
+    start: Instant,
+    restore_system_time: bool,
+    events: std::vec::IntoIter<RecordedEvent>,
+}
+
+#[cfg(feature = "serde")]
+impl Replayer {
+    /// Construct from the `KAS_REPLAY` environment variable, if set
+    ///
+    /// On success, `clock` is switched to virtual time.
+    pub(super) fn from_env(clock: &Clock) -> Option<Self> {
+        let path = PathBuf::from(std::env::var_os("KAS_REPLAY")?);
+        let recording: Recording = match Format::guess_and_read_path(&path) {
+            Ok(recording) => recording,
+            Err(error) => {
+                warn_about_error_with_path("Failed to read input recording", &error, &path);
+                return None;
+            }
+        };
+
+        log::info!("Replaying input from {}", path.display());
+        let restore_system_time = !clock.is_manual();
+        let start = clock.now();
+        clock.set_virtual(start);
+        Some(Replayer {
+            start,
+            restore_system_time,
+            events: recording.events.into_iter(),
+        })
+    }
+
+    /// True if the clock should return to system time after replay
+    pub(super) fn restore_system_time(&self) -> bool {
+        self.restore_system_time
+    }
+
+    /// Get the time of the next event, if any
+    pub(super) fn next_time(&self) -> Option<Instant> {
+        let event = self.events.as_slice().first()?;
+        Some(self.start + Duration::from_micros(event.time))
+    }
+
+    /// Take the next event
+    pub(super) fn next_event(&mut self) -> Option<(WindowId, InputEvent)> {
+        let event = self.events.next()?;
+        let Some(window) = WindowId::try_from(event.window) else {
+            log::warn!("Replay: invalid window number {}", event.window);
+            return self.next_event();
+        };
+        Some((window, event.event))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn key(c: &str) -> KeyInput {
+        KeyInput {
+            physical_key: PhysicalKey::Code(winit::keyboard::KeyCode::KeyA),
+            logical_key: Key::Character(c.into()),
+            text: Some(c.into()),
+            location: KeyLocation::Standard,
+            state: ElementState::Pressed,
+            repeat: false,
+            text_with_all_modifiers: Some(c.into()),
+            key_without_modifiers: Key::Character(c.into()),
+            is_synthetic: false,
+        }
+    }
+
+    #[test]
+    fn redact_key() {
+        let event = InputEvent::Key(key("a")).redacted();
+        let InputEvent::Key(input) = event else {
+            unreachable!();
+        };
+        assert_eq!(
+            input.physical_key,
+            PhysicalKey::Unidentified(NativeKeyCode::Unidentified)
+        );
+        assert_eq!(input.logical_key, Key::Character("*".into()));
+        assert_eq!(input.key_without_modifiers, Key::Character("*".into()));
+        assert_eq!(input.text.as_deref(), Some("*"));
+        assert_eq!(input.text_with_all_modifiers.as_deref(), Some("*"));
+        assert_eq!(input.state, ElementState::Pressed);
+    }
+
+    #[test]
+    fn redact_preserves_named_keys() {
+        use winit::keyboard::NamedKey;
+
+        let mut input = key("\r");
+        input.physical_key = PhysicalKey::Code(winit::keyboard::KeyCode::Enter);
+        input.logical_key = Key::Named(NamedKey::Enter);
+        input.key_without_modifiers = Key::Named(NamedKey::Enter);
+        let InputEvent::Key(redacted) = InputEvent::Key(input.clone()).redacted() else {
+            unreachable!();
+        };
+        assert_eq!(redacted.physical_key, input.physical_key);
+        assert_eq!(redacted.logical_key, input.logical_key);
+        assert_eq!(redacted.text.as_deref(), Some("*"));
+    }
+
+    #[test]
+    fn redact_ime() {
+        let event = InputEvent::Ime(Ime::Commit("pässword".into())).redacted();
+        assert_eq!(event, InputEvent::Ime(Ime::Commit("********".into())));
+
+        let event = InputEvent::Ime(Ime::Preedit("äb".into(), Some((2, 3)))).redacted();
+        assert_eq!(
+            event,
+            InputEvent::Ime(Ime::Preedit("**".into(), Some((1, 2))))
+        );
+
+        assert!(InputEvent::Ime(Ime::Enabled).has_text());
+        assert!(!InputEvent::PointerLeft.has_text());
+        assert_eq!(InputEvent::PointerLeft.redacted(), InputEvent::PointerLeft);
+    }
+}
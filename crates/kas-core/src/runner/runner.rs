@@ -5,7 +5,7 @@
 
 //! [`Runner`] and supporting elements
 
-use super::{AppData, GraphicsInstance, Platform, ProxyAction, Result, Shared};
+use super::{AppData, Clock, GraphicsInstance, Platform, ProxyAction, Result, Shared};
 use crate::config::{Config, ConfigFactory};
 #[allow(unused)] use crate::event::ConfigCx;
 use crate::theme::Theme;
@@ -29,6 +29,7 @@ pub struct PreLaunchState {
     proxy_tx: mpsc::SyncSender<ProxyAction>,
     proxy_rx: mpsc::Receiver<ProxyAction>,
     window_id_factory: WindowIdFactory,
+    clock: Clock,
 }
 
 impl PreLaunchState {
@@ -51,6 +52,7 @@ impl PreLaunchState {
             proxy_tx,
             proxy_rx,
             window_id_factory: Default::default(),
+            clock: Clock::default(),
         })
     }
 
@@ -60,6 +62,21 @@ impl PreLaunchState {
         &self.config
     }
 
+    /// Access the clock
+    #[inline]
+    pub fn clock(&self) -> &Clock {
+        &self.clock
+    }
+
+    /// Replace the clock
+    ///
+    /// This may be used to supply a manual clock (see [`Clock::new_manual`]).
+    /// It must be called before [`Self::run`].
+    #[inline]
+    pub fn set_clock(&mut self, clock: Clock) {
+        self.clock = clock;
+    }
+
     /// Generate a [`WindowId`]
     #[inline]
     pub fn next_window_id(&mut self) -> WindowId {
@@ -85,6 +102,10 @@ impl PreLaunchState {
         theme: T,
         windows: Vec<Box<super::Window<Data, G, T>>>,
     ) -> Result<()> {
+        #[cfg(automation)]
+        crate::automation::serve_from_env(self.create_proxy());
+        self.clock.set_waker(self.el.create_proxy());
+
         let shared = Shared::<Data, _, _>::new(
             self.platform,
             graphical,
@@ -94,6 +115,7 @@ impl PreLaunchState {
             create_waker(&self.el),
             self.proxy_rx,
             self.window_id_factory,
+            self.clock,
         )?;
 
         let l = super::Loop::new(windows, shared, data);
@@ -263,4 +285,20 @@ impl Proxy {
         self.waker.wake_up();
         Ok(())
     }
+
+    /// Handle an automation request, waiting for the response
+    ///
+    /// Fails if the application has exited.
+    #[cfg(automation)]
+    pub(crate) fn automation(
+        &self,
+        request: crate::automation::Request,
+    ) -> std::result::Result<crate::automation::Response, ClosedError> {
+        let (tx, rx) = mpsc::channel();
+        self.tx
+            .send(ProxyAction::Automation(request, tx))
+            .map_err(|_| ClosedError)?;
+        self.waker.wake_up();
+        rx.recv().map_err(|_| ClosedError)
+    }
 }
@@ -5,13 +5,17 @@
 
 //! Shared state
 
+#[cfg(feature = "serde")]
+use super::record::{Recorder, Replayer};
 use super::{
-    AppData, Error, GraphicsInstance, MessageStack, Pending, Platform, ProxyAction, RunError,
+    AppData, Clock, Error, GraphicsInstance, InputEvent, MessageStack, Pending, Platform,
+    ProxyAction, RunError,
 };
 use crate::config::Config;
 use crate::draw::{DrawShared, DrawSharedImpl, SharedState};
 use crate::messages::Erased;
 use crate::runner::GraphicsFeatures;
+use crate::text::format::RichText;
 use crate::theme::Theme;
 #[cfg(feature = "clipboard")]
 use crate::util::warn_about_error;
@@ -33,6 +37,7 @@ pub(super) struct Shared<Data: AppData, G: GraphicsInstance, T: Theme<G::Shared>
     pub(super) config: Rc<RefCell<Config>>,
     #[cfg(feature = "clipboard")]
     clipboard: Option<Clipboard>,
+    rich_clipboard: Option<RichText>,
     pub(super) instance: G,
     pub(super) draw: Option<SharedState<G::Shared>>,
     pub(super) theme: T,
@@ -43,6 +48,11 @@ pub(super) struct Shared<Data: AppData, G: GraphicsInstance, T: Theme<G::Shared>
     pub(super) waker: Waker,
     pub(super) proxy_rx: mpsc::Receiver<ProxyAction>,
     window_id_factory: WindowIdFactory,
+    pub(super) clock: Clock,
+    #[cfg(feature = "serde")]
+    recorder: Option<Recorder>,
+    #[cfg(feature = "serde")]
+    pub(super) replayer: Option<Replayer>,
 }
 
 impl<A: AppData, G: GraphicsInstance, T: Theme<G::Shared>> Shared<A, G, T>
@@ -50,6 +60,7 @@ where
     T::Window: kas::theme::Window,
 {
     /// Construct
+    #[allow(clippy::too_many_arguments)]
     pub(super) fn new(
         platform: Platform,
         instance: G,
@@ -59,6 +70,7 @@ where
         waker: Waker,
         proxy_rx: mpsc::Receiver<ProxyAction>,
         window_id_factory: WindowIdFactory,
+        clock: Clock,
     ) -> Result<Self, Error> {
         #[cfg(feature = "clipboard")]
         let clipboard = match Clipboard::new() {
@@ -69,12 +81,18 @@ where
             }
         };
 
+        #[cfg(feature = "serde")]
+        let recorder = Recorder::from_env(&clock);
+        #[cfg(feature = "serde")]
+        let replayer = Replayer::from_env(&clock);
+
         Ok(Shared {
             platform,
             config_writer,
             config,
             #[cfg(feature = "clipboard")]
             clipboard,
+            rich_clipboard: None,
             instance,
             draw: None,
             theme,
@@ -85,9 +103,36 @@ where
             waker,
             proxy_rx,
             window_id_factory,
+            clock,
+            #[cfg(feature = "serde")]
+            recorder,
+            #[cfg(feature = "serde")]
+            replayer,
         })
     }
 
+    /// Record an input event, if recording
+    #[inline]
+    pub(super) fn record(&mut self, window_id: WindowId, event: &InputEvent) {
+        #[cfg(feature = "serde")]
+        if let Some(recorder) = self.recorder.as_mut() {
+            recorder.push(self.clock.now(), window_id, event);
+        }
+
+        #[cfg(not(feature = "serde"))]
+        let _ = (window_id, event);
+    }
+
+    /// True while replaying recorded input
+    #[inline]
+    pub(super) fn is_replaying(&self) -> bool {
+        #[cfg(feature = "serde")]
+        let replaying = self.replayer.is_some();
+        #[cfg(not(feature = "serde"))]
+        let replaying = false;
+        replaying
+    }
+
     /// Redirect messages with a target defined by the type
     pub(crate) fn redirect_messages_by_type(&mut self) {
         if !self.messages.reset_and_has_any() {
@@ -299,6 +344,11 @@ pub(crate) trait RunnerT {
         }
     }
 
+    /// Access the application's rich text clipboard
+    fn rich_clipboard(&mut self) -> &mut Option<RichText> {
+        &mut self.rich_clipboard
+    }
+
     /// Get contents of primary buffer
     ///
     /// Linux has a "primary buffer" with implicit copy on text selection and
@@ -7,14 +7,14 @@
 
 use super::common::{RunError, WindowSurface};
 use super::shared::Shared;
-use super::{AppData, GraphicsInstance, Platform};
+use super::{AppData, GraphicsInstance, InputEvent, Platform};
 use crate::cast::{Cast, CastApprox};
 use crate::config::{Config, WindowConfig};
 use crate::draw::PassType;
 use crate::draw::color::Rgba;
 use crate::event::{ConfigCx, CursorIcon, EventState};
 use crate::geom::{Coord, Offset, Rect, Size};
-use crate::layout::SolveCache;
+use crate::layout::{SolveCache, record_layout};
 use crate::messages::Erased;
 use crate::theme::{DrawCx, SizeCx, Theme, ThemeDraw, Window as _};
 use crate::window::{BoxedWindow, Decorations, PopupDescriptor, WindowId, WindowWidget};
@@ -99,6 +99,7 @@ impl<A: AppData, G: GraphicsInstance, T: Theme<G::Shared>> Window<A, G, T> {
         debug_assert!(self.theme_and_window.is_none());
 
         let time = Instant::now();
+        self.ev_state.clock = shared.clock.clone();
 
         // We use the logical size and scale factor of the largest monitor as
         // an upper bound on window size and guessed scale factor.
@@ -245,7 +246,7 @@ impl<A: AppData, G: GraphicsInstance, T: Theme<G::Shared>> Window<A, G, T> {
             #[cfg(all(wayland_platform, feature = "clipboard"))]
             wayland_clipboard,
             surface,
-            frame_count: (Instant::now(), 0),
+            frame_count: (self.ev_state.now(), 0),
 
             #[cfg(feature = "accesskit")]
             accesskit,
@@ -309,6 +310,8 @@ impl<A: AppData, G: GraphicsInstance, T: Theme<G::Shared>> Window<A, G, T> {
         let (apply_size, resize, poll) = match event {
             WindowEvent::Moved(_) | WindowEvent::Destroyed => return false,
             WindowEvent::SurfaceResized(size) => {
+                let input = InputEvent::SurfaceResized(size.width, size.height);
+                shared.record(self.ev_state.window_id, &input);
                 if window
                     .surface
                     .configure(&mut shared.draw.as_mut().unwrap().draw, size.cast())
@@ -359,12 +362,22 @@ impl<A: AppData, G: GraphicsInstance, T: Theme<G::Shared>> Window<A, G, T> {
             }
             WindowEvent::RedrawRequested => return self.do_draw(shared, data).is_err(),
             event => {
-                let resize = self
-                    .ev_state
-                    .with(shared, theme.size(), window, |cx| {
+                let resize = match InputEvent::from_winit(event) {
+                    Ok(input) if shared.is_replaying() && input != InputEvent::CloseRequested => {
+                        // Live input would disturb the replay
+                        return false;
+                    }
+                    Ok(input) => {
+                        record(shared, &self.ev_state, self.widget.as_tile(), &input);
+                        self.ev_state.with(shared, theme.size(), window, |cx| {
+                            cx.handle_input(&mut self.widget, data, input);
+                        })
+                    }
+                    Err(event) => self.ev_state.with(shared, theme.size(), window, |cx| {
                         cx.handle_winit(&mut self.widget, data, event);
-                    })
-                    .is_some();
+                    }),
+                }
+                .is_some();
                 (resize, resize, false)
             }
         };
@@ -488,6 +501,64 @@ impl<A: AppData, G: GraphicsInstance, T: Theme<G::Shared>> Window<A, G, T> {
         }
     }
 
+    #[cfg(automation)]
+    pub(super) fn automation_info(&self) -> crate::automation::WindowInfo {
+        crate::automation::WindowInfo {
+            id: self.window_id().get(),
+            title: self.widget.title().to_string(),
+        }
+    }
+
+    #[cfg(automation)]
+    pub(super) fn automation_widgets(&self, widgets: &mut Vec<crate::automation::WidgetInfo>) {
+        let id = self.window_id();
+        crate::automation::window_widgets(&self.widget, &self.ev_state, id, widgets);
+    }
+
+    #[cfg(automation)]
+    pub(super) fn automation_find(&self, id: &str) -> Option<Id> {
+        crate::automation::find_id(&self.widget, id)
+    }
+
+    #[cfg(automation)]
+    pub(super) fn automation_send(
+        &mut self,
+        shared: &mut Shared<A, G, T>,
+        data: &A,
+        id: Id,
+        msg: Erased,
+    ) {
+        let Some((ref theme, ref mut window)) = self.theme_and_window else {
+            return;
+        };
+
+        let resize = self.ev_state.with(shared, theme.size(), window, |cx| {
+            cx.handle_automation_message(self.widget.as_node(data), id, msg);
+        });
+        if resize.is_some() {
+            self.apply_size(data, false, true);
+        }
+    }
+
+    #[cfg(automation)]
+    pub(super) fn automation_input(
+        &mut self,
+        shared: &mut Shared<A, G, T>,
+        data: &A,
+        input: crate::automation::Input,
+    ) {
+        let Some((ref theme, ref mut window)) = self.theme_and_window else {
+            return;
+        };
+
+        let resize = self.ev_state.with(shared, theme.size(), window, |cx| {
+            cx.handle_automation_input(&mut self.widget, data, input);
+        });
+        if resize.is_some() {
+            self.apply_size(data, false, true);
+        }
+    }
+
     pub(super) fn update_timer(
         &mut self,
         shared: &mut Shared<A, G, T>,
@@ -498,7 +569,11 @@ impl<A: AppData, G: GraphicsInstance, T: Theme<G::Shared>> Window<A, G, T> {
             return;
         };
 
-        if window.surface.common_mut().immediate_redraw() {
+        if window
+            .surface
+            .common_mut()
+            .immediate_redraw(self.ev_state.now())
+        {
             window.need_redraw = true;
             window.request_redraw();
         }
@@ -518,6 +593,32 @@ impl<A: AppData, G: GraphicsInstance, T: Theme<G::Shared>> Window<A, G, T> {
         }
     }
 
+    /// Handle a replayed input event
+    pub(super) fn replay_event(
+        &mut self,
+        shared: &mut Shared<A, G, T>,
+        data: &A,
+        event: InputEvent,
+    ) {
+        let Some((ref theme, ref mut window)) = self.theme_and_window else {
+            return;
+        };
+
+        if let InputEvent::SurfaceResized(width, height) = event {
+            let size = PhysicalSize::new(width, height);
+            let _ = window.window.request_surface_size(size.into());
+            return;
+        }
+
+        record(shared, &self.ev_state, self.widget.as_tile(), &event);
+        let resize = self.ev_state.with(shared, theme.size(), window, |cx| {
+            cx.handle_input(&mut self.widget, data, event);
+        });
+        if resize.is_some() {
+            self.apply_size(data, false, true);
+        }
+    }
+
     /// Add or reposition a pop-up
     pub(super) fn add_popup(&mut self, data: &A, id: WindowId, popup: PopupDescriptor) {
         let Some((ref theme, _)) = self.theme_and_window else {
@@ -528,6 +629,11 @@ impl<A: AppData, G: GraphicsInstance, T: Theme<G::Shared>> Window<A, G, T> {
         self.widget.add_popup(&mut cx, data, id, popup);
     }
 
+    /// Handle closure of (possibly modal) window `id`
+    pub(super) fn modal_window_closed(&mut self, id: WindowId) {
+        self.ev_state.modal_window_closed(id);
+    }
+
     pub(super) fn send_close(&mut self, id: WindowId) {
         if id == self.ev_state.window_id {
             self.ev_state.close_own_window();
@@ -590,6 +696,15 @@ impl<A: AppData, G: GraphicsInstance, T: Theme<G::Shared>> Window<A, G, T> {
             solve_cache.find_constraints(self.widget.as_node(data), &mut cx);
         }
         solve_cache.apply_rect(self.widget.as_node(data), &mut cx, rect, true);
+        if cx.layout_records.is_some() {
+            // Recording re-evaluates size rules, thus we must solve again after
+            let mut records = vec![];
+            let node = self.widget.as_node(data);
+            record_layout(node, &mut cx, Offset::ZERO, &mut records);
+            solve_cache.find_constraints(self.widget.as_node(data), &mut cx);
+            solve_cache.apply_rect(self.widget.as_node(data), &mut cx, rect, true);
+            cx.layout_records = Some(records);
+        }
         if first {
             solve_cache.print_widget_heirarchy(self.widget.as_tile());
         }
@@ -663,7 +778,10 @@ impl<A: AppData, G: GraphicsInstance, T: Theme<G::Shared>> Window<A, G, T> {
         }
         let time2 = Instant::now();
 
-        window.need_redraw = window.surface.common_mut().immediate_redraw();
+        window.need_redraw = window
+            .surface
+            .common_mut()
+            .immediate_redraw(self.ev_state.now());
         self.ev_state.action_redraw = None;
         // NOTE: we used to return Err(()) if !action.is_empty() here, e.g. if a
         // widget requested a resize during draw. Likely it's better not to do
@@ -691,14 +809,15 @@ impl<A: AppData, G: GraphicsInstance, T: Theme<G::Shared>> Window<A, G, T> {
         );
 
         const SECOND: Duration = Duration::from_secs(1);
+        let now = self.ev_state.now();
         window.frame_count.1 += 1;
-        if window.frame_count.0 + SECOND <= end {
+        if window.frame_count.0 + SECOND <= now {
             log::debug!(
                 "Window {:?}: {} frames in last second",
                 window.window_id,
                 window.frame_count.1
             );
-            window.frame_count.0 = end;
+            window.frame_count.0 = now;
             window.frame_count.1 = 0;
         }
 
@@ -706,6 +825,20 @@ impl<A: AppData, G: GraphicsInstance, T: Theme<G::Shared>> Window<A, G, T> {
     }
 }
 
+/// Record `input`, redacting text while a protected input has navigation focus
+fn record<A: AppData, G: GraphicsInstance, T: Theme<G::Shared>>(
+    shared: &mut Shared<A, G, T>,
+    ev_state: &EventState,
+    root: &dyn Tile,
+    input: &InputEvent,
+) {
+    if input.has_text() && ev_state.nav_focus_is_protected(root) {
+        shared.record(ev_state.window_id, &input.clone().redacted());
+    } else {
+        shared.record(ev_state.window_id, input);
+    }
+}
+
 #[crate::split_impl(for<G: GraphicsInstance> WindowData<G>)]
 pub(crate) trait WindowDataErased {
     /// Get the window identifier
@@ -13,7 +13,9 @@ use crate::theme::ColorsLinear;
 pub use kas_text::format::FontToken;
 
 #[cfg(feature = "markdown")] mod markdown;
-#[cfg(feature = "markdown")] pub use markdown::Markdown;
+#[cfg(feature = "markdown")] pub use markdown::{Error as MarkdownError, Markdown};
+mod rich;
+pub use rich::{RichAttr, RichFormat, RichText};
 
 /// Rgba or theme-provided color value
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -44,6 +46,14 @@ impl Color {
     pub const SELECTION: Self =
         Color(NonZeroU32::new(u32::from_ne_bytes(Rgba8Srgb::rgba(1, 1, 0, 0).0)).unwrap());
 
+    /// Use the theme-defined link color
+    ///
+    /// As a foreground color, this maps to [`ColorsLinear::link`].
+    ///
+    /// As a background color this is identical to [`Self::DEFAULT`].
+    pub const LINK: Self =
+        Color(NonZeroU32::new(u32::from_ne_bytes(Rgba8Srgb::rgba(1, 2, 0, 0).0)).unwrap());
+
     /// Use an RGBA sRGB color
     ///
     /// This will resolve to the default theme color if `color.a() == 0`.
@@ -84,6 +94,8 @@ impl Color {
     pub fn resolve_foreground(self, theme: &ColorsLinear) -> Rgba {
         if let Some(col) = self.as_rgba() {
             col
+        } else if self == Self::LINK {
+            theme.link
         } else {
             theme.text
         }
@@ -120,6 +132,8 @@ impl Colors {
     pub fn resolve_foreground(self, theme: &ColorsLinear) -> Rgba {
         if let Some(col) = self.foreground.as_rgba() {
             col
+        } else if self.foreground == Color::LINK {
+            theme.link
         } else if let Some(bg) = self.background {
             theme.text_over(bg.resolve_background(theme))
         } else {
@@ -279,3 +293,25 @@ fn sizes() {
     assert_eq!(size_of::<LineStyle>(), 0);
     assert_eq!(size_of::<Decoration>(), 8);
 }
+
+#[cfg(test)]
+#[test]
+fn theme_colors() {
+    use crate::theme::ColorsSrgb;
+
+    let theme = ColorsLinear::from(ColorsSrgb::LIGHT);
+    assert_eq!(Color::DEFAULT.resolve_foreground(&theme), theme.text);
+    assert_eq!(Color::LINK.resolve_foreground(&theme), theme.link);
+    assert_eq!(Color::LINK.resolve_background(&theme), theme.edit_bg);
+    assert_eq!(
+        Color::SELECTION.resolve_background(&theme),
+        theme.text_sel_bg
+    );
+
+    let link = Colors {
+        foreground: Color::LINK,
+        background: None,
+    };
+    assert_eq!(link.resolve_foreground(&theme), theme.link);
+    assert_eq!(Color::LINK.as_rgba_srgb(), None);
+}
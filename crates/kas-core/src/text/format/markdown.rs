@@ -5,12 +5,13 @@
 
 //! Markdown parsing
 
-use super::{Decoration, DecorationType, FontToken, FormattableText};
+use super::{Color, Colors, Decoration, DecorationType, FontToken, FormattableText};
 use crate::cast::Cast;
 use crate::text::fonts::{FamilySelector, FontSelector, FontStyle, FontWeight};
 use pulldown_cmark::{Event, HeadingLevel, Tag, TagEnd};
 use std::fmt::Write;
 use std::iter::FusedIterator;
+use std::sync::Arc;
 use thiserror::Error;
 
 /// Markdown parsing errors
@@ -33,21 +34,23 @@ pub enum Error {
 /// -   Headings
 /// -   Lists (numerated and bulleted); caveat: indentation after first line
 /// -   Bold, italic (emphasis), strike-through
+/// -   Links (displayed but not followed)
+/// -   Underline via inline HTML `<u>`
 ///
 /// Not supported:
 ///
 /// -   Block quotes
 /// -   Footnotes
-/// -   HTML
+/// -   Other HTML
 /// -   Horizontal rules
 /// -   Images
-/// -   Links
 /// -   Tables
 /// -   Task lists
 #[derive(Clone, Debug, Default, PartialEq)]
 pub struct Markdown {
-    text: String,
-    fmt: Vec<Fmt>,
+    pub(super) text: String,
+    pub(super) fmt: Vec<Fmt>,
+    colors: Vec<(u32, Colors)>,
     decorations: Vec<(u32, Decoration)>,
 }
 
@@ -126,6 +129,11 @@ impl FormattableText for Markdown {
         FontTokenIter::new(&self.fmt, dpem, font)
     }
 
+    #[inline]
+    fn color_tokens(&self) -> &[(u32, Colors)] {
+        &self.colors
+    }
+
     #[inline]
     fn decorations(&self) -> &[(u32, Decoration)] {
         &self.decorations
@@ -188,9 +196,23 @@ fn parse(input: &str) -> Result<Markdown, Error> {
             Event::InlineMath(_) | Event::DisplayMath(_) => {
                 return Err(Error::NotSupported("math expressions"));
             }
-            Event::Html(_) | Event::InlineHtml(_) => {
-                return Err(Error::NotSupported("embedded HTML"));
-            }
+            Event::InlineHtml(html) => match html.trim().to_ascii_lowercase().as_str() {
+                "<u>" => {
+                    item.fmt.start = text.len().cast();
+                    let mut clone = item.clone();
+                    clone.fmt.underline = true;
+                    stack.push(item);
+                    item = clone;
+                    set_last(&item);
+                }
+                "</u>" if item.fmt.underline => {
+                    item = stack.pop().unwrap();
+                    item.fmt.start = text.len().cast();
+                    set_last(&item);
+                }
+                _ => return Err(Error::NotSupported("embedded HTML")),
+            },
+            Event::Html(_) => return Err(Error::NotSupported("embedded HTML")),
             Event::FootnoteReference(_) => return Err(Error::NotSupported("footnote")),
             Event::SoftBreak => state.soft_break(&mut text),
             Event::HardBreak => state.hard_break(&mut text),
@@ -200,22 +222,38 @@ fn parse(input: &str) -> Result<Markdown, Error> {
     }
 
     // TODO(opt): don't need to store flags in fmt?
+    let mut colors = Vec::new();
     let mut decorations = Vec::new();
-    let mut strikethrough = false;
+    let (mut link, mut dec) = (false, DecorationType::None);
     for token in &fmt {
-        if token.strikethrough != strikethrough {
-            let mut dec = Decoration::default();
-            if token.strikethrough {
-                dec.dec = DecorationType::Strikethrough;
+        if token.link.is_some() != link {
+            link = token.link.is_some();
+            let mut col = Colors::default();
+            if link {
+                col.foreground = Color::LINK;
             }
-            decorations.push((token.start, dec));
-            strikethrough = token.strikethrough;
+            colors.push((token.start, col));
+        }
+
+        let token_dec = if token.strikethrough {
+            DecorationType::Strikethrough
+        } else if token.underline || token.link.is_some() {
+            DecorationType::Underline
+        } else {
+            DecorationType::None
+        };
+        if token_dec != dec {
+            dec = token_dec;
+            let mut decoration = Decoration::default();
+            decoration.dec = dec;
+            decorations.push((token.start, decoration));
         }
     }
 
     Ok(Markdown {
         text,
         fmt,
+        colors,
         decorations,
     })
 }
@@ -265,12 +303,14 @@ impl State {
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct Fmt {
-    start: u32,
+    pub(super) start: u32,
     rel_size: f32,
-    bold: bool,
-    italic: bool,
+    pub(super) bold: bool,
+    pub(super) italic: bool,
     monospace: bool,
-    strikethrough: bool,
+    pub(super) strikethrough: bool,
+    pub(super) underline: bool,
+    pub(super) link: Option<Arc<str>>,
 }
 
 impl Default for Fmt {
@@ -282,6 +322,8 @@ impl Default for Fmt {
             italic: false,
             monospace: false,
             strikethrough: false,
+            underline: false,
+            link: None,
         }
     }
 }
@@ -370,7 +412,9 @@ impl StackItem {
                 // kas-text doesn't support adjusting the baseline
                 return Err(Error::NotSupported("super/subscript"));
             }
-            Tag::Link { .. } => return Err(Error::NotSupported("link")),
+            Tag::Link { dest_url, .. } => with_clone(self, |item| {
+                item.fmt.link = Some(dest_url.replace("%20", " ").into());
+            }),
             Tag::Image { .. } => return Err(Error::NotSupported("image")),
             Tag::MetadataBlock(_) => return Err(Error::NotSupported("metadata block")),
         })
@@ -387,7 +431,7 @@ impl StackItem {
                 true
             }
             TagEnd::Item => false,
-            TagEnd::Emphasis | TagEnd::Strong | TagEnd::Strikethrough => true,
+            TagEnd::Emphasis | TagEnd::Strong | TagEnd::Strikethrough | TagEnd::Link => true,
             tag => unimplemented!("{:?}", tag),
         }
     }
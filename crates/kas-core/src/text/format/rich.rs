@@ -0,0 +1,789 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Rich text: a text with editable formatting runs
+
+use super::{Color, Colors, Decoration, DecorationType, FontToken, FormattableText};
+use crate::cast::Cast;
+use crate::text::fonts::{FontSelector, FontStyle, FontWeight};
+use std::cell::Cell;
+use std::ops::Range;
+use std::sync::Arc;
+
+/// A single formatting attribute
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum RichAttr {
+    /// Bold (strong) text
+    Bold,
+    /// Italic (emphasised) text
+    Italic,
+    /// Underlined text
+    Underline,
+    /// Struck-through text
+    Strikethrough,
+    /// A hyperlink to the given target
+    Link(Arc<str>),
+}
+
+/// Formatting attributes of a run of text
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct RichFormat {
+    /// Bold (strong) text
+    pub bold: bool,
+    /// Italic (emphasised) text
+    pub italic: bool,
+    /// Underlined text
+    pub underline: bool,
+    /// Struck-through text
+    pub strikethrough: bool,
+    /// Hyperlink target
+    pub link: Option<Arc<str>>,
+}
+
+impl RichFormat {
+    /// Check whether an attribute is set
+    ///
+    /// For [`RichAttr::Link`] this checks only for the presence of a link,
+    /// ignoring the link target.
+    pub fn has(&self, attr: &RichAttr) -> bool {
+        match attr {
+            RichAttr::Bold => self.bold,
+            RichAttr::Italic => self.italic,
+            RichAttr::Underline => self.underline,
+            RichAttr::Strikethrough => self.strikethrough,
+            RichAttr::Link(_) => self.link.is_some(),
+        }
+    }
+
+    /// Set or clear an attribute
+    pub fn set(&mut self, attr: &RichAttr, value: bool) {
+        match attr {
+            RichAttr::Bold => self.bold = value,
+            RichAttr::Italic => self.italic = value,
+            RichAttr::Underline => self.underline = value,
+            RichAttr::Strikethrough => self.strikethrough = value,
+            RichAttr::Link(target) => self.link = value.then(|| target.clone()),
+        }
+    }
+
+    /// Get the text colors used by this format
+    ///
+    /// Links use [`Color::LINK`]; other text uses the default theme colors.
+    pub fn colors(&self) -> Colors {
+        let mut colors = Colors::default();
+        if self.link.is_some() {
+            colors.foreground = Color::LINK;
+        }
+        colors
+    }
+
+    /// Get the text decoration used by this format
+    ///
+    /// Links are underlined. Strike-through takes precedence over underline.
+    pub fn decoration(&self) -> Decoration {
+        let mut decoration = Decoration::default();
+        if self.strikethrough {
+            decoration.dec = DecorationType::Strikethrough;
+        } else if self.underline || self.link.is_some() {
+            decoration.dec = DecorationType::Underline;
+        }
+        decoration
+    }
+
+    /// List attributes which are set
+    ///
+    /// Attributes are listed in a fixed order (outermost first) as used when
+    /// serializing text.
+    pub fn attrs(&self) -> Vec<RichAttr> {
+        let mut attrs = Vec::new();
+        if let Some(target) = self.link.as_ref() {
+            attrs.push(RichAttr::Link(target.clone()));
+        }
+        if self.bold {
+            attrs.push(RichAttr::Bold);
+        }
+        if self.italic {
+            attrs.push(RichAttr::Italic);
+        }
+        if self.strikethrough {
+            attrs.push(RichAttr::Strikethrough);
+        }
+        if self.underline {
+            attrs.push(RichAttr::Underline);
+        }
+        attrs
+    }
+}
+
+/// Rich text
+///
+/// This is a text with a sequence of formatting runs, supporting editing of
+/// both text and formatting. It supports conversion to and from (a subset of)
+/// Markdown and HTML.
+///
+/// Formatting runs are stored as a list of `(start, format)` pairs where the
+/// first `start` is zero and values are strictly increasing. Adjacent runs
+/// have distinct formats.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RichText {
+    text: String,
+    runs: Vec<(u32, RichFormat)>,
+    fonts: Vec<(u32, bool, bool)>,
+    colors: Vec<(u32, Colors)>,
+    decorations: Vec<(u32, Decoration)>,
+}
+
+impl Default for RichText {
+    fn default() -> Self {
+        RichText {
+            text: String::new(),
+            runs: vec![(0, RichFormat::default())],
+            fonts: vec![],
+            colors: vec![],
+            decorations: vec![],
+        }
+    }
+}
+
+impl RichText {
+    /// Construct from unformatted text
+    #[inline]
+    pub fn new(text: impl ToString) -> Self {
+        Self::with_format(text, RichFormat::default())
+    }
+
+    /// Construct from text using a single format
+    pub fn with_format(text: impl ToString, format: RichFormat) -> Self {
+        let mut rt = RichText {
+            text: text.to_string(),
+            runs: vec![(0, format)],
+            ..Default::default()
+        };
+        rt.update_caches();
+        rt
+    }
+
+    /// Get the length of the text in bytes
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.text.len()
+    }
+
+    /// True if the text is empty
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    /// Access formatting runs
+    ///
+    /// Each entry is a `(start, format)` pair; see type-level documentation.
+    #[inline]
+    pub fn runs(&self) -> &[(u32, RichFormat)] {
+        &self.runs
+    }
+
+    /// Iterate over formatted segments
+    pub fn segments(&self) -> impl Iterator<Item = (Range<usize>, &RichFormat)> {
+        let len = self.text.len();
+        self.runs.iter().enumerate().map(move |(i, (start, format))| {
+            let end = self.runs.get(i + 1).map(|r| r.0.cast()).unwrap_or(len);
+            (start.cast()..end, format)
+        })
+    }
+
+    /// Get the format applicable to the character at `index`
+    ///
+    /// If `index` is the text length, this returns the last format.
+    pub fn format_at(&self, index: usize) -> &RichFormat {
+        let i = self.runs.partition_point(|r| r.0 as usize <= index);
+        &self.runs[i.max(1) - 1].1
+    }
+
+    /// Get the format applicable to text inserted at `index`
+    ///
+    /// Inserted text inherits the format of the preceding character (or the
+    /// following character when at the start of the text), excepting links.
+    pub fn insertion_format(&self, index: usize) -> RichFormat {
+        let mut format = self.format_at(index.saturating_sub(1)).clone();
+        if format.link.is_some() && self.format_at(index).link != format.link {
+            format.link = None;
+        }
+        format
+    }
+
+    /// Check whether all text within `range` has attribute `attr`
+    ///
+    /// If `range` is empty, this checks the format at `range.start`.
+    pub fn range_has(&self, range: Range<usize>, attr: &RichAttr) -> bool {
+        if range.is_empty() {
+            return self.format_at(range.start).has(attr);
+        }
+        self.segments()
+            .filter(|(r, _)| r.start < range.end && range.start < r.end)
+            .all(|(_, format)| format.has(attr))
+    }
+
+    /// Get a slice of the text, with formatting
+    pub fn slice(&self, range: Range<usize>) -> RichText {
+        let mut rt = RichText::empty();
+        rt.push_range(self, range);
+        rt.update_caches();
+        rt
+    }
+
+    /// Replace `range` with `replace_with`
+    pub fn replace_range(&mut self, range: Range<usize>, replace_with: &RichText) {
+        let mut rt = RichText::empty();
+        rt.push_range(self, 0..range.start);
+        rt.push_range(replace_with, 0..replace_with.len());
+        rt.push_range(self, range.end..self.len());
+        rt.update_caches();
+        *self = rt;
+    }
+
+    /// Replace `range` with an unformatted `text` using the given `format`
+    pub fn replace_range_str(&mut self, range: Range<usize>, text: &str, format: RichFormat) {
+        self.replace_range(range, &RichText::with_format(text, format));
+    }
+
+    /// Adjust the format of text within `range`
+    pub fn update_format(&mut self, range: Range<usize>, f: impl Fn(&mut RichFormat)) {
+        let mut rt = RichText::empty();
+        rt.push_range(self, 0..range.start);
+        for (seg, format) in self.segments() {
+            let start = seg.start.max(range.start);
+            let end = seg.end.min(range.end);
+            if start < end {
+                let mut format = format.clone();
+                f(&mut format);
+                rt.push(&self.text[start..end], &format);
+            }
+        }
+        rt.push_range(self, range.end..self.len());
+        rt.update_caches();
+        *self = rt;
+    }
+
+    /// Toggle attribute `attr` over `range`
+    ///
+    /// If all text within `range` has the attribute, it is cleared, otherwise
+    /// it is set. Returns the new state of the attribute.
+    pub fn toggle(&mut self, range: Range<usize>, attr: &RichAttr) -> bool {
+        let value = !self.range_has(range.clone(), attr);
+        self.update_format(range, |format| format.set(attr, value));
+        value
+    }
+
+    /// Construct without any runs (breaks invariants until text is pushed)
+    fn empty() -> Self {
+        RichText {
+            runs: vec![],
+            ..Default::default()
+        }
+    }
+
+    /// Append `range` of `other`
+    fn push_range(&mut self, other: &RichText, range: Range<usize>) {
+        for (seg, format) in other.segments() {
+            let start = seg.start.max(range.start);
+            let end = seg.end.min(range.end);
+            if start < end {
+                self.push(&other.text[start..end], format);
+            }
+        }
+    }
+
+    /// Append `text` with `format`
+    fn push(&mut self, text: &str, format: &RichFormat) {
+        if text.is_empty() {
+            return;
+        }
+        let start: u32 = self.text.len().cast();
+        match self.runs.last_mut() {
+            Some(last) if last.1 == *format => (),
+            Some(last) if last.0 == start => last.1 = format.clone(),
+            _ => self.runs.push((start, format.clone())),
+        }
+        self.text.push_str(text);
+    }
+
+    fn update_caches(&mut self) {
+        if self.runs.is_empty() {
+            self.runs.push((0, RichFormat::default()));
+        }
+
+        self.fonts.clear();
+        self.colors.clear();
+        self.decorations.clear();
+
+        let mut font = (false, false);
+        let mut colors = Colors::default();
+        let mut decoration = Decoration::default();
+        for (start, format) in &self.runs {
+            let start = *start;
+            if (format.bold, format.italic) != font || start == 0 {
+                font = (format.bold, format.italic);
+                self.fonts.push((start, font.0, font.1));
+            }
+
+            let c = format.colors();
+            if c != colors {
+                colors = c;
+                self.colors.push((start, colors));
+            }
+
+            let d = format.decoration();
+            if d != decoration {
+                decoration = d;
+                self.decorations.push((start, decoration));
+            }
+        }
+    }
+
+    /// Serialize, opening and closing attributes as required
+    fn serialize(
+        &self,
+        out: &mut String,
+        mut open: impl FnMut(&mut String, &RichAttr),
+        mut close: impl FnMut(&mut String, &RichAttr),
+        mut text: impl FnMut(&mut String, &str),
+    ) {
+        let mut stack: Vec<RichAttr> = vec![];
+        for (range, format) in self.segments() {
+            let attrs = format.attrs();
+            let keep = stack.iter().take_while(|attr| attrs.contains(attr)).count();
+            while stack.len() > keep {
+                close(out, &stack.pop().unwrap());
+            }
+            for attr in attrs {
+                if !stack.contains(&attr) {
+                    open(out, &attr);
+                    stack.push(attr);
+                }
+            }
+            text(out, &self.text[range]);
+        }
+        while let Some(attr) = stack.pop() {
+            close(out, &attr);
+        }
+    }
+
+    /// Serialize as Markdown
+    ///
+    /// Line breaks are written as hard breaks. Underline (which has no
+    /// Markdown syntax) is written using inline HTML (`<u>`).
+    ///
+    /// Caveat: Markdown emphasis may be ambiguous where formatting changes
+    /// within a word.
+    pub fn to_markdown(&self) -> String {
+        fn delim(attr: &RichAttr, opening: bool) -> &str {
+            match attr {
+                RichAttr::Bold => "**",
+                RichAttr::Italic => "*",
+                RichAttr::Underline if opening => "<u>",
+                RichAttr::Underline => "</u>",
+                RichAttr::Strikethrough => "~~",
+                RichAttr::Link(_) if opening => "[",
+                RichAttr::Link(_) => "]",
+            }
+        }
+
+        // Emphasis delimiters must not be adjacent to inner whitespace, so we
+        // move whitespace outside of delimiters. `open_at` is the position of
+        // the first of a sequence of opening delimiters, if any.
+        let mut out = String::with_capacity(self.len());
+        let open_at = Cell::new(None);
+        self.serialize(
+            &mut out,
+            |out, attr| {
+                if open_at.get().is_none() {
+                    open_at.set(Some(out.len()));
+                }
+                out.push_str(delim(attr, true));
+            },
+            |out, attr| {
+                open_at.set(None);
+                let trimmed = out.trim_end_matches(' ').len();
+                let ws = out.split_off(trimmed);
+                out.push_str(delim(attr, false));
+                if let RichAttr::Link(target) = attr {
+                    out.push('(');
+                    out.push_str(&target.replace(' ', "%20").replace(')', "%29"));
+                    out.push(')');
+                }
+                out.push_str(&ws);
+            },
+            |out, text| {
+                let trimmed = text.trim_start_matches(' ');
+                if let Some(pos) = open_at.take() {
+                    out.insert_str(pos, &text[..text.len() - trimmed.len()]);
+                    escape_markdown(out, trimmed);
+                } else {
+                    escape_markdown(out, text);
+                }
+            },
+        );
+        out
+    }
+
+    /// Serialize as HTML
+    ///
+    /// This yields an HTML fragment using `<b>`, `<i>`, `<u>`, `<s>`, `<a>`
+    /// and `<br>` elements.
+    pub fn to_html(&self) -> String {
+        let mut out = String::with_capacity(self.len());
+        self.serialize(
+            &mut out,
+            |out, attr| match attr {
+                RichAttr::Bold => out.push_str("<b>"),
+                RichAttr::Italic => out.push_str("<i>"),
+                RichAttr::Underline => out.push_str("<u>"),
+                RichAttr::Strikethrough => out.push_str("<s>"),
+                RichAttr::Link(target) => {
+                    out.push_str("<a href=\"");
+                    escape_html(out, target);
+                    out.push_str("\">");
+                }
+            },
+            |out, attr| match attr {
+                RichAttr::Bold => out.push_str("</b>"),
+                RichAttr::Italic => out.push_str("</i>"),
+                RichAttr::Underline => out.push_str("</u>"),
+                RichAttr::Strikethrough => out.push_str("</s>"),
+                RichAttr::Link(_) => out.push_str("</a>"),
+            },
+            |out, text| {
+                for (i, line) in text.split('\n').enumerate() {
+                    if i > 0 {
+                        out.push_str("<br>");
+                    }
+                    escape_html(out, line);
+                }
+            },
+        );
+        out
+    }
+
+    /// Parse an HTML fragment
+    ///
+    /// This is a lenient parser supporting elements `b`, `strong`, `i`, `em`,
+    /// `u`, `s`, `del`, `strike`, `a` (with `href`), `br`, `p` and `div`
+    /// as well as character references. Other elements are ignored (though
+    /// their content is retained). Whitespace containing line breaks or tabs
+    /// is collapsed to a single space.
+    pub fn from_html(input: &str) -> RichText {
+        let mut rt = RichText::empty();
+        let mut format = RichFormat::default();
+        let mut stack: Vec<(String, RichFormat)> = vec![];
+
+        let mut rest = input;
+        while !rest.is_empty() {
+            if let Some(tag) = rest.strip_prefix('<') {
+                let Some(end) = tag.find('>') else {
+                    rt.push(&decode_html(rest), &format);
+                    break;
+                };
+                let (tag, tail) = (&tag[..end], &tag[end + 1..]);
+                rest = tail;
+
+                let (closing, tag) = match tag.strip_prefix('/') {
+                    Some(tag) => (true, tag),
+                    None => (false, tag),
+                };
+                let tag = tag.trim_end_matches('/');
+                let name_end = tag
+                    .find(|c: char| c.is_ascii_whitespace())
+                    .unwrap_or(tag.len());
+                let name = tag[..name_end].to_ascii_lowercase();
+
+                match name.as_str() {
+                    "br" => rt.push("\n", &format),
+                    "p" | "div" => {
+                        if !rt.text.is_empty() && !rt.text.ends_with('\n') {
+                            rt.push("\n", &format);
+                        }
+                    }
+                    "b" | "strong" | "i" | "em" | "u" | "s" | "del" | "strike" | "a" => {
+                        if closing {
+                            if let Some(i) = stack.iter().rposition(|(n, _)| *n == name) {
+                                format = stack[i].1.clone();
+                                stack.truncate(i);
+                            }
+                            continue;
+                        }
+
+                        stack.push((name.clone(), format.clone()));
+                        match name.as_str() {
+                            "b" | "strong" => format.bold = true,
+                            "i" | "em" => format.italic = true,
+                            "u" => format.underline = true,
+                            "s" | "del" | "strike" => format.strikethrough = true,
+                            _ => {
+                                let href = html_attr(&tag[name_end..], "href");
+                                format.link = Some(decode_html(href.unwrap_or("")).into());
+                            }
+                        }
+                    }
+                    _ => (),
+                }
+            } else {
+                let end = rest.find('<').unwrap_or(rest.len());
+                let mut text = String::with_capacity(end);
+                let mut ws = String::new();
+                for c in rest[..end].chars() {
+                    if c.is_ascii_whitespace() {
+                        ws.push(c);
+                    } else {
+                        if ws.contains(['\n', '\r', '\t']) {
+                            text.push(' ');
+                        } else {
+                            text.push_str(&ws);
+                        }
+                        ws.clear();
+                        text.push(c);
+                    }
+                }
+                if ws.contains(['\n', '\r', '\t']) {
+                    text.push(' ');
+                } else {
+                    text.push_str(&ws);
+                }
+                rt.push(&decode_html(&text), &format);
+                rest = &rest[end..];
+            }
+        }
+
+        rt.update_caches();
+        rt
+    }
+
+    /// Parse Markdown
+    ///
+    /// This uses the [`Markdown`](super::Markdown) parser, keeping bold,
+    /// italic, strike-through, underline (via inline HTML `<u>`) and links.
+    /// Other formatting (e.g. headings and code) is kept as plain text.
+    /// Paragraphs are separated by an empty line.
+    #[cfg(feature = "markdown")]
+    pub fn from_markdown(input: &str) -> Result<RichText, super::MarkdownError> {
+        let md = super::Markdown::new(input)?;
+
+        let mut rt = RichText::empty();
+        for (i, fmt) in md.fmt.iter().enumerate() {
+            let start = fmt.start.cast();
+            let end = md.fmt.get(i + 1).map(|next| next.start.cast());
+            let format = RichFormat {
+                bold: fmt.bold,
+                italic: fmt.italic,
+                underline: fmt.underline,
+                strikethrough: fmt.strikethrough,
+                link: fmt.link.clone(),
+            };
+            rt.push(&md.text[start..end.unwrap_or(md.text.len())], &format);
+        }
+
+        rt.update_caches();
+        Ok(rt)
+    }
+}
+
+fn escape_markdown(out: &mut String, text: &str) {
+    let mut line_start = out.is_empty() || out.ends_with('\n');
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\n' => {
+                out.push_str("\\\n");
+                line_start = true;
+                continue;
+            }
+            '\\' | '`' | '*' | '_' | '~' | '[' | ']' | '<' | '>' | '#' | '&' | '|' | '!' => {
+                out.push('\\');
+            }
+            '-' | '+' | '=' if line_start => out.push('\\'),
+            '0'..='9' if line_start => {
+                out.push(c);
+                while let Some(d) = chars.next_if(|d| d.is_ascii_digit()) {
+                    out.push(d);
+                }
+                if let Some(d) = chars.next_if(|d| *d == '.' || *d == ')') {
+                    out.push('\\');
+                    out.push(d);
+                }
+                line_start = false;
+                continue;
+            }
+            _ => (),
+        }
+        out.push(c);
+        line_start = false;
+    }
+}
+
+fn escape_html(out: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            c => out.push(c),
+        }
+    }
+}
+
+fn decode_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(i) = rest.find('&') {
+        out.push_str(&rest[..i]);
+        rest = &rest[i..];
+        let decoded = rest.find(';').filter(|end| *end <= 10).and_then(|end| {
+            let c = match &rest[1..end] {
+                "amp" => '&',
+                "lt" => '<',
+                "gt" => '>',
+                "quot" => '"',
+                "apos" => '\'',
+                "nbsp" => '\u{a0}',
+                s if s.starts_with("#x") || s.starts_with("#X") => {
+                    char::from_u32(u32::from_str_radix(&s[2..], 16).ok()?)?
+                }
+                s if s.starts_with('#') => char::from_u32(s[1..].parse().ok()?)?,
+                _ => return None,
+            };
+            Some((c, end))
+        });
+        if let Some((c, end)) = decoded {
+            out.push(c);
+            rest = &rest[end + 1..];
+        } else {
+            out.push('&');
+            rest = &rest[1..];
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Find the value of attribute `name` within the attribute list of a tag
+fn html_attr<'a>(attrs: &'a str, name: &str) -> Option<&'a str> {
+    let mut rest = attrs;
+    loop {
+        rest = rest.trim_start();
+        let end = rest.find(|c: char| c == '=' || c.is_ascii_whitespace())?;
+        let key = &rest[..end];
+        rest = rest[end..].trim_start().strip_prefix('=')?.trim_start();
+        let value;
+        if let Some(quote) = rest.chars().next().filter(|c| *c == '"' || *c == '\'') {
+            let inner = &rest[1..];
+            let end = inner.find(quote)?;
+            value = &inner[..end];
+            rest = &inner[end + 1..];
+        } else {
+            let end = rest
+                .find(|c: char| c.is_ascii_whitespace())
+                .unwrap_or(rest.len());
+            value = &rest[..end];
+            rest = &rest[end..];
+        }
+        if key.eq_ignore_ascii_case(name) {
+            return Some(value);
+        }
+    }
+}
+
+impl FormattableText for RichText {
+    #[inline]
+    fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    fn font_tokens(&self, dpem: f32, font: FontSelector) -> impl Iterator<Item = FontToken> {
+        self.fonts.iter().map(move |(start, bold, italic)| {
+            let mut font = font;
+            if *bold {
+                font.weight = FontWeight::BOLD;
+            }
+            if *italic {
+                font.style = FontStyle::Italic;
+            }
+            FontToken {
+                start: *start,
+                dpem,
+                font,
+            }
+        })
+    }
+
+    #[inline]
+    fn color_tokens(&self) -> &[(u32, Colors)] {
+        &self.colors
+    }
+
+    #[inline]
+    fn decorations(&self) -> &[(u32, Decoration)] {
+        &self.decorations
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn sample() -> RichText {
+        let mut rt = RichText::new("Some bold and italic text\nwith a link.");
+        rt.toggle(5..9, &RichAttr::Bold);
+        rt.toggle(14..20, &RichAttr::Italic);
+        rt.toggle(5..20, &RichAttr::Underline);
+        rt.update_format(33..37, |f| f.link = Some("https://example.com".into()));
+        rt
+    }
+
+    #[test]
+    fn edit() {
+        let mut rt = sample();
+        assert!(rt.range_has(5..9, &RichAttr::Bold));
+        assert!(!rt.range_has(4..9, &RichAttr::Bold));
+
+        rt.replace_range_str(7..7, "XX", rt.insertion_format(7));
+        assert_eq!(&rt.as_str()[5..11], "boXXld");
+        assert!(rt.range_has(5..11, &RichAttr::Bold));
+
+        rt.replace_range(0..11, &RichText::new(""));
+        assert_eq!(rt.runs()[0].0, 0);
+        assert_eq!(rt.slice(3..11).as_str(), "italic t");
+    }
+
+    #[test]
+    fn html_round_trip() {
+        let rt = sample();
+        let html = rt.to_html();
+        assert_eq!(
+            html,
+            "Some <b><u>bold</u></b><u> and <i>italic</i></u> text<br>with a <a href=\"https://example.com\">link</a>."
+        );
+        assert_eq!(RichText::from_html(&html), rt);
+    }
+
+    #[cfg(feature = "markdown")]
+    #[test]
+    fn markdown_round_trip() {
+        let rt = sample();
+        let md = rt.to_markdown();
+        assert_eq!(RichText::from_markdown(&md).unwrap(), rt);
+    }
+
+    #[cfg(feature = "markdown")]
+    #[test]
+    fn markdown_blocks_as_plain_text() {
+        let rt = RichText::from_markdown("# Title\n\nSome `code` and **bold**").unwrap();
+        assert_eq!(rt.as_str(), "Title\n\nSome code and bold");
+        assert_eq!(rt.segments().count(), 2);
+        assert!(rt.range_has(21..25, &RichAttr::Bold));
+
+        assert!(RichText::from_markdown("<b>html</b>").is_err());
+    }
+}
@@ -7,11 +7,11 @@
 
 use super::format::{Colors, Decoration, FormattableText};
 use super::*;
-use crate::cast::Cast;
+use crate::cast::{Cast, Conv};
 use crate::draw::color::Rgba;
 use crate::event::ConfigCx;
-use crate::geom::{Rect, Vec2};
-use crate::layout::{AlignHints, AxisInfo, SizeRules};
+use crate::geom::{Quad, Rect, Vec2};
+use crate::layout::{AlignHints, AxisInfo, BASELINE_COLOR, SizeRules};
 use crate::theme::{DrawCx, SizeCx, TextClass};
 use crate::{Layout, autoimpl};
 use std::num::NonZeroUsize;
@@ -60,6 +60,7 @@ impl<T: FormattableText> Layout for Text<T> {
             let tokens = self.color_tokens();
             draw.text(rect.pos, rect, display, tokens);
             draw.decorate_text(rect.pos, rect, display, self.decorations());
+            self.draw_baselines(&mut draw);
         }
     }
 }
@@ -256,6 +257,31 @@ impl<T: FormattableText> Text<T> {
             })];
             draw.text(rect.pos, rect, display, &tokens);
             draw.decorate_text(rect.pos, rect, display, self.decorations());
+            self.draw_baselines(&mut draw);
+        }
+    }
+
+    /// Draw line baselines while the layout debug overlay is enabled
+    fn draw_baselines(&self, draw: &mut DrawCx) {
+        if !draw.ev_state().layout_overlay() {
+            return;
+        }
+        let (Ok(lines), Ok((tl, br))) = (self.lines(), self.bounding_box()) else {
+            return;
+        };
+
+        let pos = Vec2::conv(self.rect().pos);
+        for line in lines {
+            let (top, bottom) = (line.top(), line.bottom());
+            let Ok(mut markers) = self.text_glyph_pos(line.text_range().start) else {
+                continue;
+            };
+            if let Some(m) = markers.find(|m| top <= m.pos.1 && m.pos.1 <= bottom) {
+                let y = pos.1 + m.pos.1;
+                let a = Vec2(pos.0 + tl.0, y);
+                let b = Vec2(pos.0 + br.0, y + 1.0);
+                draw.draw().rect(Quad::from_coords(a, b), BASELINE_COLOR);
+            }
         }
     }
 }
@@ -19,8 +19,8 @@ struct Config {
 #[derive(Debug)]
 pub struct AnimState {
     c: Config,
-    now: Instant, // frame start time
-    text_cursor: TextCursor,
+    now: Option<Instant>, // frame start time
+    text_cursor: Option<TextCursor>,
 }
 
 impl AnimState {
@@ -29,25 +29,22 @@ impl AnimState {
             cursor_blink_rate: config.cursor_blink_rate(),
             fade_dur: config.transition_fade_duration(),
         };
-        let now = Instant::now();
         AnimState {
             c,
-            now,
-            text_cursor: TextCursor {
-                widget: 0,
-                byte: 0,
-                state: false,
-                time: now,
-            },
+            now: None,
+            text_cursor: None,
         }
     }
 
-    pub fn update(&mut self) {
-        self.now = Instant::now();
+    /// Set the frame start time
+    ///
+    /// This must be called before drawing each frame.
+    pub fn update(&mut self, now: Instant) {
+        self.now = Some(now);
     }
 
     fn elapsed(&self, time: Instant) -> Option<Duration> {
-        if self.now > time { Some(self.now - time) } else { None }
+        self.now.filter(|now| *now > time).map(|now| now - time)
     }
 }
 
@@ -66,21 +63,29 @@ impl AnimState {
     where
         D: DrawImpl,
     {
-        let entry = &mut self.text_cursor;
+        let Some(now) = self.now else {
+            return true;
+        };
         let widget = id.to_nzu64().get();
-        if entry.widget == widget && entry.byte == byte {
-            if entry.time < self.now {
+        if let Some(entry) = self.text_cursor.as_mut()
+            && entry.widget == widget
+            && entry.byte == byte
+        {
+            if entry.time < now {
                 entry.state = !entry.state;
                 entry.time += self.c.cursor_blink_rate;
             }
             draw.animate_at(entry.time);
             entry.state
         } else {
-            entry.widget = widget;
-            entry.byte = byte;
-            entry.state = true;
-            entry.time = self.now + self.c.cursor_blink_rate;
-            draw.animate_at(entry.time);
+            let time = now + self.c.cursor_blink_rate;
+            self.text_cursor = Some(TextCursor {
+                widget,
+                byte,
+                state: true,
+                time,
+            });
+            draw.animate_at(time);
             true
         }
     }
@@ -124,6 +124,10 @@ impl InputState {
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(deserialize = "C: serde::Deserialize<'de> + From<Rgba8Srgb>"))
+)]
 pub struct Colors<C> {
     /// True if this is a dark theme
     pub is_dark: bool,
@@ -163,6 +167,36 @@ pub struct Colors<C> {
     ///
     /// This may be the same as `accent_soft`.
     pub text_sel_bg: C,
+    /// Colour indicating success (e.g. of a [`Toast`](crate::window::Toast))
+    #[cfg_attr(feature = "serde", serde(default = "default_success"))]
+    pub success: C,
+    /// Colour indicating a warning
+    #[cfg_attr(feature = "serde", serde(default = "default_warning"))]
+    pub warning: C,
+    /// Colour indicating an error
+    #[cfg_attr(feature = "serde", serde(default = "default_error"))]
+    pub error: C,
+    /// Colour of hyperlink text
+    #[cfg_attr(feature = "serde", serde(default = "default_link"))]
+    pub link: C,
+}
+
+// Defaults for colours missing from saved schemes
+#[cfg(feature = "serde")]
+fn default_success<C: From<Rgba8Srgb>>() -> C {
+    ColorsSrgb::LIGHT.success.into()
+}
+#[cfg(feature = "serde")]
+fn default_warning<C: From<Rgba8Srgb>>() -> C {
+    ColorsSrgb::LIGHT.warning.into()
+}
+#[cfg(feature = "serde")]
+fn default_error<C: From<Rgba8Srgb>>() -> C {
+    ColorsSrgb::LIGHT.error.into()
+}
+#[cfg(feature = "serde")]
+fn default_link<C: From<Rgba8Srgb>>() -> C {
+    ColorsSrgb::LIGHT.link.into()
 }
 
 /// [`Colors`] parameterised for reading and writing using sRGB
@@ -187,6 +221,10 @@ impl From<&ColorsSrgb> for ColorsLinear {
             text_invert: col.text_invert.into(),
             text_disabled: col.text_disabled.into(),
             text_sel_bg: col.text_sel_bg.into(),
+            success: col.success.into(),
+            warning: col.warning.into(),
+            error: col.error.into(),
+            link: col.link.into(),
         }
     }
 }
@@ -213,6 +251,10 @@ impl From<&ColorsLinear> for ColorsSrgb {
             text_invert: col.text_invert.into(),
             text_disabled: col.text_disabled.into(),
             text_sel_bg: col.text_sel_bg.into(),
+            success: col.success.into(),
+            warning: col.warning.into(),
+            error: col.error.into(),
+            link: col.link.into(),
         }
     }
 }
@@ -239,6 +281,10 @@ impl ColorsSrgb {
         text_invert: Rgba8Srgb::parse("FFFFFF"),
         text_disabled: Rgba8Srgb::parse("AAAAAA"),
         text_sel_bg: Rgba8Srgb::parse("A172FA"),
+        success: Rgba8Srgb::parse("2E9E48"),
+        warning: Rgba8Srgb::parse("E0901A"),
+        error: Rgba8Srgb::parse("D42626"),
+        link: Rgba8Srgb::parse("1E64C8"),
     };
 
     /// Dark scheme
@@ -256,6 +302,10 @@ impl ColorsSrgb {
         text_invert: Rgba8Srgb::parse("000000"),
         text_disabled: Rgba8Srgb::parse("CBCBCB"),
         text_sel_bg: Rgba8Srgb::parse("E77346"),
+        success: Rgba8Srgb::parse("4CC066"),
+        warning: Rgba8Srgb::parse("F5B040"),
+        error: Rgba8Srgb::parse("F25555"),
+        link: Rgba8Srgb::parse("6CA8F0"),
     };
 
     /// Blue scheme
@@ -273,6 +323,10 @@ impl ColorsSrgb {
         text_invert: Rgba8Srgb::parse("FFFFFF"),
         text_disabled: Rgba8Srgb::parse("AAAAAA"),
         text_sel_bg: Rgba8Srgb::parse("6CC0E1"),
+        success: Rgba8Srgb::parse("2E9E48"),
+        warning: Rgba8Srgb::parse("E0901A"),
+        error: Rgba8Srgb::parse("D42626"),
+        link: Rgba8Srgb::parse("1E64C8"),
     };
 }
 
@@ -79,6 +79,8 @@ crate::impl_scope! {
         pub slider_size: Vec2 = Vec2(24.0, 12.0),
         /// Minimum size for a horizontal progress bar
         pub progress_bar: Vec2 = Vec2(24.0, 8.0),
+        /// Size of a spinner
+        pub spinner: f32 = 24.0,
         /// Shadow size (average)
         pub shadow_size: Vec2 = Vec2::ZERO,
         /// Proportional offset of shadow (range: -1..=1)
@@ -113,6 +115,7 @@ pub struct Dimensions {
     pub scroll_bar: Size,
     pub slider: Size,
     pub progress_bar: Size,
+    pub spinner: i32,
     pub shadow_a: Vec2,
     pub shadow_b: Vec2,
 }
@@ -154,6 +157,7 @@ impl Dimensions {
             scroll_bar: Size::conv_nearest(params.scroll_bar_size * scale),
             slider: Size::conv_nearest(params.slider_size * scale),
             progress_bar: Size::conv_nearest(params.progress_bar * scale),
+            spinner: i32::conv_nearest(params.spinner * scale),
             shadow_a: shadow_offset - shadow_size,
             shadow_b: shadow_offset + shadow_size,
         }
@@ -278,6 +282,9 @@ impl ThemeSize for Window {
             Feature::CheckBox | Feature::RadioBox => {
                 return SizeRules::fixed(self.dims.check_box).with_margin(self.dims.m_small);
             }
+            Feature::Spinner => {
+                return SizeRules::fixed(self.dims.spinner).with_margin(self.dims.m_large);
+            }
             Feature::ScrollBar(dir) => {
                 dir_is_vertical = dir.is_vertical();
                 size = self.dims.scroll_bar;
@@ -313,6 +320,9 @@ impl ThemeSize for Window {
             Feature::CheckBox | Feature::RadioBox => {
                 ideal_size = Size::splat(self.dims.check_box);
             }
+            Feature::Spinner => {
+                ideal_size = Size::splat(self.dims.spinner);
+            }
             Feature::ScrollBar(dir) => {
                 ideal_size.set_component(dir.flipped(), self.dims.scroll_bar.1);
             }
@@ -8,12 +8,13 @@
 use winit::keyboard::Key;
 
 use super::{FrameStyle, MarkStyle, SelectionStyle, SizeCx, ThemeSize};
+use crate::cast::Conv;
 use crate::dir::Direction;
 use crate::draw::color::{ParseError, Rgb};
 use crate::draw::{Draw, DrawIface, DrawRounded, DrawShared, DrawSharedImpl, ImageId, PassType};
 use crate::event::EventState;
 #[allow(unused)] use crate::event::{Command, ConfigCx};
-use crate::geom::{Coord, Offset, Rect};
+use crate::geom::{Coord, Offset, Rect, Size};
 #[allow(unused)] use crate::text::format::FormattableText;
 use crate::text::{TextDisplay, format};
 use crate::theme::ColorsLinear;
@@ -390,6 +391,22 @@ impl<'a> DrawCx<'a> {
             .slider(&self.id, grip.id_ref(), track_rect, grip.rect(), dir);
     }
 
+    /// Draw UI element: range slider
+    ///
+    /// Each element of `handles` is a pair of the handle (used for focus
+    /// state) and the grip (used for position and mouse-over state).
+    pub fn range_slider<H: Tile, W: Tile>(
+        &mut self,
+        track_rect: Rect,
+        handles: [(&H, &W); 2],
+        dir: Direction,
+    ) {
+        let ids = handles.map(|(h, w)| (h.id_ref(), w.id_ref()));
+        let grip_rects = handles.map(|(_, w)| w.rect());
+        self.h
+            .range_slider(&self.id, ids, track_rect, grip_rects, dir);
+    }
+
     /// Draw UI element: progress bar
     ///
     /// -   `rect`: area of whole widget
@@ -400,6 +417,34 @@ impl<'a> DrawCx<'a> {
         self.h.progress_bar(&self.id, rect, dir, value);
     }
 
+    /// Draw UI element: indeterminate progress bar
+    ///
+    /// -   `rect`: area of whole widget
+    /// -   `dir`: direction of progress bar
+    /// -   `phase`: animation phase, between 0.0 and 1.0
+    pub fn progress_bar_indeterminate(&mut self, rect: Rect, dir: Direction, phase: f32) {
+        self.h
+            .progress_bar_indeterminate(&self.id, rect, dir, phase);
+    }
+
+    /// Draw UI element: stepped progress
+    ///
+    /// -   `rect`: area of whole widget
+    /// -   `dir`: direction of progress
+    /// -   `steps`: number of steps
+    /// -   `current`: index of the current step; previous steps are complete
+    pub fn progress_steps(&mut self, rect: Rect, dir: Direction, steps: usize, current: usize) {
+        self.h.progress_steps(&self.id, rect, dir, steps, current);
+    }
+
+    /// Draw UI element: spinner (busy indicator)
+    ///
+    /// -   `rect`: area of whole widget
+    /// -   `phase`: animation phase, between 0.0 and 1.0
+    pub fn spinner(&mut self, rect: Rect, phase: f32) {
+        self.h.spinner(&self.id, rect, phase);
+    }
+
     /// Draw an image
     pub fn image(&mut self, rect: Rect, id: ImageId) {
         self.h.image(id, rect);
@@ -580,6 +625,32 @@ pub trait ThemeDraw {
     /// -   `dir`: direction of slider (currently only LTR or TTB)
     fn slider(&mut self, id: &Id, grip_id: &Id, rect: Rect, grip_rect: Rect, dir: Direction);
 
+    /// Draw UI element: range slider
+    ///
+    /// -   `id`: [`Id`] of the slider
+    /// -   `ids`: [`Id`]s of the lower and upper handle and grip
+    /// -   `rect`: area of whole widget (slider track)
+    /// -   `grip_rects`: areas of the lower and upper grips
+    /// -   `dir`: direction of slider
+    ///
+    /// The default implementation draws via [`Self::slider`], without
+    /// highlighting the selected sub-range.
+    fn range_slider(
+        &mut self,
+        id: &Id,
+        ids: [(&Id, &Id); 2],
+        rect: Rect,
+        grip_rects: [Rect; 2],
+        dir: Direction,
+    ) {
+        let _ = id;
+        let [(lower, lower_grip), (upper, upper_grip)] = ids;
+        self.slider(lower, lower_grip, rect, grip_rects[0], dir);
+        // Draw the upper grip over the lower with an empty track
+        let track = Rect::new(grip_rects[1].pos, Size::ZERO);
+        self.slider(upper, upper_grip, track, grip_rects[1], dir);
+    }
+
     /// Draw UI element: progress bar
     ///
     /// -   `id`: [`Id`] of the bar
@@ -588,6 +659,51 @@ pub trait ThemeDraw {
     /// -   `value`: progress value, between 0.0 and 1.0
     fn progress_bar(&mut self, id: &Id, rect: Rect, dir: Direction, value: f32);
 
+    /// Draw UI element: indeterminate progress bar
+    ///
+    /// -   `id`: [`Id`] of the bar
+    /// -   `rect`: area of whole widget
+    /// -   `dir`: direction of progress bar
+    /// -   `phase`: animation phase, between 0.0 and 1.0
+    ///
+    /// The default implementation draws via [`Self::progress_bar`], filling
+    /// and emptying the bar over each cycle.
+    fn progress_bar_indeterminate(&mut self, id: &Id, rect: Rect, dir: Direction, phase: f32) {
+        let value = 1.0 - (2.0 * phase - 1.0).abs();
+        self.progress_bar(id, rect, dir, value);
+    }
+
+    /// Draw UI element: stepped progress
+    ///
+    /// -   `id`: [`Id`] of the widget
+    /// -   `rect`: area of whole widget
+    /// -   `dir`: direction of progress
+    /// -   `steps`: number of steps
+    /// -   `current`: index of the current step; previous steps are complete
+    ///
+    /// The default implementation draws via [`Self::progress_bar`], filled to
+    /// the fraction of complete steps.
+    fn progress_steps(&mut self, id: &Id, rect: Rect, dir: Direction, steps: usize, current: usize) {
+        let value = if steps == 0 {
+            0.0
+        } else {
+            f32::conv(current.min(steps)) / f32::conv(steps)
+        };
+        self.progress_bar(id, rect, dir, value);
+    }
+
+    /// Draw UI element: spinner (busy indicator)
+    ///
+    /// -   `id`: [`Id`] of the spinner
+    /// -   `rect`: area of whole widget
+    /// -   `phase`: animation phase, between 0.0 and 1.0
+    ///
+    /// The default implementation draws via
+    /// [`Self::progress_bar_indeterminate`].
+    fn spinner(&mut self, id: &Id, rect: Rect, phase: f32) {
+        self.progress_bar_indeterminate(id, rect, Direction::Right, phase);
+    }
+
     /// Draw an image
     fn image(&mut self, id: ImageId, rect: Rect);
 }
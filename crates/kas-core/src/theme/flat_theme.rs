@@ -9,7 +9,7 @@ use std::cell::RefCell;
 use std::f32;
 use std::time::Instant;
 
-use super::SimpleTheme;
+use super::{SimpleTheme, simple_theme};
 use crate::Id;
 use crate::cast::traits::*;
 use crate::config::{Config, WindowConfig};
@@ -109,7 +109,7 @@ where
         ev: &'a mut EventState,
         w: &'a mut Self::Window,
     ) -> Self::Draw<'a> {
-        w.anim.update();
+        w.anim.update(ev.now());
 
         DrawHandle {
             draw,
@@ -188,6 +188,39 @@ where
         inner
     }
 
+    // Draw a slider grip; force it to be square
+    fn slider_grip(&mut self, h_rect: Rect, state: InputState) {
+        let size = Size::splat(h_rect.size.0.min(h_rect.size.1));
+        let offset = Offset::conv((h_rect.size - size) / 2);
+        let outer = Quad::conv(Rect::new(h_rect.pos + offset, size));
+
+        let col = if state.nav_focus() && !state.disabled() {
+            self.cols.accent_soft
+        } else {
+            self.cols.background
+        };
+        let col = ColorsLinear::adjust_for_state(col, state);
+
+        if !self.cols.is_dark && !state.contains(InputState::DISABLED | InputState::DEPRESS) {
+            let (mut a, mut b) = (self.w.dims.shadow_a, self.w.dims.shadow_b);
+            let mut mult = 0.6;
+            if state.under_mouse() {
+                mult *= SHADOW_MOUSE_OVER;
+            }
+            a *= mult;
+            b *= mult;
+            let shadow_outer = Quad::from_coords(a + outer.a, b + outer.b);
+            let col1 = if self.cols.is_dark { col } else { Rgba::BLACK };
+            let mut col2 = col1;
+            col2.a = 0.0;
+            self.draw.circle_2col(shadow_outer, col1, col2);
+        }
+
+        self.draw.circle(outer, 0.0, col);
+        let col = self.cols.nav_region(state).unwrap_or(self.cols.frame);
+        self.draw.circle(outer, 14.0 / 16.0, col);
+    }
+
     pub fn edit_box(&mut self, id: &Id, outer: Quad, bg: Background) {
         let state = InputState::new_except_depress(self.ev, id);
         let col_bg = self.cols.from_edit_bg(bg, state);
@@ -502,36 +535,41 @@ where
         self.draw
             .rounded_frame(second, inner, 1.0 / 3.0, self.cols.frame);
 
-        // grip; force it to be square
-        let size = Size::splat(h_rect.size.0.min(h_rect.size.1));
-        let offset = Offset::conv((h_rect.size - size) / 2);
-        let outer = Quad::conv(Rect::new(h_rect.pos + offset, size));
+        self.slider_grip(h_rect, state);
+    }
 
-        let col = if state.nav_focus() && !state.disabled() {
-            self.cols.accent_soft
+    fn range_slider(
+        &mut self,
+        _: &Id,
+        ids: [(&Id, &Id); 2],
+        rect: Rect,
+        grip_rects: [Rect; 2],
+        dir: Direction,
+    ) {
+        // track
+        let mut outer = Quad::conv(rect);
+        let [a, b] = grip_rects.map(|r| Vec2::conv(r.pos + r.size / 2));
+        let mut fill;
+        if dir.is_horizontal() {
+            outer = outer.shrink_vec(Vec2(0.0, outer.size().1 * (1.0 / 3.0)));
+            fill = outer;
+            (fill.a.0, fill.b.0) = (a.0.min(b.0), a.0.max(b.0));
         } else {
-            self.cols.background
-        };
-        let col = ColorsLinear::adjust_for_state(col, state);
-
-        if !self.cols.is_dark && !state.contains(InputState::DISABLED | InputState::DEPRESS) {
-            let (mut a, mut b) = (self.w.dims.shadow_a, self.w.dims.shadow_b);
-            let mut mult = 0.6;
-            if state.under_mouse() {
-                mult *= SHADOW_MOUSE_OVER;
-            }
-            a *= mult;
-            b *= mult;
-            let shadow_outer = Quad::from_coords(a + outer.a, b + outer.b);
-            let col1 = if self.cols.is_dark { col } else { Rgba::BLACK };
-            let mut col2 = col1;
-            col2.a = 0.0;
-            self.draw.circle_2col(shadow_outer, col1, col2);
+            outer = outer.shrink_vec(Vec2(outer.size().0 * (1.0 / 3.0), 0.0));
+            fill = outer;
+            (fill.a.1, fill.b.1) = (a.1.min(b.1), a.1.max(b.1));
         }
 
-        self.draw.circle(outer, 0.0, col);
-        let col = self.cols.nav_region(state).unwrap_or(self.cols.frame);
-        self.draw.circle(outer, 14.0 / 16.0, col);
+        let inner = outer.shrink(outer.size().min_comp() / 2.0);
+        self.draw
+            .rounded_frame(outer, inner, 1.0 / 3.0, self.cols.frame);
+        let inner = fill.shrink(fill.size().min_comp() / 2.0);
+        self.draw.rounded_frame(fill, inner, 0.0, self.cols.accent);
+
+        for ((id, id2), h_rect) in ids.into_iter().zip(grip_rects) {
+            let state = InputState::new2(self.ev, id, id2);
+            self.slider_grip(h_rect, state);
+        }
     }
 
     fn progress_bar(&mut self, _: &Id, rect: Rect, dir: Direction, value: f32) {
@@ -547,4 +585,42 @@ where
         let inner = outer.shrink(outer.size().min_comp() / 2.0);
         self.draw.rounded_frame(outer, inner, 0.0, self.cols.accent);
     }
+
+    fn progress_bar_indeterminate(&mut self, _: &Id, rect: Rect, dir: Direction, phase: f32) {
+        let outer = Quad::conv(rect);
+        let inner = outer.shrink(outer.size().min_comp() / 2.0);
+        self.draw.rounded_frame(outer, inner, 0.75, self.cols.frame);
+
+        let (a, b) = simple_theme::indeterminate_section(phase);
+        if a < b {
+            let bar = simple_theme::progress_section(outer, dir, a, b);
+            let inner = bar.shrink(bar.size().min_comp() / 2.0);
+            self.draw.rounded_frame(bar, inner, 0.0, self.cols.accent);
+        }
+    }
+
+    fn progress_steps(&mut self, _: &Id, rect: Rect, dir: Direction, steps: usize, current: usize) {
+        let outer = Quad::conv(rect);
+        let gap: f32 = self.w.dims.m_inner.cast();
+        for (i, step) in simple_theme::progress_steps(outer, dir, steps, gap).enumerate() {
+            let inner = step.shrink(step.size().min_comp() / 2.0);
+            let col = match i.cmp(&current) {
+                std::cmp::Ordering::Less => self.cols.accent,
+                std::cmp::Ordering::Equal => self.cols.accent_soft,
+                std::cmp::Ordering::Greater => self.cols.frame,
+            };
+            self.draw.rounded_frame(step, inner, 0.0, col);
+        }
+    }
+
+    fn spinner(&mut self, _: &Id, rect: Rect, phase: f32) {
+        let outer = Quad::conv(rect);
+        let r = outer.size().min_comp() / 10.0;
+        for i in 0..simple_theme::SPINNER_DOTS {
+            let (pos, alpha) = simple_theme::spinner_dot(outer, r, phase, i);
+            let mut col = self.cols.accent;
+            col.a *= alpha;
+            self.draw.circle(Quad::from_center(pos, r), 0.0, col);
+        }
+    }
 }
@@ -79,7 +79,7 @@ impl<DS: DrawSharedImpl> Theme<DS> for SimpleTheme {
         ev: &'a mut EventState,
         w: &'a mut Self::Window,
     ) -> Self::Draw<'a> {
-        w.anim.update();
+        w.anim.update(ev.now());
 
         DrawHandle {
             draw,
@@ -482,6 +482,32 @@ impl<'a, DS: DrawSharedImpl> ThemeDraw for DrawHandle<'a, DS> {
         self.draw.rect(grip, col);
     }
 
+    fn range_slider(
+        &mut self,
+        _: &Id,
+        ids: [(&Id, &Id); 2],
+        rect: Rect,
+        grip_rects: [Rect; 2],
+        dir: Direction,
+    ) {
+        let mut track = Quad::conv(rect);
+        self.draw.rect(track, self.cols.frame);
+
+        let [a, b] = grip_rects.map(|r| Vec2::conv(r.pos + r.size / 2));
+        if dir.is_horizontal() {
+            (track.a.0, track.b.0) = (a.0.min(b.0), a.0.max(b.0));
+        } else {
+            (track.a.1, track.b.1) = (a.1.min(b.1), a.1.max(b.1));
+        }
+        self.draw.rect(track, self.cols.accent);
+
+        for ((id, id2), h_rect) in ids.into_iter().zip(grip_rects) {
+            let state = InputState::new2(self.ev, id, id2);
+            let col = self.cols.accent_soft_state(state);
+            self.draw.rect(Quad::conv(h_rect), col);
+        }
+    }
+
     fn progress_bar(&mut self, _: &Id, rect: Rect, dir: Direction, value: f32) {
         let mut outer = Quad::conv(rect);
         self.draw.rect(outer, self.cols.frame);
@@ -494,7 +520,183 @@ impl<'a, DS: DrawSharedImpl> ThemeDraw for DrawHandle<'a, DS> {
         self.draw.rect(outer, self.cols.accent);
     }
 
+    fn progress_bar_indeterminate(&mut self, _: &Id, rect: Rect, dir: Direction, phase: f32) {
+        let outer = Quad::conv(rect);
+        self.draw.rect(outer, self.cols.frame);
+
+        let (a, b) = indeterminate_section(phase);
+        if a < b {
+            let bar = progress_section(outer, dir, a, b);
+            self.draw.rect(bar, self.cols.accent);
+        }
+    }
+
+    fn progress_steps(&mut self, _: &Id, rect: Rect, dir: Direction, steps: usize, current: usize) {
+        let outer = Quad::conv(rect);
+        let gap: f32 = self.w.dims.m_inner.cast();
+        for (i, step) in progress_steps(outer, dir, steps, gap).enumerate() {
+            let col = match i.cmp(&current) {
+                std::cmp::Ordering::Less => self.cols.accent,
+                std::cmp::Ordering::Equal => self.cols.accent_soft,
+                std::cmp::Ordering::Greater => self.cols.frame,
+            };
+            self.draw.rect(step, col);
+        }
+    }
+
+    fn spinner(&mut self, _: &Id, rect: Rect, phase: f32) {
+        let outer = Quad::conv(rect);
+        let r = outer.size().min_comp() / 10.0;
+        for i in 0..SPINNER_DOTS {
+            let (pos, alpha) = spinner_dot(outer, r, phase, i);
+            let mut col = self.cols.accent;
+            col.a *= alpha;
+            self.draw.rect(Quad::from_center(pos, r), col);
+        }
+    }
+
     fn image(&mut self, id: ImageId, rect: Rect) {
         self.draw.image(id, rect.cast());
     }
 }
+
+/// Number of dots drawn by a spinner
+pub(super) const SPINNER_DOTS: usize = 8;
+
+/// Get the part of a progress track `outer` between fractions `a` and `b`
+pub(super) fn progress_section(mut outer: Quad, dir: Direction, a: f32, b: f32) -> Quad {
+    if dir.is_horizontal() {
+        let len = outer.b.0 - outer.a.0;
+        (outer.a.0, outer.b.0) = (outer.a.0 + a * len, outer.a.0 + b * len);
+    } else {
+        let len = outer.b.1 - outer.a.1;
+        (outer.a.1, outer.b.1) = (outer.a.1 + a * len, outer.a.1 + b * len);
+    }
+    outer
+}
+
+/// Get the section of track covered by an indeterminate progress bar
+///
+/// A bar of one third the track length sweeps across the track as `phase`
+/// goes from 0 to 1.
+pub(super) fn indeterminate_section(phase: f32) -> (f32, f32) {
+    let x = phase * 4.0 / 3.0;
+    ((x - 1.0 / 3.0).max(0.0), x.min(1.0))
+}
+
+/// Divide a progress track `outer` into `steps` sections separated by `gap`
+pub(super) fn progress_steps(
+    outer: Quad,
+    dir: Direction,
+    steps: usize,
+    gap: f32,
+) -> impl Iterator<Item = Quad> {
+    let len = match dir.is_horizontal() {
+        false => outer.b.1 - outer.a.1,
+        true => outer.b.0 - outer.a.0,
+    }
+    .max(1.0);
+    let n: f32 = steps.cast();
+    let w = ((len - gap * (n - 1.0)) / (n * len)).max(0.0);
+    let g = gap / len;
+    (0..steps).map(move |i| {
+        let i: f32 = i.cast();
+        let a = i * (w + g);
+        progress_section(outer, dir, a, a + w)
+    })
+}
+
+/// Get the position and alpha of dot `i` of a spinner with dot radius `r`
+///
+/// The leading dot is at `phase`; trailing dots fade out.
+pub(super) fn spinner_dot(outer: Quad, r: f32, phase: f32, i: usize) -> (Vec2, f32) {
+    let n: f32 = SPINNER_DOTS.cast();
+    let i: f32 = i.cast();
+    let radius = outer.size().min_comp() / 2.0 - r;
+    let angle = f32::consts::TAU * i / n;
+    let pos = outer.center() + Vec2(angle.sin(), -angle.cos()) * radius;
+    let age = (phase * n - i).rem_euclid(n);
+    (pos, 1.0 - age / n)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn approx(a: f32, b: f32) -> bool {
+        (a - b).abs() < 1e-4
+    }
+
+    fn approx_quad(q: Quad, a: (f32, f32), b: (f32, f32)) -> bool {
+        approx(q.a.0, a.0) && approx(q.a.1, a.1) && approx(q.b.0, b.0) && approx(q.b.1, b.1)
+    }
+
+    #[test]
+    fn steps_horizontal() {
+        let outer = Quad::from_coords(Vec2(0.0, 0.0), Vec2(110.0, 10.0));
+        let steps: Vec<_> = progress_steps(outer, Direction::Right, 3, 10.0).collect();
+        assert_eq!(steps.len(), 3);
+        assert!(approx_quad(steps[0], (0.0, 0.0), (30.0, 10.0)));
+        assert!(approx_quad(steps[1], (40.0, 0.0), (70.0, 10.0)));
+        assert!(approx_quad(steps[2], (80.0, 0.0), (110.0, 10.0)));
+    }
+
+    #[test]
+    fn steps_vertical() {
+        let outer = Quad::from_coords(Vec2(0.0, 0.0), Vec2(10.0, 50.0));
+        let steps: Vec<_> = progress_steps(outer, Direction::Down, 2, 10.0).collect();
+        assert_eq!(steps.len(), 2);
+        assert!(approx_quad(steps[0], (0.0, 0.0), (10.0, 20.0)));
+        assert!(approx_quad(steps[1], (0.0, 30.0), (10.0, 50.0)));
+    }
+
+    #[test]
+    fn steps_too_small() {
+        // Gaps exceed the track: sections are empty, not inverted
+        let outer = Quad::from_coords(Vec2(0.0, 0.0), Vec2(10.0, 10.0));
+        for step in progress_steps(outer, Direction::Right, 4, 5.0) {
+            assert!(step.b.0 >= step.a.0);
+        }
+    }
+
+    #[test]
+    fn indeterminate() {
+        let section = indeterminate_section;
+        assert_eq!(section(0.0), (0.0, 0.0));
+        let (a, b) = section(0.25);
+        assert!(approx(a, 0.0) && approx(b, 1.0 / 3.0));
+        let (a, b) = section(0.5);
+        assert!(approx(a, 1.0 / 3.0) && approx(b, 2.0 / 3.0));
+        let (a, b) = section(1.0);
+        assert!(approx(a, 1.0) && approx(b, 1.0));
+
+        for i in 0..=20 {
+            let x: f32 = i.cast();
+            let (a, b) = section(x / 20.0);
+            assert!((0.0..=b).contains(&a) && b <= 1.0);
+            assert!(b - a <= 1.0 / 3.0 + 1e-4);
+        }
+    }
+
+    #[test]
+    fn spinner() {
+        let outer = Quad::from_coords(Vec2(0.0, 0.0), Vec2(20.0, 20.0));
+
+        // Dot 0 is at the top; dots proceed clockwise
+        let (pos, alpha) = spinner_dot(outer, 2.0, 0.0, 0);
+        assert!(approx(pos.0, 10.0) && approx(pos.1, 2.0));
+        assert!(approx(alpha, 1.0));
+        let (pos, _) = spinner_dot(outer, 2.0, 0.0, SPINNER_DOTS / 4);
+        assert!(approx(pos.0, 18.0) && approx(pos.1, 10.0));
+
+        // The dot after the leading dot is faintest
+        let (_, alpha) = spinner_dot(outer, 2.0, 0.0, 1);
+        let n: f32 = SPINNER_DOTS.cast();
+        assert!(approx(alpha, 1.0 / n));
+
+        // Advancing the phase by one dot moves the lead
+        let phase = 1.0 / n;
+        let (_, alpha) = spinner_dot(outer, 2.0, phase, 1);
+        assert!(approx(alpha, 1.0));
+    }
+}
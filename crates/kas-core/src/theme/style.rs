@@ -73,6 +73,7 @@ pub enum Feature {
     ScrollBar(Direction),
     Slider(Direction),
     ProgressBar(Direction),
+    Spinner,
 }
 
 impl From<MarkStyle> for Feature {
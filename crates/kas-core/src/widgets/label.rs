@@ -12,7 +12,7 @@ use crate::layout::AlignHints;
 use crate::text::Text;
 use crate::text::format::FormattableText;
 use crate::theme::{SizeCx, TextClass};
-use crate::{Events, Layout, Role, RoleCx, Tile};
+use crate::{Events, Layout, Role, RoleCx, RoleCxExt, Tile};
 use kas_macros::impl_self;
 use std::fmt::Debug;
 
@@ -30,12 +30,15 @@ mod Label {
     /// alignment depends on the script direction if not specified.
     /// Line-wrapping is enabled by default.
     ///
+    /// A label may be reported as a heading; see [`Self::with_heading_level`].
+    ///
     /// This type is generic over the text type.
     #[derive(Debug)]
     #[widget]
     #[layout(self.text)]
     pub struct Label<T: FormattableText + 'static = String> {
         core: widget_core!(),
+        heading: usize,
         text: Text<T>,
     }
 
@@ -45,6 +48,7 @@ mod Label {
         pub fn new(text: T) -> Self {
             Label {
                 core: Default::default(),
+                heading: 0,
                 text: Text::new(text, TextClass::Label, true),
             }
         }
@@ -78,6 +82,24 @@ mod Label {
             self
         }
 
+        /// Get the heading level, if any
+        #[inline]
+        pub fn heading_level(&self) -> Option<usize> {
+            (self.heading > 0).then_some(self.heading)
+        }
+
+        /// Report this label as a heading (inline)
+        ///
+        /// The label is reported as a [`Role::Heading`] of the given `level`
+        /// (starting from 1) instead of as a [`Role::Label`]. This does not
+        /// affect appearance; see also [`Self::with_class`].
+        #[inline]
+        pub fn with_heading_level(mut self, level: usize) -> Self {
+            debug_assert!(level > 0);
+            self.heading = level;
+            self
+        }
+
         /// Get whether line-wrapping is enabled
         #[inline]
         pub fn wrap(&self) -> bool {
@@ -126,8 +148,15 @@ mod Label {
     }
 
     impl Tile for Self {
-        fn role(&self, _: &mut dyn RoleCx) -> Role<'_> {
-            Role::Label(self.text.as_str())
+        fn role(&self, cx: &mut dyn RoleCx) -> Role<'_> {
+            if self.heading > 0 {
+                cx.set_label(self.text.as_str());
+                Role::Heading {
+                    level: self.heading,
+                }
+            } else {
+                Role::Label(self.text.as_str())
+            }
         }
     }
 
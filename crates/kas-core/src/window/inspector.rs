@@ -0,0 +1,701 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Widget inspector
+
+use crate::core::RoleSink;
+use crate::event::components::ScrollComponent;
+use crate::event::{Scroll, TimerHandle};
+use crate::layout::{DynRowStorage, RowSetter, RowSolver, RulesSetter, RulesSolver};
+use crate::messages::SetLayoutOverlay;
+use crate::prelude::*;
+use crate::theme::{FrameStyle, SelectionStyle};
+use crate::widgets::Label;
+use std::fmt::Write;
+use std::time::Duration;
+
+/// Interval between refreshes of the inspected tree
+const REFRESH_INTERVAL: Duration = Duration::from_millis(500);
+/// Maximum length of a role summary, in chars
+const ROLE_LEN: usize = 80;
+
+const TIMER_REFRESH: TimerHandle = TimerHandle::new(0, false);
+
+/// A snapshot of one widget of the inspected window
+#[derive(Clone, Debug)]
+struct InspectNode {
+    id: Id,
+    depth: usize,
+    name: String,
+    role: String,
+    rect: Rect,
+    disabled: bool,
+    nav_focus: bool,
+    input_focus: Option<bool>,
+    under_mouse: bool,
+}
+
+impl InspectNode {
+    /// Text of this node's row in the tree
+    fn row_text(&self) -> String {
+        format!("{}{}", "  ".repeat(self.depth), self.name)
+    }
+}
+
+/// Message: request a snapshot of the target window
+///
+/// This is sent to the target window's root, which replies to `inspector`
+/// with an [`InspectSnapshot`].
+#[derive(Debug)]
+pub(crate) struct InspectRequest {
+    inspector: Id,
+    size_rules_of: Option<Id>,
+}
+
+/// Message: a snapshot of the target window's widgets
+#[derive(Debug)]
+struct InspectSnapshot {
+    nodes: Vec<InspectNode>,
+    size_rules: Option<(Id, SizeRules, SizeRules)>,
+}
+
+/// Message: highlight a widget of the target window (or clear highlighting)
+#[derive(Debug)]
+pub(crate) struct InspectHighlight(pub(crate) Option<Id>);
+
+/// Message: enable (with the inspector's id) or disable pick mode
+#[derive(Debug)]
+pub(crate) struct InspectPick(pub(crate) Option<Id>);
+
+/// Message: the widget picked from the target window, if any
+#[derive(Debug)]
+pub(crate) struct InspectPicked(pub(crate) Option<Id>);
+
+#[derive(Clone, Copy, Debug)]
+enum InspectAction {
+    Pick,
+    Refresh,
+    Layout,
+}
+
+#[derive(Debug)]
+struct SelectRow(usize);
+
+#[derive(Debug)]
+struct HoverRow(Option<usize>);
+
+/// Summarise a role, truncated to [`ROLE_LEN`]
+///
+/// The text of protected inputs is omitted.
+fn role_summary(role: &Role) -> String {
+    let mut summary = format!("{role:?}");
+    if let Some((index, _)) = summary.char_indices().nth(ROLE_LEN) {
+        summary.truncate(index);
+        summary.push('…');
+    }
+    summary
+}
+
+fn snapshot(tile: &dyn Tile, cx: &EventState, depth: usize, nodes: &mut Vec<InspectNode>) {
+    let id = tile.id();
+    nodes.push(InspectNode {
+        depth,
+        name: tile.identify().to_string(),
+        role: role_summary(&tile.role(&mut RoleSink)),
+        rect: tile.rect(),
+        disabled: cx.is_disabled(&id),
+        nav_focus: cx.has_nav_focus(&id),
+        input_focus: cx.has_input_focus(&id),
+        under_mouse: cx.is_under_mouse(&id),
+        id,
+    });
+
+    for child in tile.children() {
+        snapshot(child, cx, depth + 1, nodes);
+    }
+}
+
+impl InspectRequest {
+    /// Respond to the request from the target `window`
+    pub(crate) fn respond<W: Widget>(self, cx: &mut EventCx, window: &mut W, data: &W::Data) {
+        let mut size_rules = None;
+        if let Some(id) = self.size_rules_of {
+            size_rules = window.as_node(data).find_node(&id, |mut node| {
+                let mut cx = cx.size_cx();
+                let width = node.as_tile().rect().size.0;
+                let horiz = node.size_rules(&mut cx, AxisInfo::new(false, None));
+                let vert = node.size_rules(&mut cx, AxisInfo::new(true, Some(width)));
+                (id.clone(), horiz, vert)
+            });
+
+            // Size rules were solved out of order; solve the window again
+            cx.resize();
+        }
+
+        let mut nodes = vec![];
+        snapshot(window.as_tile(), cx, 0, &mut nodes);
+        cx.send(self.inspector, InspectSnapshot { nodes, size_rules });
+    }
+}
+
+#[impl_self]
+mod InspectButton {
+    /// A text button of the [`Inspector`]
+    #[widget]
+    #[layout(frame!(self.label).with_style(FrameStyle::Button))]
+    struct InspectButton {
+        core: widget_core!(),
+        action: InspectAction,
+        #[widget]
+        label: Label<String>,
+    }
+
+    impl Tile for Self {
+        fn navigable(&self) -> bool {
+            true
+        }
+
+        fn role(&self, cx: &mut dyn RoleCx) -> Role<'_> {
+            cx.set_label(self.label.id());
+            Role::Button
+        }
+    }
+
+    impl Events for Self {
+        type Data = ();
+
+        fn handle_event(&mut self, cx: &mut EventCx, _: &Self::Data, event: Event) -> IsUsed {
+            event.on_click(cx, self.id(), |cx| cx.push(self.action))
+        }
+
+        fn handle_messages(&mut self, cx: &mut EventCx, _: &Self::Data) {
+            if let Some(kas::messages::Activate(code)) = cx.try_pop() {
+                cx.push(self.action);
+                cx.depress_with_key(&self, code);
+            }
+        }
+    }
+
+    impl Self {
+        fn new(label: &str, action: InspectAction) -> Self {
+            InspectButton {
+                core: Default::default(),
+                action,
+                label: Label::new(label.to_string()),
+            }
+        }
+    }
+}
+
+#[impl_self]
+mod InspectRow {
+    /// A row of the [`Inspector`]'s widget tree
+    #[widget]
+    #[layout(self.label)]
+    struct InspectRow {
+        core: widget_core!(),
+        index: usize,
+        depth: usize,
+        selected: bool,
+        #[widget]
+        label: Label<String>,
+    }
+
+    impl Layout for Self {
+        fn draw(&self, mut draw: DrawCx) {
+            if self.selected {
+                draw.selection(self.rect(), SelectionStyle::Highlight);
+            }
+            self.label.draw(draw.re());
+        }
+    }
+
+    impl Tile for Self {
+        fn navigable(&self) -> bool {
+            true
+        }
+
+        fn role(&self, cx: &mut dyn RoleCx) -> Role<'_> {
+            cx.set_label(self.label.id());
+            Role::TreeItem {
+                level: self.depth + 1,
+                expanded: None,
+                selected: Some(self.selected),
+            }
+        }
+    }
+
+    impl Events for Self {
+        type Data = ();
+
+        fn handle_event(&mut self, cx: &mut EventCx, _: &Self::Data, event: Event) -> IsUsed {
+            match event {
+                Event::MouseOver(true) | Event::NavFocus(_) => {
+                    cx.push(HoverRow(Some(self.index)));
+                    Used
+                }
+                Event::MouseOver(false) | Event::LostNavFocus => {
+                    cx.push(HoverRow(None));
+                    Used
+                }
+                event => event.on_click(cx, self.id(), |cx| cx.push(SelectRow(self.index))),
+            }
+        }
+    }
+
+    impl Self {
+        fn new(index: usize, depth: usize, text: String) -> Self {
+            InspectRow {
+                core: Default::default(),
+                index,
+                depth,
+                selected: false,
+                label: Label::new(text).with_wrap(false),
+            }
+        }
+    }
+}
+
+#[impl_self]
+mod InspectTree {
+    /// The scrollable widget tree of the [`Inspector`]
+    #[widget]
+    struct InspectTree {
+        core: widget_core!(),
+        layout: DynRowStorage,
+        scroll: ScrollComponent,
+        content_size: Size,
+        rows: Vec<InspectRow>,
+    }
+
+    impl Layout for Self {
+        fn size_rules(&mut self, cx: &mut SizeCx, axis: AxisInfo) -> SizeRules {
+            let dim = (Direction::Down, self.rows.len(), false);
+            let mut solver = RowSolver::new(axis, dim, &mut self.layout);
+            for (n, row) in self.rows.iter_mut().enumerate() {
+                solver.for_child(&mut self.layout, n, |axis| row.size_rules(cx, axis));
+            }
+            let mut rules = solver.finish(&mut self.layout);
+            self.content_size.set_component(axis, rules.ideal_size());
+            rules.reduce_min_to(cx.min_scroll_size(axis, None));
+            rules.set_stretch(Stretch::High);
+            rules
+        }
+
+        fn set_rect(&mut self, cx: &mut SizeCx, rect: Rect, _: AlignHints) {
+            self.core.set_rect(rect);
+            let _ = self.scroll.set_sizes(rect.size, self.content_size);
+            let rect = Rect::new(rect.pos, self.content_size.max(rect.size));
+            let dim = (Direction::Down, self.rows.len(), false);
+            let mut setter = RowSetter::<Direction, Vec<i32>, _>::new(rect, dim, &mut self.layout);
+            for (n, row) in self.rows.iter_mut().enumerate() {
+                let child_rect = setter.child_rect(&mut self.layout, n);
+                row.set_rect(cx, child_rect, AlignHints::NONE);
+            }
+        }
+
+        fn draw(&self, mut draw: DrawCx) {
+            draw.with_clip_region(self.rect(), self.scroll.offset(), |mut draw| {
+                for row in &self.rows {
+                    row.draw(draw.re());
+                }
+            });
+        }
+    }
+
+    impl Tile for Self {
+        fn role(&self, _: &mut dyn RoleCx) -> Role<'_> {
+            Role::Tree
+        }
+
+        #[inline]
+        fn child_indices(&self) -> ChildIndices {
+            ChildIndices::range(0..self.rows.len())
+        }
+        fn get_child(&self, index: usize) -> Option<&dyn Tile> {
+            self.rows.get(index).map(|row| row.as_tile())
+        }
+
+        fn translation(&self, _: usize) -> Offset {
+            self.scroll.offset()
+        }
+    }
+
+    impl Events for Self {
+        fn probe(&self, coord: Coord) -> Id {
+            if !self.scroll.is_kinetic_scrolling() {
+                let coord = coord + self.scroll.offset();
+                for row in &self.rows {
+                    if let Some(id) = row.try_probe(coord) {
+                        return id;
+                    }
+                }
+            }
+            self.id()
+        }
+
+        fn handle_event(&mut self, cx: &mut EventCx, _: &(), event: Event) -> IsUsed {
+            self.scroll
+                .scroll_by_event(cx, event, self.id(), self.rect())
+        }
+
+        fn handle_scroll(&mut self, cx: &mut EventCx, _: &(), scroll: Scroll) {
+            self.scroll.scroll(cx, self.id(), self.rect(), scroll);
+        }
+    }
+
+    impl Widget for Self {
+        type Data = ();
+
+        fn child_node<'n>(&'n mut self, _: &'n (), index: usize) -> Option<Node<'n>> {
+            self.rows.get_mut(index).map(|row| row.as_node(&()))
+        }
+    }
+
+    impl Self {
+        fn new() -> Self {
+            InspectTree {
+                core: Default::default(),
+                layout: Default::default(),
+                scroll: Default::default(),
+                content_size: Size::ZERO,
+                rows: vec![],
+            }
+        }
+
+        fn set_nodes(&mut self, cx: &mut EventCx, nodes: &[InspectNode], selected: Option<&Id>) {
+            let len = self.rows.len();
+            self.rows.truncate(nodes.len());
+            for (index, node) in nodes.iter().enumerate() {
+                let text = node.row_text();
+                if let Some(row) = self.rows.get_mut(index) {
+                    row.depth = node.depth;
+                    row.label.set_text(cx, text);
+                } else {
+                    let mut row = InspectRow::new(index, node.depth, text);
+                    let id = self.id_ref().make_child(index);
+                    cx.configure(row.as_node(&()), id);
+                    self.rows.push(row);
+                }
+                self.rows[index].selected = Some(&node.id) == selected;
+            }
+
+            if self.rows.len() != len {
+                cx.resize();
+            }
+            cx.redraw();
+        }
+    }
+}
+
+#[impl_self]
+mod Inspector {
+    /// A live inspector of another window's widgets
+    ///
+    /// The inspector lists the widget tree of its target window, refreshed
+    /// periodically. Selecting a widget shows its [`Id`], [`Role`], [`Rect`],
+    /// [`SizeRules`] (for each axis) and focus and disabled states. The
+    /// hovered or selected widget is highlighted within the target window.
+    ///
+    /// In *pick* mode, the next mouse click in the target window selects the
+    /// widget under the mouse (instead of being handled as usual).
+    /// Highlighting and pick mode are cancelled in the target window shortly
+    /// after the inspector is closed.
+    ///
+    /// The *Layout* button toggles the target window's layout debug overlay;
+    /// see [`SetLayoutOverlay`](crate::messages::SetLayoutOverlay).
+    ///
+    /// By default, [`Command::Debug`] (usually <kbd>F8</kbd>) opens an
+    /// inspector over the current window.
+    #[widget]
+    #[layout(column![row![self.pick, self.refresh, self.layout], self.tree, self.details])]
+    pub struct Inspector {
+        core: widget_core!(),
+        target: Id,
+        nodes: Vec<InspectNode>,
+        size_rules: Option<(Id, SizeRules, SizeRules)>,
+        selected: Option<Id>,
+        hovered: Option<Id>,
+        picking: bool,
+        layout_overlay: bool,
+        #[widget]
+        pick: InspectButton,
+        #[widget]
+        refresh: InspectButton,
+        #[widget]
+        layout: InspectButton,
+        #[widget]
+        tree: InspectTree,
+        #[widget]
+        details: Label<String>,
+    }
+
+    impl Events for Self {
+        type Data = ();
+
+        fn configure(&mut self, cx: &mut ConfigCx) {
+            cx.register_nav_fallback(self.id());
+            cx.request_timer(self.id(), TIMER_REFRESH, Duration::ZERO);
+        }
+
+        fn handle_event(&mut self, cx: &mut EventCx, _: &Self::Data, event: Event) -> IsUsed {
+            match event {
+                Event::Timer(TIMER_REFRESH) => {
+                    self.request(cx, false);
+                    cx.request_timer(self.id(), TIMER_REFRESH, REFRESH_INTERVAL);
+                    Used
+                }
+                Event::Command(Command::Debug, _) => {
+                    self.request(cx, true);
+                    Used
+                }
+                _ => Unused,
+            }
+        }
+
+        fn handle_messages(&mut self, cx: &mut EventCx, _: &Self::Data) {
+            if let Some(snapshot) = cx.try_pop::<InspectSnapshot>() {
+                self.nodes = snapshot.nodes;
+                if snapshot.size_rules.is_some() {
+                    self.size_rules = snapshot.size_rules;
+                }
+                self.tree.set_nodes(cx, &self.nodes, self.selected.as_ref());
+                self.update_details(cx);
+            } else if let Some(InspectPicked(id)) = cx.try_pop() {
+                self.set_picking(cx, false);
+                if let Some(id) = id {
+                    self.select(cx, id);
+                }
+            } else if let Some(SelectRow(index)) = cx.try_pop() {
+                if let Some(node) = self.nodes.get(index) {
+                    let id = node.id.clone();
+                    self.select(cx, id);
+                }
+            } else if let Some(HoverRow(index)) = cx.try_pop() {
+                self.hovered = index
+                    .and_then(|i| self.nodes.get(i))
+                    .map(|node| node.id.clone());
+                self.highlight(cx);
+            } else if let Some(action) = cx.try_pop() {
+                match action {
+                    InspectAction::Pick => self.set_picking(cx, !self.picking),
+                    InspectAction::Refresh => self.request(cx, true),
+                    InspectAction::Layout => self.set_layout_overlay(cx, !self.layout_overlay),
+                }
+            }
+        }
+    }
+
+    impl Self {
+        /// Construct an inspector over the given `window`
+        pub fn new(window: WindowId) -> Self {
+            Inspector {
+                core: Default::default(),
+                target: Id::ROOT.make_child(window.get().cast()),
+                nodes: vec![],
+                size_rules: None,
+                selected: None,
+                hovered: None,
+                picking: false,
+                layout_overlay: false,
+                pick: InspectButton::new("Pick", InspectAction::Pick),
+                refresh: InspectButton::new("Refresh", InspectAction::Refresh),
+                layout: InspectButton::new("Layout", InspectAction::Layout),
+                tree: InspectTree::new(),
+                details: Label::new(String::new()),
+            }
+        }
+
+        /// Request a snapshot, optionally including size rules of the selection
+        fn request(&self, cx: &mut EventCx, size_rules: bool) {
+            let request = InspectRequest {
+                inspector: self.id(),
+                size_rules_of: self.selected.clone().filter(|_| size_rules),
+            };
+            cx.send(self.target.clone(), request);
+        }
+
+        fn highlight(&self, cx: &mut EventCx) {
+            let id = self.hovered.clone().or_else(|| self.selected.clone());
+            cx.send(self.target.clone(), InspectHighlight(id));
+        }
+
+        fn select(&mut self, cx: &mut EventCx, id: Id) {
+            self.selected = Some(id);
+            self.request(cx, true);
+            self.highlight(cx);
+        }
+
+        fn set_picking(&mut self, cx: &mut EventCx, picking: bool) {
+            self.picking = picking;
+            let label = if picking { "Cancel pick" } else { "Pick" };
+            self.pick.label.set_text(cx, label.to_string());
+            let inspector = picking.then(|| self.id());
+            cx.send(self.target.clone(), InspectPick(inspector));
+            self.update_details(cx);
+        }
+
+        fn set_layout_overlay(&mut self, cx: &mut EventCx, enable: bool) {
+            self.layout_overlay = enable;
+            let label = if enable { "Hide layout" } else { "Layout" };
+            self.layout.label.set_text(cx, label.to_string());
+            cx.send(self.target.clone(), SetLayoutOverlay(enable));
+        }
+
+        fn update_details(&mut self, cx: &mut EventCx) {
+            let text = details(
+                &self.nodes,
+                self.selected.as_ref(),
+                self.size_rules.as_ref(),
+                self.picking,
+            );
+            self.details.set_text(cx, text);
+        }
+    }
+}
+
+/// Describe the `selected` node (or how to select one)
+fn details(
+    nodes: &[InspectNode],
+    selected: Option<&Id>,
+    size_rules: Option<&(Id, SizeRules, SizeRules)>,
+    picking: bool,
+) -> String {
+    let mut text = String::new();
+    let node = selected.and_then(|id| nodes.iter().find(|node| node.id == *id));
+    if let Some(node) = node {
+        let Rect { pos, size } = node.rect;
+        let _ = writeln!(text, "{}", node.name);
+        let _ = writeln!(text, "Id: {}", node.id);
+        let _ = writeln!(text, "Role: {}", node.role);
+        let _ = writeln!(
+            text,
+            "Rect: x={}..{} y={}..{} ({}×{})",
+            pos.0,
+            pos.0 + size.0,
+            pos.1,
+            pos.1 + size.1,
+            size.0,
+            size.1
+        );
+        if let Some((id, horiz, vert)) = size_rules
+            && *id == node.id
+        {
+            let _ = writeln!(text, "Horizontal: {horiz:?}");
+            let _ = writeln!(text, "Vertical: {vert:?}");
+        }
+        let _ = write!(
+            text,
+            "Disabled: {}, nav focus: {}, input focus: {}, under mouse: {}",
+            node.disabled,
+            node.nav_focus,
+            match node.input_focus {
+                None => "no",
+                Some(false) => "selection",
+                Some(true) => "keyboard",
+            },
+            node.under_mouse
+        );
+    } else if picking {
+        text.push_str("Click a widget in the target window to select it");
+    } else {
+        text.push_str("Select a widget from the tree or use Pick");
+    }
+    text
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn node(index: usize, depth: usize, name: &str) -> InspectNode {
+        InspectNode {
+            id: Id::ROOT.make_child(index),
+            depth,
+            name: name.to_string(),
+            role: "Button".to_string(),
+            rect: Rect::new(Coord(10, 20), Size(30, 40)),
+            disabled: false,
+            nav_focus: true,
+            input_focus: None,
+            under_mouse: false,
+        }
+    }
+
+    fn text_input(text: &str, protected: bool) -> Role<'_> {
+        Role::TextInput {
+            text,
+            multi_line: false,
+            protected,
+            cursor: 0.into(),
+        }
+    }
+
+    #[test]
+    fn role_summary_redacts_protected_text() {
+        let summary = role_summary(&text_input("hunter2", true));
+        assert!(summary.starts_with("TextInput"));
+        assert!(summary.contains("protected: true"));
+        assert!(!summary.contains("hunter2"));
+
+        let summary = role_summary(&text_input("hello", false));
+        assert!(summary.contains("text: \"hello\""));
+    }
+
+    #[test]
+    fn role_summary_truncates() {
+        let text = "x".repeat(2 * ROLE_LEN);
+        let summary = role_summary(&Role::Label(&text));
+        assert_eq!(summary.chars().count(), ROLE_LEN + 1);
+        assert!(summary.ends_with('…'));
+
+        assert_eq!(role_summary(&Role::Button), "Button");
+    }
+
+    #[test]
+    fn row_text_indents_by_depth() {
+        assert_eq!(node(0, 0, "Window").row_text(), "Window");
+        assert_eq!(node(1, 2, "Button").row_text(), "    Button");
+    }
+
+    #[test]
+    fn details_of_selection() {
+        let nodes = [node(0, 0, "Window"), node(1, 1, "Button")];
+        let text = details(&nodes, Some(&nodes[1].id), None, false);
+        assert!(text.starts_with("Button\n"));
+        assert!(text.contains("Role: Button\n"));
+        assert!(text.contains("Rect: x=10..40 y=20..60 (30×40)\n"));
+        assert!(text.contains("nav focus: true, input focus: no"));
+        assert!(!text.contains("Horizontal"));
+
+        let rules = (nodes[1].id.clone(), SizeRules::EMPTY, SizeRules::EMPTY);
+        let text = details(&nodes, Some(&nodes[1].id), Some(&rules), false);
+        assert!(text.contains("Horizontal: "));
+        assert!(text.contains("Vertical: "));
+
+        // Size rules of another widget are not shown
+        let text = details(&nodes, Some(&nodes[0].id), Some(&rules), false);
+        assert!(text.starts_with("Window\n"));
+        assert!(!text.contains("Horizontal"));
+    }
+
+    #[test]
+    fn details_while_picking() {
+        let nodes = [node(0, 0, "Window")];
+        let pick = "Click a widget in the target window to select it";
+        assert_eq!(details(&nodes, None, None, true), pick);
+        assert_eq!(
+            details(&nodes, None, None, false),
+            "Select a widget from the tree or use Pick"
+        );
+
+        // A picked widget missing from the snapshot is not yet described
+        let missing = Id::ROOT.make_child(7);
+        assert_eq!(details(&nodes, Some(&missing), None, true), pick);
+
+        // Once picked, the selection is described even in pick mode
+        assert!(details(&nodes, Some(&nodes[0].id), None, true).starts_with("Window\n"));
+    }
+}
@@ -5,14 +5,20 @@
 
 //! Special window widgets
 
+mod inspector;
 mod popup;
+mod toast;
 mod window;
 
-#[cfg(feature = "accesskit")]
+#[doc(inline)] pub use inspector::Inspector;
+pub(crate) use inspector::{InspectHighlight, InspectPick, InspectPicked, InspectRequest};
+#[cfg(any(feature = "accesskit", automation))]
 #[doc(inline)]
 pub(crate) use popup::POPUP_INNER_INDEX;
 #[doc(inline)] pub use popup::Popup;
 #[doc(inline)] pub(crate) use popup::PopupDescriptor;
+pub(crate) use toast::ToastLayer;
+#[doc(inline)] pub use toast::{Severity, Toast};
 pub use window::*;
 pub use winit::icon;
 pub use winit::window::ResizeDirection;
@@ -7,7 +7,7 @@
 
 use crate::dir::Direction;
 use crate::event::{Event, EventCx, IsUsed, Scroll, Unused, Used};
-#[allow(unused)] use crate::geom::Rect;
+use crate::geom::Rect;
 use crate::layout::Align;
 use crate::window::WindowId;
 use crate::{ChildIndices, Events, Id, Tile, Widget};
@@ -30,6 +30,10 @@ pub(crate) struct PopupDescriptor {
     /// alignment. In this case, [`Align::TL`] would imply that the top of the
     /// popup would be aligned to the top of the parent.
     pub align: Align,
+    /// Anchor rect, if not the rect of [`Self::parent`]
+    ///
+    /// This is in the coordinate space of [`Self::parent`].
+    pub anchor: Option<Rect>,
 }
 
 pub(crate) const POPUP_INNER_INDEX: usize = 0;
@@ -177,12 +181,42 @@ mod Popup {
             data: &W::Data,
             parent: Id,
             set_focus: bool,
+        ) -> bool {
+            self.open_impl(cx, data, parent, None, set_focus)
+        }
+
+        /// Open or reposition the popup next to an `anchor` rect
+        ///
+        /// This is identical to [`Self::open`] except that the popup is
+        /// positioned next to `anchor` instead of the `parent`'s rect. The
+        /// `anchor` is specified in the coordinate space of `parent` (that
+        /// used by `parent`'s [`Layout::rect`](crate::Layout::rect)); for
+        /// example, this may be a text cursor's rect.
+        pub fn open_at(
+            &mut self,
+            cx: &mut EventCx,
+            data: &W::Data,
+            parent: Id,
+            anchor: Rect,
+            set_focus: bool,
+        ) -> bool {
+            self.open_impl(cx, data, parent, Some(anchor), set_focus)
+        }
+
+        fn open_impl(
+            &mut self,
+            cx: &mut EventCx,
+            data: &W::Data,
+            parent: Id,
+            anchor: Option<Rect>,
+            set_focus: bool,
         ) -> bool {
             let desc = PopupDescriptor {
                 id: self.id(),
                 parent,
                 direction: self.direction,
                 align: self.align,
+                anchor,
             };
 
             if let Some(id) = self.win_id {
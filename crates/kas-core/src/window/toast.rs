@@ -0,0 +1,618 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Toast notifications
+
+use crate::draw::color::Rgba;
+use crate::event::TimerHandle;
+use crate::geom::Quad;
+use crate::layout::{DynRowStorage, RowSetter, RowSolver, RulesSetter, RulesSolver};
+use crate::prelude::*;
+use crate::theme::{ColorsLinear, FrameStyle, MarkStyle};
+use crate::widgets::{Label, MarkButton};
+use kas_macros::impl_self;
+use std::fmt::Debug;
+use std::time::{Duration, Instant};
+
+/// Default timeout of a [`Toast`]
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+/// Duration of the slide-in animation
+const APPEAR_DURATION: Duration = Duration::from_millis(200);
+/// Delay before closing once the mouse leaves an expired toast
+const HOVER_GRACE: Duration = Duration::from_secs(1);
+/// Width of the severity stripe
+const STRIPE_WIDTH: i32 = 4;
+
+const TIMER_APPEAR: TimerHandle = TimerHandle::new(0, true);
+const TIMER_TIMEOUT: TimerHandle = TimerHandle::new(1, false);
+
+/// Severity of a [`Toast`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Severity {
+    /// General information
+    #[default]
+    Info,
+    /// Successful completion of some action
+    Success,
+    /// A warning
+    Warning,
+    /// An error
+    Error,
+}
+
+impl Severity {
+    /// Get the theme colour indicating this severity
+    fn color(self, cols: &ColorsLinear) -> Rgba {
+        match self {
+            Severity::Info => cols.accent,
+            Severity::Success => cols.success,
+            Severity::Warning => cols.warning,
+            Severity::Error => cols.error,
+        }
+    }
+
+    /// Whether this should interrupt a screen reader
+    fn is_urgent(self) -> bool {
+        matches!(self, Severity::Warning | Severity::Error)
+    }
+}
+
+/// A toast notification
+///
+/// A toast is a short, non-modal message shown as an overlay in a corner of
+/// the window. Toasts stack with each other and close automatically after a
+/// timeout (paused while under the mouse) or when dismissed. They are announced
+/// by screen readers (where the `accesskit` feature is enabled).
+///
+/// Show using [`EventState::show_toast`](crate::event::EventState::show_toast).
+pub struct Toast {
+    text: String,
+    severity: Severity,
+    timeout: Option<Duration>,
+    action: Option<(String, Box<dyn Fn(&mut EventCx)>)>,
+}
+
+impl Debug for Toast {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Toast")
+            .field("text", &self.text)
+            .field("severity", &self.severity)
+            .field("timeout", &self.timeout)
+            .field("action", &self.action.as_ref().map(|a| &a.0))
+            .finish()
+    }
+}
+
+impl Toast {
+    /// Construct with the given `text`
+    ///
+    /// By default, the severity is [`Severity::Info`] and the toast closes
+    /// after a timeout of five seconds.
+    pub fn new(text: impl ToString) -> Self {
+        Toast {
+            text: text.to_string(),
+            severity: Severity::default(),
+            timeout: Some(DEFAULT_TIMEOUT),
+            action: None,
+        }
+    }
+
+    /// Set the severity (inline)
+    #[must_use]
+    pub fn with_severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+
+    /// Set the timeout (inline)
+    ///
+    /// If `None`, the toast is shown until dismissed.
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Add an action button (inline)
+    ///
+    /// On activation, the toast is closed and a clone of `msg` is pushed to
+    /// the message stack. Since the toast is a child of the window, this
+    /// message is handled by [`AppData::handle_message`](crate::runner::AppData::handle_message).
+    #[must_use]
+    pub fn with_action<M>(mut self, label: impl ToString, msg: M) -> Self
+    where
+        M: Clone + Debug + 'static,
+    {
+        let f = move |cx: &mut EventCx| cx.push(msg.clone());
+        self.action = Some((label.to_string(), Box::new(f)));
+        self
+    }
+
+    /// Get the text
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Get the severity
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+}
+
+/// Message: activate the action of a [`ToastItem`]
+#[derive(Clone, Debug)]
+struct ToastAct;
+
+/// Message: close a [`ToastItem`]
+#[derive(Clone, Debug)]
+struct ToastClose;
+
+#[impl_self]
+mod ToastAction {
+    /// The action button of a toast
+    ///
+    /// When not visible, this has zero size and is not interactive.
+    #[widget]
+    struct ToastAction {
+        core: widget_core!(),
+        visible: bool,
+        offset: Offset,
+        size: Size,
+        #[widget]
+        label: Label<String>,
+    }
+
+    impl Layout for Self {
+        fn size_rules(&mut self, cx: &mut SizeCx, axis: AxisInfo) -> SizeRules {
+            if !self.visible {
+                return SizeRules::EMPTY;
+            }
+
+            let inner = self.label.size_rules(cx, axis);
+            let frame = cx.frame(FrameStyle::Button, axis);
+            let (rules, offset, size) = frame.surround(inner);
+            self.offset.set_component(axis, offset);
+            self.size.set_component(axis, size);
+            rules
+        }
+
+        fn set_rect(&mut self, cx: &mut SizeCx, rect: Rect, hints: AlignHints) {
+            self.core.set_rect(rect);
+            if self.visible {
+                let inner = Rect::new(rect.pos + self.offset, rect.size - self.size);
+                self.label.set_rect(cx, inner, hints);
+            }
+        }
+
+        fn draw(&self, mut draw: DrawCx) {
+            if self.visible {
+                draw.frame(self.rect(), FrameStyle::Button, Default::default());
+                self.label.draw(draw.re());
+            }
+        }
+    }
+
+    impl Tile for Self {
+        fn navigable(&self) -> bool {
+            self.visible
+        }
+
+        fn role(&self, cx: &mut dyn RoleCx) -> Role<'_> {
+            if self.visible {
+                cx.set_label(self.label.id());
+                Role::Button
+            } else {
+                Role::None
+            }
+        }
+
+        fn child_indices(&self) -> ChildIndices {
+            if self.visible {
+                ChildIndices::one(widget_index!(self.label))
+            } else {
+                ChildIndices::none()
+            }
+        }
+    }
+
+    impl Events for Self {
+        const REDRAW_ON_MOUSE_OVER: bool = true;
+
+        type Data = ();
+
+        fn probe(&self, _: Coord) -> Id {
+            self.id()
+        }
+
+        fn handle_event(&mut self, cx: &mut EventCx, _: &(), event: Event) -> IsUsed {
+            if !self.visible {
+                return Unused;
+            }
+            match event {
+                Event::Command(cmd, code) if cmd.is_activate() => {
+                    cx.depress_with_key(self.id(), code);
+                    cx.push(ToastAct);
+                    Used
+                }
+                event => event.on_click(cx, self.id(), |cx| cx.push(ToastAct)),
+            }
+        }
+    }
+}
+
+#[impl_self]
+mod ToastItem {
+    /// A single toast
+    #[widget]
+    #[layout(frame!(row![self.label, self.action, self.close]).with_style(FrameStyle::Popup))]
+    struct ToastItem {
+        core: widget_core!(),
+        key: usize,
+        severity: Severity,
+        timeout: Option<Duration>,
+        deadline: Option<Instant>,
+        // Remaining time while paused (under the mouse)
+        remaining: Option<Duration>,
+        shown: Option<Instant>,
+        #[widget]
+        label: Label<String>,
+        #[widget]
+        action: ToastAction,
+        #[widget]
+        close: MarkButton<ToastClose>,
+        on_action: Option<Box<dyn Fn(&mut EventCx)>>,
+    }
+
+    impl Layout for Self {
+        fn draw(&self, mut draw: DrawCx) {
+            kas::MacroDefinedLayout::draw(self, draw.re());
+            let rect = self.rect();
+            let stripe = Rect::new(rect.pos, Size(STRIPE_WIDTH, rect.size.1));
+            let color = self.severity.color(draw.colors());
+            draw.draw().rect(Quad::conv(stripe), color);
+        }
+    }
+
+    impl Tile for Self {
+        fn role(&self, cx: &mut dyn RoleCx) -> Role<'_> {
+            cx.set_label(self.label.id());
+            Role::Notification {
+                urgent: self.severity.is_urgent(),
+            }
+        }
+    }
+
+    impl Events for Self {
+        type Data = ();
+
+        fn probe(&self, coord: Coord) -> Id {
+            // The toast body (including its text) is the hover target, thus
+            // receiving Event::MouseOver used to pause the timeout.
+            self.action
+                .try_probe(coord)
+                .or_else(|| self.close.try_probe(coord))
+                .unwrap_or_else(|| self.id())
+        }
+
+        fn configure(&mut self, cx: &mut ConfigCx) {
+            if self.shown.is_some() {
+                return;
+            }
+
+            cx.request_frame_timer(self.id(), TIMER_APPEAR);
+            if let Some(timeout) = self.start(cx.now()) {
+                cx.request_timer(self.id(), TIMER_TIMEOUT, timeout);
+            }
+        }
+
+        fn handle_event(&mut self, cx: &mut EventCx, _: &(), event: Event) -> IsUsed {
+            match event {
+                Event::Timer(TIMER_APPEAR) => {
+                    if self.appear_fraction(cx.now()) < 1.0 {
+                        cx.request_frame_timer(self.id(), TIMER_APPEAR);
+                    }
+                    cx.redraw();
+                    Used
+                }
+                Event::Timer(TIMER_TIMEOUT) => {
+                    if self.is_expired(cx.now()) {
+                        if cx.is_under_mouse_any(self.id_ref()) {
+                            // The mouse is over a button: defer closing
+                            cx.request_timer(self.id(), TIMER_TIMEOUT, HOVER_GRACE);
+                        } else {
+                            cx.push(ToastClose);
+                        }
+                    }
+                    Used
+                }
+                Event::MouseOver(true) => {
+                    self.pause(cx.now());
+                    Used
+                }
+                Event::MouseOver(false) => {
+                    if let Some(remaining) = self.resume(cx.now()) {
+                        cx.request_timer(self.id(), TIMER_TIMEOUT, remaining);
+                    }
+                    Used
+                }
+                _ => Unused,
+            }
+        }
+
+        fn handle_messages(&mut self, cx: &mut EventCx, _: &()) {
+            if let Some(ToastAct) = cx.try_pop() {
+                if let Some(f) = self.on_action.as_ref() {
+                    f(cx);
+                }
+                cx.push(ToastClose);
+            }
+        }
+    }
+
+    impl Self {
+        fn new(key: usize, toast: Toast) -> Self {
+            let (action_label, on_action) = match toast.action {
+                Some((label, f)) => (label, Some(f)),
+                None => (String::new(), None),
+            };
+            ToastItem {
+                core: Default::default(),
+                key,
+                severity: toast.severity,
+                timeout: toast.timeout,
+                deadline: None,
+                remaining: None,
+                shown: None,
+                label: Label::new(toast.text),
+                action: ToastAction {
+                    core: Default::default(),
+                    visible: on_action.is_some(),
+                    offset: Offset::ZERO,
+                    size: Size::ZERO,
+                    label: Label::new(action_label),
+                },
+                close: MarkButton::new_msg(MarkStyle::X, "Dismiss", ToastClose),
+                on_action,
+            }
+        }
+
+        /// Show at time `now`
+        ///
+        /// Returns the timeout, if any.
+        fn start(&mut self, now: Instant) -> Option<Duration> {
+            self.shown = Some(now);
+            self.deadline = self.timeout.map(|timeout| now + timeout);
+            self.timeout
+        }
+
+        /// True if the timeout has expired and is not paused
+        fn is_expired(&self, now: Instant) -> bool {
+            self.remaining.is_none() && self.deadline.is_some_and(|deadline| deadline <= now)
+        }
+
+        /// Pause the timeout (while under the mouse)
+        fn pause(&mut self, now: Instant) {
+            if self.remaining.is_none()
+                && let Some(deadline) = self.deadline
+            {
+                self.remaining = Some(deadline.saturating_duration_since(now));
+            }
+        }
+
+        /// Resume the timeout, if paused
+        ///
+        /// Returns the remaining time.
+        fn resume(&mut self, now: Instant) -> Option<Duration> {
+            let remaining = self.remaining.take()?;
+            self.deadline = Some(now + remaining);
+            Some(remaining)
+        }
+
+        /// Progress of the slide-in animation at time `now`, from 0 to 1
+        fn appear_fraction(&self, now: Instant) -> f32 {
+            match self.shown {
+                Some(shown) => {
+                    let elapsed = now.saturating_duration_since(shown);
+                    (elapsed.as_secs_f32() / APPEAR_DURATION.as_secs_f32()).min(1.0)
+                }
+                None => 0.0,
+            }
+        }
+    }
+}
+
+#[impl_self]
+mod ToastLayer {
+    /// The layer of toasts of a window
+    ///
+    /// Toasts are stacked vertically, the newest at the bottom.
+    #[widget]
+    pub(crate) struct ToastLayer {
+        core: widget_core!(),
+        layout: DynRowStorage,
+        toasts: Vec<ToastItem>,
+        next: usize,
+    }
+
+    impl Layout for Self {
+        fn size_rules(&mut self, cx: &mut SizeCx, axis: AxisInfo) -> SizeRules {
+            let dim = (Direction::Down, self.toasts.len(), true);
+            let mut solver = RowSolver::new(axis, dim, &mut self.layout);
+            for (n, child) in self.toasts.iter_mut().enumerate() {
+                solver.for_child(&mut self.layout, n, |axis| child.size_rules(cx, axis));
+            }
+            solver.finish(&mut self.layout)
+        }
+
+        fn set_rect(&mut self, cx: &mut SizeCx, rect: Rect, hints: AlignHints) {
+            self.core.set_rect(rect);
+            let dim = (Direction::Down, self.toasts.len(), true);
+            let mut setter = RowSetter::<Direction, Vec<i32>, _>::new(rect, dim, &mut self.layout);
+            for (n, child) in self.toasts.iter_mut().enumerate() {
+                child.set_rect(cx, setter.child_rect(&mut self.layout, n), hints);
+            }
+        }
+
+        fn draw(&self, mut draw: DrawCx) {
+            let width = self.rect().size.0;
+            let now = draw.ev_state().now();
+            for child in &self.toasts {
+                let t = 1.0 - child.appear_fraction(now);
+                let dx: i32 = (t * t * f32::conv(width)).cast_nearest();
+                if dx == 0 {
+                    child.draw(draw.re());
+                } else {
+                    // Slide in from the right
+                    draw.with_clip_region(self.rect(), Offset(-dx, 0), |draw| child.draw(draw));
+                }
+            }
+        }
+    }
+
+    impl Tile for Self {
+        fn role(&self, _: &mut dyn RoleCx) -> Role<'_> {
+            Role::None
+        }
+
+        #[inline]
+        fn child_indices(&self) -> ChildIndices {
+            ChildIndices::range(0..self.toasts.len())
+        }
+
+        fn get_child(&self, index: usize) -> Option<&dyn Tile> {
+            self.toasts.get(index).map(|w| w.as_tile())
+        }
+
+        fn find_child_index(&self, id: &Id) -> Option<usize> {
+            let key = id.next_key_after(self.id_ref())?;
+            self.toasts.iter().position(|w| w.key == key)
+        }
+    }
+
+    impl Events for Self {
+        fn make_child_id(&mut self, index: usize) -> Id {
+            match self.toasts.get(index) {
+                Some(child) => self.id_ref().make_child(child.key),
+                None => Id::default(),
+            }
+        }
+
+        fn probe(&self, coord: Coord) -> Id {
+            for child in &self.toasts {
+                if let Some(id) = child.try_probe(coord) {
+                    return id;
+                }
+            }
+            self.id()
+        }
+
+        fn handle_messages(&mut self, cx: &mut EventCx, _: &()) {
+            if let Some(ToastClose) = cx.try_pop()
+                && let Some(index) = cx.last_child()
+                && index < self.toasts.len()
+            {
+                self.toasts.remove(index);
+                cx.resize();
+            }
+        }
+    }
+
+    impl Widget for Self {
+        type Data = ();
+
+        fn child_node<'n>(&'n mut self, data: &'n (), index: usize) -> Option<Node<'n>> {
+            self.toasts.get_mut(index).map(|w| w.as_node(data))
+        }
+    }
+
+    impl Default for Self {
+        fn default() -> Self {
+            ToastLayer {
+                core: Default::default(),
+                layout: Default::default(),
+                toasts: vec![],
+                next: 0,
+            }
+        }
+    }
+
+    impl Self {
+        /// True if no toasts are shown
+        pub(crate) fn is_empty(&self) -> bool {
+            self.toasts.is_empty()
+        }
+
+        /// Add a toast
+        pub(crate) fn push(&mut self, cx: &mut ConfigCx, toast: Toast) {
+            let item = self.insert(toast);
+            let id = self.id_ref().make_child(item.key);
+            cx.configure(item.as_node(&()), id);
+            cx.resize();
+        }
+
+        /// Append an item for `toast` with a new key
+        fn insert(&mut self, toast: Toast) -> &mut ToastItem {
+            let key = self.next;
+            self.next += 1;
+            self.toasts.push(ToastItem::new(key, toast));
+            self.toasts.last_mut().unwrap()
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn queue() {
+        let mut layer = ToastLayer::default();
+        assert!(layer.is_empty());
+        for text in ["a", "b", "c"] {
+            layer.insert(Toast::new(text));
+        }
+        let texts: Vec<_> = layer.toasts.iter().map(|t| t.label.as_str()).collect();
+        assert_eq!(texts, ["a", "b", "c"]);
+
+        // Keys are not reused after a toast is closed
+        layer.toasts.remove(1);
+        assert_eq!(layer.insert(Toast::new("d")).key, 3);
+        let keys: Vec<_> = layer.toasts.iter().map(|t| t.key).collect();
+        assert_eq!(keys, [0, 2, 3]);
+    }
+
+    #[test]
+    fn expiry() {
+        let mut item = ToastItem::new(0, Toast::new("text"));
+        let now = Instant::now();
+        assert_eq!(item.start(now), Some(DEFAULT_TIMEOUT));
+        assert!(!item.is_expired(now));
+        assert!(!item.is_expired(now + DEFAULT_TIMEOUT - Duration::from_millis(1)));
+        assert!(item.is_expired(now + DEFAULT_TIMEOUT));
+
+        let mut item = ToastItem::new(0, Toast::new("text").with_timeout(None));
+        assert_eq!(item.start(now), None);
+        assert!(!item.is_expired(now + Duration::from_secs(3600)));
+    }
+
+    #[test]
+    fn pause_on_hover() {
+        let timeout = Duration::from_secs(5);
+        let mut item = ToastItem::new(0, Toast::new("text").with_timeout(Some(timeout)));
+        let now = Instant::now();
+        item.start(now);
+
+        item.pause(now + Duration::from_secs(2));
+        assert!(!item.is_expired(now + Duration::from_secs(10)));
+        // Pausing again does not reset the remaining time
+        item.pause(now + Duration::from_secs(4));
+
+        let resumed = now + Duration::from_secs(10);
+        assert_eq!(item.resume(resumed), Some(Duration::from_secs(3)));
+        assert_eq!(item.resume(resumed), None);
+        assert!(!item.is_expired(resumed + Duration::from_secs(2)));
+        assert!(item.is_expired(resumed + Duration::from_secs(3)));
+    }
+}
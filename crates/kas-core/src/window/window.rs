@@ -6,11 +6,13 @@
 //! Window widgets
 
 use super::icon::Icon;
-use super::{Decorations, Popup, PopupDescriptor, ResizeDirection, WindowId};
+use super::{Decorations, Popup, PopupDescriptor, ResizeDirection, Toast, ToastLayer, WindowId};
+use super::{InspectHighlight, InspectPick, InspectRequest};
 use crate::cast::Cast;
 use crate::dir::{Direction, Directional};
-use crate::event::{Command, ConfigCx, Event, EventCx, IsUsed, Scroll, Unused, Used};
-use crate::geom::{Coord, Offset, Rect, Size};
+use crate::draw::color::Rgba;
+use crate::event::{Command, ConfigCx, Event, EventCx, IsUsed, Scroll, TimerHandle, Unused, Used};
+use crate::geom::{Coord, Offset, Quad, Rect, Size};
 use crate::layout::{self, Align, AlignHints, AxisInfo, SizeRules};
 use crate::runner::AppData;
 use crate::theme::{DrawCx, FrameStyle, SizeCx};
@@ -19,11 +21,51 @@ use crate::widgets::{Border, Label, TitleBar};
 use crate::{Events, Id, Layout, Role, RoleCx, Tile, TileExt, Widget, WidgetCoreRect};
 use kas_macros::{autoimpl, impl_self};
 use smallvec::SmallVec;
+use std::time::Duration;
+
+#[impl_self]
+mod Tooltip {
+    /// The content of a tooltip pop-up
+    #[widget]
+    #[layout(self.label)]
+    struct Tooltip {
+        core: widget_core!(),
+        #[widget]
+        label: Label<String>,
+    }
+
+    impl Tile for Self {
+        fn role(&self, _: &mut dyn RoleCx) -> Role<'_> {
+            Role::Tooltip
+        }
+    }
+
+    impl Events for Self {
+        type Data = ();
+    }
+
+    impl Self {
+        fn new() -> Self {
+            Tooltip {
+                core: Default::default(),
+                label: Label::new(String::new()),
+            }
+        }
+    }
+}
+
+/// Color used to highlight widgets selected by an [`Inspector`](super::Inspector)
+const INSPECT_COLOR: Rgba = Rgba::rgb(0.2, 0.5, 1.0);
+/// Time without contact from an [`Inspector`](super::Inspector) after which
+/// highlighting and pick mode are cancelled (e.g. since it was closed)
+const INSPECT_TIMEOUT: Duration = Duration::from_secs(2);
+
+const TIMER_INSPECT: TimerHandle = TimerHandle::new(0, false);
 
 // TODO(Rust): replace with type-alias-impl-trait when available
-#[cfg(feature = "accesskit")]
+#[cfg(any(feature = "accesskit", automation))]
 pub(crate) struct PopupIterator<'a>(usize, &'a [(WindowId, PopupDescriptor, Offset)]);
-#[cfg(feature = "accesskit")]
+#[cfg(any(feature = "accesskit", automation))]
 impl<'a> Iterator for PopupIterator<'a> {
     type Item = &'a PopupDescriptor;
 
@@ -47,7 +89,7 @@ pub(crate) trait WindowErased: Tile {
     fn close_tooltip(&mut self, cx: &mut EventCx);
 
     /// Iterate over popups
-    #[cfg(feature = "accesskit")]
+    #[cfg(any(feature = "accesskit", automation))]
     fn iter_popups(&self) -> PopupIterator<'_>;
 }
 
@@ -142,6 +184,15 @@ mod Window {
     /// [`kas::messages::SetWindowTitle`] may be used to set the title.
     ///
     /// [`kas::messages::SetWindowIcon`] may be used to set the icon.
+    ///
+    /// [`kas::messages::SetLayoutOverlay`] toggles the layout debug overlay.
+    /// Recorded layout is refreshed on each full resize of the window; local
+    /// resizes (e.g. within a scroll region) are not reflected until then.
+    ///
+    /// A [`Toast`] sent to the window is shown as a notification; see
+    /// [`EventState::show_toast`](crate::event::EventState::show_toast).
+    ///
+    /// The window also answers requests from an [`Inspector`](super::Inspector).
     #[widget]
     pub struct Window<Data: AppData> {
         core: widget_core!(),
@@ -149,7 +200,7 @@ mod Window {
         #[widget]
         inner: Box<dyn Widget<Data = Data>>,
         #[widget(&())]
-        tooltip: Popup<Label<String>>,
+        tooltip: Popup<Tooltip>,
         #[widget(&())]
         title_bar: TitleBar,
         #[widget(&())]
@@ -168,6 +219,10 @@ mod Window {
         b_sw: Border,
         #[widget(&())]
         b_se: Border,
+        #[widget(&())]
+        toasts: ToastLayer,
+        toast_size: Size,
+        inspect_highlight: Option<Id>,
         bar_h: i32,
         bar_margin: i32,
         dec_offset: Offset,
@@ -203,6 +258,9 @@ mod Window {
             let _ = self.b_se.size_rules(cx, axis);
             let _ = self.b_sw.size_rules(cx, axis);
 
+            let toasts = self.toasts.size_rules(cx, axis);
+            self.toast_size.set_component(axis, toasts.ideal_size());
+
             if matches!(
                 self.props.decorations,
                 Decorations::Border | Decorations::Toolkit
@@ -268,6 +326,12 @@ mod Window {
                 s_in -= Size(0, self.bar_h + self.bar_margin);
             }
             self.inner.set_rect(cx, Rect::new(p_in, s_in), hints);
+
+            // Toasts are placed in the bottom-right corner of the inner region
+            let s_toast = self.toast_size.min(s_in);
+            let p_toast = p_in + (s_in - s_toast);
+            self.toasts
+                .set_rect(cx, Rect::new(p_toast, s_toast), AlignHints::NONE);
         }
 
         fn draw(&self, mut draw: DrawCx) {
@@ -289,6 +353,31 @@ mod Window {
                 }
             }
             self.inner.draw(draw.re());
+
+            if !self.toasts.is_empty() {
+                draw.with_overlay(self.toasts.rect(), Offset::ZERO, |draw| {
+                    self.toasts.draw(draw);
+                });
+            }
+
+            if let Some(records) = draw.ev_state().layout_records.take() {
+                draw.with_overlay(self.rect(), Offset::ZERO, |mut draw| {
+                    layout::draw_layout_overlay(&mut draw, &records);
+                });
+                draw.ev_state().layout_records = Some(records);
+            }
+
+            let pick = draw.ev_state().inspect_pick_over();
+            if let Some(id) = pick.as_ref().or(self.inspect_highlight.as_ref())
+                && let Some((rect, translation)) = self.as_tile().find_tile_rect(id)
+            {
+                let quad = Quad::conv(rect - translation);
+                let fill = INSPECT_COLOR.as_rgb().with_alpha(0.25);
+                draw.with_overlay(self.rect(), Offset::ZERO, |mut draw| {
+                    draw.draw().rect(quad, fill);
+                    draw.draw().frame(quad, quad.shrink(2.0), INSPECT_COLOR);
+                });
+            }
         }
     }
 
@@ -302,6 +391,11 @@ mod Window {
         type Data = Data;
 
         fn probe(&self, coord: Coord) -> Id {
+            if !self.toasts.is_empty()
+                && let Some(id) = self.toasts.try_probe(coord)
+            {
+                return id;
+            }
             for (_, popup, translation) in self.popups.iter().rev() {
                 if let Some(widget) = self.inner.find_tile(&popup.id)
                     && let Some(id) = widget.try_probe(coord + *translation)
@@ -362,12 +456,20 @@ mod Window {
                     cx.timer_expiry_tooltip(self);
                     Used
                 }
+                Event::Timer(TIMER_INSPECT) => {
+                    self.inspect_highlight = None;
+                    cx.inspect_picker = None;
+                    cx.redraw();
+                    Used
+                }
                 _ => Unused,
             }
         }
 
-        fn handle_messages(&mut self, cx: &mut EventCx, _: &Self::Data) {
-            if let Some(kas::messages::SetWindowTitle(title)) = cx.try_pop() {
+        fn handle_messages(&mut self, cx: &mut EventCx, data: &Self::Data) {
+            if let Some(toast) = cx.try_pop::<Toast>() {
+                self.toasts.push(cx, toast);
+            } else if let Some(kas::messages::SetWindowTitle(title)) = cx.try_pop() {
                 self.title_bar.set_title(cx, title);
                 if self.props.decorations == Decorations::Server
                     && let Some(w) = cx.winit_window()
@@ -382,6 +484,26 @@ mod Window {
                     return; // do not set self.icon
                 }
                 self.props.icon = icon;
+            } else if let Some(kas::messages::SetLayoutOverlay(enable)) = cx.try_pop() {
+                if enable != cx.layout_overlay() {
+                    cx.layout_records = enable.then(Vec::new);
+                    if enable {
+                        // Layout is recorded during the next full resize
+                        cx.resize();
+                    }
+                    cx.redraw();
+                }
+            } else if let Some(request) = cx.try_pop::<InspectRequest>() {
+                request.respond(cx, self, data);
+                cx.request_timer(self.id(), TIMER_INSPECT, INSPECT_TIMEOUT);
+            } else if let Some(InspectHighlight(id)) = cx.try_pop() {
+                self.inspect_highlight = id;
+                cx.request_timer(self.id(), TIMER_INSPECT, INSPECT_TIMEOUT);
+                cx.redraw();
+            } else if let Some(InspectPick(inspector)) = cx.try_pop() {
+                cx.inspect_picker = inspector;
+                cx.request_timer(self.id(), TIMER_INSPECT, INSPECT_TIMEOUT);
+                cx.redraw();
             }
         }
 
@@ -401,7 +523,7 @@ mod Window {
         }
 
         fn show_tooltip(&mut self, cx: &mut EventCx, id: Id, text: String) {
-            self.tooltip.inner.set_text(cx, text);
+            self.tooltip.inner.label.set_text(cx, text);
             self.tooltip.open(cx, &(), id, false);
         }
 
@@ -409,7 +531,7 @@ mod Window {
             self.tooltip.close(cx);
         }
 
-        #[cfg(feature = "accesskit")]
+        #[cfg(any(feature = "accesskit", automation))]
         fn iter_popups(&self) -> PopupIterator<'_> {
             PopupIterator(0, &self.popups)
         }
@@ -445,6 +567,9 @@ impl Window<()> {
             b_ne: self.b_ne,
             b_sw: self.b_sw,
             b_se: self.b_se,
+            toasts: self.toasts,
+            toast_size: Size::ZERO,
+            inspect_highlight: None,
             bar_h: 0,
             bar_margin: 0,
             dec_offset: Default::default(),
@@ -466,7 +591,7 @@ impl<Data: AppData> Window<Data> {
             core: Default::default(),
             props: Properties::default(),
             inner: ui,
-            tooltip: Popup::new(Label::new("".to_string()), Direction::Down).align(Align::Center),
+            tooltip: Popup::new(Tooltip::new(), Direction::Down).align(Align::Center),
             title_bar: TitleBar::new(title),
             b_w: Border::new(ResizeDirection::West),
             b_e: Border::new(ResizeDirection::East),
@@ -476,6 +601,9 @@ impl<Data: AppData> Window<Data> {
             b_ne: Border::new(ResizeDirection::NorthEast),
             b_sw: Border::new(ResizeDirection::SouthWest),
             b_se: Border::new(ResizeDirection::SouthEast),
+            toasts: ToastLayer::default(),
+            toast_size: Size::ZERO,
+            inspect_highlight: None,
             bar_h: 0,
             bar_margin: 0,
             dec_offset: Default::default(),
@@ -708,9 +836,12 @@ impl<Data: AppData> Window<Data> {
             (pos, size)
         };
 
-        let Some((c, t)) = self.as_tile().find_tile_rect(&popup.parent) else {
+        let Some((mut c, t)) = self.as_tile().find_tile_rect(&popup.parent) else {
             return;
         };
+        if let Some(anchor) = popup.anchor {
+            c = anchor;
+        }
         self.popups[index].2 = t;
         let r = r + t; // work in translated coordinate space
         let result = Widget::as_node(self, data).find_node(&popup.id, |mut node| {
@@ -141,10 +141,46 @@ impl Methods {
                 (#base).slider(id, id2, rect, h_rect, dir);
             }
 
+            fn range_slider(
+                &mut self,
+                id: &Id,
+                ids: [(&Id, &Id); 2],
+                rect: Rect,
+                grip_rects: [Rect; 2],
+                dir: Direction,
+            ) {
+                (#base).range_slider(id, ids, rect, grip_rects, dir);
+            }
+
             fn progress_bar(&mut self, id: &Id, rect: Rect, dir: Direction, value: f32) {
                 (#base).progress_bar(id, rect, dir, value);
             }
 
+            fn progress_bar_indeterminate(
+                &mut self,
+                id: &Id,
+                rect: Rect,
+                dir: Direction,
+                phase: f32,
+            ) {
+                (#base).progress_bar_indeterminate(id, rect, dir, phase);
+            }
+
+            fn progress_steps(
+                &mut self,
+                id: &Id,
+                rect: Rect,
+                dir: Direction,
+                steps: usize,
+                current: usize,
+            ) {
+                (#base).progress_steps(id, rect, dir, steps, current);
+            }
+
+            fn spinner(&mut self, id: &Id, rect: Rect, phase: f32) {
+                (#base).spinner(id, rect, phase);
+            }
+
             fn image(&mut self, id: ImageId, rect: Rect) {
                 (#base).image(id, rect);
             }
@@ -38,6 +38,7 @@ mod GridCell {
     struct GridCell<K, I, V: Driver<K, I>> {
         core: widget_core!(),
         index: GridIndex,
+        header_len: GridIndex,
         selected: Option<bool>,
         /// The inner widget
         #[widget]
@@ -51,6 +52,7 @@ mod GridCell {
             GridCell {
                 core: Default::default(),
                 index: GridIndex::default(),
+                header_len: GridIndex::ZERO,
                 selected: None,
                 inner,
             }
@@ -62,9 +64,16 @@ mod GridCell {
             if let Some(label) = V::label(&self.inner) {
                 cx.set_label(label);
             }
-            Role::GridCell {
-                info: Some(GridCellInfo::new(self.index.col, self.index.row)),
-                selected: self.selected,
+            let info = Some(GridCellInfo::new(self.index.col, self.index.row));
+            if self.index.row < self.header_len.row {
+                Role::ColumnHeader { info }
+            } else if self.index.col < self.header_len.col {
+                Role::RowHeader { info }
+            } else {
+                Role::GridCell {
+                    info,
+                    selected: self.selected,
+                }
             }
         }
 
@@ -194,6 +203,7 @@ mod GridView {
         ideal_len: GridIndex,
         alloc_len: GridIndex,
         data_len: GridIndex,
+        header_len: GridIndex,
         token_update: Update,
         rect_update: bool,
         immediate_scroll_update: bool,
@@ -238,6 +248,7 @@ mod GridView {
                 ideal_len: GridIndex { col: 3, row: 5 },
                 alloc_len: GridIndex::ZERO,
                 data_len: GridIndex::ZERO,
+                header_len: GridIndex::ZERO,
                 token_update: Update::None,
                 rect_update: false,
                 immediate_scroll_update: false,
@@ -408,6 +419,20 @@ mod GridView {
             self
         }
 
+        /// Set the number of header columns and rows (inline)
+        ///
+        /// Cells in the first `rows` rows are reported to accessibility tools
+        /// as column headers; other cells in the first `cols` columns are
+        /// reported as row headers. This does not affect layout or scrolling.
+        #[must_use]
+        pub fn with_headers(mut self, cols: u32, rows: u32) -> Self {
+            self.header_len = GridIndex {
+                col: cols,
+                row: rows,
+            };
+            self
+        }
+
         /// Widgets in the range `0..self.cur_end()` are currently in use
         #[inline]
         fn cur_end(&self) -> usize {
@@ -549,6 +574,7 @@ mod GridView {
                     let mut rect_update = self.rect_update;
                     if changes.key() || self.token_update == Update::Configure {
                         w.item.index = cell;
+                        w.item.header_len = self.header_len;
                         // TODO(opt): some impls of Driver::set_key do nothing
                         // and do not need re-configure (beyond the first).
                         self.driver.set_key(&mut w.item.inner, token.borrow());
@@ -98,7 +98,7 @@ where
         ev: &'a mut EventState,
         w: &'a mut Self::Window,
     ) -> Self::Draw<'a> {
-        w.anim.update();
+        w.anim.update(ev.now());
 
         DrawHandle {
             draw,
@@ -340,6 +340,42 @@ where
         self.draw_grip(h_rect, state);
     }
 
+    fn range_slider(
+        &mut self,
+        _: &Id,
+        ids: [(&Id, &Id); 2],
+        rect: Rect,
+        grip_rects: [Rect; 2],
+        dir: Direction,
+    ) {
+        // track
+        let mut outer = Quad::conv(rect);
+        outer = match dir.is_horizontal() {
+            true => outer.shrink_vec(Vec2(0.0, outer.size().1 * (3.0 / 8.0))),
+            false => outer.shrink_vec(Vec2(outer.size().0 * (3.0 / 8.0), 0.0)),
+        };
+        let inner = outer.shrink(outer.size().min_comp() / 2.0);
+        let col = self.cols.background;
+        self.draw.shaded_round_frame(outer, inner, NORMS_TRACK, col);
+
+        // filled range between grips
+        let [a, b] = grip_rects.map(|r| Vec2::conv(r.pos + r.size / 2));
+        if dir.is_horizontal() {
+            (outer.a.0, outer.b.0) = (a.0.min(b.0), a.0.max(b.0));
+        } else {
+            (outer.a.1, outer.b.1) = (a.1.min(b.1), a.1.max(b.1));
+        }
+        let inner = outer.shrink(outer.size().min_comp() / 2.0);
+        self.draw
+            .shaded_round_frame(outer, inner, NORMS_TRACK, self.cols.accent);
+
+        // grips
+        for ((id, id2), h_rect) in ids.into_iter().zip(grip_rects) {
+            let state = InputState::new2(self.ev, id, id2);
+            self.draw_grip(h_rect, state);
+        }
+    }
+
     fn progress_bar(&mut self, _: &Id, rect: Rect, dir: Direction, value: f32) {
         let mut outer = Quad::conv(rect);
         let inner = outer.shrink(outer.size().min_comp() / 2.0);
@@ -99,6 +99,36 @@ pub trait AdaptWidget: Widget + Sized {
         AdaptEvents::new(self).on_configure(f)
     }
 
+    /// Enable spatial navigation within this widget
+    ///
+    /// Arrow keys not handled by the focused widget move navigation focus to
+    /// the nearest navigable descendant in that direction. See
+    /// [`ConfigCx::register_spatial_nav`](kas::event::ConfigCx::register_spatial_nav).
+    ///
+    /// Returns a wrapper around the input widget.
+    #[must_use]
+    fn with_spatial_nav(self) -> AdaptEvents<Self> {
+        AdaptEvents::new(self).on_configure(|cx, _| {
+            let id = cx.id();
+            cx.register_spatial_nav(id);
+        })
+    }
+
+    /// Make this widget a focus scope
+    ///
+    /// While navigation focus is within this widget, <kbd>Tab</kbd> cycles
+    /// through its navigable descendants only. See
+    /// [`ConfigCx::register_focus_scope`](kas::event::ConfigCx::register_focus_scope).
+    ///
+    /// Returns a wrapper around the input widget.
+    #[must_use]
+    fn with_focus_scope(self) -> AdaptEvents<Self> {
+        AdaptEvents::new(self).on_configure(|cx, _| {
+            let id = cx.id();
+            cx.register_focus_scope(id);
+        })
+    }
+
     /// Call the given closure on [`Events::update`]
     ///
     /// Returns a wrapper around the input widget.
@@ -65,7 +65,7 @@ mod CheckBox {
             let new_state = (self.state_fn)(cx, data);
             if self.state != new_state {
                 self.state = new_state;
-                self.last_change = Some(Instant::now());
+                self.last_change = Some(cx.now());
                 cx.redraw();
             }
         }
@@ -163,7 +163,7 @@ mod CheckBox {
             }
 
             // Do animate (even if state never changes):
-            self.last_change = Some(Instant::now());
+            self.last_change = Some(cx.now());
             cx.redraw();
         }
     }
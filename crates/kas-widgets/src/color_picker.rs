@@ -0,0 +1,964 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Colour picker widgets
+
+use crate::{Button, EditBox, Row, Slider, SpinBox, edit::ParseGuard};
+use kas::draw::color::{Rgba, Rgba8Srgb};
+use kas::draw::{Draw, ImageFormat, ImageHandle};
+use kas::event::{CursorIcon, FocusSource};
+use kas::geom::{Quad, Vec2};
+use kas::messages::{Collapse, Expand};
+use kas::prelude::*;
+use kas::theme::{Background, FrameStyle, TextClass};
+use kas::window::Popup;
+use std::fmt;
+use std::str::FromStr;
+
+/// Number of recent colours remembered
+const RECENT: usize = 8;
+
+/// Texture size (per axis) used to draw the saturation/value square
+const SV_TEXELS: u32 = 32;
+
+/// Texture length used to draw the hue bar
+const HUE_TEXELS: u32 = 120;
+
+#[derive(Clone, Copy, Debug)]
+enum ColorMsg {
+    SatVal(f32, f32),
+    Hue(f32),
+    Alpha(u8),
+    Red(u8),
+    Green(u8),
+    Blue(u8),
+    H(u16),
+    S(u8),
+    V(u8),
+    Hex(Rgba8Srgb),
+    Pick(Rgba8Srgb),
+    /// End of an interactive adjustment
+    Commit,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct PickerChanged(Rgba8Srgb);
+
+/// Request to re-render a gradient texture
+#[derive(Debug)]
+struct Render;
+
+/// Convert HSV (hue in degrees) to sRGB
+fn hsv_to_rgb([h, s, v]: [f32; 3]) -> [u8; 3] {
+    let c = v * s;
+    let hp = (h / 60.0).rem_euclid(6.0);
+    let x = c * (1.0 - (hp % 2.0 - 1.0).abs());
+    let (r, g, b) = match u32::conv_trunc(hp) {
+        0 => (c, x, 0.0),
+        1 => (x, c, 0.0),
+        2 => (0.0, c, x),
+        3 => (0.0, x, c),
+        4 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+    let m = v - c;
+    [r, g, b].map(|x| ((x + m) * 255.0).clamp(0.0, 255.0).cast_nearest())
+}
+
+/// Convert sRGB to HSV (hue in degrees)
+fn rgb_to_hsv(r: u8, g: u8, b: u8) -> [f32; 3] {
+    let [r, g, b] = [r, g, b].map(|x| f32::from(x) / 255.0);
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let delta = max - min;
+    let h = if delta == 0.0 {
+        0.0
+    } else if max == r {
+        60.0 * ((g - b) / delta).rem_euclid(6.0)
+    } else if max == g {
+        60.0 * ((b - r) / delta + 2.0)
+    } else {
+        60.0 * ((r - g) / delta + 4.0)
+    };
+    let s = if max == 0.0 { 0.0 } else { delta / max };
+    [h, s, max]
+}
+
+/// Render `size` texels of sRGBA data, sampling `f` at texel centres
+///
+/// Inputs to `f` are relative positions in the range `0..1`.
+fn render_gradient(size: Size, f: impl Fn(f32, f32) -> [f32; 3]) -> Vec<u8> {
+    let (w, h): (u32, u32) = (size.0.cast(), size.1.cast());
+    let mut data = Vec::with_capacity(usize::conv(4 * w * h));
+    for j in 0..h {
+        let y = (f32::conv(j) + 0.5) / f32::conv(h);
+        for i in 0..w {
+            let x = (f32::conv(i) + 0.5) / f32::conv(w);
+            data.extend(hsv_to_rgb(f(x, y)));
+            data.push(255);
+        }
+    }
+    data
+}
+
+/// Upload `data` to `image`, allocating if necessary
+fn upload_gradient(cx: &mut EventCx, image: &mut Option<ImageHandle>, size: Size, data: &[u8]) {
+    let ds = cx.draw_shared();
+    if image.is_none() {
+        *image = ds
+            .image_alloc(ImageFormat::Rgba8, size)
+            .map_err(|err| log::warn!("ColorPicker: allocate failed: {err}"))
+            .ok();
+    }
+
+    if let Some(handle) = image.as_ref() {
+        match ds.image_upload(handle, data) {
+            Ok(_) => cx.redraw(),
+            Err(err) => log::warn!("ColorPicker: image upload failed: {err}"),
+        }
+    }
+}
+
+/// Draw `color` over a checkerboard (where translucent)
+fn draw_color(draw: &mut dyn Draw, quad: Quad, color: Rgba8Srgb) {
+    if color.a() < 255 {
+        let size = quad.size();
+        let cell = (size.1 / 2.0).max(1.0);
+        let (cols, rows): (u32, u32) = ((size.0 / cell).cast_ceil(), (size.1 / cell).cast_ceil());
+        for j in 0..rows {
+            for i in 0..cols {
+                let shade = if (i + j) % 2 == 0 { 0.8 } else { 0.5 };
+                let a = quad.a + Vec2(i.cast(), j.cast()) * cell;
+                let b = (a + Vec2::splat(cell)).min(quad.b);
+                draw.rect(Quad::from_coords(a, b), Rgba::grey(shade));
+            }
+        }
+    }
+    draw.rect(quad, color.into());
+}
+
+/// Picker state
+///
+/// This is stored as HSV to preserve hue and saturation where not
+/// determined by an RGB value.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct ColorState {
+    hsv: [f32; 3],
+    alpha: u8,
+}
+
+impl ColorState {
+    fn from_rgba(color: Rgba8Srgb, prev: &ColorState) -> Self {
+        let [mut h, mut s, v] = rgb_to_hsv(color.r(), color.g(), color.b());
+        if v == 0.0 {
+            [h, s] = [prev.hsv[0], prev.hsv[1]];
+        } else if s == 0.0 {
+            h = prev.hsv[0];
+        }
+        ColorState {
+            hsv: [h, s, v],
+            alpha: color.a(),
+        }
+    }
+
+    fn rgba(&self) -> Rgba8Srgb {
+        let [r, g, b] = hsv_to_rgb(self.hsv);
+        Rgba8Srgb::rgba(r, g, b, self.alpha)
+    }
+}
+
+/// Hex colour format used by the text entry
+#[derive(Debug)]
+struct Hex(Rgba8Srgb);
+
+impl fmt::Display for Hex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0.format_html())
+    }
+}
+
+impl FromStr for Hex {
+    type Err = kas::draw::color::ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.trim().parse().map(Hex)
+    }
+}
+
+/// Get the relative position of `coord` within `rect`, clamped to `0..=1`
+fn rel_pos(rect: Rect, coord: Coord) -> (f32, f32) {
+    let x = f32::conv(coord.0 - rect.pos.0) / f32::conv(rect.size.0.max(1));
+    let y = f32::conv(coord.1 - rect.pos.1) / f32::conv(rect.size.1.max(1));
+    (x.clamp(0.0, 1.0), y.clamp(0.0, 1.0))
+}
+
+fn area_rules(cx: &mut SizeCx, axis: AxisInfo, min_em: f32, ideal_em: f32) -> SizeRules {
+    let dpem = cx.dpem(TextClass::Standard);
+    let (min, ideal) = ((min_em * dpem).cast_ceil(), (ideal_em * dpem).cast_ceil());
+    SizeRules::new(min, ideal, Stretch::High).with_margins(cx.inner_margins().extract(axis))
+}
+
+#[impl_self]
+mod SatValArea {
+    /// A square selecting saturation (horizontal) and value (vertical)
+    #[widget]
+    struct SatValArea {
+        core: widget_core!(),
+        hsv: [f32; 3],
+        /// Hue of the rendered texture
+        image_hue: Option<f32>,
+        image: Option<ImageHandle>,
+    }
+
+    impl Layout for Self {
+        fn size_rules(&mut self, cx: &mut SizeCx, axis: AxisInfo) -> SizeRules {
+            area_rules(cx, axis, 6.0, 10.0)
+        }
+
+        fn draw(&self, mut draw: DrawCx) {
+            if let Some(handle) = self.image.as_ref() {
+                draw.image(self.rect(), handle.id());
+            }
+
+            let quad = Quad::conv(self.rect());
+            let [_, s, v] = self.hsv;
+            let d = draw.draw();
+            let pos = quad.a + Vec2(s * quad.size().0, (1.0 - v) * quad.size().1);
+            let r = (quad.size().min_comp() * 0.03).max(4.0);
+            let col = if v > 0.5 { Rgba::BLACK } else { Rgba::WHITE };
+            d.frame(
+                Quad::from_center(pos, r),
+                Quad::from_center(pos, r - 1.5),
+                col,
+            );
+
+            draw.frame(self.rect(), FrameStyle::NavFocus, Background::Default);
+        }
+    }
+
+    impl Tile for Self {
+        fn navigable(&self) -> bool {
+            true
+        }
+
+        fn role(&self, _: &mut dyn RoleCx) -> Role<'_> {
+            Role::Canvas
+        }
+    }
+
+    impl Events for Self {
+        type Data = ColorState;
+
+        fn update(&mut self, cx: &mut ConfigCx, state: &ColorState) {
+            if self.hsv != state.hsv {
+                self.hsv = state.hsv;
+                cx.redraw();
+            }
+            if self.image_hue != Some(self.hsv[0]) {
+                cx.send(self.id(), Render);
+            }
+        }
+
+        fn handle_event(&mut self, cx: &mut EventCx, _: &ColorState, event: Event) -> IsUsed {
+            let [_, s, v] = self.hsv;
+            match event {
+                Event::Command(cmd, code) => {
+                    let (s, v) = match cmd {
+                        Command::Left => (s - 0.01, v),
+                        Command::Right => (s + 0.01, v),
+                        Command::Up => (s, v + 0.01),
+                        Command::Down => (s, v - 0.01),
+                        _ => return Unused,
+                    };
+                    cx.depress_with_key(&self, code);
+                    cx.push(ColorMsg::SatVal(s.clamp(0.0, 1.0), v.clamp(0.0, 1.0)));
+                    cx.push(ColorMsg::Commit);
+                    Used
+                }
+                Event::PressStart(press) => {
+                    press
+                        .grab_move(self.id())
+                        .with_icon(CursorIcon::Grabbing)
+                        .complete(cx);
+                    cx.request_nav_focus(self.id(), FocusSource::Pointer);
+                    let (x, y) = rel_pos(self.rect(), press.coord());
+                    cx.push(ColorMsg::SatVal(x, 1.0 - y));
+                    Used
+                }
+                Event::PressMove { press, .. } => {
+                    let (x, y) = rel_pos(self.rect(), press.coord);
+                    cx.push(ColorMsg::SatVal(x, 1.0 - y));
+                    Used
+                }
+                Event::PressEnd { .. } => {
+                    cx.push(ColorMsg::Commit);
+                    Used
+                }
+                _ => Unused,
+            }
+        }
+
+        fn handle_messages(&mut self, cx: &mut EventCx, _: &ColorState) {
+            if let Some(Render) = cx.try_pop()
+                && self.image_hue != Some(self.hsv[0])
+            {
+                let h = self.hsv[0];
+                let size = Size::splat(SV_TEXELS.cast());
+                let data = render_gradient(size, |x, y| [h, x, 1.0 - y]);
+                upload_gradient(cx, &mut self.image, size, &data);
+                self.image_hue = Some(h);
+            }
+        }
+    }
+
+    impl Self {
+        fn new() -> Self {
+            SatValArea {
+                core: Default::default(),
+                hsv: [0.0; 3],
+                image_hue: None,
+                image: None,
+            }
+        }
+    }
+}
+
+#[impl_self]
+mod HueBar {
+    /// A vertical bar selecting hue
+    #[widget]
+    struct HueBar {
+        core: widget_core!(),
+        hue: f32,
+        image: Option<ImageHandle>,
+    }
+
+    impl Layout for Self {
+        fn size_rules(&mut self, cx: &mut SizeCx, axis: AxisInfo) -> SizeRules {
+            if axis.is_horizontal() {
+                let dpem = cx.dpem(TextClass::Standard);
+                SizeRules::fixed((1.5 * dpem).cast_ceil())
+                    .with_margins(cx.inner_margins().extract(axis))
+            } else {
+                area_rules(cx, axis, 6.0, 10.0)
+            }
+        }
+
+        fn draw(&self, mut draw: DrawCx) {
+            if let Some(handle) = self.image.as_ref() {
+                draw.image(self.rect(), handle.id());
+            }
+
+            let quad = Quad::conv(self.rect());
+            let size = quad.size();
+            let d = draw.draw();
+            let y = quad.a.1 + self.hue / 360.0 * size.1;
+            let outer = Quad::from_coords(Vec2(quad.a.0, y - 2.5), Vec2(quad.b.0, y + 2.5));
+            let inner =
+                Quad::from_coords(Vec2(quad.a.0 + 1.5, y - 1.0), Vec2(quad.b.0 - 1.5, y + 1.0));
+            d.frame(outer, inner, Rgba::BLACK);
+
+            draw.frame(self.rect(), FrameStyle::NavFocus, Background::Default);
+        }
+    }
+
+    impl Tile for Self {
+        fn navigable(&self) -> bool {
+            true
+        }
+
+        fn role(&self, _: &mut dyn RoleCx) -> Role<'_> {
+            Role::Slider {
+                min: 0.0,
+                max: 360.0,
+                step: 1.0,
+                value: self.hue.into(),
+                direction: Direction::Down,
+            }
+        }
+    }
+
+    impl Events for Self {
+        type Data = ColorState;
+
+        fn configure(&mut self, cx: &mut ConfigCx) {
+            if self.image.is_none() {
+                cx.send(self.id(), Render);
+            }
+        }
+
+        fn update(&mut self, cx: &mut ConfigCx, state: &ColorState) {
+            if self.hue != state.hsv[0] {
+                self.hue = state.hsv[0];
+                cx.redraw();
+            }
+        }
+
+        fn handle_event(&mut self, cx: &mut EventCx, _: &ColorState, event: Event) -> IsUsed {
+            match event {
+                Event::Command(cmd, code) => {
+                    let hue = match cmd {
+                        Command::Up => self.hue - 1.0,
+                        Command::Down => self.hue + 1.0,
+                        Command::PageUp => self.hue - 10.0,
+                        Command::PageDown => self.hue + 10.0,
+                        _ => return Unused,
+                    };
+                    cx.depress_with_key(&self, code);
+                    cx.push(ColorMsg::Hue(hue.rem_euclid(360.0)));
+                    cx.push(ColorMsg::Commit);
+                    Used
+                }
+                Event::PressStart(press) => {
+                    press
+                        .grab_move(self.id())
+                        .with_icon(CursorIcon::Grabbing)
+                        .complete(cx);
+                    cx.request_nav_focus(self.id(), FocusSource::Pointer);
+                    let (_, y) = rel_pos(self.rect(), press.coord());
+                    cx.push(ColorMsg::Hue((y * 360.0).min(359.9)));
+                    Used
+                }
+                Event::PressMove { press, .. } => {
+                    let (_, y) = rel_pos(self.rect(), press.coord);
+                    cx.push(ColorMsg::Hue((y * 360.0).min(359.9)));
+                    Used
+                }
+                Event::PressEnd { .. } => {
+                    cx.push(ColorMsg::Commit);
+                    Used
+                }
+                _ => Unused,
+            }
+        }
+
+        fn handle_messages(&mut self, cx: &mut EventCx, _: &ColorState) {
+            if let Some(Render) = cx.try_pop() {
+                let size = Size(1, HUE_TEXELS.cast());
+                let data = render_gradient(size, |_, y| [y * 360.0, 1.0, 1.0]);
+                upload_gradient(cx, &mut self.image, size, &data);
+            }
+        }
+    }
+
+    impl Self {
+        fn new() -> Self {
+            HueBar {
+                core: Default::default(),
+                hue: 0.0,
+                image: None,
+            }
+        }
+    }
+}
+
+#[impl_self]
+mod Swatch {
+    /// A colour swatch
+    ///
+    /// When used in the recent-colours palette, a click emits
+    /// [`ColorMsg::Pick`].
+    #[widget]
+    struct Swatch {
+        core: widget_core!(),
+        color: Option<Rgba8Srgb>,
+        size: (f32, f32),
+        pickable: bool,
+    }
+
+    impl Layout for Self {
+        fn size_rules(&mut self, cx: &mut SizeCx, axis: AxisInfo) -> SizeRules {
+            let dpem = cx.dpem(TextClass::Standard);
+            let size = if axis.is_horizontal() { self.size.0 } else { self.size.1 };
+            SizeRules::fixed((size * dpem).cast_ceil())
+                .with_margins(cx.inner_margins().extract(axis))
+        }
+
+        fn draw(&self, mut draw: DrawCx) {
+            if let Some(color) = self.color {
+                draw_color(draw.draw(), Quad::conv(self.rect()), color);
+            } else {
+                draw.frame(self.rect(), FrameStyle::Frame, Background::Default);
+            }
+        }
+    }
+
+    impl Tile for Self {
+        fn role(&self, cx: &mut dyn RoleCx) -> Role<'_> {
+            let Some(color) = self.color else {
+                return Role::None;
+            };
+            cx.set_label(color.format_html());
+            if self.pickable { Role::Button } else { Role::Image }
+        }
+    }
+
+    impl Events for Self {
+        type Data = ();
+
+        fn handle_event(&mut self, cx: &mut EventCx, _: &(), event: Event) -> IsUsed {
+            match self.color {
+                Some(color) if self.pickable => {
+                    event.on_click(cx, self.id(), |cx| cx.push(ColorMsg::Pick(color)))
+                }
+                _ => Unused,
+            }
+        }
+    }
+
+    impl Self {
+        fn new(size: (f32, f32), pickable: bool) -> Self {
+            Swatch {
+                core: Default::default(),
+                color: None,
+                size,
+                pickable,
+            }
+        }
+
+        fn set_color(&mut self, cx: &mut EventState, color: Option<Rgba8Srgb>) {
+            if color != self.color {
+                self.color = color;
+                cx.redraw(self);
+            }
+        }
+    }
+}
+
+#[impl_self]
+mod ColorPicker {
+    /// A colour selection widget
+    ///
+    /// The colour may be chosen via a saturation/value square and a hue bar,
+    /// an alpha slider, hexadecimal (`#RRGGBB` or `#RRGGBBAA`) entry, RGB and
+    /// HSV entry or a palette of recently chosen colours.
+    ///
+    /// Colours are in the sRGB space. Hue, saturation and value are
+    /// calculated on sRGB components.
+    ///
+    /// A colour is added to the recent-colours palette on completion of an
+    /// adjustment (e.g. release of the mouse button after dragging over the
+    /// saturation/value square).
+    ///
+    /// See also [`ColorButton`], which opens a picker in a pop-up.
+    #[widget]
+    #[layout(column![
+        row![self.sv, self.hue],
+        row!["A", self.alpha],
+        row!["R", self.red, "G", self.green, "B", self.blue],
+        row!["H", self.h, "S", self.s, "V", self.v],
+        row![self.hex, self.preview],
+        self.recent,
+    ])]
+    pub struct ColorPicker<A> {
+        core: widget_core!(),
+        state: ColorState,
+        #[widget(&self.state)]
+        sv: SatValArea,
+        #[widget(&self.state)]
+        hue: HueBar,
+        #[widget(&self.state)]
+        alpha: Slider<ColorState, u8, kas::dir::Right>,
+        #[widget(&self.state)]
+        red: SpinBox<ColorState, u8>,
+        #[widget(&self.state)]
+        green: SpinBox<ColorState, u8>,
+        #[widget(&self.state)]
+        blue: SpinBox<ColorState, u8>,
+        #[widget(&self.state)]
+        h: SpinBox<ColorState, u16>,
+        #[widget(&self.state)]
+        s: SpinBox<ColorState, u8>,
+        #[widget(&self.state)]
+        v: SpinBox<ColorState, u8>,
+        #[widget(&self.state)]
+        hex: EditBox<ParseGuard<ColorState, Hex>>,
+        #[widget(&())]
+        preview: Swatch,
+        #[widget(&())]
+        recent: Row<Vec<Swatch>>,
+        recent_colors: Vec<Rgba8Srgb>,
+        state_fn: Box<dyn Fn(&ConfigCx, &A) -> Rgba8Srgb + Send>,
+        on_change: Option<Box<dyn Fn(&mut EventCx, &A, Rgba8Srgb) + Send>>,
+    }
+
+    impl Events for Self {
+        type Data = A;
+
+        fn update(&mut self, cx: &mut ConfigCx, data: &A) {
+            let color = (self.state_fn)(cx, data);
+            if color != self.state.rgba() {
+                self.state = ColorState::from_rgba(color, &self.state);
+            }
+            self.preview.set_color(cx, Some(color));
+        }
+
+        fn handle_messages(&mut self, cx: &mut EventCx, data: &A) {
+            let Some(msg) = cx.try_pop::<ColorMsg>() else {
+                return;
+            };
+
+            let mut state = self.state;
+            let rgba = state.rgba();
+            let mut commit = true;
+            match msg {
+                ColorMsg::SatVal(s, v) => {
+                    [state.hsv[1], state.hsv[2]] = [s, v];
+                    commit = false;
+                }
+                ColorMsg::Hue(h) => {
+                    state.hsv[0] = h;
+                    commit = false;
+                }
+                ColorMsg::Alpha(a) => {
+                    state.alpha = a;
+                    commit = false;
+                }
+                ColorMsg::Red(r) => state = self.with_rgb([r, rgba.g(), rgba.b()]),
+                ColorMsg::Green(g) => state = self.with_rgb([rgba.r(), g, rgba.b()]),
+                ColorMsg::Blue(b) => state = self.with_rgb([rgba.r(), rgba.g(), b]),
+                ColorMsg::H(h) => state.hsv[0] = f32::from(h % 360),
+                ColorMsg::S(s) => state.hsv[1] = f32::from(s.min(100)) / 100.0,
+                ColorMsg::V(v) => state.hsv[2] = f32::from(v.min(100)) / 100.0,
+                ColorMsg::Hex(color) | ColorMsg::Pick(color) => {
+                    state = ColorState::from_rgba(color, &state);
+                }
+                ColorMsg::Commit => (),
+            }
+
+            if state != self.state {
+                self.state = state;
+                let color = state.rgba();
+                self.update_children(cx);
+                self.preview.set_color(cx, Some(color));
+                if let Some(ref f) = self.on_change {
+                    f(cx, data, color);
+                }
+            }
+            if commit {
+                self.push_recent(cx, self.state.rgba());
+            }
+        }
+    }
+
+    impl Self {
+        /// Construct a colour picker
+        ///
+        /// The closure `state_fn` extracts the current colour from input data.
+        pub fn new(state_fn: impl Fn(&ConfigCx, &A) -> Rgba8Srgb + Send + 'static) -> Self {
+            let spin = |range, f: fn(&ColorState) -> u8, msg: fn(u8) -> ColorMsg| {
+                SpinBox::new_msg(range, move |_, state: &ColorState| f(state), msg)
+            };
+            let percent = |x: f32| -> u8 { (x * 100.0).cast_nearest() };
+
+            ColorPicker {
+                core: Default::default(),
+                state: ColorState {
+                    hsv: [0.0, 0.0, 0.0],
+                    alpha: 255,
+                },
+                sv: SatValArea::new(),
+                hue: HueBar::new(),
+                alpha: Slider::right(0..=255, |_, state: &ColorState| state.alpha)
+                    .with_msg(ColorMsg::Alpha)
+                    .with_release(|cx, _, _| cx.push(ColorMsg::Commit)),
+                red: spin(0..=255, |state| state.rgba().r(), ColorMsg::Red),
+                green: spin(0..=255, |state| state.rgba().g(), ColorMsg::Green),
+                blue: spin(0..=255, |state| state.rgba().b(), ColorMsg::Blue),
+                h: SpinBox::new_msg(
+                    0..=359,
+                    |_, state: &ColorState| u16::conv_nearest(state.hsv[0]) % 360,
+                    ColorMsg::H,
+                ),
+                s: SpinBox::new_msg(
+                    0..=100,
+                    move |_, state: &ColorState| percent(state.hsv[1]),
+                    ColorMsg::S,
+                ),
+                v: SpinBox::new_msg(
+                    0..=100,
+                    move |_, state: &ColorState| percent(state.hsv[2]),
+                    ColorMsg::V,
+                ),
+                hex: EditBox::parser(
+                    |state: &ColorState| Hex(state.rgba()),
+                    |hex| ColorMsg::Hex(hex.0),
+                )
+                .with_width_em(5.0, 6.0),
+                preview: Swatch::new((3.0, 1.5), false),
+                recent: Row::new((0..RECENT).map(|_| Swatch::new((1.5, 1.5), true)).collect()),
+                recent_colors: vec![],
+                state_fn: Box::new(state_fn),
+                on_change: None,
+            }
+        }
+
+        /// Construct a colour picker which sends a message on change
+        ///
+        /// See [`Self::new`] and [`Self::with_msg`].
+        #[inline]
+        pub fn new_msg<M: std::fmt::Debug + 'static>(
+            state_fn: impl Fn(&ConfigCx, &A) -> Rgba8Srgb + Send + 'static,
+            msg_fn: impl Fn(Rgba8Srgb) -> M + Send + 'static,
+        ) -> Self {
+            ColorPicker::new(state_fn).with_msg(msg_fn)
+        }
+
+        /// Send the message generated by `f` on change
+        #[inline]
+        #[must_use]
+        pub fn with_msg<M>(self, f: impl Fn(Rgba8Srgb) -> M + Send + 'static) -> Self
+        where
+            M: std::fmt::Debug + 'static,
+        {
+            self.with(move |cx, _, color| cx.push(f(color)))
+        }
+
+        /// Call the handler `f` on change
+        ///
+        /// This closure is called whenever the colour is changed, including
+        /// during drag operations.
+        #[inline]
+        #[must_use]
+        pub fn with(mut self, f: impl Fn(&mut EventCx, &A, Rgba8Srgb) + Send + 'static) -> Self {
+            debug_assert!(self.on_change.is_none());
+            self.on_change = Some(Box::new(f));
+            self
+        }
+
+        /// Set the initial palette of recent colours (inline)
+        ///
+        /// At most eight colours are used; the first is the most recent.
+        #[must_use]
+        pub fn with_recent(mut self, colors: impl IntoIterator<Item = Rgba8Srgb>) -> Self {
+            self.recent_colors = colors.into_iter().take(RECENT).collect();
+            for (i, color) in self.recent_colors.iter().enumerate() {
+                self.recent[i].color = Some(*color);
+            }
+            self
+        }
+
+        /// Get the current colour
+        #[inline]
+        pub fn color(&self) -> Rgba8Srgb {
+            self.state.rgba()
+        }
+
+        /// Get the palette of recent colours (most recent first)
+        #[inline]
+        pub fn recent(&self) -> &[Rgba8Srgb] {
+            &self.recent_colors
+        }
+
+        /// Add a colour to the palette of recent colours
+        pub fn push_recent(&mut self, cx: &mut EventState, color: Rgba8Srgb) {
+            if self.recent_colors.first() == Some(&color) {
+                return;
+            }
+
+            self.recent_colors.retain(|c| *c != color);
+            self.recent_colors.insert(0, color);
+            self.recent_colors.truncate(RECENT);
+            for i in 0..RECENT {
+                let color = self.recent_colors.get(i).copied();
+                self.recent[i].set_color(cx, color);
+            }
+        }
+
+        fn with_rgb(&self, [r, g, b]: [u8; 3]) -> ColorState {
+            let color = Rgba8Srgb::rgba(r, g, b, self.state.alpha);
+            ColorState::from_rgba(color, &self.state)
+        }
+
+        fn update_children(&mut self, cx: &mut ConfigCx) {
+            let state = &self.state;
+            cx.update(self.sv.as_node(state));
+            cx.update(self.hue.as_node(state));
+            cx.update(self.alpha.as_node(state));
+            cx.update(self.red.as_node(state));
+            cx.update(self.green.as_node(state));
+            cx.update(self.blue.as_node(state));
+            cx.update(self.h.as_node(state));
+            cx.update(self.s.as_node(state));
+            cx.update(self.v.as_node(state));
+            cx.update(self.hex.as_node(state));
+        }
+    }
+}
+
+#[impl_self]
+mod ColorButton {
+    /// A colour swatch button opening a [`ColorPicker`] in a pop-up
+    ///
+    /// ### Messages
+    ///
+    /// [`Expand`] and [`Collapse`] may be used to open and close the picker.
+    #[widget]
+    #[layout(self.button)]
+    pub struct ColorButton<A> {
+        core: widget_core!(),
+        color: Rgba8Srgb,
+        #[widget(&())]
+        button: Button<Swatch>,
+        #[widget(&self.color)]
+        popup: Popup<ColorPicker<Rgba8Srgb>>,
+        state_fn: Box<dyn Fn(&ConfigCx, &A) -> Rgba8Srgb + Send>,
+        on_change: Option<Box<dyn Fn(&mut EventCx, &A, Rgba8Srgb) + Send>>,
+    }
+
+    impl Events for Self {
+        type Data = A;
+
+        fn update(&mut self, cx: &mut ConfigCx, data: &A) {
+            self.color = (self.state_fn)(cx, data);
+            self.button.inner.set_color(cx, Some(self.color));
+        }
+
+        fn handle_messages(&mut self, cx: &mut EventCx, data: &A) {
+            if let Some(PickerChanged(color)) = cx.try_pop() {
+                self.color = color;
+                self.button.inner.set_color(cx, Some(color));
+                if let Some(ref f) = self.on_change {
+                    f(cx, data, color);
+                }
+            } else if let Some(Expand) = cx.try_pop() {
+                if self.popup.open(cx, &self.color, self.id(), true) {
+                    cx.next_nav_focus(self.popup.inner.id(), false, FocusSource::Synthetic);
+                }
+            } else if let Some(Collapse) = cx.try_pop() {
+                self.popup.close(cx);
+            }
+        }
+    }
+
+    impl Self {
+        /// Construct a colour button
+        ///
+        /// The closure `state_fn` extracts the current colour from input data.
+        pub fn new(state_fn: impl Fn(&ConfigCx, &A) -> Rgba8Srgb + Send + 'static) -> Self {
+            let picker = ColorPicker::new_msg(|_, color: &Rgba8Srgb| *color, PickerChanged);
+            ColorButton {
+                core: Default::default(),
+                color: Rgba8Srgb::BLACK,
+                button: Button::new_msg(Swatch::new((2.0, 1.0), false), Expand),
+                popup: Popup::new(picker, Direction::Down),
+                state_fn: Box::new(state_fn),
+                on_change: None,
+            }
+        }
+
+        /// Construct a colour button which sends a message on change
+        ///
+        /// See [`Self::new`] and [`Self::with_msg`].
+        #[inline]
+        pub fn new_msg<M: std::fmt::Debug + 'static>(
+            state_fn: impl Fn(&ConfigCx, &A) -> Rgba8Srgb + Send + 'static,
+            msg_fn: impl Fn(Rgba8Srgb) -> M + Send + 'static,
+        ) -> Self {
+            ColorButton::new(state_fn).with_msg(msg_fn)
+        }
+
+        /// Send the message generated by `f` on change
+        #[inline]
+        #[must_use]
+        pub fn with_msg<M>(self, f: impl Fn(Rgba8Srgb) -> M + Send + 'static) -> Self
+        where
+            M: std::fmt::Debug + 'static,
+        {
+            self.with(move |cx, _, color| cx.push(f(color)))
+        }
+
+        /// Call the handler `f` on change
+        #[inline]
+        #[must_use]
+        pub fn with(mut self, f: impl Fn(&mut EventCx, &A, Rgba8Srgb) + Send + 'static) -> Self {
+            debug_assert!(self.on_change.is_none());
+            self.on_change = Some(Box::new(f));
+            self
+        }
+
+        /// Access the pop-up picker
+        #[inline]
+        pub fn picker(&self) -> &ColorPicker<Rgba8Srgb> {
+            &self.popup.inner
+        }
+
+        /// Get the current colour
+        #[inline]
+        pub fn color(&self) -> Rgba8Srgb {
+            self.color
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn approx(a: f32, b: f32) -> bool {
+        (a - b).abs() < 1e-3
+    }
+
+    #[test]
+    fn rgb_hsv_round_trip() {
+        let values = (0..=255).step_by(15);
+        for r in values.clone() {
+            for g in values.clone() {
+                for b in values.clone() {
+                    assert_eq!(hsv_to_rgb(rgb_to_hsv(r, g, b)), [r, g, b]);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn hsv_rgb_round_trip() {
+        assert_eq!(hsv_to_rgb([0.0, 1.0, 1.0]), [255, 0, 0]);
+        assert_eq!(hsv_to_rgb([120.0, 1.0, 1.0]), [0, 255, 0]);
+        assert_eq!(hsv_to_rgb([240.0, 1.0, 0.5]), [0, 0, 128]);
+        assert_eq!(hsv_to_rgb([360.0, 1.0, 1.0]), [255, 0, 0]);
+
+        for h in (0..360).step_by(30) {
+            let hsv = [f32::conv(h), 0.6, 0.8];
+            let [r, g, b] = hsv_to_rgb(hsv);
+            let [h2, s2, v2] = rgb_to_hsv(r, g, b);
+            assert!(
+                approx(h2, hsv[0]) || approx(h2, hsv[0] + 360.0),
+                "hue {h}: {h2}"
+            );
+            // 8-bit channels limit precision of saturation and value
+            assert!((s2 - hsv[1]).abs() < 0.01 && (v2 - hsv[2]).abs() < 0.01);
+        }
+    }
+
+    #[test]
+    fn grey_preserves_hue() {
+        let prev = ColorState {
+            hsv: [200.0, 0.5, 0.5],
+            alpha: 255,
+        };
+        let grey = ColorState::from_rgba(Rgba8Srgb::rgba(90, 90, 90, 255), &prev);
+        assert_eq!(grey.hsv[0], 200.0);
+        let black = ColorState::from_rgba(Rgba8Srgb::rgba(0, 0, 0, 128), &prev);
+        assert_eq!(black.hsv[..2], prev.hsv[..2]);
+        assert_eq!(black.alpha, 128);
+    }
+
+    #[test]
+    fn parse_hex() {
+        let parse = |s: &str| s.parse::<Hex>().map(|hex| hex.0).ok();
+        assert_eq!(parse("#FF8000"), Some(Rgba8Srgb::rgba(255, 128, 0, 255)));
+        assert_eq!(parse(" ff800080 "), Some(Rgba8Srgb::rgba(255, 128, 0, 128)));
+        assert_eq!(parse("#FF80"), None);
+        assert_eq!(parse("#GG0000"), None);
+
+        for color in [
+            Rgba8Srgb::rgba(18, 52, 86, 255),
+            Rgba8Srgb::rgba(171, 205, 239, 16),
+        ] {
+            assert_eq!(parse(&Hex(color).to_string()), Some(color));
+        }
+    }
+}
@@ -0,0 +1,473 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Calendar widget
+
+use super::{Date, Weekday, first_day_of_week};
+use crate::{Grid, Label, MarkButton};
+use kas::event::FocusSource;
+use kas::layout::GridCellInfo;
+use kas::prelude::*;
+use kas::theme::{MarkStyle, SelectionStyle, TextClass};
+
+/// Number of weeks shown
+const WEEKS: usize = 6;
+
+#[derive(Clone, Copy, Debug)]
+enum MonthBtn {
+    Prev,
+    Next,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct SelectDate(Date);
+
+#[impl_self]
+mod CalendarCell {
+    /// A cell of the calendar grid
+    ///
+    /// This is either a weekday heading or a day. Days outside of the shown
+    /// month are blank. Cells are not navigable: the [`Calendar`] handles
+    /// keyboard input.
+    #[widget]
+    #[layout(self.label.align(AlignHints::CENTER))]
+    struct CalendarCell {
+        core: widget_core!(),
+        info: GridCellInfo,
+        date: Option<Date>,
+        selected: bool,
+        #[widget]
+        label: Label<String>,
+    }
+
+    impl Layout for Self {
+        fn draw(&self, mut draw: DrawCx) {
+            if self.selected {
+                draw.selection(self.rect(), SelectionStyle::Highlight);
+            }
+            self.label.draw(draw.re());
+        }
+    }
+
+    impl Tile for Self {
+        fn role(&self, cx: &mut dyn RoleCx) -> Role<'_> {
+            cx.set_label(self.label.id());
+            Role::GridCell {
+                info: Some(self.info),
+                selected: self.date.map(|_| self.selected),
+            }
+        }
+    }
+
+    impl Events for Self {
+        type Data = ();
+
+        fn probe(&self, _: Coord) -> Id {
+            self.id()
+        }
+
+        fn handle_event(&mut self, cx: &mut EventCx, _: &(), event: Event) -> IsUsed {
+            if let Some(date) = self.date {
+                event.on_click(cx, self.id(), |cx| cx.push(SelectDate(date)))
+            } else {
+                Unused
+            }
+        }
+    }
+
+    impl Self {
+        fn new(col: u32, row: u32, class: TextClass) -> Self {
+            CalendarCell {
+                core: Default::default(),
+                info: GridCellInfo::new(col, row),
+                date: None,
+                selected: false,
+                label: Label::new(String::new()).with_class(class),
+            }
+        }
+    }
+}
+
+#[impl_self]
+mod Calendar {
+    /// A month calendar supporting date selection
+    ///
+    /// The calendar shows one month as a grid of days with weekday headings.
+    /// Buttons in the header (or <kbd>PageUp</kbd> / <kbd>PageDown</kbd>)
+    /// change the month shown.
+    ///
+    /// The calendar is navigable. With navigation focus, a *cursor* date is
+    /// marked and may be moved with the arrow keys (by one day or one week),
+    /// <kbd>Home</kbd> / <kbd>End</kbd> (start / end of the week) and
+    /// <kbd>PageUp</kbd> / <kbd>PageDown</kbd> (by one month). Activation
+    /// (e.g. <kbd>Enter</kbd>) selects the cursor date.
+    ///
+    /// Dates may be bounded via [`Self::with_min`] and [`Self::with_max`];
+    /// days outside of these bounds are disabled.
+    ///
+    /// By default, the first day of the week is determined from the locale
+    /// (see [`first_day_of_week`]).
+    ///
+    /// ### Messages
+    ///
+    /// [`kas::messages::SetValueText`] may be used to select a date (in
+    /// `YYYY-MM-DD` format).
+    #[widget]
+    #[layout(column![
+        row![self.prev, self.title.align(AlignHints::CENTER), self.next],
+        self.grid,
+    ])]
+    pub struct Calendar<A> {
+        core: widget_core!(),
+        #[widget(&())]
+        prev: MarkButton<MonthBtn>,
+        #[widget(&())]
+        title: Label<String>,
+        #[widget(&())]
+        next: MarkButton<MonthBtn>,
+        #[widget(&())]
+        grid: Grid<Vec<(GridCellInfo, CalendarCell)>>,
+        first_day: Weekday,
+        month: Date,
+        start: Date,
+        cursor: Date,
+        selected: Option<Date>,
+        min: Option<Date>,
+        max: Option<Date>,
+        state_fn: Box<dyn Fn(&ConfigCx, &A) -> Option<Date> + Send>,
+        on_select: Option<Box<dyn Fn(&mut EventCx, &A, Date) + Send>>,
+    }
+
+    impl Layout for Self {
+        fn draw(&self, mut draw: DrawCx) {
+            kas::MacroDefinedLayout::draw(self, draw.re());
+            if draw.ev_state().has_nav_focus(self.id_ref())
+                && let Some(cell) = self.cell(self.cursor)
+            {
+                draw.selection(cell.rect(), SelectionStyle::Frame);
+            }
+        }
+    }
+
+    impl Tile for Self {
+        fn navigable(&self) -> bool {
+            true
+        }
+
+        fn role(&self, _: &mut dyn RoleCx) -> Role<'_> {
+            Role::Grid {
+                columns: Some(7),
+                rows: Some(WEEKS + 1),
+            }
+        }
+    }
+
+    impl Events for Self {
+        type Data = A;
+
+        fn post_configure(&mut self, cx: &mut ConfigCx) {
+            self.refresh(cx);
+        }
+
+        fn update(&mut self, cx: &mut ConfigCx, data: &A) {
+            let selected = (self.state_fn)(cx, data);
+            if selected != self.selected {
+                self.selected = selected;
+                if let Some(date) = selected {
+                    self.cursor = date;
+                    self.month = date.first_of_month();
+                }
+                self.refresh(cx);
+            }
+        }
+
+        fn handle_event(&mut self, cx: &mut EventCx, data: &A, event: Event) -> IsUsed {
+            match event {
+                Event::Command(cmd, _) => {
+                    let cursor = self.cursor;
+                    let offset = cursor.weekday().days_since(self.first_day);
+                    let date = match cmd {
+                        cmd if cmd.is_activate() => {
+                            if self.in_bounds(cursor) {
+                                self.select(cx, data, cursor);
+                            }
+                            return Used;
+                        }
+                        Command::Left => cursor.checked_add_days(-1),
+                        Command::Right => cursor.checked_add_days(1),
+                        Command::Up => cursor.checked_add_days(-7),
+                        Command::Down => cursor.checked_add_days(7),
+                        Command::Home => cursor.checked_add_days(-i64::from(offset)),
+                        Command::End => cursor.checked_add_days(i64::from(6 - offset)),
+                        Command::PageUp => cursor.checked_add_months(-1),
+                        Command::PageDown => cursor.checked_add_months(1),
+                        _ => return Unused,
+                    };
+                    if let Some(date) = date {
+                        self.set_cursor(cx, date);
+                    }
+                    Used
+                }
+                Event::Scroll(delta) => {
+                    if let Some(y) = delta.as_wheel_action(cx) {
+                        self.shift_month(cx, -y);
+                        Used
+                    } else {
+                        Unused
+                    }
+                }
+                _ => Unused,
+            }
+        }
+
+        fn handle_messages(&mut self, cx: &mut EventCx, data: &A) {
+            if let Some(SelectDate(date)) = cx.try_pop() {
+                cx.request_nav_focus(self.id(), FocusSource::Pointer);
+                self.select(cx, data, date);
+            } else if let Some(btn) = cx.try_pop::<MonthBtn>() {
+                let months = match btn {
+                    MonthBtn::Prev => -1,
+                    MonthBtn::Next => 1,
+                };
+                self.shift_month(cx, months);
+            } else if let Some(kas::messages::SetValueText(text)) = cx.try_pop() {
+                match text.parse() {
+                    Ok(date) => self.select(cx, data, date),
+                    Err(err) => log::warn!("Calendar: failed to parse {text:?}: {err}"),
+                }
+            }
+        }
+    }
+
+    impl Self {
+        /// Construct a calendar
+        ///
+        /// The closure `state_fn` selects the date from input data. Initially
+        /// the month of the selected date (or of [`Date::today`]) is shown.
+        pub fn new(state_fn: impl Fn(&ConfigCx, &A) -> Option<Date> + Send + 'static) -> Self {
+            let first_day = first_day_of_week();
+            let today = Date::today();
+            let grid = Grid::new(
+                (0..7u32)
+                    .map(|col| (col, 0, TextClass::Small))
+                    .chain(
+                        (1..=u32::conv(WEEKS))
+                            .flat_map(|row| (0..7u32).map(move |col| (col, row, TextClass::Label))),
+                    )
+                    .map(|(col, row, class)| {
+                        let cell = CalendarCell::new(col, row, class);
+                        (cell.info, cell)
+                    })
+                    .collect(),
+            );
+
+            let mut calendar = Calendar {
+                core: Default::default(),
+                prev: MarkButton::new_msg(
+                    MarkStyle::Chevron(Direction::Left),
+                    "Previous month",
+                    MonthBtn::Prev,
+                ),
+                title: Label::new(String::new())
+                    .with_class(TextClass::Label)
+                    .with_heading_level(2),
+                next: MarkButton::new_msg(
+                    MarkStyle::Chevron(Direction::Right),
+                    "Next month",
+                    MonthBtn::Next,
+                ),
+                grid,
+                first_day,
+                month: today.first_of_month(),
+                start: today,
+                cursor: today,
+                selected: None,
+                min: None,
+                max: None,
+                state_fn: Box::new(state_fn),
+                on_select: None,
+            };
+            calendar.init_first_day(first_day);
+            calendar
+        }
+
+        /// Construct a calendar which sends a message on selection
+        ///
+        /// See [`Self::new`] and [`Self::with_msg`].
+        #[inline]
+        pub fn new_msg<M: std::fmt::Debug + 'static>(
+            state_fn: impl Fn(&ConfigCx, &A) -> Option<Date> + Send + 'static,
+            msg_fn: impl Fn(Date) -> M + Send + 'static,
+        ) -> Self {
+            Calendar::new(state_fn).with_msg(msg_fn)
+        }
+
+        /// Send the message generated by `f` on selection
+        #[inline]
+        #[must_use]
+        pub fn with_msg<M>(self, f: impl Fn(Date) -> M + Send + 'static) -> Self
+        where
+            M: std::fmt::Debug + 'static,
+        {
+            self.with(move |cx, _, date| cx.push(f(date)))
+        }
+
+        /// Call the handler `f` on selection
+        ///
+        /// This closure is called when a date is selected by click, activation
+        /// or [`kas::messages::SetValueText`].
+        #[inline]
+        #[must_use]
+        pub fn with(mut self, f: impl Fn(&mut EventCx, &A, Date) + Send + 'static) -> Self {
+            debug_assert!(self.on_select.is_none());
+            self.on_select = Some(Box::new(f));
+            self
+        }
+
+        /// Set the minimum selectable date (inline)
+        #[inline]
+        #[must_use]
+        pub fn with_min(mut self, min: Date) -> Self {
+            self.init_bounds(Some(min), self.max);
+            self
+        }
+
+        /// Set the maximum selectable date (inline)
+        #[inline]
+        #[must_use]
+        pub fn with_max(mut self, max: Date) -> Self {
+            self.init_bounds(self.min, Some(max));
+            self
+        }
+
+        /// Set the first day of the week (inline)
+        #[inline]
+        #[must_use]
+        pub fn with_first_day(mut self, first_day: Weekday) -> Self {
+            self.init_first_day(first_day);
+            self
+        }
+
+        /// Get the minimum selectable date, if any
+        #[inline]
+        pub fn min(&self) -> Option<Date> {
+            self.min
+        }
+
+        /// Get the maximum selectable date, if any
+        #[inline]
+        pub fn max(&self) -> Option<Date> {
+            self.max
+        }
+
+        /// Set the selectable range
+        pub fn set_bounds(&mut self, cx: &mut ConfigCx, min: Option<Date>, max: Option<Date>) {
+            self.init_bounds(min, max);
+            self.refresh(cx);
+        }
+
+        /// Get the first day of the month shown
+        #[inline]
+        pub fn month(&self) -> Date {
+            self.month
+        }
+
+        /// Get the selected date, if any
+        #[inline]
+        pub fn selected(&self) -> Option<Date> {
+            self.selected
+        }
+
+        /// Show the month containing `date` and move the cursor to `date`
+        ///
+        /// The cursor is clamped to the selectable range.
+        pub fn set_cursor(&mut self, cx: &mut ConfigCx, date: Date) {
+            let date = self.clamp(date);
+            if date != self.cursor {
+                self.cursor = date;
+                self.month = date.first_of_month();
+                self.refresh(cx);
+            }
+        }
+
+        /// Set bounds without refreshing the grid
+        pub(super) fn init_bounds(&mut self, min: Option<Date>, max: Option<Date>) {
+            self.min = min;
+            self.max = max;
+            self.cursor = self.clamp(self.cursor);
+            self.month = self.cursor.first_of_month();
+        }
+
+        /// Set the first day of the week without refreshing the grid
+        pub(super) fn init_first_day(&mut self, first_day: Weekday) {
+            self.first_day = first_day;
+            for col in 0..7 {
+                let name = first_day.nth_after(col).short_name();
+                self.grid[usize::from(col)].1.label =
+                    Label::new(name.to_string()).with_class(TextClass::Small);
+            }
+        }
+
+        fn in_bounds(&self, date: Date) -> bool {
+            self.min.is_none_or(|min| date >= min) && self.max.is_none_or(|max| date <= max)
+        }
+
+        fn clamp(&self, date: Date) -> Date {
+            let date = self.min.map(|min| date.max(min)).unwrap_or(date);
+            self.max.map(|max| date.min(max)).unwrap_or(date)
+        }
+
+        fn cell(&self, date: Date) -> Option<&CalendarCell> {
+            let index = (date.to_days() - self.start.to_days()).try_into().ok()?;
+            (index < 7 * WEEKS)
+                .then(|| &self.grid[7 + index].1)
+                .filter(|cell| cell.date == Some(date))
+        }
+
+        fn shift_month(&mut self, cx: &mut ConfigCx, months: i32) {
+            if let Some(date) = self.cursor.checked_add_months(months) {
+                self.set_cursor(cx, date);
+            }
+        }
+
+        fn select(&mut self, cx: &mut EventCx, data: &A, date: Date) {
+            let date = self.clamp(date);
+            self.selected = Some(date);
+            self.cursor = date;
+            self.month = date.first_of_month();
+            self.refresh(cx);
+            if let Some(ref f) = self.on_select {
+                f(cx, data, date);
+            }
+        }
+
+        /// Update the title and grid cells
+        fn refresh(&mut self, cx: &mut ConfigCx) {
+            let title = format!("{} {}", self.month.month_name(), self.month.year());
+            self.title.set_text(cx, title);
+
+            let offset = self.month.weekday().days_since(self.first_day);
+            self.start = self
+                .month
+                .checked_add_days(-i64::from(offset))
+                .unwrap_or(self.month);
+
+            for index in 0..7 * WEEKS {
+                let date = self.start.checked_add_days(index.cast());
+                let date = date.filter(|date| date.first_of_month() == self.month);
+                let enabled = date.is_some_and(|date| self.in_bounds(date));
+                let cell = &mut self.grid[7 + index].1;
+                cell.date = date;
+                cell.selected = date.is_some() && date == self.selected;
+                let text = date.map(|date| date.day().to_string()).unwrap_or_default();
+                cell.label.set_text(cx, text);
+                if cell.id_ref().is_valid() {
+                    cx.set_disabled(cell.id(), date.is_some() && !enabled);
+                }
+            }
+            cx.redraw();
+        }
+    }
+}
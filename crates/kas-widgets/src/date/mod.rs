@@ -0,0 +1,510 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Date and time widgets
+//!
+//! -   [`Calendar`] is a month grid supporting selection of a [`Date`]
+//! -   [`DatePicker`] is an edit box with a pop-up [`Calendar`]
+//! -   [`TimePicker`] is an edit box with a pop-up list of [`Time`] values
+//!
+//! Widgets use the small [`Date`] and [`Time`] types defined here. With the
+//! `chrono` feature, these may be converted from and to the corresponding
+//! `chrono` types.
+
+mod calendar;
+mod picker;
+
+pub use calendar::Calendar;
+pub use picker::{DatePicker, TimePicker};
+
+use kas::cast::{Cast, Conv};
+use std::fmt;
+use std::str::FromStr;
+use std::time::SystemTime;
+
+/// Error type for parsing [`Date`] and [`Time`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, thiserror::Error)]
+#[error("invalid date or time")]
+pub struct ParseError;
+
+/// A day of the week
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Weekday {
+    /// Monday (the first day of the week in ISO 8601)
+    Monday,
+    /// Tuesday
+    Tuesday,
+    /// Wednesday
+    Wednesday,
+    /// Thursday
+    Thursday,
+    /// Friday
+    Friday,
+    /// Saturday
+    Saturday,
+    /// Sunday
+    Sunday,
+}
+
+impl Weekday {
+    const ALL: [Weekday; 7] = [
+        Weekday::Monday,
+        Weekday::Tuesday,
+        Weekday::Wednesday,
+        Weekday::Thursday,
+        Weekday::Friday,
+        Weekday::Saturday,
+        Weekday::Sunday,
+    ];
+
+    /// Get the number of days since Monday (0-6)
+    #[inline]
+    pub fn days_from_monday(self) -> u8 {
+        self as u8
+    }
+
+    /// Get the weekday `n` days after `self`
+    pub fn nth_after(self, n: u8) -> Self {
+        let index = usize::from(self.days_from_monday()) + usize::from(n);
+        Weekday::ALL[index % 7]
+    }
+
+    /// Get the number of days from `start` to `self` (0-6)
+    pub fn days_since(self, start: Weekday) -> u8 {
+        (self.days_from_monday() + 7 - start.days_from_monday()) % 7
+    }
+
+    /// Get a two-letter abbreviation (English)
+    pub fn short_name(self) -> &'static str {
+        match self {
+            Weekday::Monday => "Mo",
+            Weekday::Tuesday => "Tu",
+            Weekday::Wednesday => "We",
+            Weekday::Thursday => "Th",
+            Weekday::Friday => "Fr",
+            Weekday::Saturday => "Sa",
+            Weekday::Sunday => "Su",
+        }
+    }
+}
+
+/// Get the first day of the week for the current locale
+///
+/// The region is read from the first non-empty environment variable of
+/// `LC_ALL`, `LC_TIME` and `LANG` (e.g. `en_US.UTF-8` has region `US`).
+/// Regions are mapped to a first day according to CLDR data; where no region
+/// is available, the result is [`Weekday::Monday`] (ISO 8601).
+pub fn first_day_of_week() -> Weekday {
+    let locale = ["LC_ALL", "LC_TIME", "LANG"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.is_empty())
+        .unwrap_or_default();
+    let region = locale
+        .split(['.', '@'])
+        .next()
+        .and_then(|lang| lang.split_once(['_', '-']))
+        .map(|(_, region)| region)
+        .unwrap_or("");
+    first_day_of_week_for_region(region)
+}
+
+fn first_day_of_week_for_region(region: &str) -> Weekday {
+    const SUNDAY: &[&str] = &[
+        "AG", "AS", "AU", "BD", "BR", "BS", "BT", "BW", "BZ", "CA", "CN", "CO", "DM", "DO", "ET",
+        "GT", "GU", "HK", "HN", "ID", "IL", "IN", "JM", "JP", "KE", "KH", "KR", "LA", "MH", "MM",
+        "MO", "MT", "MX", "MZ", "NI", "NP", "PA", "PE", "PH", "PK", "PR", "PT", "PY", "SA", "SG",
+        "SV", "TH", "TT", "TW", "UM", "US", "VE", "VI", "WS", "YE", "ZA", "ZW",
+    ];
+    const SATURDAY: &[&str] = &[
+        "AE", "AF", "BH", "DJ", "DZ", "EG", "IQ", "IR", "JO", "KW", "LY", "OM", "QA", "SD", "SY",
+    ];
+
+    let region = region.to_ascii_uppercase();
+    if SUNDAY.contains(&region.as_str()) {
+        Weekday::Sunday
+    } else if SATURDAY.contains(&region.as_str()) {
+        Weekday::Saturday
+    } else {
+        Weekday::Monday
+    }
+}
+
+/// A date in the proleptic Gregorian calendar
+///
+/// This is a minimal date type supporting the needs of [`Calendar`] and
+/// [`DatePicker`]. Dates are ordered chronologically.
+///
+/// The text representation is ISO 8601 (`YYYY-MM-DD`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    year: i32,
+    month: u8,
+    day: u8,
+}
+
+impl Date {
+    /// Construct from year, month (1-12) and day (1-31)
+    ///
+    /// Returns `None` if the date is invalid.
+    pub fn new(year: i32, month: u8, day: u8) -> Option<Self> {
+        (day >= 1 && day <= Self::days_in_month(year, month)).then_some(Date { year, month, day })
+    }
+
+    /// Get the current date (UTC)
+    pub fn today() -> Self {
+        let secs = match SystemTime::now().duration_since(SystemTime::UNIX_EPOCH) {
+            Ok(d) => d.as_secs().cast(),
+            Err(e) => -i64::conv(e.duration().as_secs()),
+        };
+        Self::from_days(secs.div_euclid(86400)).unwrap_or(Date {
+            year: 1970,
+            month: 1,
+            day: 1,
+        })
+    }
+
+    /// True if `year` is a leap year
+    pub fn is_leap_year(year: i32) -> bool {
+        year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+    }
+
+    /// Get the number of days in the given `month` (1-12)
+    ///
+    /// Returns 0 if `month` is invalid.
+    pub fn days_in_month(year: i32, month: u8) -> u8 {
+        match month {
+            1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+            4 | 6 | 9 | 11 => 30,
+            2 if Self::is_leap_year(year) => 29,
+            2 => 28,
+            _ => 0,
+        }
+    }
+
+    /// Get the year
+    #[inline]
+    pub fn year(self) -> i32 {
+        self.year
+    }
+
+    /// Get the month (1-12)
+    #[inline]
+    pub fn month(self) -> u8 {
+        self.month
+    }
+
+    /// Get the day of the month (1-31)
+    #[inline]
+    pub fn day(self) -> u8 {
+        self.day
+    }
+
+    /// Get the English name of the month
+    pub fn month_name(self) -> &'static str {
+        const NAMES: [&str; 12] = [
+            "January",
+            "February",
+            "March",
+            "April",
+            "May",
+            "June",
+            "July",
+            "August",
+            "September",
+            "October",
+            "November",
+            "December",
+        ];
+        NAMES[usize::from(self.month - 1)]
+    }
+
+    /// Get the day of the week
+    pub fn weekday(self) -> Weekday {
+        // 1970-01-01 was a Thursday
+        Weekday::ALL[usize::conv((self.to_days() + 3).rem_euclid(7))]
+    }
+
+    /// Get the first day of the month
+    #[inline]
+    pub fn first_of_month(self) -> Self {
+        Date { day: 1, ..self }
+    }
+
+    /// Add `days` (which may be negative)
+    ///
+    /// Returns `None` on overflow.
+    pub fn checked_add_days(self, days: i64) -> Option<Self> {
+        Self::from_days(self.to_days().checked_add(days)?)
+    }
+
+    /// Add `months` (which may be negative)
+    ///
+    /// The day is clamped to the length of the resulting month.
+    /// Returns `None` on overflow.
+    pub fn checked_add_months(self, months: i32) -> Option<Self> {
+        let index = i64::from(self.year) * 12 + i64::from(self.month - 1) + i64::from(months);
+        let year = i32::try_from(index.div_euclid(12)).ok()?;
+        let month = u8::conv(index.rem_euclid(12)) + 1;
+        let day = self.day.min(Self::days_in_month(year, month));
+        Date::new(year, month, day)
+    }
+
+    /// Get the number of days since 1970-01-01
+    ///
+    /// This uses Howard Hinnant's `days_from_civil` algorithm.
+    fn to_days(self) -> i64 {
+        let m = i64::from(self.month);
+        let y = i64::from(self.year) - i64::from(m <= 2);
+        let era = y.div_euclid(400);
+        let yoe = y - era * 400;
+        let mp = if m > 2 { m - 3 } else { m + 9 };
+        let doy = (153 * mp + 2) / 5 + i64::from(self.day) - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+        era * 146097 + doe - 719468
+    }
+
+    /// Construct from the number of days since 1970-01-01
+    ///
+    /// This uses Howard Hinnant's `civil_from_days` algorithm.
+    fn from_days(days: i64) -> Option<Self> {
+        let z = days.checked_add(719468)?;
+        let era = z.div_euclid(146097);
+        let doe = z - era * 146097;
+        let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = u8::conv(doy - (153 * mp + 2) / 5 + 1);
+        let month = u8::conv(if mp < 10 { mp + 3 } else { mp - 9 });
+        let year = i32::try_from(yoe + era * 400 + i64::from(month <= 2)).ok()?;
+        Some(Date { year, month, day })
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl FromStr for Date {
+    type Err = ParseError;
+
+    /// Parse from ISO 8601 format (`YYYY-MM-DD`)
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        let (rest, day) = s.trim().rsplit_once('-').ok_or(ParseError)?;
+        let (year, month) = rest.rsplit_once('-').ok_or(ParseError)?;
+        let year = year.parse().map_err(|_| ParseError)?;
+        let month = month.parse().map_err(|_| ParseError)?;
+        let day = day.parse().map_err(|_| ParseError)?;
+        Date::new(year, month, day).ok_or(ParseError)
+    }
+}
+
+/// A time of day, with minute precision
+///
+/// The text representation is `HH:MM` (24-hour clock).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Time {
+    hour: u8,
+    minute: u8,
+}
+
+impl Time {
+    /// Midnight (00:00)
+    pub const MIDNIGHT: Time = Time { hour: 0, minute: 0 };
+
+    /// The last minute of the day (23:59)
+    pub const LAST: Time = Time {
+        hour: 23,
+        minute: 59,
+    };
+
+    /// Construct from hour (0-23) and minute (0-59)
+    ///
+    /// Returns `None` if the time is invalid.
+    pub fn new(hour: u8, minute: u8) -> Option<Self> {
+        (hour < 24 && minute < 60).then_some(Time { hour, minute })
+    }
+
+    /// Construct from the number of minutes since midnight
+    ///
+    /// Returns `None` if `minutes` is not less than one day.
+    pub fn from_minutes(minutes: u16) -> Option<Self> {
+        Time::new((minutes / 60).try_into().ok()?, u8::conv(minutes % 60))
+    }
+
+    /// Get the hour (0-23)
+    #[inline]
+    pub fn hour(self) -> u8 {
+        self.hour
+    }
+
+    /// Get the minute (0-59)
+    #[inline]
+    pub fn minute(self) -> u8 {
+        self.minute
+    }
+
+    /// Get the number of minutes since midnight
+    #[inline]
+    pub fn minutes(self) -> u16 {
+        u16::from(self.hour) * 60 + u16::from(self.minute)
+    }
+}
+
+impl fmt::Display for Time {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02}:{:02}", self.hour, self.minute)
+    }
+}
+
+impl FromStr for Time {
+    type Err = ParseError;
+
+    /// Parse from `H:MM` or `HH:MM` format
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        let (hour, minute) = s.trim().split_once(':').ok_or(ParseError)?;
+        if minute.len() != 2 {
+            return Err(ParseError);
+        }
+        let hour = hour.parse().map_err(|_| ParseError)?;
+        let minute = minute.parse().map_err(|_| ParseError)?;
+        Time::new(hour, minute).ok_or(ParseError)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn date(year: i32, month: u8, day: u8) -> Date {
+        Date::new(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn leap_years() {
+        assert!(Date::is_leap_year(2024));
+        assert!(Date::is_leap_year(2000));
+        assert!(!Date::is_leap_year(1900));
+        assert!(!Date::is_leap_year(2023));
+        assert!(Date::is_leap_year(-4));
+    }
+
+    #[test]
+    fn days_in_month() {
+        assert_eq!(Date::days_in_month(2023, 1), 31);
+        assert_eq!(Date::days_in_month(2023, 2), 28);
+        assert_eq!(Date::days_in_month(2024, 2), 29);
+        assert_eq!(Date::days_in_month(1900, 2), 28);
+        assert_eq!(Date::days_in_month(2023, 4), 30);
+        assert_eq!(Date::days_in_month(2023, 12), 31);
+        assert_eq!(Date::days_in_month(2023, 0), 0);
+        assert_eq!(Date::days_in_month(2023, 13), 0);
+
+        assert!(Date::new(2024, 2, 29).is_some());
+        assert!(Date::new(2023, 2, 29).is_none());
+        assert!(Date::new(2023, 4, 31).is_none());
+        assert!(Date::new(2023, 1, 0).is_none());
+    }
+
+    #[test]
+    fn days_round_trip() {
+        assert_eq!(date(1970, 1, 1).to_days(), 0);
+        assert_eq!(date(2000, 3, 1).to_days(), 11017);
+        assert_eq!(date(1969, 12, 31).to_days(), -1);
+        for days in (-800_000..800_000).step_by(997) {
+            let d = Date::from_days(days).unwrap();
+            assert_eq!(d.to_days(), days);
+        }
+    }
+
+    #[test]
+    fn weekday() {
+        assert_eq!(date(1970, 1, 1).weekday(), Weekday::Thursday);
+        assert_eq!(date(2000, 1, 1).weekday(), Weekday::Saturday);
+        assert_eq!(date(2024, 2, 29).weekday(), Weekday::Thursday);
+        assert_eq!(date(1969, 12, 28).weekday(), Weekday::Sunday);
+
+        assert_eq!(Weekday::Saturday.nth_after(2), Weekday::Monday);
+        assert_eq!(Weekday::Monday.days_since(Weekday::Sunday), 1);
+        assert_eq!(Weekday::Sunday.days_since(Weekday::Monday), 6);
+        assert_eq!(Weekday::Saturday.days_since(Weekday::Saturday), 0);
+    }
+
+    #[test]
+    fn week_start() {
+        assert_eq!(first_day_of_week_for_region("US"), Weekday::Sunday);
+        assert_eq!(first_day_of_week_for_region("us"), Weekday::Sunday);
+        assert_eq!(first_day_of_week_for_region("GB"), Weekday::Monday);
+        assert_eq!(first_day_of_week_for_region("EG"), Weekday::Saturday);
+        assert_eq!(first_day_of_week_for_region(""), Weekday::Monday);
+    }
+
+    #[test]
+    fn add() {
+        let add_days = |d: Date, n| d.checked_add_days(n).unwrap();
+        assert_eq!(add_days(date(2024, 2, 28), 1), date(2024, 2, 29));
+        assert_eq!(add_days(date(2023, 12, 31), 1), date(2024, 1, 1));
+        assert_eq!(add_days(date(2024, 3, 1), -1), date(2024, 2, 29));
+
+        let add_months = |d: Date, n| d.checked_add_months(n).unwrap();
+        assert_eq!(add_months(date(2024, 1, 31), 1), date(2024, 2, 29));
+        assert_eq!(add_months(date(2023, 1, 31), 1), date(2023, 2, 28));
+        assert_eq!(add_months(date(2024, 1, 15), -1), date(2023, 12, 15));
+        assert_eq!(add_months(date(2024, 5, 31), 13), date(2025, 6, 30));
+    }
+
+    #[test]
+    fn parse_and_display() {
+        assert_eq!("2024-02-29".parse::<Date>(), Ok(date(2024, 2, 29)));
+        assert_eq!("2023-02-29".parse::<Date>(), Err(ParseError));
+        assert_eq!(date(987, 6, 5).to_string(), "0987-06-05");
+        assert_eq!("9:05".parse::<Time>(), Ok(Time::new(9, 5).unwrap()));
+        assert_eq!("9:5".parse::<Time>(), Err(ParseError));
+        assert_eq!(Time::new(23, 59).unwrap().to_string(), "23:59");
+    }
+}
+
+#[cfg(feature = "chrono")]
+mod chrono_impls {
+    use super::{Date, Time};
+    use chrono::{Datelike, NaiveDate, NaiveTime, Timelike};
+    use kas::cast::Conv;
+
+    impl From<NaiveDate> for Date {
+        fn from(date: NaiveDate) -> Self {
+            Date {
+                year: date.year(),
+                month: u8::conv(date.month()),
+                day: u8::conv(date.day()),
+            }
+        }
+    }
+
+    impl Date {
+        /// Convert to a [`NaiveDate`]
+        ///
+        /// Returns `None` if out of range of [`NaiveDate`].
+        pub fn to_naive_date(self) -> Option<NaiveDate> {
+            NaiveDate::from_ymd_opt(self.year, self.month.into(), self.day.into())
+        }
+    }
+
+    impl From<NaiveTime> for Time {
+        fn from(time: NaiveTime) -> Self {
+            Time {
+                hour: u8::conv(time.hour()),
+                minute: u8::conv(time.minute()),
+            }
+        }
+    }
+
+    impl From<Time> for NaiveTime {
+        fn from(time: Time) -> Self {
+            NaiveTime::from_hms_opt(time.hour.into(), time.minute.into(), 0).unwrap()
+        }
+    }
+}
@@ -0,0 +1,538 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Date and time picker widgets
+
+use super::{Calendar, Date, Time, Weekday};
+use crate::edit::{EditBoxCore, EditGuard, Editor};
+use crate::menu::MenuEntry;
+use crate::{ClipRegion, Column, MarkButton, ScrollRegion};
+use kas::event::FocusSource;
+use kas::messages::{Collapse, Expand, SetValueText};
+use kas::prelude::*;
+use kas::theme::{Background, FrameStyle, MarkStyle};
+use kas::window::Popup;
+use std::fmt::Debug;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+#[derive(Debug)]
+struct ValueMsg<T>(Option<T>);
+
+#[derive(Clone, Copy, Debug)]
+struct PickDate(Date);
+
+/// Guard parsing a value of type `T`
+///
+/// An empty field is a valid input representing no value.
+#[autoimpl(Debug ignore self.state_fn where T: trait)]
+struct PickerGuard<A, T> {
+    value: Option<T>,
+    parsed: Option<Option<T>>,
+    min: Option<T>,
+    max: Option<T>,
+    state_fn: Box<dyn Fn(&ConfigCx, &A) -> Option<T> + Send>,
+}
+
+impl<A, T: Copy + Ord> PickerGuard<A, T> {
+    fn new(state_fn: Box<dyn Fn(&ConfigCx, &A) -> Option<T> + Send>) -> Self {
+        PickerGuard {
+            value: None,
+            parsed: None,
+            min: None,
+            max: None,
+            state_fn,
+        }
+    }
+
+    fn in_bounds(&self, value: T) -> bool {
+        self.min.is_none_or(|min| value >= min) && self.max.is_none_or(|max| value <= max)
+    }
+}
+
+impl<A, T> EditGuard for PickerGuard<A, T>
+where
+    T: Copy + Ord + Debug + FromStr + ToString + 'static,
+{
+    type Data = A;
+
+    fn update(&mut self, edit: &mut Editor, cx: &mut ConfigCx, data: &A) {
+        self.value = (self.state_fn)(cx, data);
+        let text = self
+            .value
+            .map(|value| value.to_string())
+            .unwrap_or_default();
+        edit.set_string(cx, text);
+    }
+
+    fn focus_lost(&mut self, edit: &mut Editor, cx: &mut EventCx, data: &A) {
+        if let Some(value) = self.parsed.take() {
+            self.value = value;
+            cx.push(ValueMsg(value));
+        } else {
+            self.update(edit, cx, data);
+        }
+    }
+
+    fn edit(&mut self, edit: &mut Editor, cx: &mut EventCx, _: &A) {
+        let text = edit.as_str().trim();
+        let error = if text.is_empty() {
+            self.parsed = Some(None);
+            None
+        } else if let Ok(value) = text.parse::<T>() {
+            if self.in_bounds(value) {
+                self.parsed = Some(Some(value));
+                None
+            } else {
+                self.parsed = None;
+                Some("out of range")
+            }
+        } else {
+            self.parsed = None;
+            Some("parse failure")
+        };
+        if let Some(error) = error {
+            edit.set_error(cx, Some(error.into()));
+        }
+    }
+}
+
+#[impl_self]
+mod DatePicker {
+    /// A date entry field with a pop-up calendar
+    ///
+    /// Dates may be typed in `YYYY-MM-DD` format or selected from a pop-up
+    /// [`Calendar`], opened via the button or <kbd>Down</kbd> key. An empty
+    /// field represents no date.
+    ///
+    /// ### Messages
+    ///
+    /// [`SetValueText`] may be used to set the input as a text value.
+    ///
+    /// [`Expand`] and [`Collapse`] may be used to open and close the calendar.
+    #[widget]
+    #[layout(
+        frame!(row![self.edit, self.button])
+            .with_style(FrameStyle::EditBox)
+            .with_stretch(None, Stretch::Filler)
+    )]
+    pub struct DatePicker<A> {
+        core: widget_core!(),
+        #[widget]
+        edit: EditBoxCore<PickerGuard<A, Date>>,
+        #[widget(&())]
+        button: MarkButton<Expand>,
+        #[widget(&self.edit.guard.value)]
+        popup: Popup<Calendar<Option<Date>>>,
+        on_change: Option<Box<dyn Fn(&mut EventCx, &A, Option<Date>) + Send>>,
+    }
+
+    impl Self {
+        /// Construct a date picker
+        ///
+        /// The closure `state_fn` extracts the current date from input data.
+        #[inline]
+        pub fn new(state_fn: impl Fn(&ConfigCx, &A) -> Option<Date> + Send + 'static) -> Self {
+            let calendar = Calendar::new_msg(|_, date: &Option<Date>| *date, PickDate);
+            DatePicker {
+                core: Default::default(),
+                edit: EditBoxCore::new(PickerGuard::new(Box::new(state_fn)))
+                    .with_width_em(6.0, 8.0),
+                button: MarkButton::new_msg(
+                    MarkStyle::Chevron(Direction::Down),
+                    "Choose date",
+                    Expand,
+                ),
+                popup: Popup::new(calendar, Direction::Down),
+                on_change: None,
+            }
+        }
+
+        /// Construct a date picker which sends a message on change
+        ///
+        /// See [`Self::new`] and [`Self::with_msg`].
+        #[inline]
+        pub fn new_msg<M: Debug + 'static>(
+            state_fn: impl Fn(&ConfigCx, &A) -> Option<Date> + Send + 'static,
+            msg_fn: impl Fn(Option<Date>) -> M + Send + 'static,
+        ) -> Self {
+            DatePicker::new(state_fn).with_msg(msg_fn)
+        }
+
+        /// Send the message generated by `f` on change
+        #[inline]
+        #[must_use]
+        pub fn with_msg<M>(self, f: impl Fn(Option<Date>) -> M + Send + 'static) -> Self
+        where
+            M: Debug + 'static,
+        {
+            self.with(move |cx, _, date| cx.push(f(date)))
+        }
+
+        /// Call the handler `f` on change
+        ///
+        /// This closure is called when a date is selected from the calendar
+        /// or when a valid (or empty) input is entered in the edit box.
+        #[inline]
+        #[must_use]
+        pub fn with(mut self, f: impl Fn(&mut EventCx, &A, Option<Date>) + Send + 'static) -> Self {
+            debug_assert!(self.on_change.is_none());
+            self.on_change = Some(Box::new(f));
+            self
+        }
+
+        /// Set the minimum date (inline)
+        #[inline]
+        #[must_use]
+        pub fn with_min(mut self, min: Date) -> Self {
+            self.edit.guard.min = Some(min);
+            self.popup.inner.init_bounds(Some(min), self.edit.guard.max);
+            self
+        }
+
+        /// Set the maximum date (inline)
+        #[inline]
+        #[must_use]
+        pub fn with_max(mut self, max: Date) -> Self {
+            self.edit.guard.max = Some(max);
+            self.popup.inner.init_bounds(self.edit.guard.min, Some(max));
+            self
+        }
+
+        /// Set the first day of the week (inline)
+        ///
+        /// By default this is determined from the locale.
+        #[inline]
+        #[must_use]
+        pub fn with_first_day(mut self, first_day: Weekday) -> Self {
+            self.popup.inner.init_first_day(first_day);
+            self
+        }
+
+        /// Get the current date, if any
+        #[inline]
+        pub fn date(&self) -> Option<Date> {
+            self.edit.guard.value
+        }
+    }
+
+    impl Layout for Self {
+        fn draw(&self, mut draw: DrawCx) {
+            draw_edit_frame(&mut draw, self.rect(), &self.edit);
+            self.edit.draw(draw.re());
+            self.button.draw(draw.re());
+        }
+    }
+
+    impl Events for Self {
+        type Data = A;
+
+        fn probe(&self, coord: Coord) -> Id {
+            self.button
+                .try_probe(coord)
+                .unwrap_or_else(|| self.edit.id())
+        }
+
+        fn handle_event(&mut self, cx: &mut EventCx, _: &A, event: Event) -> IsUsed {
+            match event {
+                Event::Command(Command::Down, _) if !self.popup.is_open() => {
+                    self.open_popup(cx, FocusSource::Key);
+                    Used
+                }
+                Event::Command(Command::Escape, _) if self.popup.is_open() => {
+                    self.popup.close(cx);
+                    Used
+                }
+                _ => Unused,
+            }
+        }
+
+        fn handle_messages(&mut self, cx: &mut EventCx, data: &A) {
+            let new_value = if let Some(ValueMsg(value)) = cx.try_pop() {
+                cx.update(self.popup.as_node(&self.edit.guard.value));
+                Some(value)
+            } else if let Some(PickDate(date)) = cx.try_pop() {
+                self.popup.close(cx);
+                self.set_value(cx, data, Some(date));
+                Some(Some(date))
+            } else if let Some(SetValueText(string)) = cx.try_pop() {
+                self.edit
+                    .edit(cx, data, |edit, cx| edit.set_string(cx, string));
+                let value = self.edit.guard.parsed.take();
+                if let Some(value) = value {
+                    self.edit.guard.value = value;
+                    cx.update(self.popup.as_node(&self.edit.guard.value));
+                }
+                value
+            } else if let Some(Expand) = cx.try_pop() {
+                self.open_popup(cx, FocusSource::Synthetic);
+                None
+            } else if let Some(Collapse) = cx.try_pop() {
+                self.popup.close(cx);
+                None
+            } else {
+                None
+            };
+
+            if let Some(value) = new_value
+                && let Some(ref f) = self.on_change
+            {
+                f(cx, data, value);
+            }
+        }
+    }
+
+    impl Self {
+        fn open_popup(&mut self, cx: &mut EventCx, source: FocusSource) {
+            if self.popup.open(cx, &self.edit.guard.value, self.id(), true) {
+                cx.next_nav_focus(self.popup.inner.id(), false, source);
+            }
+        }
+
+        fn set_value(&mut self, cx: &mut EventCx, data: &A, value: Option<Date>) {
+            let text = value.map(|date| date.to_string()).unwrap_or_default();
+            self.edit
+                .edit(cx, data, |edit, cx| edit.set_string(cx, text));
+            self.edit.guard.parsed = None;
+            self.edit.guard.value = value;
+            cx.update(self.popup.as_node(&self.edit.guard.value));
+        }
+    }
+}
+
+#[impl_self]
+mod TimePicker {
+    /// A time entry field with a pop-up list of times
+    ///
+    /// Times may be typed in `HH:MM` format or selected from a pop-up list,
+    /// opened via the button or <kbd>Down</kbd> key. The list contains times
+    /// at intervals of [`Self::with_step`] minutes (default: 30). An empty
+    /// field represents no time.
+    ///
+    /// ### Messages
+    ///
+    /// [`SetValueText`] may be used to set the input as a text value.
+    ///
+    /// [`Expand`] and [`Collapse`] may be used to open and close the list.
+    #[widget]
+    #[layout(
+        frame!(row![self.edit, self.button])
+            .with_style(FrameStyle::EditBox)
+            .with_stretch(None, Stretch::Filler)
+    )]
+    pub struct TimePicker<A> {
+        core: widget_core!(),
+        #[widget]
+        edit: EditBoxCore<PickerGuard<A, Time>>,
+        #[widget(&())]
+        button: MarkButton<Expand>,
+        #[widget(&())]
+        popup: Popup<ScrollRegion<ClipRegion<Column<Vec<MenuEntry<Time>>>>>>,
+        step: u16,
+        on_change: Option<Box<dyn Fn(&mut EventCx, &A, Option<Time>) + Send>>,
+    }
+
+    impl Self {
+        /// Construct a time picker
+        ///
+        /// The closure `state_fn` extracts the current time from input data.
+        #[inline]
+        pub fn new(state_fn: impl Fn(&ConfigCx, &A) -> Option<Time> + Send + 'static) -> Self {
+            let list = ScrollRegion::new_clip(Column::new(vec![]));
+            let mut picker = TimePicker {
+                core: Default::default(),
+                edit: EditBoxCore::new(PickerGuard::new(Box::new(state_fn)))
+                    .with_width_em(3.0, 5.0),
+                button: MarkButton::new_msg(
+                    MarkStyle::Chevron(Direction::Down),
+                    "Choose time",
+                    Expand,
+                ),
+                popup: Popup::new(list, Direction::Down),
+                step: 30,
+                on_change: None,
+            };
+            picker.init_entries();
+            picker
+        }
+
+        /// Construct a time picker which sends a message on change
+        ///
+        /// See [`Self::new`] and [`Self::with_msg`].
+        #[inline]
+        pub fn new_msg<M: Debug + 'static>(
+            state_fn: impl Fn(&ConfigCx, &A) -> Option<Time> + Send + 'static,
+            msg_fn: impl Fn(Option<Time>) -> M + Send + 'static,
+        ) -> Self {
+            TimePicker::new(state_fn).with_msg(msg_fn)
+        }
+
+        /// Send the message generated by `f` on change
+        #[inline]
+        #[must_use]
+        pub fn with_msg<M>(self, f: impl Fn(Option<Time>) -> M + Send + 'static) -> Self
+        where
+            M: Debug + 'static,
+        {
+            self.with(move |cx, _, time| cx.push(f(time)))
+        }
+
+        /// Call the handler `f` on change
+        ///
+        /// This closure is called when a time is selected from the list or
+        /// when a valid (or empty) input is entered in the edit box.
+        #[inline]
+        #[must_use]
+        pub fn with(mut self, f: impl Fn(&mut EventCx, &A, Option<Time>) + Send + 'static) -> Self {
+            debug_assert!(self.on_change.is_none());
+            self.on_change = Some(Box::new(f));
+            self
+        }
+
+        /// Set the interval between listed times in minutes (inline)
+        ///
+        /// Panics if `minutes` is zero.
+        #[must_use]
+        pub fn with_step(mut self, minutes: u16) -> Self {
+            assert!(minutes > 0);
+            self.step = minutes;
+            self.init_entries();
+            self
+        }
+
+        /// Set the range of valid times (inline)
+        #[must_use]
+        pub fn with_range(mut self, range: RangeInclusive<Time>) -> Self {
+            let (min, max) = range.into_inner();
+            self.edit.guard.min = Some(min);
+            self.edit.guard.max = Some(max);
+            self.init_entries();
+            self
+        }
+
+        /// Get the current time, if any
+        #[inline]
+        pub fn time(&self) -> Option<Time> {
+            self.edit.guard.value
+        }
+
+        fn entries(&self) -> &Column<Vec<MenuEntry<Time>>> {
+            self.popup.inner.inner().inner()
+        }
+
+        fn init_entries(&mut self) {
+            let min = self.edit.guard.min.unwrap_or(Time::MIDNIGHT);
+            let max = self.edit.guard.max.unwrap_or(Time::LAST);
+            let entries = (min.minutes()..=max.minutes())
+                .step_by(usize::from(self.step))
+                .filter_map(Time::from_minutes)
+                .map(|time| MenuEntry::new_msg(time.to_string(), time))
+                .collect();
+            *self.popup.inner.inner_mut().inner_mut() = Column::new(entries);
+        }
+    }
+
+    impl Layout for Self {
+        fn draw(&self, mut draw: DrawCx) {
+            draw_edit_frame(&mut draw, self.rect(), &self.edit);
+            self.edit.draw(draw.re());
+            self.button.draw(draw.re());
+        }
+    }
+
+    impl Events for Self {
+        type Data = A;
+
+        fn probe(&self, coord: Coord) -> Id {
+            self.button
+                .try_probe(coord)
+                .unwrap_or_else(|| self.edit.id())
+        }
+
+        fn handle_event(&mut self, cx: &mut EventCx, _: &A, event: Event) -> IsUsed {
+            match event {
+                Event::Command(Command::Down, _) if !self.popup.is_open() => {
+                    self.open_popup(cx, FocusSource::Key);
+                    Used
+                }
+                Event::Command(Command::Escape, _) if self.popup.is_open() => {
+                    self.popup.close(cx);
+                    Used
+                }
+                _ => Unused,
+            }
+        }
+
+        fn handle_messages(&mut self, cx: &mut EventCx, data: &A) {
+            let new_value = if let Some(ValueMsg(value)) = cx.try_pop() {
+                Some(value)
+            } else if let Some(time) = cx.try_pop::<Time>() {
+                self.popup.close(cx);
+                self.set_value(cx, data, Some(time));
+                Some(Some(time))
+            } else if let Some(SetValueText(string)) = cx.try_pop() {
+                self.edit
+                    .edit(cx, data, |edit, cx| edit.set_string(cx, string));
+                let value = self.edit.guard.parsed.take();
+                if let Some(value) = value {
+                    self.edit.guard.value = value;
+                }
+                value
+            } else if let Some(Expand) = cx.try_pop() {
+                self.open_popup(cx, FocusSource::Synthetic);
+                None
+            } else if let Some(Collapse) = cx.try_pop() {
+                self.popup.close(cx);
+                None
+            } else {
+                None
+            };
+
+            if let Some(value) = new_value
+                && let Some(ref f) = self.on_change
+            {
+                f(cx, data, value);
+            }
+        }
+    }
+
+    impl Self {
+        fn open_popup(&mut self, cx: &mut EventCx, source: FocusSource) {
+            if self.popup.open(cx, &(), self.id(), true) {
+                // Focus the first entry not before the current value
+                let min = self.edit.guard.min.unwrap_or(Time::MIDNIGHT).minutes();
+                let index = match self.edit.guard.value {
+                    Some(value) => value.minutes().saturating_sub(min).div_ceil(self.step),
+                    None => 0,
+                };
+                let entries = self.entries();
+                let index = usize::from(index).min(entries.len().saturating_sub(1));
+                if let Some(w) = entries.get_child(index) {
+                    cx.next_nav_focus(w.id(), false, source);
+                }
+            }
+        }
+
+        fn set_value(&mut self, cx: &mut EventCx, data: &A, value: Option<Time>) {
+            let text = value.map(|time| time.to_string()).unwrap_or_default();
+            self.edit
+                .edit(cx, data, |edit, cx| edit.set_string(cx, text));
+            self.edit.guard.parsed = None;
+            self.edit.guard.value = value;
+        }
+    }
+}
+
+/// Draw the edit-box frame of a picker, with error state of `edit`
+fn draw_edit_frame<G: EditGuard>(draw: &mut DrawCx, rect: Rect, edit: &EditBoxCore<G>) {
+    let mut draw_edit = draw.re();
+    draw_edit.set_id(edit.id());
+    let bg = if edit.has_error() {
+        Background::Error
+    } else {
+        Background::Default
+    };
+    draw_edit.frame(rect, FrameStyle::EditBox, bg);
+}
@@ -16,6 +16,7 @@
 use crate::adapt::AdaptWidgetAny;
 use crate::edit::Editor;
 use crate::{AccessLabel, Button, EditBox, Filler, ScrollLabel};
+use kas::Relation;
 use kas::prelude::*;
 use kas::runner::AppData;
 use kas::text::format::FormattableText;
@@ -59,6 +60,13 @@ mod MessageBox {
         }
     }
 
+    impl Tile for Self {
+        fn role(&self, cx: &mut dyn RoleCx) -> Role<'_> {
+            cx.add_relation(Relation::DescribedBy, self.label.id());
+            Role::Dialog
+        }
+    }
+
     impl Events for Self {
         type Data = ();
 
@@ -145,6 +153,14 @@ mod AlertError {
         }
     }
 
+    impl Tile for Self {
+        fn role(&self, cx: &mut dyn RoleCx) -> Role<'_> {
+            cx.add_relation(Relation::DescribedBy, self.label.id());
+            cx.add_relation(Relation::DescribedBy, self.details.id());
+            Role::AlertDialog
+        }
+    }
+
     impl Events for Self {
         type Data = ();
 
@@ -234,6 +250,13 @@ mod AlertUnsaved {
         }
     }
 
+    impl Tile for Self {
+        fn role(&self, cx: &mut dyn RoleCx) -> Role<'_> {
+            cx.add_relation(Relation::DescribedBy, self.label.id());
+            Role::AlertDialog
+        }
+    }
+
     impl Events for Self {
         type Data = ();
 
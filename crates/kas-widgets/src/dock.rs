@@ -0,0 +1,1414 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! A container of dockable panels
+
+use crate::optional_mark::OptionalMark;
+use crate::{AccessLabel, Button, Label, List, Page, Splitter, Stack, Tab, TabStack};
+use kas::event::CursorIcon;
+use kas::prelude::*;
+use kas::runner::AppData;
+use kas::theme::MarkStyle;
+use std::fmt;
+use std::sync::Arc;
+
+/// A dock area
+///
+/// Panels of a [`Dock`] are placed in one of these areas, surrounding the
+/// central widget.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DockArea {
+    /// Left of the central widget
+    Left,
+    /// Right of the central widget
+    Right,
+    /// Below the central widget (and left and right areas)
+    Bottom,
+}
+
+impl DockArea {
+    /// All dock areas
+    pub const ALL: [DockArea; 3] = [DockArea::Left, DockArea::Right, DockArea::Bottom];
+
+    fn index(self) -> usize {
+        match self {
+            DockArea::Left => 0,
+            DockArea::Right => 1,
+            DockArea::Bottom => 2,
+        }
+    }
+}
+
+/// Layout of a group of panels within a [`DockArea`]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct DockGroupLayout {
+    /// Keys of panels in this group, in tab order
+    pub panels: Vec<String>,
+    /// Index of the active panel
+    pub active: usize,
+}
+
+/// Layout of a single [`DockArea`]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct DockAreaLayout {
+    /// Groups of panels, each shown as a tab stack
+    ///
+    /// An area with more than one group is split: vertically for the left
+    /// and right areas, horizontally for the bottom area.
+    pub groups: Vec<DockGroupLayout>,
+    /// Whether the area is collapsed to the edge
+    pub collapsed: bool,
+}
+
+/// Layout of a [`Dock`]
+///
+/// This describes the arrangement of panels, identified by key. It may be
+/// read with [`Dock::layout`] and applied with [`Dock::with_layout`] or
+/// [`Dock::set_layout`].
+///
+/// With feature `serde`, this type supports serialisation, thus it may be
+/// saved and restored using [`kas::config::Format`], for example:
+/// ```ignore
+/// Format::guess_and_write_path(path, &dock.layout())?;
+/// let layout: DockLayout = Format::guess_and_read_path(path)?;
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct DockLayout {
+    /// The left area
+    pub left: DockAreaLayout,
+    /// The right area
+    pub right: DockAreaLayout,
+    /// The bottom area
+    pub bottom: DockAreaLayout,
+    /// Keys of panels floated into their own window
+    pub floating: Vec<String>,
+}
+
+impl DockLayout {
+    /// Access the layout of an area
+    pub fn area(&self, area: DockArea) -> &DockAreaLayout {
+        match area {
+            DockArea::Left => &self.left,
+            DockArea::Right => &self.right,
+            DockArea::Bottom => &self.bottom,
+        }
+    }
+
+    /// Access the layout of an area
+    pub fn area_mut(&mut self, area: DockArea) -> &mut DockAreaLayout {
+        match area {
+            DockArea::Left => &mut self.left,
+            DockArea::Right => &mut self.right,
+            DockArea::Bottom => &mut self.bottom,
+        }
+    }
+}
+
+/// A panel while not attached to a [`Group`]
+struct Panel<A> {
+    key: String,
+    title: String,
+    home: DockArea,
+    page: Page<A>,
+}
+
+/// Panels and active index of a group
+type GroupPanels<A> = (Vec<Panel<A>>, usize);
+
+/// Distribute `panels` over areas and groups according to `layout`
+///
+/// Panels not listed by any group are appended to the first group of their
+/// home area. Groups without any panel are omitted.
+fn arrange<A>(mut panels: Vec<Panel<A>>, layout: &DockLayout) -> [Vec<GroupPanels<A>>; 3] {
+    let mut areas: [Vec<GroupPanels<A>>; 3] = Default::default();
+    for area in DockArea::ALL {
+        for group in &layout.area(area).groups {
+            let mut list = vec![];
+            for key in &group.panels {
+                if let Some(pos) = panels.iter().position(|panel| panel.key == *key) {
+                    list.push(panels.remove(pos));
+                }
+            }
+            if !list.is_empty() {
+                areas[area.index()].push((list, group.active));
+            }
+        }
+    }
+    for panel in panels {
+        let groups = &mut areas[panel.home.index()];
+        match groups.first_mut() {
+            Some(group) => group.0.push(panel),
+            None => groups.push((vec![panel], 0)),
+        }
+    }
+    areas
+}
+
+/// Target of a dropped panel
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum DropTarget {
+    /// Append to a group of an area (creating the group if necessary)
+    Group(DockArea, usize),
+    /// Insert a new group at the given index of an area
+    Split(DockArea, usize),
+    /// Float the panel
+    Float,
+}
+
+/// Find the target of a panel dropped at `coord`
+///
+/// `groups` lists the area, index and rect of each visible group while
+/// `center` is the rect of the central widget.
+fn find_drop_target(
+    groups: &[(DockArea, usize, Rect)],
+    center: Rect,
+    coord: Coord,
+) -> Option<DropTarget> {
+    for &(area, index, rect) in groups {
+        if rect.contains(coord) {
+            // The far end of a group splits the area
+            let split = match area {
+                DockArea::Left | DockArea::Right => {
+                    coord.1 >= rect.pos.1 + rect.size.1 - rect.size.1 / 4
+                }
+                DockArea::Bottom => coord.0 >= rect.pos.0 + rect.size.0 - rect.size.0 / 4,
+            };
+            return Some(match split {
+                false => DropTarget::Group(area, index),
+                true => DropTarget::Split(area, index + 1),
+            });
+        }
+    }
+
+    // Edges of the central widget target the adjacent area
+    if !center.contains(coord) {
+        return None;
+    }
+    let (x, y) = (coord.0 - center.pos.0, coord.1 - center.pos.1);
+    let (w, h) = (center.size.0, center.size.1);
+    Some(if x < w / 4 {
+        DropTarget::Group(DockArea::Left, 0)
+    } else if x >= w - w / 4 {
+        DropTarget::Group(DockArea::Right, 0)
+    } else if y >= h - h / 4 {
+        DropTarget::Group(DockArea::Bottom, 0)
+    } else {
+        DropTarget::Float
+    })
+}
+
+/// Support for floating panels
+///
+/// These functions require `A: AppData`, which [`Dock`] does not otherwise.
+struct FloatFns<A> {
+    /// Open a window for a panel
+    float: fn(&mut EventCx, Panel<A>, Id, Arc<()>) -> WindowId,
+    /// Pop a [`Redock`] message
+    redock: fn(&mut EventCx) -> Option<Panel<A>>,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum HeaderMsg {
+    Collapse,
+    Float,
+    Drop(Coord),
+}
+
+#[derive(Clone, Copy, Debug)]
+enum SlotMsg {
+    Collapse,
+    /// Expand the area showing panel `.1` of group `.0`
+    Expand(usize, usize),
+    /// Titles or tab order changed
+    Changed,
+}
+
+/// The active tab of a [`Group`] changed
+#[derive(Debug)]
+struct ActiveTab;
+
+/// A tab of a [`Group`] was moved
+#[derive(Debug)]
+struct MovedTab(usize, usize);
+
+/// Request from a [`Group`] to float (`None`) or drop (at a coordinate) a panel
+#[derive(Debug)]
+struct PanelRequest(String, Option<Coord>);
+
+/// Float panels pending from the initial layout
+#[derive(Debug)]
+struct FloatPending;
+
+/// Request from a [`FloatPanel`] to return to its [`Dock`]
+#[derive(Clone, Debug)]
+struct RedockRequest;
+
+/// A floating panel returned to its [`Dock`]
+struct Redock<A>(Panel<A>);
+
+impl<A> fmt::Debug for Redock<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Redock").field(&self.0.key).finish()
+    }
+}
+
+#[impl_self]
+mod DragHandle {
+    /// The title of a group, which may be dragged to move the active panel
+    #[widget]
+    #[layout(self.label)]
+    struct DragHandle {
+        core: widget_core!(),
+        #[widget]
+        label: Label<String>,
+        // Press start coordinate and whether a drag is in progress
+        press: Option<(Coord, bool)>,
+    }
+
+    impl Tile for Self {
+        fn tooltip(&self) -> Option<&str> {
+            Some("Drag to move panel")
+        }
+
+        fn role(&self, cx: &mut dyn RoleCx) -> Role<'_> {
+            cx.set_label(self.label.id());
+            Role::Grip
+        }
+    }
+
+    impl Events for Self {
+        type Data = ();
+
+        #[inline]
+        fn mouse_over_icon(&self) -> Option<CursorIcon> {
+            Some(CursorIcon::Grab)
+        }
+
+        fn probe(&self, _: Coord) -> Id {
+            self.id()
+        }
+
+        fn handle_event(&mut self, cx: &mut EventCx, _: &(), event: Event) -> IsUsed {
+            match event {
+                Event::PressStart(press) if press.is_primary() => {
+                    self.press = Some((press.coord(), false));
+                    press
+                        .grab_move(self.id())
+                        .with_icon(CursorIcon::Grabbing)
+                        .complete(cx)
+                }
+                Event::PressMove { press, .. } => {
+                    if let Some((start, ref mut dragging)) = self.press
+                        && !*dragging
+                        && cx.config_test_pan_thresh(press.coord - start)
+                    {
+                        *dragging = true;
+                    }
+                    Used
+                }
+                Event::PressEnd { press, success } => {
+                    if let Some((_, true)) = self.press.take()
+                        && success
+                    {
+                        cx.push(HeaderMsg::Drop(press.coord));
+                    }
+                    Used
+                }
+                _ => Unused,
+            }
+        }
+    }
+}
+
+#[impl_self]
+mod Header {
+    /// The header of a group
+    #[widget]
+    #[layout(row![self.handle, self.collapse, self.float])]
+    struct Header {
+        core: widget_core!(),
+        #[widget]
+        handle: DragHandle,
+        #[widget]
+        collapse: OptionalMark<HeaderMsg>,
+        #[widget]
+        float: OptionalMark<HeaderMsg>,
+    }
+
+    impl Events for Self {
+        type Data = ();
+    }
+
+    impl Self {
+        fn new(area: DockArea, title: String, floatable: bool) -> Self {
+            let dir = match area {
+                DockArea::Left => Direction::Left,
+                DockArea::Right => Direction::Right,
+                DockArea::Bottom => Direction::Down,
+            };
+            Header {
+                core: Default::default(),
+                handle: DragHandle {
+                    core: Default::default(),
+                    label: Label::new(title),
+                    press: None,
+                },
+                collapse: OptionalMark::new(
+                    true,
+                    MarkStyle::Chevron(dir),
+                    "Collapse",
+                    HeaderMsg::Collapse,
+                ),
+                float: OptionalMark::new(
+                    floatable,
+                    MarkStyle::Plus,
+                    "Float panel",
+                    HeaderMsg::Float,
+                ),
+            }
+        }
+    }
+}
+
+#[impl_self]
+mod Group {
+    /// A group of panels within a dock area
+    ///
+    /// This shows a header (titled by the active panel) over a [`TabStack`].
+    #[widget]
+    #[layout(column![self.header, self.tabs])]
+    struct Group<A> {
+        core: widget_core!(),
+        // Key and home area of each panel, in tab order
+        panels: Vec<(String, DockArea)>,
+        #[widget(&())]
+        header: Header,
+        #[widget]
+        tabs: TabStack<A>,
+    }
+
+    impl Events for Self {
+        type Data = A;
+
+        fn handle_messages(&mut self, cx: &mut EventCx, _: &A) {
+            if let Some(msg) = cx.try_pop::<HeaderMsg>() {
+                let key = self.panels[self.active()].0.clone();
+                match msg {
+                    HeaderMsg::Collapse => cx.push(SlotMsg::Collapse),
+                    HeaderMsg::Float => cx.push(PanelRequest(key, None)),
+                    HeaderMsg::Drop(coord) => cx.push(PanelRequest(key, Some(coord))),
+                }
+            } else if let Some(ActiveTab) = cx.try_pop() {
+                self.refresh(cx);
+            } else if let Some(MovedTab(from, to)) = cx.try_pop() {
+                let panel = self.panels.remove(from);
+                self.panels.insert(to, panel);
+                cx.push(SlotMsg::Changed);
+            }
+        }
+    }
+
+    impl Self {
+        fn new(area: DockArea, panels: Vec<Panel<A>>, active: usize, floatable: bool) -> Self {
+            let mut keys = Vec::with_capacity(panels.len());
+            let mut titles = Vec::with_capacity(panels.len());
+            let mut pages = Vec::with_capacity(panels.len());
+            for panel in panels {
+                keys.push((panel.key, panel.home));
+                titles.push(panel.title);
+                pages.push(panel.page);
+            }
+
+            let active = active.min(titles.len().saturating_sub(1));
+            let title = titles.get(active).cloned().unwrap_or_default();
+            let tabs = TabStack::from(titles.into_iter().zip(pages))
+                .with_active(active)
+                .with_msg(|_, _| ActiveTab)
+                .with_move_msg(MovedTab);
+
+            Group {
+                core: Default::default(),
+                panels: keys,
+                header: Header::new(area, title, floatable),
+                tabs,
+            }
+        }
+
+        fn active(&self) -> usize {
+            self.tabs.active()
+        }
+
+        fn position(&self, key: &str) -> Option<usize> {
+            self.panels.iter().position(|panel| panel.0 == key)
+        }
+
+        fn titles(&self) -> Vec<String> {
+            (0..self.tabs.len())
+                .filter_map(|index| self.tabs.get_tab(index))
+                .map(|tab| tab.as_str().to_string())
+                .collect()
+        }
+
+        fn state(&self) -> DockGroupLayout {
+            DockGroupLayout {
+                panels: self.panels.iter().map(|panel| panel.0.clone()).collect(),
+                active: self.active(),
+            }
+        }
+
+        /// Update the header title
+        fn refresh(&mut self, cx: &mut ConfigCx) {
+            let title = self
+                .tabs
+                .get_tab(self.active())
+                .map(|tab| tab.as_str().to_string())
+                .unwrap_or_default();
+            self.header.handle.label.set_text(cx, title);
+        }
+
+        fn take(&mut self, cx: &mut ConfigCx, data: &A, index: usize) -> Panel<A> {
+            let active = self.active();
+            let (tab, page) = self.tabs.remove(cx, index);
+            let (key, home) = self.panels.remove(index);
+            if !self.panels.is_empty() {
+                let active = match active {
+                    a if a > index => a - 1,
+                    a => a.min(self.panels.len() - 1),
+                };
+                self.tabs.set_active(cx, data, active);
+            }
+            self.refresh(cx);
+
+            Panel {
+                key,
+                title: tab.as_str().to_string(),
+                home,
+                page,
+            }
+        }
+
+        fn take_all(&mut self, cx: &mut ConfigCx) -> Vec<Panel<A>> {
+            let mut panels = Vec::with_capacity(self.panels.len());
+            while let Some((tab, page)) = self.tabs.pop(cx) {
+                let (key, home) = self.panels.pop().unwrap();
+                panels.push(Panel {
+                    key,
+                    title: tab.as_str().to_string(),
+                    home,
+                    page,
+                });
+            }
+            panels.reverse();
+            panels
+        }
+
+        /// Append `panel` and make it active
+        fn put(&mut self, cx: &mut ConfigCx, data: &A, panel: Panel<A>) {
+            let tab = Tab::new(panel.title);
+            let index = self.tabs.push(cx, data, tab, panel.page);
+            self.panels.push((panel.key, panel.home));
+            self.tabs.set_active(cx, data, index);
+            self.refresh(cx);
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Mode {
+    Center,
+    Empty,
+    Collapsed,
+    Open,
+}
+
+#[impl_self]
+mod Slot {
+    /// A dock area or the central area of a [`Dock`]
+    ///
+    /// Only one child is visible: the central widget, the area's groups
+    /// (separated by a [`Splitter`]) or (when collapsed) a strip of buttons.
+    #[widget]
+    struct Slot<A> {
+        core: widget_core!(),
+        area: Option<DockArea>,
+        collapsed: bool,
+        floatable: bool,
+        #[widget]
+        center: Stack<A>,
+        #[widget]
+        groups: Splitter<Vec<Group<A>>, Direction>,
+        #[widget(&())]
+        strip: List<Vec<Button<AccessLabel>>, Direction>,
+    }
+
+    impl Layout for Self {
+        fn size_rules(&mut self, cx: &mut SizeCx, axis: AxisInfo) -> SizeRules {
+            match self.mode() {
+                Mode::Center => {
+                    let rules = self.center.size_rules(cx, axis);
+                    rules.with_stretch(rules.stretch().max(Stretch::High))
+                }
+                Mode::Empty => SizeRules::EMPTY,
+                Mode::Collapsed => self.strip.size_rules(cx, axis),
+                Mode::Open => self.groups.size_rules(cx, axis),
+            }
+        }
+
+        fn set_rect(&mut self, cx: &mut SizeCx, rect: Rect, hints: AlignHints) {
+            self.core.set_rect(rect);
+            match self.mode() {
+                Mode::Center => self.center.set_rect(cx, rect, hints),
+                Mode::Empty => (),
+                Mode::Collapsed => self.strip.set_rect(cx, rect, hints),
+                Mode::Open => self.groups.set_rect(cx, rect, hints),
+            }
+        }
+
+        fn draw(&self, draw: DrawCx) {
+            match self.mode() {
+                Mode::Center => self.center.draw(draw),
+                Mode::Empty => (),
+                Mode::Collapsed => self.strip.draw(draw),
+                Mode::Open => self.groups.draw(draw),
+            }
+        }
+    }
+
+    impl Tile for Self {
+        fn role(&self, _: &mut dyn RoleCx) -> Role<'_> {
+            Role::None
+        }
+
+        fn child_indices(&self) -> ChildIndices {
+            match self.mode() {
+                Mode::Center => ChildIndices::one(widget_index![self.center]),
+                Mode::Empty => ChildIndices::none(),
+                Mode::Collapsed => ChildIndices::one(widget_index![self.strip]),
+                Mode::Open => ChildIndices::one(widget_index![self.groups]),
+            }
+        }
+    }
+
+    impl Events for Self {
+        type Data = A;
+
+        fn probe(&self, coord: Coord) -> Id {
+            let id = match self.mode() {
+                Mode::Center => self.center.try_probe(coord),
+                Mode::Empty => None,
+                Mode::Collapsed => self.strip.try_probe(coord),
+                Mode::Open => self.groups.try_probe(coord),
+            };
+            id.unwrap_or_else(|| self.id())
+        }
+
+        #[inline]
+        fn recurse_indices(&self) -> ChildIndices {
+            // Configure all children, allowing the mode to change
+            ChildIndices::range(0..3)
+        }
+
+        fn handle_messages(&mut self, cx: &mut EventCx, data: &A) {
+            if let Some(msg) = cx.try_pop::<SlotMsg>() {
+                match msg {
+                    SlotMsg::Collapse => self.set_collapsed(cx, true),
+                    SlotMsg::Expand(group, index) => {
+                        if let Some(group) = self.groups.get_mut(group) {
+                            group.tabs.set_active(cx, data, index);
+                            group.refresh(cx);
+                        }
+                        self.set_collapsed(cx, false);
+                    }
+                    SlotMsg::Changed => self.refresh(cx),
+                }
+            }
+        }
+    }
+
+    impl Self {
+        /// Construct the central slot
+        fn new_center(widget: Box<dyn Widget<Data = A>>) -> Self {
+            Slot {
+                core: Default::default(),
+                area: None,
+                collapsed: false,
+                floatable: false,
+                center: Stack::from([Page::new_boxed(widget)]),
+                groups: Splitter::new_dir(vec![], Direction::Down),
+                strip: List::new_dir(vec![], Direction::Down),
+            }
+        }
+
+        /// Construct the slot of a dock area
+        fn new_area(
+            area: DockArea,
+            groups: Vec<GroupPanels<A>>,
+            collapsed: bool,
+            floatable: bool,
+        ) -> Self {
+            let groups: Vec<_> = groups
+                .into_iter()
+                .map(|(panels, active)| Group::new(area, panels, active, floatable))
+                .collect();
+            let strip = Self::strip_buttons(&groups);
+            let dir = Self::direction(area);
+
+            Slot {
+                core: Default::default(),
+                area: Some(area),
+                collapsed,
+                floatable,
+                center: Stack::new(),
+                groups: Splitter::new_dir(groups, dir),
+                strip: List::new_dir(strip, dir),
+            }
+        }
+
+        /// Direction of groups and of the collapsed strip
+        fn direction(area: DockArea) -> Direction {
+            match area {
+                DockArea::Left | DockArea::Right => Direction::Down,
+                DockArea::Bottom => Direction::Right,
+            }
+        }
+
+        fn strip_buttons<'a>(
+            groups: impl IntoIterator<Item = &'a Group<A>>,
+        ) -> Vec<Button<AccessLabel>>
+        where
+            A: 'a,
+        {
+            let mut buttons = vec![];
+            for (g, group) in groups.into_iter().enumerate() {
+                for (index, title) in group.titles().into_iter().enumerate() {
+                    buttons.push(Button::label_msg(title, SlotMsg::Expand(g, index)));
+                }
+            }
+            buttons
+        }
+
+        fn mode(&self) -> Mode {
+            if self.area.is_none() {
+                Mode::Center
+            } else if self.groups.is_empty() {
+                Mode::Empty
+            } else if self.collapsed {
+                Mode::Collapsed
+            } else {
+                Mode::Open
+            }
+        }
+
+        fn groups(&self) -> impl Iterator<Item = &Group<A>> {
+            (0..self.groups.len()).map(|index| &self.groups[index])
+        }
+
+        /// Find a panel, returning its group and index
+        fn position(&self, key: &str) -> Option<(usize, usize)> {
+            self.groups()
+                .enumerate()
+                .find_map(|(g, group)| Some((g, group.position(key)?)))
+        }
+
+        fn state(&self) -> DockAreaLayout {
+            DockAreaLayout {
+                groups: self.groups().map(|group| group.state()).collect(),
+                collapsed: self.collapsed,
+            }
+        }
+
+        fn set_collapsed(&mut self, cx: &mut ConfigCx, collapsed: bool) {
+            if collapsed != self.collapsed {
+                self.collapsed = collapsed;
+                cx.resize();
+            }
+        }
+
+        fn set_floatable(&mut self, floatable: bool) {
+            self.floatable = floatable;
+            for index in 0..self.groups.len() {
+                self.groups[index].header.float.set_visible(floatable);
+            }
+        }
+
+        /// Update the collapsed strip
+        fn refresh(&mut self, cx: &mut ConfigCx) {
+            let buttons = Self::strip_buttons(self.groups());
+            self.strip.clear();
+            self.strip.extend(cx, &(), buttons);
+            cx.resize();
+        }
+
+        /// Take panel `index` of `group`, removing the group if left empty
+        fn take(&mut self, cx: &mut ConfigCx, data: &A, group: usize, index: usize) -> Panel<A> {
+            let panel = self.groups[group].take(cx, data, index);
+            if self.groups[group].panels.is_empty() {
+                self.groups.remove(cx, group);
+            }
+            self.refresh(cx);
+            panel
+        }
+
+        fn take_all(&mut self, cx: &mut ConfigCx) -> Vec<Panel<A>> {
+            let mut panels = vec![];
+            for index in 0..self.groups.len() {
+                panels.extend(self.groups[index].take_all(cx));
+            }
+            self.groups.clear();
+            self.refresh(cx);
+            panels
+        }
+
+        /// Append `panel` to `group` and make it active
+        ///
+        /// If `group` does not exist, a new group is appended.
+        fn put(&mut self, cx: &mut ConfigCx, data: &A, group: usize, panel: Panel<A>) {
+            if group < self.groups.len() {
+                self.groups[group].put(cx, data, panel);
+                self.collapsed = false;
+                self.refresh(cx);
+            } else {
+                self.split(cx, data, group, panel);
+            }
+        }
+
+        /// Insert a new group at `index` holding `panel`
+        fn split(&mut self, cx: &mut ConfigCx, data: &A, index: usize, panel: Panel<A>) {
+            let area = self.area.unwrap_or(panel.home);
+            let group = Group::new(area, vec![panel], 0, self.floatable);
+            let index = index.min(self.groups.len());
+            self.groups.insert(cx, data, index, group);
+            self.collapsed = false;
+            self.refresh(cx);
+        }
+
+        fn put_all(
+            &mut self,
+            cx: &mut ConfigCx,
+            data: &A,
+            groups: Vec<GroupPanels<A>>,
+            collapsed: bool,
+        ) {
+            let area = self.area.unwrap_or(DockArea::Left);
+            for (panels, active) in groups {
+                let group = Group::new(area, panels, active, self.floatable);
+                self.groups.push(cx, data, group);
+            }
+            self.collapsed = collapsed;
+            self.refresh(cx);
+        }
+    }
+}
+
+#[impl_self]
+mod FloatPanel {
+    /// The content of a floating panel's window
+    ///
+    /// A button returns the panel to its [`Dock`].
+    #[widget]
+    #[layout(column![self.redock.align(AlignHints::RIGHT), self.stack])]
+    struct FloatPanel<A: 'static> {
+        core: widget_core!(),
+        key: String,
+        title: String,
+        home: DockArea,
+        dock: Id,
+        // Held while the window is open; see Dock::layout
+        _alive: Arc<()>,
+        #[widget(&())]
+        redock: OptionalMark<RedockRequest>,
+        #[widget]
+        stack: Stack<A>,
+    }
+
+    impl Events for Self {
+        type Data = A;
+
+        fn handle_messages(&mut self, cx: &mut EventCx, _: &A) {
+            if let Some(RedockRequest) = cx.try_pop()
+                && let Some(page) = self.stack.pop(cx)
+            {
+                let panel = Panel {
+                    key: self.key.clone(),
+                    title: self.title.clone(),
+                    home: self.home,
+                    page,
+                };
+                cx.send(self.dock.clone(), Redock(panel));
+                cx.close_own_window();
+            }
+        }
+    }
+
+    impl Self {
+        fn new(panel: Panel<A>, dock: Id, alive: Arc<()>) -> Self {
+            FloatPanel {
+                core: Default::default(),
+                key: panel.key,
+                title: panel.title,
+                home: panel.home,
+                dock,
+                _alive: alive,
+                redock: OptionalMark::new(true, MarkStyle::Minus, "Dock panel", RedockRequest),
+                stack: Stack::from([panel.page]),
+            }
+        }
+    }
+}
+
+#[impl_self]
+mod Dock {
+    /// A container of dockable panels
+    ///
+    /// A dock surrounds a central widget with three [dock areas](DockArea):
+    /// left, right and bottom. The areas are separated from the central widget
+    /// (and each other) by [`Splitter`] grips. Each area holds one or more
+    /// groups of panels; each group shows its panels as tabs of a
+    /// [`TabStack`], below a header showing the active panel's title.
+    /// Empty areas are hidden.
+    ///
+    /// Panels are identified by a string key, which must be unique.
+    ///
+    /// Panels may be:
+    ///
+    /// -   moved to another group by dragging the title of a group's header to
+    ///     that group, or to another area by dragging to the corresponding
+    ///     edge of the central widget (see also [`Self::move_panel`])
+    /// -   split into a new group by dragging the title to the far end (the
+    ///     bottom of a left or right area, or the right of the bottom area) of
+    ///     a group (see also [`Self::split_panel`])
+    /// -   reordered within a group by dragging tabs
+    /// -   collapsed to the edge using the header's collapse button; a
+    ///     collapsed area shows a button per panel to expand it again
+    ///     (see also [`Self::set_collapsed`])
+    /// -   floated into their own window, if enabled via
+    ///     [`Self::with_floating`], using the header's float button or by
+    ///     dropping a panel in the middle of the central widget
+    ///     (see also [`Self::float_panel`])
+    ///
+    /// A floating window has a button to return the panel to its home area.
+    /// Otherwise, the panel belongs to its window: closing that window closes
+    /// the panel, which is then no longer listed by [`Self::layout`].
+    ///
+    /// The arrangement of panels may be saved with [`Self::layout`] and
+    /// restored with [`Self::with_layout`] or [`Self::set_layout`];
+    /// see [`DockLayout`].
+    #[widget]
+    #[layout(self.split)]
+    pub struct Dock<A> {
+        core: widget_core!(),
+        #[widget]
+        split: Splitter<Vec<Splitter<Vec<Slot<A>>, kas::dir::Right>>, kas::dir::Down>,
+        // Panels and layout to apply on configure
+        pending: Vec<Panel<A>>,
+        pending_layout: DockLayout,
+        float_fns: Option<FloatFns<A>>,
+        // Panels to float (on handling FloatPending)
+        to_float: Vec<String>,
+        // Key, window and liveness handle of floating panels
+        floating: Vec<(String, WindowId, Arc<()>)>,
+    }
+
+    impl Self {
+        /// Construct with the given central widget
+        ///
+        /// Add panels using [`Self::with_panel`].
+        pub fn new(center: impl Widget<Data = A> + 'static) -> Self {
+            let area = |area| Slot::new_area(area, vec![], false, false);
+            let main = Splitter::right(vec![
+                area(DockArea::Left),
+                Slot::new_center(Box::new(center)),
+                area(DockArea::Right),
+            ]);
+            let bottom = Splitter::right(vec![area(DockArea::Bottom)]);
+            Dock {
+                core: Default::default(),
+                split: Splitter::down(vec![main, bottom]),
+                pending: vec![],
+                pending_layout: DockLayout::default(),
+                float_fns: None,
+                to_float: vec![],
+                floating: vec![],
+            }
+        }
+
+        /// Add a panel (inline)
+        ///
+        /// The panel, identified by `key` and shown with `title`, is added to
+        /// its home `area` unless the layout (see [`Self::with_layout`])
+        /// places it elsewhere.
+        #[must_use]
+        pub fn with_panel(
+            mut self,
+            area: DockArea,
+            key: impl ToString,
+            title: impl ToString,
+            widget: impl Widget<Data = A> + 'static,
+        ) -> Self {
+            self.pending.push(Panel {
+                key: key.to_string(),
+                title: title.to_string(),
+                home: area,
+                page: Page::new(widget),
+            });
+            self
+        }
+
+        /// Set the initial layout (inline)
+        ///
+        /// This is applied to panels added by [`Self::with_panel`] when the
+        /// dock is configured. Panels not listed by `layout` are placed in
+        /// their home area; keys not matching any panel are ignored.
+        #[must_use]
+        pub fn with_layout(mut self, layout: DockLayout) -> Self {
+            self.pending_layout = layout;
+            self
+        }
+
+        fn slot_index(area: Option<DockArea>) -> (usize, usize) {
+            match area {
+                Some(DockArea::Left) => (0, 0),
+                None => (0, 1),
+                Some(DockArea::Right) => (0, 2),
+                Some(DockArea::Bottom) => (1, 0),
+            }
+        }
+
+        fn slot(&self, area: Option<DockArea>) -> &Slot<A> {
+            let (i, j) = Self::slot_index(area);
+            &self.split[i][j]
+        }
+
+        fn slot_mut(&mut self, area: Option<DockArea>) -> &mut Slot<A> {
+            let (i, j) = Self::slot_index(area);
+            &mut self.split[i][j]
+        }
+
+        /// Find a docked panel, returning its area, group and index
+        fn find(&self, key: &str) -> Option<(DockArea, usize, usize)> {
+            DockArea::ALL.into_iter().find_map(|area| {
+                let (group, index) = self.slot(Some(area)).position(key)?;
+                Some((area, group, index))
+            })
+        }
+
+        /// Find the target of a panel dropped at `coord`
+        fn drop_target(&self, coord: Coord) -> Option<DropTarget> {
+            let mut groups = vec![];
+            for area in DockArea::ALL {
+                let slot = self.slot(Some(area));
+                match slot.mode() {
+                    Mode::Open => groups.extend(
+                        slot.groups()
+                            .enumerate()
+                            .map(|(index, group)| (area, index, group.rect())),
+                    ),
+                    Mode::Collapsed => groups.push((area, 0, slot.rect())),
+                    Mode::Center | Mode::Empty => (),
+                }
+            }
+            find_drop_target(&groups, self.slot(None).rect(), coord)
+        }
+
+        /// Move the panel at `index` of `group` of `area` to `target`
+        fn move_to(
+            &mut self,
+            cx: &mut EventCx,
+            data: &A,
+            (area, group, index): (DockArea, usize, usize),
+            target: DropTarget,
+        ) {
+            let (to, at, split) = match target {
+                DropTarget::Float => return self.float_index(cx, data, area, group, index),
+                DropTarget::Group(to, at) => (to, at, false),
+                DropTarget::Split(to, at) => (to, at, true),
+            };
+
+            let slot = self.slot_mut(Some(area));
+            let alone = slot.groups[group].panels.len() == 1;
+            let unmoved = match split {
+                false => at == group,
+                true => alone && (at == group || at == group + 1),
+            };
+            if to == area && unmoved {
+                return;
+            }
+
+            let len = slot.groups.len();
+            let panel = slot.take(cx, data, group, index);
+            // Adjust for removal of the source group
+            let at = match to == area && slot.groups.len() < len && group < at {
+                true => at - 1,
+                false => at,
+            };
+            match split {
+                false => self.slot_mut(Some(to)).put(cx, data, at, panel),
+                true => self.slot_mut(Some(to)).split(cx, data, at, panel),
+            }
+        }
+
+        fn float_index(
+            &mut self,
+            cx: &mut EventCx,
+            data: &A,
+            area: DockArea,
+            group: usize,
+            index: usize,
+        ) {
+            let Some(float) = self.float_fns.as_ref().map(|f| f.float) else {
+                return;
+            };
+            let panel = self.slot_mut(Some(area)).take(cx, data, group, index);
+            let key = panel.key.clone();
+            let alive = Arc::new(());
+            let id = float(cx, panel, self.id(), alive.clone());
+            self.floating.retain(|f| Arc::strong_count(&f.2) > 1);
+            self.floating.push((key, id, alive));
+        }
+
+        /// Iterate over floating panels whose window is open
+        fn floating(&self) -> impl Iterator<Item = &(String, WindowId, Arc<()>)> {
+            self.floating.iter().filter(|f| Arc::strong_count(&f.2) > 1)
+        }
+
+        /// Get the current layout
+        pub fn layout(&self) -> DockLayout {
+            DockLayout {
+                left: self.slot(Some(DockArea::Left)).state(),
+                right: self.slot(Some(DockArea::Right)).state(),
+                bottom: self.slot(Some(DockArea::Bottom)).state(),
+                floating: self.floating().map(|f| f.0.clone()).collect(),
+            }
+        }
+
+        /// Apply a layout
+        ///
+        /// Panels are moved between areas and groups as described by
+        /// `layout`. Panels not listed by `layout` are moved to their home
+        /// area; keys not matching any docked panel are ignored. Panels listed
+        /// as floating are floated if enabled (see [`Self::with_floating`]).
+        pub fn set_layout(&mut self, cx: &mut EventCx, data: &A, layout: &DockLayout) {
+            let mut panels = vec![];
+            for area in DockArea::ALL {
+                panels.extend(self.slot_mut(Some(area)).take_all(cx));
+            }
+            for (area, groups) in DockArea::ALL.into_iter().zip(arrange(panels, layout)) {
+                let collapsed = layout.area(area).collapsed;
+                self.slot_mut(Some(area))
+                    .put_all(cx, data, groups, collapsed);
+            }
+            for key in &layout.floating {
+                self.float_panel(cx, data, key);
+            }
+        }
+
+        /// Add a panel
+        ///
+        /// The panel, identified by `key` and shown with `title`, is appended
+        /// to the first group of `area` and made active.
+        pub fn add_panel(
+            &mut self,
+            cx: &mut ConfigCx,
+            data: &A,
+            area: DockArea,
+            key: impl ToString,
+            title: impl ToString,
+            widget: impl Widget<Data = A> + 'static,
+        ) {
+            let panel = Panel {
+                key: key.to_string(),
+                title: title.to_string(),
+                home: area,
+                page: Page::new(widget),
+            };
+            self.slot_mut(Some(area)).put(cx, data, 0, panel);
+        }
+
+        /// Remove a docked panel
+        ///
+        /// Returns the panel's widget (wrapped as a [`Page`]) if found.
+        pub fn remove_panel(&mut self, cx: &mut ConfigCx, data: &A, key: &str) -> Option<Page<A>> {
+            let (area, group, index) = self.find(key)?;
+            Some(self.slot_mut(Some(area)).take(cx, data, group, index).page)
+        }
+
+        /// Get the area of a docked panel
+        pub fn panel_area(&self, key: &str) -> Option<DockArea> {
+            self.find(key).map(|(area, _, _)| area)
+        }
+
+        /// Move a docked panel to `area`
+        ///
+        /// The panel is appended to the first group of `area` and made active.
+        /// Returns `false` if the panel was not found.
+        pub fn move_panel(
+            &mut self,
+            cx: &mut ConfigCx,
+            data: &A,
+            key: &str,
+            area: DockArea,
+        ) -> bool {
+            let Some((from, group, index)) = self.find(key) else {
+                return false;
+            };
+            if from != area {
+                let panel = self.slot_mut(Some(from)).take(cx, data, group, index);
+                self.slot_mut(Some(area)).put(cx, data, 0, panel);
+            }
+            true
+        }
+
+        /// Split a docked panel into a new group
+        ///
+        /// The new group is inserted after the panel's current group. This
+        /// does nothing if the panel is already alone in its group. Returns
+        /// `false` if the panel was not found.
+        pub fn split_panel(&mut self, cx: &mut ConfigCx, data: &A, key: &str) -> bool {
+            let Some((area, group, index)) = self.find(key) else {
+                return false;
+            };
+            let slot = self.slot_mut(Some(area));
+            if slot.groups[group].panels.len() > 1 {
+                let panel = slot.take(cx, data, group, index);
+                slot.split(cx, data, group + 1, panel);
+            }
+            true
+        }
+
+        /// Float a docked panel into its own window
+        ///
+        /// Returns `false` if the panel was not found or floating is not
+        /// enabled (see [`Self::with_floating`]).
+        pub fn float_panel(&mut self, cx: &mut EventCx, data: &A, key: &str) -> bool {
+            match self.find(key) {
+                Some((area, group, index)) if self.float_fns.is_some() => {
+                    self.float_index(cx, data, area, group, index);
+                    true
+                }
+                _ => false,
+            }
+        }
+
+        /// Get the window of a floating panel
+        pub fn floating_window(&self, key: &str) -> Option<WindowId> {
+            self.floating().find(|f| f.0 == key).map(|f| f.1)
+        }
+
+        /// True if `area` is collapsed
+        pub fn is_collapsed(&self, area: DockArea) -> bool {
+            self.slot(Some(area)).collapsed
+        }
+
+        /// Collapse or expand `area`
+        pub fn set_collapsed(&mut self, cx: &mut ConfigCx, area: DockArea, collapsed: bool) {
+            self.slot_mut(Some(area)).set_collapsed(cx, collapsed);
+        }
+    }
+
+    impl Tile for Self {
+        fn role(&self, _: &mut dyn RoleCx) -> Role<'_> {
+            Role::None
+        }
+    }
+
+    impl Events for Self {
+        type Data = A;
+
+        fn configure(&mut self, cx: &mut ConfigCx) {
+            if self.pending.is_empty() {
+                return;
+            }
+
+            let panels = std::mem::take(&mut self.pending);
+            let layout = std::mem::take(&mut self.pending_layout);
+            let floatable = self.float_fns.is_some();
+            for (area, groups) in DockArea::ALL.into_iter().zip(arrange(panels, &layout)) {
+                let collapsed = layout.area(area).collapsed;
+                *self.slot_mut(Some(area)) = Slot::new_area(area, groups, collapsed, floatable);
+            }
+
+            if floatable && !layout.floating.is_empty() {
+                self.to_float = layout.floating;
+                cx.send(self.id(), FloatPending);
+            }
+        }
+
+        fn handle_messages(&mut self, cx: &mut EventCx, data: &A) {
+            if let Some(PanelRequest(key, coord)) = cx.try_pop() {
+                let Some(from) = self.find(&key) else {
+                    return;
+                };
+                let target = match coord {
+                    None => DropTarget::Float,
+                    Some(coord) => match self.drop_target(coord) {
+                        Some(target) => target,
+                        None => return,
+                    },
+                };
+                self.move_to(cx, data, from, target);
+            } else if let Some(FloatPending) = cx.try_pop() {
+                for key in std::mem::take(&mut self.to_float) {
+                    self.float_panel(cx, data, &key);
+                }
+            } else if let Some(redock) = self.float_fns.as_ref().map(|f| f.redock)
+                && let Some(panel) = redock(cx)
+            {
+                self.floating.retain(|f| f.0 != panel.key);
+                let area = panel.home;
+                self.slot_mut(Some(area)).put(cx, data, 0, panel);
+            }
+        }
+    }
+}
+
+impl<A: AppData> Dock<A> {
+    /// Enable floating panels (inline)
+    ///
+    /// Floating panels are opened in a new window using
+    /// [`EventCx::add_window`]; this requires that `A` is the application's
+    /// data type.
+    #[must_use]
+    pub fn with_floating(mut self) -> Self {
+        self.float_fns = Some(FloatFns {
+            float: |cx, panel, dock, alive| {
+                let title = panel.title.clone();
+                let panel = FloatPanel::new(panel, dock, alive);
+                cx.add_window(Window::new(panel, title), false)
+            },
+            redock: |cx| cx.try_pop::<Redock<A>>().map(|redock| redock.0),
+        });
+        for area in DockArea::ALL {
+            self.slot_mut(Some(area)).set_floatable(true);
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Filler;
+
+    fn panel(key: &str, home: DockArea) -> Panel<()> {
+        Panel {
+            key: key.to_string(),
+            title: key.to_string(),
+            home,
+            page: Page::new(Filler::new()),
+        }
+    }
+
+    fn group(keys: &[&str], active: usize) -> DockGroupLayout {
+        DockGroupLayout {
+            panels: keys.iter().map(|key| key.to_string()).collect(),
+            active,
+        }
+    }
+
+    fn keys(groups: &[GroupPanels<()>]) -> Vec<(Vec<&str>, usize)> {
+        groups
+            .iter()
+            .map(|(panels, active)| (panels.iter().map(|p| p.key.as_str()).collect(), *active))
+            .collect()
+    }
+
+    #[test]
+    fn arrange_home() {
+        let panels = vec![
+            panel("a", DockArea::Left),
+            panel("b", DockArea::Bottom),
+            panel("c", DockArea::Left),
+        ];
+        let [left, right, bottom] = arrange(panels, &DockLayout::default());
+        assert_eq!(keys(&left), [(vec!["a", "c"], 0)]);
+        assert!(right.is_empty());
+        assert_eq!(keys(&bottom), [(vec!["b"], 0)]);
+    }
+
+    #[test]
+    fn arrange_layout() {
+        let panels = vec![
+            panel("a", DockArea::Left),
+            panel("b", DockArea::Left),
+            panel("c", DockArea::Left),
+            panel("d", DockArea::Bottom),
+        ];
+        let layout = DockLayout {
+            left: DockAreaLayout {
+                groups: vec![group(&["x"], 0), group(&["c", "y"], 1)],
+                collapsed: false,
+            },
+            right: DockAreaLayout {
+                groups: vec![group(&["b"], 0), group(&["a"], 0)],
+                collapsed: true,
+            },
+            ..Default::default()
+        };
+        let [left, right, bottom] = arrange(panels, &layout);
+        // The group of unknown key "x" is omitted
+        assert_eq!(keys(&left), [(vec!["c"], 1)]);
+        assert_eq!(keys(&right), [(vec!["b"], 0), (vec!["a"], 0)]);
+        assert_eq!(keys(&bottom), [(vec!["d"], 0)]);
+    }
+
+    #[test]
+    fn drop_target() {
+        use DockArea::*;
+        use DropTarget::*;
+
+        let center = Rect::new(Coord(100, 0), Size(400, 400));
+        let groups = [
+            (Left, 0, Rect::new(Coord(0, 0), Size(100, 200))),
+            (Left, 1, Rect::new(Coord(0, 200), Size(100, 200))),
+            (Bottom, 0, Rect::new(Coord(0, 400), Size(500, 100))),
+        ];
+        let target = |x, y| find_drop_target(&groups, center, Coord(x, y));
+
+        assert_eq!(target(50, 10), Some(Group(Left, 0)));
+        assert_eq!(target(50, 180), Some(Split(Left, 1)));
+        assert_eq!(target(50, 210), Some(Group(Left, 1)));
+        assert_eq!(target(50, 399), Some(Split(Left, 2)));
+        assert_eq!(target(10, 450), Some(Group(Bottom, 0)));
+        assert_eq!(target(490, 450), Some(Split(Bottom, 1)));
+
+        assert_eq!(target(110, 200), Some(Group(Left, 0)));
+        assert_eq!(target(490, 200), Some(Group(Right, 0)));
+        assert_eq!(target(300, 390), Some(Group(Bottom, 0)));
+        assert_eq!(target(300, 200), Some(Float));
+        assert_eq!(target(600, 200), None);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn layout_serde() {
+        let layout = DockLayout {
+            left: DockAreaLayout {
+                groups: vec![group(&["files", "search"], 1), group(&["outline"], 0)],
+                collapsed: false,
+            },
+            right: DockAreaLayout::default(),
+            bottom: DockAreaLayout {
+                groups: vec![group(&["log"], 0)],
+                collapsed: true,
+            },
+            floating: vec!["preview".to_string()],
+        };
+        let text = serde_json::to_string(&layout).unwrap();
+        let result: DockLayout = serde_json::from_str(&text).unwrap();
+        assert_eq!(result, layout);
+
+        // Missing fields take default values
+        let result: DockLayout = serde_json::from_str(r#"{"floating":["a"]}"#).unwrap();
+        assert_eq!(result.left, DockAreaLayout::default());
+        assert_eq!(result.floating, ["a"]);
+    }
+}
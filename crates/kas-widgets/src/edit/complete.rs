@@ -0,0 +1,203 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Completion pop-up support
+
+use crate::{Column, Label};
+use kas::prelude::*;
+use kas::theme::{FrameStyle, SelectionStyle, TextClass};
+
+/// Set the list of completion candidates
+///
+/// This message may be sent to an [`EditBox`](super::EditBox) or
+/// [`EditBoxCore`](super::EditBoxCore) to supply completion candidates
+/// asynchronously (see [`EditGuard::completions`](super::EditGuard::completions)).
+/// An empty list closes the completion pop-up.
+#[derive(Clone, Debug)]
+pub struct SetCompletions(pub Vec<String>);
+
+/// Accept the completion at the given index
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct AcceptCompletion(pub usize);
+
+#[impl_self]
+mod CompletionEntry {
+    /// A completion candidate
+    ///
+    /// This is not navigable: the edit field retains focus.
+    #[widget]
+    #[layout(self.label)]
+    struct CompletionEntry {
+        core: widget_core!(),
+        index: usize,
+        selected: bool,
+        #[widget]
+        label: Label<String>,
+    }
+
+    impl Layout for Self {
+        fn draw(&self, mut draw: DrawCx) {
+            draw.frame(self.rect(), FrameStyle::MenuEntry, Default::default());
+            if self.selected {
+                draw.selection(self.rect(), SelectionStyle::Highlight);
+            }
+            self.label.draw(draw.re());
+        }
+    }
+
+    impl Tile for Self {
+        fn role(&self, cx: &mut dyn RoleCx) -> Role<'_> {
+            cx.set_label(self.label.id());
+            Role::OptionListItem {
+                index: Some(self.index),
+                selected: Some(self.selected),
+            }
+        }
+    }
+
+    impl Events for Self {
+        type Data = ();
+
+        fn probe(&self, _: Coord) -> Id {
+            self.id()
+        }
+
+        fn handle_event(&mut self, cx: &mut EventCx, _: &(), event: Event) -> IsUsed {
+            let index = self.index;
+            event.on_click(cx, self.id(), |cx| cx.push(AcceptCompletion(index)))
+        }
+    }
+}
+
+#[impl_self]
+mod CompletionList {
+    /// The list of completion candidates shown within a pop-up
+    #[widget]
+    #[layout(self.list)]
+    pub(crate) struct CompletionList {
+        core: widget_core!(),
+        candidates: Vec<String>,
+        selected: Option<usize>,
+        #[widget]
+        list: Column<Vec<CompletionEntry>>,
+    }
+
+    impl Tile for Self {
+        fn role(&self, _: &mut dyn RoleCx) -> Role<'_> {
+            Role::OptionList {
+                len: Some(self.candidates.len()),
+                direction: Direction::Down,
+            }
+        }
+    }
+
+    impl Events for Self {
+        type Data = ();
+    }
+
+    impl Default for Self {
+        fn default() -> Self {
+            CompletionList {
+                core: Default::default(),
+                candidates: vec![],
+                selected: None,
+                list: Column::new(vec![]),
+            }
+        }
+    }
+
+    impl Self {
+        /// Get the number of candidates
+        #[inline]
+        pub fn len(&self) -> usize {
+            self.candidates.len()
+        }
+
+        /// Get candidate `index`
+        #[inline]
+        pub fn get(&self, index: usize) -> Option<&str> {
+            self.candidates.get(index).map(|s| s.as_str())
+        }
+
+        /// Get the selected index, if any
+        #[inline]
+        pub fn selected(&self) -> Option<usize> {
+            self.selected
+        }
+
+        /// Replace the list of candidates
+        ///
+        /// This clears the selection.
+        pub fn set_candidates(&mut self, cx: &mut ConfigCx, candidates: Vec<String>) {
+            self.list.clear();
+            for (index, text) in candidates.iter().enumerate() {
+                let entry = CompletionEntry {
+                    core: Default::default(),
+                    index,
+                    selected: false,
+                    label: Label::new(text.clone()).with_class(TextClass::Label),
+                };
+                self.list.push(cx, &(), entry);
+            }
+            self.candidates = candidates;
+            self.selected = None;
+        }
+
+        /// Move the selection by `delta` places, wrapping around
+        pub fn move_selection(&mut self, cx: &mut EventCx, delta: isize) {
+            let len = self.candidates.len();
+            if len == 0 {
+                return;
+            }
+
+            let index = step_index(self.selected, len, delta);
+            self.set_selected(cx, Some(index));
+        }
+
+        fn set_selected(&mut self, cx: &mut EventCx, index: Option<usize>) {
+            if let Some(i) = self.selected.take() {
+                self.list[i].selected = false;
+            }
+            if let Some(i) = index {
+                self.list[i].selected = true;
+            }
+            self.selected = index;
+            cx.redraw();
+        }
+    }
+}
+
+/// Step from `index` by `delta` places within `0..len`, wrapping around
+///
+/// From `None`, a positive `delta` selects the first and a negative `delta`
+/// the last index. Requires `len > 0`.
+fn step_index(index: Option<usize>, len: usize, delta: isize) -> usize {
+    let Some(index) = index else {
+        return if delta < 0 { len - 1 } else { 0 };
+    };
+    let step = delta.unsigned_abs() % len;
+    if delta < 0 {
+        (index + len - step) % len
+    } else {
+        (index + step) % len
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn step_index_wraps() {
+        assert_eq!(step_index(None, 3, 1), 0);
+        assert_eq!(step_index(None, 3, -1), 2);
+        assert_eq!(step_index(Some(0), 3, 1), 1);
+        assert_eq!(step_index(Some(2), 3, 1), 0);
+        assert_eq!(step_index(Some(0), 3, -1), 2);
+        assert_eq!(step_index(Some(1), 3, -7), 0);
+        assert_eq!(step_index(Some(1), 3, isize::MIN), 2);
+        assert_eq!(step_index(Some(1), 3, isize::MAX), 2);
+    }
+}
@@ -59,6 +59,9 @@ mod EditBox {
     /// Both add an item to the undo history and invoke the action handler
     /// [`EditGuard::edit`].
     ///
+    /// [`FoldAll`], [`UnfoldAll`] and [`ToggleFold`] adjust folding when
+    /// [enabled](Self::with_folding).
+    ///
     /// [`kas::messages::SetScrollOffset`] may be used to set the scroll offset.
     #[autoimpl(Debug where G: trait, H: trait)]
     #[autoimpl(Deref<Target = Editor> using self.inner)]
@@ -191,8 +194,12 @@ mod EditBox {
                 pos: self.rect().pos + self.frame_offset,
                 size: self.rect().size - self.frame_size,
             };
+            let offset = self.scroll.offset();
             let used = self.scroll.scroll_by_event(cx, event, self.id(), rect);
             self.update_content_size(cx);
+            if self.scroll.offset() != offset {
+                self.inner.reposition_completions(cx);
+            }
             used
         }
 
@@ -211,6 +218,7 @@ mod EditBox {
             if let Some(moved) = self.scroll.set_offset(offset) {
                 cx.action_moved(moved);
                 self.update_scroll_offset(cx);
+                self.inner.reposition_completions(cx);
             }
         }
 
@@ -222,8 +230,12 @@ mod EditBox {
 
         fn handle_scroll(&mut self, cx: &mut EventCx<'_>, _: &G::Data, scroll: Scroll) {
             let rect = self.inner.rect();
+            let offset = self.scroll.offset();
             self.scroll.scroll(cx, self.id(), rect, scroll);
             self.update_scroll_offset(cx);
+            if self.scroll.offset() != offset {
+                self.inner.reposition_completions(cx);
+            }
         }
     }
 
@@ -371,6 +383,35 @@ impl<A: 'static> EditBox<DefaultGuard<A>> {
     ) -> EditBox<InstantParseGuard<A, T>> {
         EditBox::new(InstantParseGuard::new(value_fn, msg_fn))
     }
+
+    /// Construct an `EditBox` for masked input
+    ///
+    /// Input is restricted according to `mask` (see [`MaskGuard`]). On
+    /// update, `value_fn` is used to extract an unmasked value from input
+    /// data. If, however, the input field has focus, the update is ignored.
+    ///
+    /// No other action happens unless [`EditBox::with_msg`] is used.
+    pub fn masked(
+        mask: InputMask,
+        value_fn: impl Fn(&A) -> String + Send + 'static,
+    ) -> EditBox<MaskGuard<A>> {
+        EditBox::new(MaskGuard::new(mask, value_fn))
+    }
+}
+
+impl<A: 'static> EditBox<MaskGuard<A>> {
+    /// Assign a message function for the unmasked value
+    ///
+    /// The `msg_fn` is called when the field is activated (<kbd>Enter</kbd>)
+    /// and when it loses focus after content is changed.
+    #[must_use]
+    pub fn with_msg<M>(mut self, msg_fn: impl Fn(&str) -> M + Send + 'static) -> Self
+    where
+        M: Debug + 'static,
+    {
+        self.inner.guard = self.inner.guard.with_msg(msg_fn);
+        self
+    }
 }
 
 impl<A: 'static> EditBox<StringGuard<A>> {
@@ -432,6 +473,40 @@ impl<G: EditGuard, H: Highlighter> EditBox<G, H> {
         self
     }
 
+    /// Enable or disable code folding (inline)
+    ///
+    /// See [`EditBoxCore::with_folding`].
+    #[inline]
+    #[must_use]
+    pub fn with_folding(mut self, folding: bool) -> Self {
+        self.inner = self.inner.with_folding(folding);
+        self
+    }
+
+    /// Enable or disable password mode (inline)
+    ///
+    /// See [`EditBoxCore::with_password`] and [`PasswordBox`].
+    #[inline]
+    #[must_use]
+    pub fn with_password(mut self, password: bool) -> Self {
+        self.inner = self.inner.with_password(password);
+        self
+    }
+
+    /// Set whether password text is revealed (inline)
+    #[inline]
+    #[must_use]
+    pub fn with_password_revealed(mut self, revealed: bool) -> Self {
+        self.inner = self.inner.with_password_revealed(revealed);
+        self
+    }
+
+    /// Set whether password text is revealed
+    #[inline]
+    pub fn set_password_revealed(&mut self, cx: &mut EventCx, revealed: bool) {
+        self.inner.set_password_revealed(cx, revealed);
+    }
+
     /// Adjust the height allocation
     #[inline]
     pub fn set_lines(&mut self, min_lines: f32, ideal_lines: f32) {
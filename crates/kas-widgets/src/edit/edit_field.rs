@@ -5,14 +5,16 @@
 
 //! The [`EditBoxCore`] widget
 
+use super::complete::{AcceptCompletion, CompletionList};
 use super::editor::{Component, EventAction};
 use super::*;
 use crate::edit::highlight::{Highlighter, Plain};
-use kas::event::CursorIcon;
-use kas::messages::{ReplaceSelectedText, SetValueText};
+use kas::event::{Command, CursorIcon, ElementState};
+use kas::messages::{ReplaceSelectedText, SetTextSelection, SetValueText};
 use kas::prelude::*;
 use kas::text::Direction;
 use kas::theme::{Background, TextClass};
+use kas::window::Popup;
 use std::ops::Deref;
 
 #[impl_self]
@@ -58,6 +60,24 @@ mod EditBoxCore {
     /// widget is not [read-only](Editor::is_read_only). Both add an item to
     /// the undo history and invoke the action handler [`EditGuard::edit`].
     ///
+    /// [`SetTextSelection`] may be used to set the cursor and selection.
+    ///
+    /// [`FoldAll`], [`UnfoldAll`] and [`ToggleFold`] adjust folding when
+    /// [enabled](Self::with_folding).
+    ///
+    /// [`SetCompletions`] may be used to supply completion candidates (see
+    /// below).
+    ///
+    /// ### Completion
+    ///
+    /// After each user edit, [`EditGuard::completions`] is called. When this
+    /// yields candidates, these are shown in a pop-up below the text cursor.
+    /// While the pop-up is open, [`Command::Up`] and [`Command::Down`] select
+    /// a candidate, [`Command::Enter`] or [`Command::Tab`] accepts the
+    /// selected candidate (see [`EditGuard::accept_completion`]) and
+    /// [`Command::Escape`] closes the pop-up. The edit field retains input
+    /// focus throughout.
+    ///
     /// ### Special behaviour
     ///
     /// This is a [`Viewport`] widget.
@@ -71,6 +91,8 @@ mod EditBoxCore {
         editor: Component<H>,
         /// The associated [`EditGuard`] implementation
         pub guard: G,
+        #[widget(&())]
+        completions: Popup<CompletionList>,
     }
 
     impl Deref for Self {
@@ -142,10 +164,12 @@ mod EditBoxCore {
             self.editor.0.error_message()
         }
 
-        fn role(&self, _: &mut dyn RoleCx) -> Role<'_> {
+        fn role(&self, cx: &mut dyn RoleCx) -> Role<'_> {
+            cx.set_text_runs(self.text_runs());
             Role::TextInput {
                 text: self.as_str(),
                 multi_line: self.multi_line(),
+                protected: self.is_password(),
                 cursor: self.cursor_range(),
             }
         }
@@ -179,14 +203,28 @@ mod EditBoxCore {
         }
 
         fn handle_event(&mut self, cx: &mut EventCx, data: &G::Data, event: Event) -> IsUsed {
+            if self.completions.is_open()
+                && let Some(result) = self.handle_completion_event(cx, data, &event)
+            {
+                return result;
+            }
+
             let mut result = Used;
-            match self.editor.handle_event(cx, event) {
+            let from = self.editor.0.cursor_range().edit_index();
+            let guard = &mut self.guard;
+            let mut filter = |text: &str, insertion| guard.filter_insert(text, insertion);
+            match self.editor.handle_event_filtered(cx, event, &mut filter) {
                 EventAction::Unused => return Unused,
-                EventAction::Used | EventAction::Cursor | EventAction::Preedit => return Used,
+                EventAction::Used | EventAction::Preedit | EventAction::Fold => return Used,
+                EventAction::Cursor => {
+                    self.guard.cursor_moved(&mut self.editor.0, cx, data, from);
+                    return Used;
+                }
                 EventAction::FocusGained => {
                     self.guard.focus_gained(&mut self.editor.0, cx, data);
                 }
                 EventAction::FocusLost => {
+                    self.completions.close(cx);
                     self.guard.focus_lost(&mut self.editor.0, cx, data);
                 }
                 EventAction::Activate(code) => {
@@ -195,6 +233,11 @@ mod EditBoxCore {
                 }
                 EventAction::Edit => {
                     self.call_guard_edit(cx, data);
+                    if let Some(list) = self.guard.completions(&self.editor.0, cx, data) {
+                        self.set_completions(cx, list);
+                    } else if self.completions.is_open() {
+                        self.open_completions(cx);
+                    }
                     return Used;
                 }
             }
@@ -204,11 +247,35 @@ mod EditBoxCore {
         }
 
         fn handle_messages(&mut self, cx: &mut EventCx, data: &G::Data) {
+            if let Some(FoldAll) = cx.try_pop() {
+                self.editor.0.fold_all();
+                self.editor.prepare_and_scroll(cx);
+                return;
+            } else if let Some(UnfoldAll) = cx.try_pop() {
+                self.editor.0.unfold_all();
+                self.editor.prepare_and_scroll(cx);
+                return;
+            } else if let Some(ToggleFold) = cx.try_pop() {
+                self.editor.0.toggle_fold();
+                self.editor.prepare_and_scroll(cx);
+                return;
+            } else if let Some(SetTextSelection(range)) = cx.try_pop() {
+                if self.is_valid_cursor_range(range) {
+                    self.editor.0.set_cursor_range(range);
+                    cx.redraw();
+                }
+                return;
+            }
+
             if self.is_read_only() {
                 return;
             }
 
-            if let Some(SetValueText(string)) = cx.try_pop() {
+            if let Some(SetCompletions(list)) = cx.try_pop() {
+                self.set_completions(cx, list);
+            } else if let Some(AcceptCompletion(index)) = cx.try_pop() {
+                self.accept_completion(cx, data, index);
+            } else if let Some(SetValueText(string)) = cx.try_pop() {
                 self.edit(cx, data, |edit, cx| {
                     edit.pre_commit();
                     edit.set_string(cx, string);
@@ -242,6 +309,7 @@ mod EditBoxCore {
                 lines: (1.0, 1.0),
                 editor: Component::new(false),
                 guard,
+                completions: Popup::new(Default::default(), kas::dir::Direction::Down),
             }
         }
     }
@@ -258,6 +326,7 @@ mod EditBoxCore {
                 lines: self.lines,
                 editor: self.editor.with_highlighter(highlighter),
                 guard: self.guard,
+                completions: self.completions,
             }
         }
 
@@ -289,6 +358,79 @@ mod EditBoxCore {
             self.guard.edit(&mut self.editor.0, cx, data);
             self.editor.prepare_and_scroll(cx);
         }
+
+        /// Handle events while the completion pop-up is open
+        ///
+        /// Returns `None` if the event should be handled by the editor.
+        fn handle_completion_event(
+            &mut self,
+            cx: &mut EventCx,
+            data: &G::Data,
+            event: &Event,
+        ) -> Option<IsUsed> {
+            let cmd = match event {
+                Event::Command(cmd, _) => *cmd,
+                Event::Key(key, false) if key.state == ElementState::Pressed => cx
+                    .config()
+                    .shortcuts()
+                    .try_match_event(cx.modifiers(), key)?,
+                _ => return None,
+            };
+
+            match cmd {
+                Command::Up => self.completions.inner.move_selection(cx, -1),
+                Command::Down => self.completions.inner.move_selection(cx, 1),
+                Command::Escape => self.completions.close(cx),
+                Command::Enter | Command::Tab => {
+                    let index = self.completions.inner.selected()?;
+                    self.accept_completion(cx, data, index);
+                }
+                _ => return None,
+            }
+            Some(Used)
+        }
+
+        /// Set the list of completion candidates
+        fn set_completions(&mut self, cx: &mut EventCx, list: Vec<String>) {
+            let is_empty = list.is_empty();
+            if is_empty {
+                self.completions.close(cx);
+            }
+            self.completions.inner.set_candidates(cx, list);
+            if !is_empty && self.has_input_focus() {
+                self.open_completions(cx);
+            }
+        }
+
+        /// Reposition the completion pop-up, if open
+        ///
+        /// This should be called by a parent scrolling this widget, since the
+        /// pop-up's position is fixed relative to the scroll offset at the time
+        /// of placement.
+        pub(crate) fn reposition_completions(&mut self, cx: &mut EventCx) {
+            if self.completions.is_open() {
+                self.open_completions(cx);
+            }
+        }
+
+        /// Open or reposition the completion pop-up below the text cursor
+        fn open_completions(&mut self, cx: &mut EventCx) {
+            let id = self.id();
+            let anchor = self.editor.0.cursor_rect().unwrap_or(self.rect());
+            self.completions.open_at(cx, &(), id, anchor, false);
+        }
+
+        /// Accept completion candidate `index`
+        fn accept_completion(&mut self, cx: &mut EventCx, data: &G::Data, index: usize) {
+            self.completions.close(cx);
+            let Some(completion) = self.completions.inner.get(index) else {
+                return;
+            };
+            let completion = completion.to_string();
+            self.guard
+                .accept_completion(&mut self.editor.0, cx, data, &completion);
+            self.call_guard_edit(cx, data);
+        }
     }
 }
 
@@ -347,6 +489,47 @@ impl<G: EditGuard, H: Highlighter> EditBoxCore<G, H> {
         self
     }
 
+    /// Enable or disable code folding (inline)
+    ///
+    /// When enabled, foldable ranges are reported by the [`Highlighter`]
+    /// (by default, using indentation) and fold markers are drawn in a gutter
+    /// to the left of the text. Clicking a marker toggles the fold. Folding is
+    /// only useful in multi-line mode.
+    #[inline]
+    #[must_use]
+    pub fn with_folding(mut self, folding: bool) -> Self {
+        self.editor.set_folding(folding);
+        self
+    }
+
+    /// Enable or disable password mode (inline)
+    ///
+    /// In password mode, text is displayed masked and may not be copied; see
+    /// [`Editor::set_password`]. Password mode should not be combined with
+    /// multi-line mode or folding.
+    #[inline]
+    #[must_use]
+    pub fn with_password(mut self, password: bool) -> Self {
+        self.editor.0.set_password(password);
+        self
+    }
+
+    /// Set whether password text is revealed (inline)
+    #[inline]
+    #[must_use]
+    pub fn with_password_revealed(mut self, revealed: bool) -> Self {
+        self.editor.0.set_password_revealed(revealed);
+        self
+    }
+
+    /// Set whether password text is revealed
+    ///
+    /// See [`Editor::set_password_revealed`].
+    pub fn set_password_revealed(&mut self, cx: &mut EventCx, revealed: bool) {
+        self.editor.0.set_password_revealed(revealed);
+        self.editor.prepare_and_scroll(cx);
+    }
+
     /// Adjust the height allocation
     #[inline]
     pub fn set_lines(&mut self, min_lines: f32, ideal_lines: f32) {
@@ -14,7 +14,9 @@
 //! single-paragraph editor should have one of each while a multi-paragraph
 //! editor might use multiple [`Part`]s.
 
+use super::fold::{self, Folds};
 use super::highlight::{self, Highlighter, SchemeColors};
+use super::password::{MASK, zeroize};
 use super::*;
 use kas::cast::Cast;
 use kas::event::components::{TextInput, TextInputAction};
@@ -25,10 +27,12 @@ use kas::geom::{Rect, Vec2};
 use kas::layout::{AlignHints, AxisInfo, SizeRules};
 use kas::prelude::*;
 use kas::text::fonts::FontSelector;
-use kas::text::{CursorRange, Direction, NotReady, SelectionHelper, Status, TextDisplay, format};
-use kas::theme::{Background, DrawCx, SizeCx, TextClass};
+use kas::text::{
+    CursorRange, Direction, MarkerPosIter, NotReady, SelectionHelper, Status, TextDisplay, format,
+};
+use kas::theme::{Background, DrawCx, MarkStyle, SizeCx, TextClass};
 use kas::util::UndoStack;
-use kas::{Layout, autoimpl};
+use kas::{Layout, Live, TextRun, autoimpl};
 use std::borrow::Cow;
 use std::num::NonZeroUsize;
 use unicode_segmentation::{GraphemeCursor, UnicodeSegmentation};
@@ -56,15 +60,41 @@ pub enum EventAction {
     Preedit,
     /// Text was edited by key command
     Edit,
+    /// Fold state changed (text is unchanged)
+    Fold,
 }
 
 impl EventAction {
     /// If true, text has been edited and must be re-prepared.
     pub fn requires_repreparation(&self) -> bool {
-        matches!(self, EventAction::Preedit | EventAction::Edit)
+        matches!(
+            self,
+            EventAction::Preedit | EventAction::Edit | EventAction::Fold
+        )
     }
 }
 
+/// A pending text insertion
+///
+/// This is passed to an insertion filter (see
+/// [`Component::handle_event_filtered`] and [`EditGuard::filter_insert`])
+/// before user input is inserted into the text.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Insertion {
+    /// The range of existing text to replace
+    pub range: Range<usize>,
+    /// The text to insert
+    pub text: String,
+    /// The cursor position after insertion, relative to `range.start`
+    pub cursor: usize,
+}
+
+/// An insertion filter
+///
+/// This is passed the current text and a pending [`Insertion`], returning the
+/// (potentially modified) insertion or `None` to reject the insertion.
+pub type InsertFilter<'a> = dyn FnMut(&str, Insertion) -> Option<Insertion> + 'a;
+
 /// Editor state common to all parts
 #[derive(Debug, Default)]
 pub struct Common<H: Highlighter> {
@@ -136,15 +166,22 @@ pub struct Part {
     direction: Direction,
     wrap: bool,
     read_only: bool,
+    folding: bool,
+    password: bool,
+    revealed: bool,
+    gutter: i32,
     rect: Rect,
     status: Status,
     display: TextDisplay,
     highlight: highlight::Cache,
     text: String,
+    folds: Folds,
     selection: SelectionHelper,
     edit_x_coord: Option<f32>,
     last_edit: Option<EditOp>,
-    undo_stack: UndoStack<(String, CursorRange)>,
+    // Owner-defined tag saved with undo states
+    undo_tag: u64,
+    undo_stack: UndoStack<(String, CursorRange, u64)>,
     has_key_focus: bool,
     current: CurrentAction,
     input_handler: TextInput,
@@ -158,6 +195,8 @@ pub struct Part {
 pub struct Editor {
     part: Part,
     error_state: Option<Option<Cow<'static, str>>>,
+    /// The error message shown before the last call to [`Component::clear_error`]
+    last_error: Option<Cow<'static, str>>,
 }
 
 /// Editor component
@@ -210,6 +249,7 @@ impl<H: Highlighter> Component<H> {
         let editor = Editor {
             part: Part::new(wrap),
             error_state: None,
+            last_error: None,
         };
         Component(editor, Common::default())
     }
@@ -221,6 +261,16 @@ impl<H: Highlighter> Component<H> {
         self.0.part.status = Status::New;
     }
 
+    /// Set whether code folding is enabled
+    ///
+    /// When enabled, foldable ranges are reported by
+    /// [`Highlighter::fold_ranges`] and a gutter displaying fold markers is
+    /// reserved to the left of the text.
+    #[inline]
+    pub fn set_folding(&mut self, folding: bool) {
+        self.0.part.set_folding(folding);
+    }
+
     /// Set the base text direction
     ///
     /// If [`Direction::Auto`] or [`Direction::AutoRtl`] is used, the direction
@@ -247,6 +297,22 @@ impl<H: Highlighter> Component<H> {
         self.0.part.require_reprepare();
     }
 
+    /// Access the highlighter
+    #[inline]
+    pub fn highlighter(&self) -> &H {
+        &self.1.highlighter
+    }
+
+    /// Access the highlighter mutably
+    ///
+    /// This forces re-preparation of the text (as with
+    /// [`Self::set_highlighter`]).
+    #[inline]
+    pub fn highlighter_mut(&mut self) -> &mut H {
+        self.0.part.require_reprepare();
+        &mut self.1.highlighter
+    }
+
     /// Get the background color
     ///
     /// Uses the UI theme's error color if applicable.
@@ -333,7 +399,18 @@ impl<H: Highlighter> Component<H> {
     /// Handle an event
     #[inline]
     pub fn handle_event(&mut self, cx: &mut EventCx, event: Event) -> EventAction {
-        let action = self.0.part.handle_event(cx, event);
+        self.handle_event_filtered(cx, event, &mut |_, insertion| Some(insertion))
+    }
+
+    /// Handle an event, filtering user input through `filter`
+    #[inline]
+    pub fn handle_event_filtered(
+        &mut self,
+        cx: &mut EventCx,
+        event: Event,
+        filter: &mut InsertFilter,
+    ) -> EventAction {
+        let action = self.0.part.handle_event_filtered(cx, event, filter);
         if action.requires_repreparation() {
             self.0.part.prepare_and_scroll(&mut self.1, cx);
         }
@@ -343,7 +420,15 @@ impl<H: Highlighter> Component<H> {
     /// Clear the error state
     #[inline]
     pub fn clear_error(&mut self) {
-        self.0.error_state = None;
+        self.0.last_error = self.0.error_state.take().flatten();
+    }
+}
+
+impl Drop for Part {
+    fn drop(&mut self) {
+        if self.password {
+            zeroize(&mut self.text);
+        }
     }
 }
 
@@ -358,14 +443,20 @@ impl Part {
             direction: Direction::Auto,
             wrap,
             read_only: false,
+            folding: false,
+            password: false,
+            revealed: false,
+            gutter: 0,
             rect: Rect::ZERO,
             status: Status::New,
             display: TextDisplay::default(),
             highlight: Default::default(),
             text: Default::default(),
+            folds: Default::default(),
             selection: Default::default(),
             edit_x_coord: None,
             last_edit: Some(EditOp::Initial),
+            undo_tag: 0,
             undo_stack: UndoStack::new(),
             has_key_focus: false,
             current: CurrentAction::None,
@@ -384,6 +475,18 @@ impl Part {
         self.status = Status::New;
     }
 
+    /// Set whether code folding is enabled
+    ///
+    /// See [`Component::set_folding`].
+    pub fn set_folding(&mut self, folding: bool) {
+        self.folding = folding;
+        self.gutter = if folding { self.dpem.cast_ceil() } else { 0 };
+        if !folding {
+            self.folds.clear();
+        }
+        self.status = Status::New;
+    }
+
     /// Set the initial text (inline)
     ///
     /// This method should only be used on a new `Part`.
@@ -426,6 +529,64 @@ impl Part {
         self.status == Status::Ready
     }
 
+    /// Get the layout of each line of text
+    ///
+    /// This is used to describe text to accessibility tools; see
+    /// [`RoleCx::set_text_runs`]. Returns an empty list when the text is not
+    /// prepared and for password editors.
+    pub fn text_runs(&self) -> Vec<TextRun> {
+        let mut runs = vec![];
+        if self.password || !self.is_prepared() {
+            return runs;
+        }
+
+        let offset: Vec2 = self.text_pos().cast();
+        for (index, line) in self.display.lines().enumerate() {
+            let range = line.text_range();
+            let range = self.to_source(range.start)..self.to_source(range.end);
+            let (top, bottom) = (line.top(), line.bottom());
+            let x_at = |index: usize| {
+                self.text_glyph_pos(index)
+                    .find(|m| top <= m.pos.1 && m.pos.1 <= bottom)
+                    .map(|m| m.pos.0)
+            };
+
+            let mut xs: Vec<f32> = vec![];
+            for (i, _) in self.text[range.clone()].char_indices() {
+                let x = x_at(range.start + i).or(xs.last().copied());
+                xs.push(x.unwrap_or(0.0));
+            }
+            let end_x = x_at(range.end).or(xs.last().copied()).unwrap_or(0.0);
+
+            let left = xs.iter().fold(end_x, |a, b| a.min(*b));
+            let right = xs.iter().fold(end_x, |a, b| a.max(*b));
+            let p1: Coord = (offset + Vec2(left, top)).cast_floor();
+            let p2: Coord = (offset + Vec2(right, bottom)).cast_ceil();
+            let rect = Rect::from_coords(p1, p2);
+
+            let rtl = self.display.line_is_rtl(index).unwrap_or(false);
+            let lead = match rtl {
+                false => f32::conv(rect.pos.0) - offset.0,
+                true => f32::conv(rect.pos.0 + rect.size.0) - offset.0,
+            };
+            let widths = xs
+                .iter()
+                .zip(xs.iter().skip(1).chain(std::iter::once(&end_x)))
+                .map(|(x0, x1)| (x1 - x0).abs())
+                .collect();
+            let positions = xs.iter().map(|x| (x - lead).abs()).collect();
+
+            runs.push(TextRun {
+                range,
+                rect,
+                rtl,
+                positions,
+                widths,
+            });
+        }
+        runs
+    }
+
     /// Force full repreparation of text
     #[inline]
     pub fn require_reprepare(&mut self) {
@@ -448,6 +609,9 @@ impl Part {
             self.dpem = dpem;
             self.status = self.status.min(Status::ResizeLevelRuns);
         }
+        if self.folding {
+            self.gutter = dpem.cast_ceil();
+        }
         self.prepare_runs(common);
     }
 
@@ -464,14 +628,35 @@ impl Part {
             part.highlight
                 .highlight(&part.text, &mut common.highlighter);
 
-            let text = part.text.as_str();
-            let font_tokens = part.highlight.font_tokens(part.dpem, part.font);
+            if part.folding {
+                let mut ranges = vec![];
+                common
+                    .highlighter
+                    .fold_ranges(&part.text, |range| ranges.push(range));
+                part.folds.set_available(ranges);
+                part.folds.unfold_containing(part.selection.edit_index());
+            }
+
+            let text = match part.is_masked() {
+                true => Cow::Owned(part.text.graphemes(true).map(|_| MASK).collect()),
+                false => part.folds.display_text(&part.text),
+            };
+            let font_tokens: Vec<_> = part
+                .highlight
+                .font_tokens(part.dpem, part.font)
+                .map(|token| (token.start, (token.dpem, token.font)))
+                .collect();
+            let font_tokens = part.map_tokens(&font_tokens);
+            let font_tokens = font_tokens
+                .iter()
+                .map(|&(start, (dpem, font))| format::FontToken { start, dpem, font });
+
             match part.status {
                 Status::New => part
                     .display
-                    .prepare_runs(text, part.direction, font_tokens)
+                    .prepare_runs(&text, part.direction, font_tokens)
                     .expect("no suitable font found"),
-                Status::ResizeLevelRuns => part.display.resize_runs(text, font_tokens),
+                Status::ResizeLevelRuns => part.display.resize_runs(&text, font_tokens),
                 _ => return,
             }
 
@@ -506,17 +691,19 @@ impl Part {
                 if self.status >= Status::LevelRuns {
                     bound = self.display.measure_width(ideal.cast()).cast_ceil();
                 }
-                SizeRules::new(bound.min(min), bound.min(ideal), Stretch::Filler)
+                let (min, ideal) = (bound.min(min), bound.min(ideal));
+                SizeRules::new(min + self.gutter, ideal + self.gutter, Stretch::Filler)
             } else {
                 if self.status >= Status::LevelRuns {
                     bound = self.display.measure_width(f32::INFINITY).cast_ceil();
                 }
+                bound += self.gutter;
                 SizeRules::new(bound, bound, Stretch::Filler)
             }
         } else {
             let wrap_width = self
                 .wrap
-                .then(|| axis.other().map(|w| w.cast()))
+                .then(|| axis.other().map(|w| (w - self.gutter).cast()))
                 .flatten()
                 .unwrap_or(f32::INFINITY);
             let mut bound = 0i32;
@@ -557,6 +744,12 @@ impl Part {
         self.rect.pos = pos;
     }
 
+    /// Get the position of the text (excluding the fold gutter, if any)
+    #[inline]
+    fn text_pos(&self) -> Coord {
+        self.rect.pos + Offset(self.gutter, 0)
+    }
+
     /// Perform line wrapping and alignment
     ///
     /// This represents a high-level step of preparation required before
@@ -575,7 +768,7 @@ impl Part {
         let bb = self.display.bounding_box();
 
         if self.status == Status::LevelRuns {
-            let align_width = self.rect.size.0.cast();
+            let align_width = (self.rect.size.0 - self.gutter).max(0).cast();
             let wrap_width = if !self.wrap { f32::INFINITY } else { align_width };
             self.display
                 .prepare_lines(wrap_width, align_width, Align::Default);
@@ -629,7 +822,9 @@ impl Part {
         }
 
         let (tl, br) = self.display.bounding_box();
-        (Vec2::from(br) - Vec2::from(tl)).cast_ceil()
+        let mut size: Size = (Vec2::from(br) - Vec2::from(tl)).cast_ceil();
+        size.0 += self.gutter;
+        size
     }
 
     /// Implementation of [`Viewport::draw_with_offset`]
@@ -644,10 +839,12 @@ impl Part {
             return;
         }
 
-        let pos = self.rect.pos - offset;
-        let range: Range<u32> = self.selection.range().cast();
+        let pos = self.text_pos() - offset;
+        let range = self.selection.range();
+        let range: Range<u32> = (self.to_display(range.start)..self.to_display(range.end)).cast();
 
-        let color_tokens = self.highlight.color_tokens();
+        let color_tokens = self.map_tokens(self.highlight.color_tokens());
+        let color_tokens: &[_] = &color_tokens;
         let default_colors = format::Colors {
             foreground: colors.foreground,
             background: None,
@@ -731,12 +928,15 @@ impl Part {
         };
         draw.text(pos, rect, &self.display, tokens);
 
-        let decorations = self.highlight.decorations();
+        let decorations = self.map_tokens(self.highlight.decorations());
         if !decorations.is_empty() {
-            draw.decorate_text(pos, rect, &self.display, decorations);
+            draw.decorate_text(pos, rect, &self.display, &decorations);
         }
 
         if let CurrentAction::ImePreedit { edit_range } = self.current.clone() {
+            let edit_range: Range<u32> = (self.to_display(edit_range.start.cast())
+                ..self.to_display(edit_range.end.cast()))
+                .cast();
             let tokens = [
                 Default::default(),
                 (edit_range.start, format::Decoration {
@@ -754,10 +954,48 @@ impl Part {
                 pos,
                 rect,
                 &self.display,
-                self.selection.edit_index(),
+                self.to_display(self.selection.edit_index()),
                 Some(colors.cursor),
             );
         }
+
+        if self.folding {
+            self.draw_fold_markers(&mut draw, rect, offset);
+        }
+    }
+
+    /// Draw fold markers in the gutter
+    fn draw_fold_markers(&self, draw: &mut DrawCx, rect: Rect, offset: Offset) {
+        let x0 = self.rect.pos.0 - offset.0;
+        let y0 = self.rect.pos.1 - offset.1;
+        for range in self.folds.visible_available() {
+            let index = self.folds.to_display(range.start);
+            let Some(line) = self
+                .display
+                .find_line(index)
+                .and_then(|(line, _)| self.display.get_line(line))
+            else {
+                continue;
+            };
+
+            let top: i32 = line.top().cast_floor();
+            let bottom: i32 = line.bottom().cast_ceil();
+            let size = self.gutter.min(bottom - top);
+            let pos = Coord(
+                x0 + (self.gutter - size) / 2,
+                y0 + top + (bottom - top - size) / 2,
+            );
+            if pos.1 < rect.pos.1 || pos.1 + size > rect.pos.1 + rect.size.1 {
+                continue;
+            }
+
+            let dir = if self.folds.is_folded(range) {
+                kas::dir::Direction::Right
+            } else {
+                kas::dir::Direction::Down
+            };
+            draw.mark(Rect::new(pos, Size::splat(size)), MarkStyle::Chevron(dir));
+        }
     }
 
     /// Handle an event
@@ -766,6 +1004,22 @@ impl Part {
     /// re-prepare the text by calling [`Self::prepare_and_scroll`].
     #[inline]
     pub fn handle_event(&mut self, cx: &mut EventCx, event: Event) -> EventAction {
+        self.handle_event_filtered(cx, event, &mut |_, insertion| Some(insertion))
+    }
+
+    /// Handle an event, filtering user input through `filter`
+    ///
+    /// All insertions of text by the user (key input, IME commit and paste)
+    /// are passed through `filter` before being applied. IME pre-edit text is
+    /// not filtered.
+    ///
+    /// See also [`Self::handle_event`].
+    pub fn handle_event_filtered(
+        &mut self,
+        cx: &mut EventCx,
+        event: Event,
+        filter: &mut InsertFilter,
+    ) -> EventAction {
         if !self.is_prepared() {
             debug_assert!(false);
             return EventAction::Unused;
@@ -796,7 +1050,10 @@ impl Part {
 
                 if self.current.is_none() {
                     let hint = Default::default();
-                    let purpose = ImePurpose::Normal;
+                    let purpose = match self.password {
+                        false => ImePurpose::Normal,
+                        true => ImePurpose::Password,
+                    };
                     let surrounding_text = self.ime_surrounding_text();
                     cx.replace_ime_focus(self.id.clone(), hint, purpose, surrounding_text);
                     EventAction::FocusGained
@@ -823,7 +1080,7 @@ impl Part {
                 cx.redraw();
                 EventAction::Used
             }
-            Event::Command(cmd, code) => match self.cmd_action(cx, cmd, code) {
+            Event::Command(cmd, code) => match self.cmd_action(cx, cmd, code, filter) {
                 Ok(action) => {
                     if matches!(action, EventAction::Cursor) {
                         self.set_view_offset_from_cursor(cx);
@@ -838,18 +1095,17 @@ impl Part {
                     self.cancel_selection_and_ime(cx);
 
                     let selection = self.selection.range();
-                    self.replace_range(selection.clone(), text);
-                    self.selection.set_cursor(selection.start + text.len());
-                    self.edit_x_coord = None;
-
-                    EventAction::Edit
+                    match self.insert_filtered(filter, selection, text) {
+                        true => EventAction::Edit,
+                        false => EventAction::Used,
+                    }
                 } else {
                     let opt_cmd = cx
                         .config()
                         .shortcuts()
                         .try_match_event(cx.modifiers(), event);
                     if let Some(cmd) = opt_cmd {
-                        match self.cmd_action(cx, cmd, Some(event.physical_key)) {
+                        match self.cmd_action(cx, cmd, Some(event.physical_key), filter) {
                             Ok(action) => {
                                 if matches!(action, EventAction::Cursor) {
                                     self.set_view_offset_from_cursor(cx);
@@ -924,8 +1180,11 @@ impl Part {
                         _ => return EventAction::Used,
                     };
 
-                    self.replace_range(edit_range.clone(), text);
-                    self.selection.set_cursor(edit_range.start + text.len());
+                    if !self.insert_filtered(filter, edit_range.clone(), text) {
+                        // Remove pre-edit text
+                        self.replace_range(edit_range.clone(), "");
+                        self.selection.set_cursor(edit_range.start);
+                    }
 
                     self.current = CurrentAction::ImePreedit {
                         edit_range: self.selection.range().cast(),
@@ -972,6 +1231,17 @@ impl Part {
                     EventAction::Used
                 }
             },
+            Event::PressStart(press)
+                if self.folding && press.is_primary() && press.coord().0 < self.text_pos().0 =>
+            {
+                let y: f32 = (press.coord().1 - self.rect.pos.1).cast();
+                let index = self.text_index_nearest(Vec2(0.0, y));
+                if self.toggle_fold_at(index) {
+                    EventAction::Fold
+                } else {
+                    EventAction::Used
+                }
+            }
             Event::PressStart(press) if press.is_tertiary() => {
                 match press.grab_click(self.id.clone()).complete(cx) {
                     Unused => EventAction::Unused,
@@ -989,10 +1259,10 @@ impl Part {
                     let index = self.selection.edit_index();
                     let range = self.trim_paste(&content);
 
-                    self.replace_range(index..index, &content[range.clone()]);
-                    self.selection.set_cursor(index + range.len());
-                    self.edit_x_coord = None;
-                    EventAction::Edit
+                    match self.insert_filtered(filter, index..index, &content[range]) {
+                        true => EventAction::Edit,
+                        false => EventAction::Used,
+                    }
                 } else {
                     EventAction::Used
                 }
@@ -1017,8 +1287,8 @@ impl Part {
                     if repeats > 1 {
                         self.selection.expand(
                             self.text.as_str(),
-                            &|index| self.display.find_line(index).map(|r| r.1),
-                            repeats >= 3,
+                            &|index| self.find_line(index).map(|r| r.1),
+                            repeats >= 3 || self.password,
                         );
                     }
 
@@ -1031,8 +1301,8 @@ impl Part {
                         if repeats > 1 {
                             self.selection.expand(
                                 self.text.as_str(),
-                                &|index| self.display.find_line(index).map(|r| r.1),
-                                repeats >= 3,
+                                &|index| self.find_line(index).map(|r| r.1),
+                                repeats >= 3 || self.password,
                             );
                         }
                     }
@@ -1071,10 +1341,162 @@ impl Part {
     /// [`Text::set_text`]. This may change in the future (TODO).
     #[inline]
     fn replace_range(&mut self, range: std::ops::Range<usize>, replace_with: &str) {
+        let len = self.text.len() - range.len() + replace_with.len();
+        if self.password && len > self.text.capacity() {
+            // Re-allocate here so that the old buffer may be zeroed
+            let mut text = String::with_capacity(len.max(2 * self.text.capacity()));
+            text.push_str(&self.text);
+            zeroize(&mut self.text);
+            self.text = text;
+        }
+
+        self.folds.edit(range.clone(), replace_with.len());
         self.text.replace_range(range, replace_with);
         self.require_reprepare();
     }
 
+    /// True if the display text is masked
+    #[inline]
+    fn is_masked(&self) -> bool {
+        self.password && !self.revealed
+    }
+
+    /// Map a source text index to a display text index
+    ///
+    /// The display text differs from the source text when folded or masked.
+    fn to_display(&self, index: usize) -> usize {
+        if self.is_masked() {
+            let iter = self.text.grapheme_indices(true);
+            iter.take_while(|(i, _)| *i < index).count() * MASK.len_utf8()
+        } else {
+            self.folds.to_display(index)
+        }
+    }
+
+    /// Map a display text index to a source text index
+    fn to_source(&self, index: usize) -> usize {
+        if self.is_masked() {
+            self.text
+                .grapheme_indices(true)
+                .nth(index / MASK.len_utf8())
+                .map(|(i, _)| i)
+                .unwrap_or(self.text.len())
+        } else {
+            self.folds.to_source(index)
+        }
+    }
+
+    /// Map tokens from source text to display text
+    fn map_tokens<'a, T: Copy>(&self, tokens: &'a [(u32, T)]) -> Cow<'a, [(u32, T)]> {
+        if self.is_masked() {
+            fold::map_tokens(tokens, |index| self.to_display(index))
+        } else {
+            self.folds.map_tokens(tokens)
+        }
+    }
+
+    /// Replace `range` with `text`, after filtering
+    ///
+    /// Sets the cursor after the inserted text. Returns `false` if the
+    /// insertion was rejected.
+    fn insert_filtered(
+        &mut self,
+        filter: &mut InsertFilter,
+        range: Range<usize>,
+        text: &str,
+    ) -> bool {
+        let insertion = Insertion {
+            range,
+            text: text.to_string(),
+            cursor: text.len(),
+        };
+        let Some(insertion) = filter(&self.text, insertion) else {
+            return false;
+        };
+
+        let Insertion {
+            range,
+            text,
+            cursor,
+        } = insertion;
+        if range.start > range.end
+            || !self.text.is_char_boundary(range.start)
+            || !self.text.is_char_boundary(range.end)
+            || !text.is_char_boundary(cursor)
+        {
+            log::warn!("Editor: insertion filter returned invalid range or cursor");
+            return false;
+        }
+
+        self.replace_range(range.clone(), &text);
+        self.selection.set_cursor(range.start + cursor);
+        self.edit_x_coord = None;
+        true
+    }
+
+    /// Find the line containing text `index`
+    ///
+    /// Returns the line number and the text-range of the line.
+    /// This is equivalent to [`TextDisplay::find_line`] but maps through folds.
+    fn find_line(&self, index: usize) -> Option<(usize, Range<usize>)> {
+        let index = self.to_display(index);
+        self.display.find_line(index).map(|(line, range)| {
+            let range = self.to_source(range.start)..self.to_source(range.end);
+            (line, range)
+        })
+    }
+
+    /// Find the position of glyph(s) at text `index`
+    ///
+    /// This is equivalent to [`TextDisplay::text_glyph_pos`] but maps through folds.
+    fn text_glyph_pos(&self, index: usize) -> MarkerPosIter {
+        self.display.text_glyph_pos(self.to_display(index))
+    }
+
+    /// Find the text index for the glyph nearest the given `pos`
+    ///
+    /// This is equivalent to [`TextDisplay::text_index_nearest`] but maps through folds.
+    fn text_index_nearest(&self, pos: Vec2) -> usize {
+        self.to_source(self.display.text_index_nearest(pos.into()))
+    }
+
+    /// Find the text index nearest horizontal-coordinate `x` on `line`
+    ///
+    /// This is equivalent to [`TextDisplay::line_index_nearest`] but maps through folds.
+    fn line_index_nearest(&self, line: usize, x: f32) -> Option<usize> {
+        self.display
+            .line_index_nearest(line, x)
+            .map(|index| self.to_source(index))
+    }
+
+    /// Toggle the fold on the line containing `index`
+    ///
+    /// Returns `true` if the fold state changed.
+    fn toggle_fold_at(&mut self, index: usize) -> bool {
+        let line_end = self.text[index..]
+            .find(['\r', '\n'])
+            .map(|i| index + i)
+            .unwrap_or(self.text.len());
+        let changed = self.folds.toggle_at(index, line_end);
+        if changed {
+            self.fold_changed();
+        }
+        changed
+    }
+
+    /// Update state after a change to folds
+    ///
+    /// This moves the cursor out of folded ranges and requires repreparation.
+    fn fold_changed(&mut self) {
+        let index = self.selection.edit_index();
+        let new_index = self.folds.skip(index, false);
+        if new_index != index {
+            self.selection.set_cursor(new_index);
+        }
+        self.edit_x_coord = None;
+        self.require_reprepare();
+    }
+
     /// Cancel on-going selection and IME actions
     ///
     /// This should be called if e.g. key-input interrupts the current
@@ -1106,6 +1528,10 @@ impl Part {
     fn ime_surrounding_text(&self) -> Option<ImeSurroundingText> {
         const MAX_TEXT_BYTES: usize = ImeSurroundingText::MAX_TEXT_BYTES;
 
+        if self.password {
+            return None;
+        }
+
         let sel_range = self.selection.range();
         let edit_range = match self.current.clone() {
             CurrentAction::ImePreedit { edit_range } => Some(edit_range.cast()),
@@ -1116,10 +1542,10 @@ impl Part {
         let edit_len = edit_range.clone().map(|r| r.len()).unwrap_or(0);
 
         if self.status >= Status::Wrapped {
-            if let Some((_, line_range)) = self.display.find_line(range.start) {
+            if let Some((_, line_range)) = self.find_line(range.start) {
                 range.start = line_range.start;
             }
-            if let Some((_, line_range)) = self.display.find_line(range.end) {
+            if let Some((_, line_range)) = self.find_line(range.end) {
                 range.end = line_range.end;
             }
         }
@@ -1173,12 +1599,12 @@ impl Part {
 
         let (m1, m2);
         if range.is_empty() {
-            let mut iter = self.display.text_glyph_pos(range.start);
+            let mut iter = self.text_glyph_pos(range.start);
             m1 = iter.next();
             m2 = iter.next();
         } else {
-            m1 = self.display.text_glyph_pos(range.start).next_back();
-            m2 = self.display.text_glyph_pos(range.end).next();
+            m1 = self.text_glyph_pos(range.start).next_back();
+            m2 = self.text_glyph_pos(range.end).next();
         }
 
         let rect = if let Some((c1, c2)) = m1.zip(m2) {
@@ -1197,13 +1623,18 @@ impl Part {
             return;
         };
 
-        cx.set_ime_cursor_area(&self.id, rect + Offset::conv(self.rect.pos));
+        cx.set_ime_cursor_area(&self.id, rect + Offset::conv(self.text_pos()));
     }
 
     /// Call before an edit to (potentially) commit current state based on last_edit
     ///
     /// Call with [`None`] to force commit of any uncommitted changes.
     fn save_undo_state(&mut self, edit: Option<EditOp>) {
+        if self.password {
+            // Password entries do not keep copies of old contents
+            return;
+        }
+
         if let Some(op) = edit
             && op.try_merge(&mut self.last_edit)
         {
@@ -1212,7 +1643,7 @@ impl Part {
 
         self.last_edit = edit;
         self.undo_stack
-            .try_push((self.as_str().to_string(), *self.selection));
+            .try_push((self.as_str().to_string(), *self.selection, self.undo_tag));
     }
 
     /// Request key focus, if we don't have it or IME
@@ -1244,6 +1675,7 @@ impl Part {
         cx: &mut EventCx,
         mut cmd: Command,
         code: Option<PhysicalKey>,
+        filter: &mut InsertFilter,
     ) -> Result<EventAction, NotReady> {
         debug_assert!(self.is_prepared());
 
@@ -1303,6 +1735,9 @@ impl Part {
                 .unwrap()
                 .map(|index| Action::Move(index, None))
                 .unwrap_or(Action::None),
+            // Word boundaries could reveal the structure of a password
+            Command::WordLeft if self.password => Action::Move(0, None),
+            Command::WordRight if self.password => Action::Move(len, None),
             Command::WordLeft if cursor > 0 => {
                 let mut iter = self.as_str()[0..cursor].split_word_bound_indices();
                 let mut p = iter.next_back().map(|(index, _)| index).unwrap_or(0);
@@ -1343,13 +1778,12 @@ impl Part {
                 let x = match self.edit_x_coord {
                     Some(x) => x,
                     None => self
-                        .display
                         .text_glyph_pos(cursor)
                         .next_back()
                         .map(|r| r.pos.0)
                         .unwrap_or(0.0),
                 };
-                let mut line = self.display.find_line(cursor).map(|r| r.0).unwrap_or(0);
+                let mut line = self.find_line(cursor).map(|r| r.0).unwrap_or(0);
                 // We can tolerate invalid line numbers here!
                 line = match cmd {
                     Command::Up => line.wrapping_sub(1),
@@ -1361,25 +1795,16 @@ impl Part {
                     0..=HALF => len,
                     _ => 0,
                 };
-                self.display
-                    .line_index_nearest(line, x)
+                self.line_index_nearest(line, x)
                     .map(|index| Action::Move(index, Some(x)))
                     .unwrap_or(Action::Move(nearest_end, None))
             }
             Command::Home if cursor > 0 => {
-                let index = self
-                    .display
-                    .find_line(cursor)
-                    .map(|r| r.1.start)
-                    .unwrap_or(0);
+                let index = self.find_line(cursor).map(|r| r.1.start).unwrap_or(0);
                 Action::Move(index, None)
             }
             Command::End if cursor < len => {
-                let index = self
-                    .display
-                    .find_line(cursor)
-                    .map(|r| r.1.end)
-                    .unwrap_or(len);
+                let index = self.find_line(cursor).map(|r| r.1.end).unwrap_or(len);
                 Action::Move(index, None)
             }
             Command::DocHome if cursor > 0 => Action::Move(0, None),
@@ -1388,7 +1813,6 @@ impl Part {
             Command::Home | Command::End | Command::DocHome | Command::DocEnd => Action::None,
             Command::PageUp | Command::PageDown if multi_line => {
                 let mut v = self
-                    .display
                     .text_glyph_pos(cursor)
                     .next_back()
                     .map(|r| r.pos.into())
@@ -1406,7 +1830,7 @@ impl Part {
                     h_dist *= -1.0;
                 }
                 v.1 += h_dist;
-                Action::Move(self.display.text_index_nearest(v.into()), Some(v.0))
+                Action::Move(self.text_index_nearest(v), Some(v.0))
             }
             Command::Delete | Command::DelBack if editable && have_sel => {
                 Action::Delete(selection.clone(), EditOp::Delete)
@@ -1421,6 +1845,12 @@ impl Part {
                 .unwrap()
                 .map(|prev| Action::Delete(prev..cursor, EditOp::Delete))
                 .unwrap_or(Action::None),
+            Command::DelWord if editable && self.password => {
+                Action::Delete(cursor..len, EditOp::Delete)
+            }
+            Command::DelWordBack if editable && self.password => {
+                Action::Delete(0..cursor, EditOp::Delete)
+            }
             Command::DelWord if editable => {
                 let next = self.as_str()[cursor..]
                     .split_word_bound_indices()
@@ -1442,6 +1872,7 @@ impl Part {
                 shift = true; // hack
                 Action::Move(len, None)
             }
+            Command::Cut | Command::Copy if self.password => Action::None,
             Command::Cut if editable && have_sel => {
                 cx.set_clipboard((self.as_str()[selection.clone()]).into());
                 Action::Delete(selection.clone(), EditOp::Clipboard)
@@ -1490,17 +1921,11 @@ impl Part {
             }
             Action::Activate => EventAction::Activate(code),
             Action::Insert(s, _) => {
-                let mut index = cursor;
-                let range = if have_sel {
-                    index = selection.start;
-                    selection.clone()
-                } else {
-                    index..index
-                };
-                self.replace_range(range, s);
-                self.selection.set_cursor(index + s.len());
-                self.edit_x_coord = None;
-                EventAction::Edit
+                let range = if have_sel { selection.clone() } else { cursor..cursor };
+                match self.insert_filtered(filter, range, s) {
+                    true => EventAction::Edit,
+                    false => EventAction::Used,
+                }
             }
             Action::Delete(sel, _) => {
                 self.replace_range(sel.clone(), "");
@@ -1509,6 +1934,7 @@ impl Part {
                 EventAction::Edit
             }
             Action::Move(index, x_coord) => {
+                let index = self.folds.skip(index, index > cursor);
                 self.selection.set_edit_index(index);
                 if !shift {
                     self.selection.set_empty();
@@ -1520,9 +1946,11 @@ impl Part {
                 EventAction::Cursor
             }
             Action::UndoRedo(redo) => {
-                if let Some((text, cursor)) = self.undo_stack.undo_or_redo(redo) {
+                if let Some((text, cursor, tag)) = self.undo_stack.undo_or_redo(redo) {
+                    self.undo_tag = *tag;
                     if self.text.as_str() != text {
                         self.text = text.clone();
+                        self.folds.clear();
                         self.status = Status::New;
                         self.edit_x_coord = None;
                     }
@@ -1541,9 +1969,9 @@ impl Part {
     ///
     /// Committing undo state is the responsibility of the caller.
     fn set_cursor_from_coord(&mut self, cx: &mut EventCx, coord: Coord) {
-        let rel_pos: Vec2 = (coord - self.rect.pos).cast();
+        let rel_pos: Vec2 = (coord - self.text_pos()).cast();
         if self.is_prepared() {
-            let index = self.display.text_index_nearest(rel_pos.into());
+            let index = self.text_index_nearest(rel_pos);
             if index != self.selection.edit_index() {
                 self.selection.set_edit_index(index);
                 self.set_view_offset_from_cursor(cx);
@@ -1555,7 +1983,7 @@ impl Part {
 
     /// Set primary clipboard (mouse buffer) contents from selection
     fn set_primary(&self, cx: &mut EventCx) {
-        if self.has_key_focus && !self.selection.is_empty() && cx.has_primary() {
+        if self.has_key_focus && !self.password && !self.selection.is_empty() && cx.has_primary() {
             let range = self.selection.range();
             cx.set_primary(String::from(&self.as_str()[range]));
         }
@@ -1569,10 +1997,10 @@ impl Part {
     fn set_view_offset_from_cursor(&mut self, cx: &mut EventCx) {
         let cursor = self.selection.edit_index();
         if self.is_prepared()
-            && let Some(marker) = self.display.text_glyph_pos(cursor).next_back()
+            && let Some(marker) = self.text_glyph_pos(cursor).next_back()
         {
             let y0 = (marker.pos.1 - marker.ascent).cast_floor();
-            let pos = self.rect.pos + Offset(marker.pos.0.cast_nearest(), y0);
+            let pos = self.text_pos() + Offset(marker.pos.0.cast_nearest(), y0);
             let size = Size(0, i32::conv_ceil(marker.pos.1 - marker.descent) - y0);
             cx.set_scroll(Scroll::Rect(Rect { pos, size }));
         }
@@ -1623,6 +2051,25 @@ impl Editor {
         self.part.save_undo_state(Some(EditOp::Synthetic));
     }
 
+    /// Get the tag of the current undo state
+    ///
+    /// See [`Self::set_undo_tag`].
+    #[inline]
+    pub(crate) fn undo_tag(&self) -> u64 {
+        self.part.undo_tag
+    }
+
+    /// Set the tag saved with the next undo state
+    ///
+    /// Undo states record this tag along with the text and cursor; undo and
+    /// redo restore it. This allows the owner to associate additional state
+    /// (e.g. formatting) with undo history. Call [`Self::pre_commit`] first
+    /// to make the change a distinct undo step.
+    #[inline]
+    pub(crate) fn set_undo_tag(&mut self, tag: u64) {
+        self.part.undo_tag = tag;
+    }
+
     /// Clear text contents and undo history
     #[inline]
     pub fn clear(&mut self, cx: &mut EventState) {
@@ -1658,7 +2105,11 @@ impl Editor {
 
         self.part.cancel_selection_and_ime(cx);
 
+        if self.part.password {
+            zeroize(&mut self.part.text);
+        }
         self.part.text = text;
+        self.part.folds.clear();
         self.part.require_reprepare();
 
         let len = self.as_str().len();
@@ -1698,6 +2149,14 @@ impl Editor {
         self.part.selection = range.into();
     }
 
+    /// True if `range` is a valid cursor range over the current text
+    ///
+    /// This checks that both indices are on `char` boundaries within the text.
+    pub fn is_valid_cursor_range(&self, range: CursorRange) -> bool {
+        let text = self.as_str();
+        text.is_char_boundary(range.sel_index()) && text.is_char_boundary(range.edit_index())
+    }
+
     /// Get whether this text-edit widget is read-only
     #[inline]
     pub fn is_read_only(&self) -> bool {
@@ -1710,6 +2169,47 @@ impl Editor {
         self.part.read_only = read_only;
     }
 
+    /// Get whether this is a password editor
+    #[inline]
+    pub fn is_password(&self) -> bool {
+        self.part.password
+    }
+
+    /// Set whether this is a password editor
+    ///
+    /// In password mode, text is displayed masked (unless
+    /// [revealed](Self::set_password_revealed)), copy, cut and the primary
+    /// selection buffer are disabled, undo history is not recorded, the
+    /// accessibility role is reported as protected and the text buffer is
+    /// zeroed when replaced or dropped.
+    ///
+    /// Setting this clears the undo history.
+    pub fn set_password(&mut self, password: bool) {
+        self.part.password = password;
+        self.part.last_edit = Some(EditOp::Initial);
+        self.part.undo_stack.clear();
+        self.part.require_reprepare();
+    }
+
+    /// Get whether password text is revealed
+    #[inline]
+    pub fn is_password_revealed(&self) -> bool {
+        self.part.revealed
+    }
+
+    /// Set whether password text is revealed
+    ///
+    /// This only affects display of text in [password](Self::set_password)
+    /// mode. The text must be re-prepared after calling this method.
+    pub fn set_password_revealed(&mut self, revealed: bool) {
+        if revealed != self.part.revealed {
+            self.part.revealed = revealed;
+            if self.part.password {
+                self.part.require_reprepare();
+            }
+        }
+    }
+
     /// True if the editor uses multi-line mode
     #[inline]
     pub fn multi_line(&self) -> bool {
@@ -1724,6 +2224,73 @@ impl Editor {
         self.part.has_key_focus || self.part.current.is_ime_enabled()
     }
 
+    /// Get the rect of the text cursor
+    ///
+    /// This is in the coordinate space of the editor's widget, as used by
+    /// [`TileExt::find_tile_rect`](kas::TileExt::find_tile_rect) and
+    /// [`Scroll::Rect`]: where the widget is scrolled by a parent (e.g.
+    /// [`EditBox`](super::EditBox)), the scroll offset is the translation
+    /// returned by `find_tile_rect` and is not included here. Returns `None`
+    /// if the text is not prepared.
+    pub fn cursor_rect(&self) -> Option<Rect> {
+        if !self.part.is_prepared() {
+            return None;
+        }
+
+        let index = self.part.selection.edit_index();
+        let c = self.part.text_glyph_pos(index).next()?;
+        let p1 = Vec2(c.pos.0, c.pos.1 - c.ascent).cast_floor();
+        let p2: Coord = Vec2(c.pos.0, c.pos.1 - c.descent).cast_ceil();
+        let rect = Rect::from_coords(p1, p2 + Offset(1, 0));
+        Some(rect + Offset::conv(self.part.text_pos()))
+    }
+
+    /// Get the layout of each line of text
+    ///
+    /// See [`Part::text_runs`].
+    #[inline]
+    pub fn text_runs(&self) -> Vec<TextRun> {
+        self.part.text_runs()
+    }
+
+    /// True if code folding is enabled
+    #[inline]
+    pub fn is_folding(&self) -> bool {
+        self.part.folding
+    }
+
+    /// Fold all foldable ranges
+    ///
+    /// Returns `true` if the fold state changed. Does nothing unless
+    /// folding is enabled.
+    pub fn fold_all(&mut self) -> bool {
+        let changed = self.part.folds.fold_all();
+        if changed {
+            self.part.fold_changed();
+        }
+        changed
+    }
+
+    /// Unfold all folded ranges
+    ///
+    /// Returns `true` if the fold state changed.
+    pub fn unfold_all(&mut self) -> bool {
+        let changed = self.part.folds.unfold_all();
+        if changed {
+            self.part.fold_changed();
+        }
+        changed
+    }
+
+    /// Fold or unfold the innermost foldable range at the cursor
+    ///
+    /// Returns `true` if the fold state changed. Does nothing unless
+    /// folding is enabled.
+    pub fn toggle_fold(&mut self) -> bool {
+        let index = self.part.selection.edit_index();
+        self.part.toggle_fold_at(index)
+    }
+
     /// Get whether the input state is erroneous
     #[inline]
     pub fn has_error(&self) -> bool {
@@ -1743,8 +2310,16 @@ impl Editor {
     /// in case a text is directly assigned (e.g. using [`Self::set_string`]).
     ///
     /// When set, the input field's background is drawn red. If a message is
-    /// supplied, then a tooltip will be available on mouse-hover.
+    /// supplied, then a tooltip will be available on mouse-hover and the
+    /// message is [announced](EventState::announce) to screen readers (unless
+    /// the same message was shown before the last edit).
     pub fn set_error(&mut self, cx: &mut EventState, message: Option<Cow<'static, str>>) {
+        if let Some(text) = message.as_deref()
+            && self.error_message().or(self.last_error.as_deref()) != Some(text)
+        {
+            cx.announce(text, Live::Assertive);
+        }
+        self.last_error = None;
         self.error_state = Some(message);
         cx.redraw(self.id_ref());
     }
@@ -0,0 +1,359 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Code folding support
+
+use kas::cast::Cast;
+use std::borrow::Cow;
+use std::ops::Range;
+
+/// Fold all foldable ranges
+///
+/// This message may be sent to an [`EditBox`](super::EditBox) or
+/// [`EditBoxCore`](super::EditBoxCore) with folding enabled.
+#[derive(Clone, Debug)]
+pub struct FoldAll;
+
+/// Unfold all folded ranges
+///
+/// This message may be sent to an [`EditBox`](super::EditBox) or
+/// [`EditBoxCore`](super::EditBoxCore) with folding enabled.
+#[derive(Clone, Debug)]
+pub struct UnfoldAll;
+
+/// Fold or unfold the innermost foldable range at the cursor
+///
+/// This message may be sent to an [`EditBox`](super::EditBox) or
+/// [`EditBoxCore`](super::EditBoxCore) with folding enabled.
+#[derive(Clone, Debug)]
+pub struct ToggleFold;
+
+/// Text displayed in place of a folded range
+pub(crate) const PLACEHOLDER: &str = " ⋯";
+const PLACEHOLDER_LEN: usize = PLACEHOLDER.len();
+
+/// Fold state of an editor
+///
+/// All ranges are over the source text. The display text is the source text
+/// with each folded range replaced by [`PLACEHOLDER`].
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Folds {
+    /// Foldable ranges, sorted by start
+    available: Vec<Range<usize>>,
+    /// Folded ranges, sorted and non-overlapping
+    folded: Vec<Range<usize>>,
+}
+
+impl Folds {
+    /// Set the list of foldable ranges
+    ///
+    /// Folded ranges which are no longer available are unfolded.
+    pub fn set_available(&mut self, mut ranges: Vec<Range<usize>>) {
+        ranges.sort_by_key(|r| (r.start, usize::MAX - r.end));
+        ranges.retain(|r| !r.is_empty());
+        self.folded.retain(|f| ranges.contains(f));
+        self.available = ranges;
+    }
+
+    /// Iterate over foldable ranges which are not hidden by another fold
+    pub fn visible_available(&self) -> impl Iterator<Item = &Range<usize>> {
+        self.available.iter().filter(|r| {
+            !self
+                .folded
+                .iter()
+                .any(|f| f.start < r.start && r.start < f.end)
+        })
+    }
+
+    /// True if no ranges are folded
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.folded.is_empty()
+    }
+
+    /// True if `range` is folded
+    #[inline]
+    pub fn is_folded(&self, range: &Range<usize>) -> bool {
+        self.folded.contains(range)
+    }
+
+    /// Fold `range`
+    ///
+    /// Folded ranges contained within `range` are unfolded. Returns `true` on
+    /// success or `false` when `range` is already hidden.
+    pub fn fold(&mut self, range: Range<usize>) -> bool {
+        if self
+            .folded
+            .iter()
+            .any(|f| f.start <= range.start && range.end <= f.end)
+        {
+            return false;
+        }
+
+        self.folded
+            .retain(|f| !(range.start <= f.start && f.end <= range.end));
+        let index = self.folded.partition_point(|f| f.start < range.start);
+        self.folded.insert(index, range);
+        true
+    }
+
+    /// Unfold `range`, returning `true` if it was folded
+    pub fn unfold(&mut self, range: &Range<usize>) -> bool {
+        let len = self.folded.len();
+        self.folded.retain(|f| f != range);
+        self.folded.len() != len
+    }
+
+    /// Fold all top-level foldable ranges
+    pub fn fold_all(&mut self) -> bool {
+        let mut changed = false;
+        for i in 0..self.available.len() {
+            changed |= self.fold(self.available[i].clone());
+        }
+        changed
+    }
+
+    /// Unfold all ranges
+    pub fn unfold_all(&mut self) -> bool {
+        let changed = !self.folded.is_empty();
+        self.folded.clear();
+        changed
+    }
+
+    /// Toggle the innermost foldable range with header ending on the line
+    /// containing `index`
+    ///
+    /// Requires `line_end`: the end of the line containing `index` (excluding
+    /// the line break). Returns `true` if anything changed.
+    pub fn toggle_at(&mut self, index: usize, line_end: usize) -> bool {
+        if let Some(range) = self
+            .folded
+            .iter()
+            .find(|f| f.start == line_end || (f.start <= index && index <= f.end))
+            .cloned()
+        {
+            return self.unfold(&range);
+        }
+
+        let candidate = self
+            .available
+            .iter()
+            .filter(|r| r.start == line_end || (r.start < index && index < r.end))
+            .max_by_key(|r| r.start)
+            .cloned();
+        candidate.map(|r| self.fold(r)).unwrap_or(false)
+    }
+
+    /// Unfold any range hiding `index`
+    pub fn unfold_containing(&mut self, index: usize) -> bool {
+        let len = self.folded.len();
+        self.folded.retain(|f| !(f.start < index && index < f.end));
+        self.folded.len() != len
+    }
+
+    /// Adjust ranges for replacement of `range` with text of length `len`
+    ///
+    /// Folded ranges affected by the edit are unfolded.
+    pub fn edit(&mut self, range: Range<usize>, len: usize) {
+        let adjust = |r: &mut Range<usize>| {
+            if range.end <= r.start {
+                r.start = r.start - range.len() + len;
+                r.end = r.end - range.len() + len;
+                true
+            } else {
+                r.end <= range.start
+            }
+        };
+        self.folded.retain_mut(adjust);
+        self.available.retain_mut(adjust);
+    }
+
+    /// Clear all state
+    pub fn clear(&mut self) {
+        self.available.clear();
+        self.folded.clear();
+    }
+
+    /// Move `index` out of any folded range
+    ///
+    /// If `forward`, the result is the end of the folded range, otherwise the
+    /// start.
+    pub fn skip(&self, index: usize, forward: bool) -> usize {
+        for f in &self.folded {
+            if f.start < index && index < f.end {
+                return if forward { f.end } else { f.start };
+            }
+        }
+        index
+    }
+
+    /// Get the display text
+    pub fn display_text<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        if self.folded.is_empty() {
+            return Cow::Borrowed(text);
+        }
+
+        let mut s = String::with_capacity(text.len());
+        let mut start = 0;
+        for f in &self.folded {
+            s.push_str(&text[start..f.start]);
+            s.push_str(PLACEHOLDER);
+            start = f.end;
+        }
+        s.push_str(&text[start..]);
+        Cow::Owned(s)
+    }
+
+    /// Map a source text index to a display text index
+    ///
+    /// Indices within a folded range map to the start of the placeholder.
+    pub fn to_display(&self, index: usize) -> usize {
+        let mut hidden = 0;
+        let mut count = 0;
+        for f in &self.folded {
+            if index <= f.start {
+                break;
+            } else if index < f.end {
+                return f.start - hidden + count * PLACEHOLDER_LEN;
+            }
+            hidden += f.len();
+            count += 1;
+        }
+        index - hidden + count * PLACEHOLDER_LEN
+    }
+
+    /// Map a display text index to a source text index
+    ///
+    /// Indices within a placeholder map to the start of the folded range.
+    pub fn to_source(&self, index: usize) -> usize {
+        let mut hidden = 0;
+        let mut count = 0;
+        for f in &self.folded {
+            let start = f.start - hidden + count * PLACEHOLDER_LEN;
+            if index <= start {
+                break;
+            } else if index < start + PLACEHOLDER_LEN {
+                return f.start;
+            }
+            hidden += f.len();
+            count += 1;
+        }
+        index + hidden - count * PLACEHOLDER_LEN
+    }
+
+    /// Map tokens from source text to display text
+    pub fn map_tokens<'a, T: Copy>(&self, tokens: &'a [(u32, T)]) -> Cow<'a, [(u32, T)]> {
+        if self.folded.is_empty() {
+            return Cow::Borrowed(tokens);
+        }
+
+        map_tokens(tokens, |index| self.to_display(index))
+    }
+}
+
+/// Map tokens from source text to display text using `to_display`
+///
+/// Tokens mapping to the same display index are de-duplicated, preferring
+/// the last.
+pub(crate) fn map_tokens<'a, T: Copy>(
+    tokens: &'a [(u32, T)],
+    to_display: impl Fn(usize) -> usize,
+) -> Cow<'a, [(u32, T)]> {
+    let mut result: Vec<(u32, T)> = Vec::with_capacity(tokens.len());
+    for (start, token) in tokens.iter().copied() {
+        let start: u32 = to_display(start.cast()).cast();
+        if let Some(last) = result.last_mut()
+            && last.0 == start
+        {
+            last.1 = token;
+        } else {
+            result.push((start, token));
+        }
+    }
+    Cow::Owned(result)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn folds(text_len: usize, ranges: &[Range<usize>]) -> Folds {
+        let mut folds = Folds::default();
+        folds.set_available(ranges.to_vec());
+        for range in ranges {
+            assert!(range.end <= text_len);
+            assert!(folds.fold(range.clone()));
+        }
+        folds
+    }
+
+    #[test]
+    fn display_text() {
+        let text = "0123456789";
+        let folds = folds(text.len(), &[2..5, 7..9]);
+        assert_eq!(folds.display_text(text), "01 ⋯56 ⋯9");
+        assert_eq!(Folds::default().display_text(text), text);
+    }
+
+    #[test]
+    fn to_display() {
+        let folds = folds(10, &[2..5, 7..9]);
+        let expected = [0, 1, 2, 2, 2, 6, 7, 8, 8, 12, 13];
+        for (index, display) in expected.into_iter().enumerate() {
+            assert_eq!(folds.to_display(index), display, "index {index}");
+        }
+    }
+
+    #[test]
+    fn to_source() {
+        let folds = folds(10, &[2..5, 7..9]);
+        let expected = [0, 1, 2, 2, 2, 2, 5, 6, 7, 7, 7, 7, 9, 10];
+        for (index, source) in expected.into_iter().enumerate() {
+            assert_eq!(folds.to_source(index), source, "index {index}");
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let folds = folds(20, &[3..8, 10..11, 15..19]);
+        for index in 0..=20 {
+            let hidden = folds.skip(index, false) != index;
+            if !hidden {
+                assert_eq!(folds.to_source(folds.to_display(index)), index);
+            }
+        }
+    }
+
+    #[test]
+    fn fold_nested() {
+        let mut folds = Folds::default();
+        folds.set_available(vec![2..8, 4..6]);
+        assert!(folds.fold(4..6));
+        assert!(folds.fold(2..8));
+        assert!(!folds.is_folded(&(4..6)));
+        assert!(!folds.fold(4..6));
+        assert_eq!(folds.to_display(8), 2 + PLACEHOLDER_LEN);
+        assert_eq!(folds.visible_available().count(), 1);
+    }
+
+    #[test]
+    fn edit() {
+        let mut folds = folds(10, &[2..5, 7..9]);
+        // Insert before the first fold: both shift
+        folds.edit(0..0, 2);
+        assert!(folds.is_folded(&(4..7)) && folds.is_folded(&(9..11)));
+        // Replace within the first fold: it is removed
+        folds.edit(5..6, 1);
+        assert!(!folds.is_folded(&(4..7)) && folds.is_folded(&(9..11)));
+    }
+
+    #[test]
+    fn map_tokens() {
+        let folds = folds(10, &[2..5]);
+        let tokens = [(0u32, 'a'), (2, 'b'), (3, 'c'), (5, 'd')];
+        let mapped = folds.map_tokens(&tokens);
+        assert_eq!(&*mapped, &[(0, 'a'), (2, 'c'), (6, 'd')]);
+    }
+}
@@ -6,6 +6,7 @@
 //! The [`EditGuard`] trait and some implementations
 
 use super::Editor;
+use super::editor::Insertion;
 use kas::prelude::*;
 use std::fmt::{Debug, Display};
 use std::marker::PhantomData;
@@ -90,6 +91,83 @@ pub trait EditGuard: Sized {
     fn edit(&mut self, edit: &mut Editor, cx: &mut EventCx, data: &Self::Data) {
         let _ = (edit, cx, data);
     }
+
+    /// Cursor guard
+    ///
+    /// This function is called after the text cursor is moved by user input
+    /// without editing the text (e.g. by arrow keys or the mouse). `from` is
+    /// the previous [edit index](kas::text::CursorRange::edit_index). The
+    /// guard may adjust the cursor using [`Editor::set_cursor_range`].
+    ///
+    /// The default implementation does nothing.
+    fn cursor_moved(
+        &mut self,
+        edit: &mut Editor,
+        cx: &mut EventCx,
+        data: &Self::Data,
+        from: usize,
+    ) {
+        let _ = (edit, cx, data, from);
+    }
+
+    /// Insertion filter
+    ///
+    /// This function is called before user input (key input, IME commit or
+    /// paste) is inserted into the text. `text` is the current text. The
+    /// guard may return a modified `insertion` or `None` to reject the input.
+    /// [`Self::edit`] is called afterwards unless the input was rejected.
+    ///
+    /// Programmatic edits (e.g. [`Editor::set_string`]) are not filtered.
+    ///
+    /// The default implementation returns `insertion` unmodified.
+    fn filter_insert(&mut self, text: &str, insertion: Insertion) -> Option<Insertion> {
+        let _ = text;
+        Some(insertion)
+    }
+
+    /// Completion guard
+    ///
+    /// This function is called after [`Self::edit`] on user edits to supply
+    /// completion candidates for the current text. Returning `Some(list)`
+    /// replaces the list of candidates shown in a pop-up below the text
+    /// cursor (an empty list closes the pop-up) while `None` leaves the list
+    /// unchanged.
+    ///
+    /// Candidates may instead be supplied asynchronously by sending
+    /// [`SetCompletions`](super::SetCompletions) to [`Editor::id`], for
+    /// example via [`EventCx::send_async`].
+    ///
+    /// The default implementation returns `None`.
+    fn completions(
+        &mut self,
+        edit: &Editor,
+        cx: &mut EventCx,
+        data: &Self::Data,
+    ) -> Option<Vec<String>> {
+        let _ = (edit, cx, data);
+        None
+    }
+
+    /// Completion-accepted guard
+    ///
+    /// This function is called when the user accepts a `completion` from the
+    /// completion pop-up (see [`Self::completions`]). [`Self::edit`] is called
+    /// afterwards.
+    ///
+    /// The default implementation replaces the whole text with `completion`,
+    /// placing the cursor at the end.
+    fn accept_completion(
+        &mut self,
+        edit: &mut Editor,
+        cx: &mut EventCx,
+        data: &Self::Data,
+        completion: &str,
+    ) {
+        let _ = data;
+        edit.pre_commit();
+        edit.set_str(cx, completion);
+        edit.set_cursor_range(completion.len().into());
+    }
 }
 
 /// Ignore all events and data updates
@@ -18,6 +18,7 @@ pub use syntect::{
 use kas::event::ConfigCx;
 use kas::text::fonts::{FontStyle, FontWeight};
 use kas::text::format::{Color, Colors, Decoration};
+use std::ops::Range;
 
 /// Action: highlighting must be restarted
 #[must_use]
@@ -118,6 +119,72 @@ pub trait Highlighter {
         line: &str,
         push_token: impl FnMut(usize, Token),
     ) -> Result<(), Self::Error>;
+
+    /// Find foldable ranges of a whole `text`
+    ///
+    /// Each range yielded via `push_range` should start at the end of a
+    /// *header* line (excluding its line break) and end at the end of the last
+    /// line of the block (again excluding the line break). When folded, the
+    /// range is replaced by a placeholder. Ranges may be nested and may be
+    /// yielded in any order.
+    ///
+    /// The default implementation uses [`indent_fold_ranges`].
+    fn fold_ranges(&self, text: &str, push_range: impl FnMut(Range<usize>)) {
+        indent_fold_ranges(text, push_range);
+    }
+}
+
+/// Find foldable ranges using indentation
+///
+/// A block is any sequence of lines with greater indentation than the
+/// preceding (header) line. Blank lines do not affect the indentation level
+/// and are not included at the end of a block. Tabs are considered equivalent
+/// to four spaces.
+///
+/// This is the default implementation of [`Highlighter::fold_ranges`]; see
+/// there for the format of yielded ranges.
+pub fn indent_fold_ranges(text: &str, mut push_range: impl FnMut(Range<usize>)) {
+    const TAB_WIDTH: usize = 4;
+
+    // Stack of open blocks: (indent of header, end of header line)
+    let mut stack: Vec<(usize, usize)> = vec![];
+    // Indent and end of the last non-blank line
+    let mut last: Option<(usize, usize)> = None;
+
+    let mut start = 0;
+    for line in text.split_inclusive('\n') {
+        let content = line.trim_end_matches(['\n', '\r']);
+        let trimmed = content.trim_start_matches([' ', '\t']);
+        if !trimmed.is_empty() {
+            let indent = content[..content.len() - trimmed.len()]
+                .chars()
+                .map(|c| if c == '\t' { TAB_WIDTH } else { 1 })
+                .sum();
+            let end = start + content.len();
+
+            if let Some((last_indent, last_end)) = last {
+                while let Some(&(header_indent, header_end)) = stack.last()
+                    && header_indent >= indent
+                {
+                    stack.pop();
+                    push_range(header_end..last_end);
+                }
+
+                if indent > last_indent {
+                    stack.push((last_indent, last_end));
+                }
+            }
+
+            last = Some((indent, end));
+        }
+        start += line.len();
+    }
+
+    if let Some((_, last_end)) = last {
+        for (_, header_end) in stack.into_iter().rev() {
+            push_range(header_end..last_end);
+        }
+    }
 }
 
 /// An implementation of [`Highlighter`] which doesn't highlight anything
@@ -0,0 +1,432 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Input masks
+
+use super::editor::Insertion;
+use super::{EditGuard, Editor};
+use kas::prelude::*;
+use kas::text::CursorRange;
+use std::fmt::Debug;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Slot {
+    Digit,
+    Alpha,
+    AlphaNumeric,
+    Any,
+    Literal(char),
+}
+
+impl Slot {
+    fn accepts(self, c: char) -> bool {
+        match self {
+            Slot::Digit => c.is_ascii_digit(),
+            Slot::Alpha => c.is_alphabetic(),
+            Slot::AlphaNumeric => c.is_alphanumeric(),
+            Slot::Any => !c.is_control(),
+            Slot::Literal(l) => l == c,
+        }
+    }
+}
+
+/// An input mask
+///
+/// A mask is a sequence of input slots and literals, parsed from a pattern
+/// string where:
+///
+/// -   `#` accepts an ASCII digit
+/// -   `A` accepts an alphabetic character
+/// -   `*` accepts an alphanumeric character
+/// -   `?` accepts any non-control character
+/// -   `\` escapes the following character as a literal
+/// -   any other character is a literal
+///
+/// For example, `####-##-##` describes an ISO 8601 date and
+/// `(###) ###-####` a North American phone number.
+///
+/// Masked text consists of input characters interleaved with literals. The
+/// *unmasked* value consists of input characters only.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InputMask {
+    slots: Vec<Slot>,
+}
+
+impl InputMask {
+    /// Construct from a pattern
+    pub fn new(pattern: &str) -> Self {
+        let mut slots = vec![];
+        let mut iter = pattern.chars();
+        while let Some(c) = iter.next() {
+            slots.push(match c {
+                '#' => Slot::Digit,
+                'A' => Slot::Alpha,
+                '*' => Slot::AlphaNumeric,
+                '?' => Slot::Any,
+                '\\' => match iter.next() {
+                    Some(c) => Slot::Literal(c),
+                    None => Slot::Literal('\\'),
+                },
+                c => Slot::Literal(c),
+            });
+        }
+        InputMask { slots }
+    }
+
+    /// Get the number of input slots (excluding literals)
+    pub fn capacity(&self) -> usize {
+        self.slots
+            .iter()
+            .filter(|slot| !matches!(slot, Slot::Literal(_)))
+            .count()
+    }
+
+    /// Apply the mask to `value`
+    ///
+    /// Input characters of `value` are placed in slots, with literals inserted
+    /// as required. Literal characters of `value` matching the next literal of
+    /// the mask are accepted; other characters not accepted by the next slot
+    /// are dropped, as are characters exceeding the capacity of the mask.
+    pub fn apply(&self, value: &str) -> String {
+        let mut pos = 0;
+        let mut out = String::with_capacity(value.len());
+        for c in value.chars() {
+            self.push_char(&mut pos, &mut out, c);
+        }
+        out
+    }
+
+    /// Extract the unmasked value from `text`
+    pub fn unmask(&self, text: &str) -> String {
+        self.unmask_from(text, 0)
+    }
+
+    /// True if all slots of `text` are filled
+    pub fn is_complete(&self, text: &str) -> bool {
+        let mut chars = text.chars();
+        self.slots
+            .iter()
+            .all(|slot| chars.next().is_some_and(|c| slot.accepts(c)))
+            && chars.next().is_none()
+    }
+
+    /// Filter an insertion into masked `text`
+    ///
+    /// The result replaces the whole of `text` with re-masked content. The
+    /// cursor is placed after the last accepted input character and any
+    /// following literals. Returns `None` if no input character is accepted.
+    pub fn filter(&self, text: &str, insertion: Insertion) -> Option<Insertion> {
+        let range = insertion.range;
+        let prefix = self.unmask(&text[..range.start]);
+        let suffix_slot = text[..range.end].chars().count();
+        let suffix = self.unmask_from(&text[range.end..], suffix_slot);
+
+        let mut pos = 0;
+        let mut out = String::with_capacity(text.len() + insertion.text.len());
+        for c in prefix.chars() {
+            self.push_char(&mut pos, &mut out, c);
+        }
+
+        let mut accepted = insertion.text.is_empty();
+        for c in insertion.text.chars() {
+            accepted |= self.push_char(&mut pos, &mut out, c);
+        }
+        if !accepted {
+            return None;
+        }
+
+        let mut cursor = out.len();
+        for c in suffix.chars() {
+            self.push_char(&mut pos, &mut out, c);
+        }
+
+        let mut slot = out[..cursor].chars().count();
+        while let Some(Slot::Literal(l)) = self.slots.get(slot).copied()
+            && out[cursor..].starts_with(l)
+        {
+            cursor += l.len_utf8();
+            slot += 1;
+        }
+
+        Some(Insertion {
+            range: 0..text.len(),
+            text: out,
+            cursor,
+        })
+    }
+
+    /// Get the index in masked `text` following `n` input characters
+    fn input_index(&self, text: &str, n: usize) -> usize {
+        let mut count = 0;
+        for ((index, _), slot) in text.char_indices().zip(&self.slots) {
+            if count == n {
+                return index;
+            }
+            if !matches!(slot, Slot::Literal(_)) {
+                count += 1;
+            }
+        }
+        text.len()
+    }
+
+    /// Move byte `index` in masked `text` such that it does not precede a
+    /// literal
+    ///
+    /// If `forward`, literals are skipped forwards, otherwise backwards (where
+    /// possible).
+    fn skip_literals(&self, text: &str, index: usize, forward: bool) -> usize {
+        let len = text.chars().count();
+        let is_literal = |n: usize| n < len && matches!(self.slots.get(n), Some(Slot::Literal(_)));
+
+        let mut n = text[..index].chars().count();
+        if !forward {
+            while n > 0 && is_literal(n) {
+                n -= 1;
+            }
+        }
+        while is_literal(n) {
+            n += 1;
+        }
+
+        text.char_indices()
+            .nth(n)
+            .map(|(index, _)| index)
+            .unwrap_or(text.len())
+    }
+
+    fn unmask_from(&self, text: &str, first_slot: usize) -> String {
+        text.chars()
+            .zip(first_slot..)
+            .filter(|(c, i)| !matches!(self.slots.get(*i), Some(Slot::Literal(l)) if l == c))
+            .map(|(c, _)| c)
+            .collect()
+    }
+
+    /// Push `c` to `out`, auto-inserting literals
+    ///
+    /// On failure, `pos` and `out` are left unchanged.
+    fn push_char(&self, pos: &mut usize, out: &mut String, c: char) -> bool {
+        let (start_pos, start_len) = (*pos, out.len());
+        while let Some(slot) = self.slots.get(*pos).copied() {
+            *pos += 1;
+            if let Slot::Literal(l) = slot {
+                out.push(l);
+                if l == c {
+                    return true;
+                }
+            } else if slot.accepts(c) {
+                out.push(c);
+                return true;
+            } else {
+                break;
+            }
+        }
+
+        *pos = start_pos;
+        out.truncate(start_len);
+        false
+    }
+}
+
+#[impl_self]
+mod MaskGuard {
+    /// An [`EditGuard`] for masked input
+    ///
+    /// Input is restricted by an [`InputMask`]: invalid keystrokes are
+    /// rejected before insertion while literals are inserted automatically
+    /// and skipped by the cursor. Values passed to and from this guard are
+    /// *unmasked* (see [`InputMask::unmask`]).
+    ///
+    /// The error state is set while the mask is incomplete.
+    #[autoimpl(Debug ignore self.value_fn, self.on_afl)]
+    pub struct MaskGuard<A> {
+        mask: InputMask,
+        value_fn: Box<dyn Fn(&A) -> String + Send>,
+        on_afl: Option<Box<dyn Fn(&mut EventCx, &A, &str) + Send>>,
+        edited: bool,
+    }
+
+    impl Self {
+        /// Construct with a mask and value function
+        ///
+        /// On update, `value_fn` is used to extract an unmasked value from
+        /// input data. If, however, the input field has focus, the update is
+        /// ignored.
+        ///
+        /// No other action happens unless [`Self::with_msg`] is used.
+        pub fn new(mask: InputMask, value_fn: impl Fn(&A) -> String + Send + 'static) -> Self {
+            MaskGuard {
+                mask,
+                value_fn: Box::new(value_fn),
+                on_afl: None,
+                edited: false,
+            }
+        }
+
+        /// Call the handler `f` on activation / focus loss
+        ///
+        /// On field **a**ctivation and **f**ocus **l**oss (AFL) after an edit,
+        /// `f` is called with the unmasked value.
+        pub fn with(mut self, f: impl Fn(&mut EventCx, &A, &str) + Send + 'static) -> Self {
+            debug_assert!(self.on_afl.is_none());
+            self.on_afl = Some(Box::new(f));
+            self
+        }
+
+        /// Send the message generated by `f` on activation / focus loss
+        ///
+        /// On field **a**ctivation and **f**ocus **l**oss (AFL) after an edit,
+        /// `f` is used to construct a message from the unmasked value to be
+        /// emitted via [`EventCx::push`].
+        pub fn with_msg<M: Debug + 'static>(self, f: impl Fn(&str) -> M + Send + 'static) -> Self {
+            self.with(move |cx, _, value| cx.push(f(value)))
+        }
+
+        /// Access the mask
+        #[inline]
+        pub fn mask(&self) -> &InputMask {
+            &self.mask
+        }
+    }
+
+    impl EditGuard for Self {
+        type Data = A;
+
+        fn update(&mut self, edit: &mut Editor, cx: &mut ConfigCx, data: &A) {
+            let value = (self.value_fn)(data);
+            edit.set_string(cx, self.mask.apply(&value));
+        }
+
+        fn focus_lost(&mut self, edit: &mut Editor, cx: &mut EventCx, data: &A) {
+            if self.edited {
+                self.edited = false;
+                if let Some(ref on_afl) = self.on_afl {
+                    on_afl(cx, data, &self.mask.unmask(edit.as_str()));
+                }
+            } else {
+                // Reset data on focus loss (update is inhibited with focus).
+                self.update(edit, cx, data);
+            }
+        }
+
+        fn edit(&mut self, edit: &mut Editor, cx: &mut EventCx, _: &A) {
+            self.edited = true;
+
+            let text = edit.as_str();
+            let masked = self.mask.apply(&self.mask.unmask(text));
+            if masked != text {
+                // Deletion may leave literals out of place: re-mask
+                let cursor = edit.cursor_range().edit_index();
+                let n = self.mask.unmask(&text[..cursor]).chars().count();
+                let index = self.mask.input_index(&masked, n);
+                edit.set_string(cx, masked);
+                edit.set_cursor_range(index.into());
+            }
+
+            if !self.mask.is_complete(edit.as_str()) {
+                edit.set_error(cx, Some("incomplete input".into()));
+            }
+        }
+
+        fn cursor_moved(&mut self, edit: &mut Editor, _: &mut EventCx, _: &A, from: usize) {
+            let range = edit.cursor_range();
+            let index = range.edit_index();
+            let skipped = self.mask.skip_literals(edit.as_str(), index, index >= from);
+            if skipped != index {
+                edit.set_cursor_range(CursorRange::new(range.sel_index(), skipped));
+            }
+        }
+
+        fn filter_insert(&mut self, text: &str, insertion: Insertion) -> Option<Insertion> {
+            self.mask.filter(text, insertion)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::ops::Range;
+
+    fn insertion(range: Range<usize>, text: &str) -> Insertion {
+        Insertion {
+            range,
+            text: text.to_string(),
+            cursor: text.len(),
+        }
+    }
+
+    #[test]
+    fn parse() {
+        use Slot::*;
+        let mask = InputMask::new("(###) A*?\\#\\");
+        let expected = [
+            Literal('('),
+            Digit,
+            Digit,
+            Digit,
+            Literal(')'),
+            Literal(' '),
+            Alpha,
+            AlphaNumeric,
+            Any,
+            Literal('#'),
+            Literal('\\'),
+        ];
+        assert_eq!(mask.slots, expected);
+        assert_eq!(mask.capacity(), 6);
+    }
+
+    #[test]
+    fn apply_and_unmask() {
+        let mask = InputMask::new("####-##-##");
+        assert_eq!(mask.apply("1234"), "1234");
+        assert_eq!(mask.apply("12345678"), "1234-56-78");
+        assert_eq!(mask.apply("2024-01-02"), "2024-01-02");
+        assert_eq!(mask.apply("12a34"), "1234");
+        assert_eq!(mask.apply("123456789"), "1234-56-78");
+        assert_eq!(mask.unmask("1234-56-78"), "12345678");
+        assert!(mask.is_complete("1234-56-78"));
+        assert!(!mask.is_complete("1234-56-7"));
+        assert!(!mask.is_complete("1234x56-78"));
+    }
+
+    #[test]
+    fn filter_append() {
+        let mask = InputMask::new("####-##-##");
+        let result = mask.filter("1234", insertion(4..4, "5")).unwrap();
+        assert_eq!(result.range, 0..4);
+        assert_eq!(result.text, "1234-5");
+        assert_eq!(result.cursor, 6);
+    }
+
+    #[test]
+    fn filter_insert_middle() {
+        let mask = InputMask::new("####-##-##");
+        let result = mask.filter("1234-56", insertion(2..2, "9")).unwrap();
+        assert_eq!(result.range, 0..7);
+        assert_eq!(result.text, "1293-45-6");
+        assert_eq!(result.cursor, 3);
+    }
+
+    #[test]
+    fn filter_reject() {
+        let mask = InputMask::new("####");
+        assert_eq!(mask.filter("12", insertion(2..2, "a")), None);
+        assert_eq!(mask.filter("1234", insertion(4..4, "5")), None);
+    }
+
+    #[test]
+    fn skip_literals() {
+        let mask = InputMask::new("##-##");
+        assert_eq!(mask.skip_literals("12-34", 2, true), 3);
+        assert_eq!(mask.skip_literals("12-34", 2, false), 1);
+        assert_eq!(mask.skip_literals("12-34", 1, true), 1);
+
+        let mask = InputMask::new("(##)");
+        assert_eq!(mask.skip_literals("(12)", 0, false), 1);
+        assert_eq!(mask.skip_literals("(12)", 3, true), 4);
+    }
+}
@@ -3,18 +3,28 @@
 // You may obtain a copy of the License in the LICENSE-APACHE file or at:
 //     https://www.apache.org/licenses/LICENSE-2.0
 
-//! The [`EditBoxCore`] and [`EditBox`] widgets, plus supporting items
+//! The [`EditBoxCore`], [`EditBox`], [`PasswordBox`] and [`RichEditBox`] widgets, plus supporting items
 
+mod complete;
 mod edit_box;
 mod edit_field;
 pub mod editor;
+mod fold;
 mod guard;
 pub mod highlight;
+mod mask;
+mod password;
+mod rich;
 
+pub use complete::SetCompletions;
 pub use edit_box::EditBox;
 pub use edit_field::EditBoxCore;
-pub use editor::Editor;
+pub use editor::{Editor, Insertion};
+pub use fold::{FoldAll, ToggleFold, UnfoldAll};
 pub use guard::*;
+pub use mask::{InputMask, MaskGuard};
+pub use password::PasswordBox;
+pub use rich::{RequestLink, RichEditBox, RichEditField, RichHighlighter, SetLink};
 
 use kas::event::PhysicalKey;
 use std::fmt::Debug;
@@ -0,0 +1,178 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Password entry support
+
+use super::{DefaultGuard, EditBox, EditGuard, Editor};
+use crate::CheckButton;
+use kas::prelude::*;
+use zeroize::Zeroize;
+
+/// Character displayed in place of each grapheme of masked text
+pub(crate) const MASK: char = '•';
+
+/// Overwrite and release the buffer of `s`
+///
+/// The whole allocation is overwritten (see [`Zeroize`]), including spare
+/// capacity which may hold bytes of earlier (longer) contents. Prior
+/// re-allocations may still have left copies elsewhere.
+pub(crate) fn zeroize(s: &mut String) {
+    overwrite(s);
+    *s = String::new();
+}
+
+/// Overwrite the whole allocation of `s` with zeros and clear `s`
+fn overwrite(s: &mut String) {
+    s.zeroize();
+}
+
+#[derive(Clone, Debug)]
+struct RevealPassword(bool);
+
+#[impl_self]
+mod PasswordBox {
+    /// A password entry box with a reveal toggle
+    ///
+    /// This is an [`EditBox`] in [password mode](Editor::set_password) with a
+    /// check button allowing the text to be revealed. Use
+    /// [`EditBox::with_password`] for password entry without the toggle.
+    ///
+    /// ### Messages
+    ///
+    /// Messages are handled as by [`EditBox`].
+    #[autoimpl(Debug where G: trait)]
+    #[autoimpl(Deref<Target = Editor> using self.edit)]
+    #[widget]
+    #[layout(row![self.edit, self.reveal])]
+    pub struct PasswordBox<G: EditGuard = DefaultGuard<()>> {
+        core: widget_core!(),
+        revealed: bool,
+        #[widget]
+        edit: EditBox<G>,
+        #[widget(&self.revealed)]
+        reveal: CheckButton<bool>,
+    }
+
+    impl Events for Self {
+        type Data = G::Data;
+
+        fn handle_messages(&mut self, cx: &mut EventCx, _: &G::Data) {
+            if let Some(RevealPassword(revealed)) = cx.try_pop() {
+                self.set_revealed(cx, revealed);
+            }
+        }
+    }
+
+    impl<G: EditGuard> Default for PasswordBox<G>
+    where
+        G: Default,
+    {
+        #[inline]
+        fn default() -> Self {
+            PasswordBox::new(G::default())
+        }
+    }
+
+    impl Self {
+        /// Construct with an [`EditGuard`]
+        pub fn new(guard: G) -> Self {
+            PasswordBox {
+                core: Default::default(),
+                revealed: false,
+                edit: EditBox::new(guard).with_password(true),
+                reveal: CheckButton::new_msg(
+                    "Show",
+                    |_, revealed: &bool| *revealed,
+                    RevealPassword,
+                ),
+            }
+        }
+
+        /// Access the edit box
+        #[inline]
+        pub fn edit_box(&self) -> &EditBox<G> {
+            &self.edit
+        }
+
+        /// Access the edit box mutably
+        #[inline]
+        pub fn edit_box_mut(&mut self) -> &mut EditBox<G> {
+            &mut self.edit
+        }
+
+        /// Get whether the password is revealed
+        #[inline]
+        pub fn is_revealed(&self) -> bool {
+            self.revealed
+        }
+
+        /// Set whether the password is revealed
+        pub fn set_revealed(&mut self, cx: &mut EventCx, revealed: bool) {
+            if revealed == self.revealed {
+                return;
+            }
+
+            self.revealed = revealed;
+            self.edit.set_password_revealed(cx, revealed);
+            cx.update(self.reveal.as_node(&self.revealed));
+        }
+    }
+}
+
+impl<A: 'static> PasswordBox<DefaultGuard<A>> {
+    /// Construct a `PasswordBox` with the given inital `text` (no event handling)
+    #[inline]
+    pub fn text<S: ToString>(text: S) -> Self {
+        PasswordBox {
+            edit: EditBox::text(text).with_password(true),
+            ..Default::default()
+        }
+    }
+}
+
+impl<G: EditGuard> PasswordBox<G> {
+    /// Set the initial text (inline)
+    ///
+    /// This method should only be used on a new `PasswordBox`.
+    #[inline]
+    #[must_use]
+    pub fn with_text(mut self, text: impl ToString) -> Self {
+        self.edit = self.edit.with_text(text);
+        self
+    }
+
+    /// Set whether the password is initially revealed (inline)
+    #[inline]
+    #[must_use]
+    pub fn with_revealed(mut self, revealed: bool) -> Self {
+        self.revealed = revealed;
+        self.edit = self.edit.with_password_revealed(revealed);
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn zeroize_overwrites_text() {
+        let mut s = String::with_capacity(64);
+        s.push_str("correct horse battery staple");
+        s.truncate(7);
+        let (ptr, cap) = (s.as_ptr(), s.capacity());
+
+        overwrite(&mut s);
+        assert!(s.is_empty());
+        assert_eq!((s.as_ptr(), s.capacity()), (ptr, cap));
+        // Safety: the allocation is live and was initialized by `overwrite`
+        let bytes = unsafe { std::slice::from_raw_parts(ptr, cap) };
+        assert!(bytes.iter().all(|b| *b == 0));
+
+        zeroize(&mut s);
+        assert!(s.is_empty());
+        assert_eq!(s.capacity(), 0);
+    }
+}
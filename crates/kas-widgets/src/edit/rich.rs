@@ -0,0 +1,645 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! The [`RichEditField`] and [`RichEditBox`] widgets
+
+use super::editor::{Component, EventAction};
+use super::highlight::{ActionRestart, Highlighter, SchemeColors, Token};
+use super::*;
+use crate::ScrollRegion;
+use kas::event::{Command, ConfigCx, CursorIcon, ElementState};
+use kas::messages::{ReplaceSelectedText, SetTextSelection, SetValueText};
+use kas::prelude::*;
+use kas::text::fonts::{FontStyle, FontWeight};
+use kas::text::format::{RichAttr, RichFormat, RichText};
+use kas::theme::{FrameStyle, TextClass};
+use std::collections::VecDeque;
+use std::ops::{Deref, Range};
+
+/// Maximum number of formatting snapshots retained for undo
+const HISTORY_LEN: usize = 100;
+
+/// Message: request a link target
+///
+/// This message is emitted by a [`RichEditField`] when the user invokes
+/// [`Command::Link`] over a selection which is not already a link. The
+/// handler should determine a link target (e.g. by opening a dialog) and
+/// reply by sending [`SetLink`] to the widget with id [`Self::id`].
+#[derive(Clone, Debug)]
+pub struct RequestLink {
+    /// Identifier of the originating widget
+    pub id: Id,
+    /// The selected text
+    pub text: String,
+}
+
+/// Message: set or clear a link over the selection
+///
+/// This message may be sent to a [`RichEditField`] or [`RichEditBox`],
+/// usually in reply to [`RequestLink`].
+#[derive(Clone, Debug)]
+pub struct SetLink(pub Option<String>);
+
+/// A [`Highlighter`] for [`RichText`]
+///
+/// This highlighter does not parse the text but yields tokens from the
+/// formatting runs of a [`RichText`] mirroring the editor's content.
+#[derive(Clone, Debug, Default)]
+pub struct RichHighlighter {
+    text: RichText,
+}
+
+impl RichHighlighter {
+    /// Access the rich text
+    #[inline]
+    pub fn text(&self) -> &RichText {
+        &self.text
+    }
+}
+
+impl Highlighter for RichHighlighter {
+    type Error = std::convert::Infallible;
+    /// Byte offset of the next line
+    type State = usize;
+
+    #[inline]
+    fn configure(&mut self, _: &mut ConfigCx) -> Option<ActionRestart> {
+        None
+    }
+
+    #[inline]
+    fn scheme_colors(&self) -> SchemeColors {
+        SchemeColors::default()
+    }
+
+    #[inline]
+    fn new_state(&self) -> Self::State {
+        0
+    }
+
+    fn highlight_line(
+        &self,
+        state: &mut Self::State,
+        line: &str,
+        mut push_token: impl FnMut(usize, Token),
+    ) -> Result<(), Self::Error> {
+        let start = *state;
+        let end = start + line.len();
+        *state = end;
+
+        for (range, format) in self.text.segments() {
+            if range.end <= start {
+                continue;
+            } else if end <= range.start {
+                break;
+            }
+
+            let token = Token {
+                colors: format.colors(),
+                weight: match format.bold {
+                    false => FontWeight::default(),
+                    true => FontWeight::BOLD,
+                },
+                style: match format.italic {
+                    false => FontStyle::default(),
+                    true => FontStyle::Italic,
+                },
+                decoration: format.decoration(),
+            };
+            push_token(range.start.max(start) - start, token);
+        }
+        Ok(())
+    }
+}
+
+/// Find the changed range between two texts
+///
+/// Returns the range replaced in `old` and the replacement range in `new`.
+fn diff(old: &str, new: &str) -> (Range<usize>, Range<usize>) {
+    let mut prefix = old
+        .bytes()
+        .zip(new.bytes())
+        .take_while(|(a, b)| a == b)
+        .count();
+    while !old.is_char_boundary(prefix) {
+        prefix -= 1;
+    }
+
+    let max = old.len().min(new.len()) - prefix;
+    let mut suffix = old[prefix..]
+        .bytes()
+        .rev()
+        .zip(new[prefix..].bytes().rev())
+        .take_while(|(a, b)| a == b)
+        .count()
+        .min(max);
+    while !old.is_char_boundary(old.len() - suffix) || !new.is_char_boundary(new.len() - suffix) {
+        suffix -= 1;
+    }
+
+    (prefix..old.len() - suffix, prefix..new.len() - suffix)
+}
+
+#[impl_self]
+mod RichEditField {
+    /// A multi-line editor for rich text
+    ///
+    /// The [`RichEditBox`] widget should usually be preferred; this widget is
+    /// a component of [`RichEditBox`].
+    ///
+    /// Content is represented as a [`RichText`]. Standard text editing is
+    /// supported as with [`EditBoxCore`]; in addition formatting may be applied
+    /// to the selection using [`Command::Bold`], [`Command::Italic`],
+    /// [`Command::Underline`] and [`Command::Link`] (by default, bound to
+    /// <kbd>Ctrl+B</kbd>, <kbd>Ctrl+I</kbd>, <kbd>Ctrl+U</kbd> and
+    /// <kbd>Ctrl+K</kbd>). When the selection is empty, formatting commands
+    /// instead affect the next text typed.
+    ///
+    /// Formatting is preserved by copy and paste within the application (see
+    /// [`EventCx::set_rich_clipboard`]); other applications receive plain text.
+    /// A snapshot of the formatted text is taken on each text or formatting
+    /// change and associated with the corresponding step of the undo history,
+    /// thus undo and redo restore formatting. Formatting changes (including
+    /// links) are distinct undo steps. Only the most recent snapshots are
+    /// retained; undo beyond these restores text without its formatting.
+    ///
+    /// Content may be converted to or from Markdown and HTML using methods of
+    /// [`RichText`].
+    ///
+    /// ### Messages
+    ///
+    /// [`RequestLink`] is emitted on [`Command::Link`] over a selection which
+    /// is not already a link; [`SetLink`] may be sent in reply. When the
+    /// selection is already a link, [`Command::Link`] removes the link.
+    ///
+    /// [`SetValueText`] may be used to replace the entire text and
+    /// [`ReplaceSelectedText`] may be used to replace selected text when this
+    /// widget is not [read-only](Editor::is_read_only). Inserted text is
+    /// unformatted.
+    ///
+    /// [`SetTextSelection`] may be used to set the cursor and selection.
+    ///
+    /// ### Special behaviour
+    ///
+    /// This is a [`Viewport`] widget.
+    #[autoimpl(Debug ignore self.on_edit)]
+    #[widget]
+    #[layout(self.editor)]
+    pub struct RichEditField {
+        core: widget_core!(),
+        width: (f32, f32),
+        lines: (f32, f32),
+        editor: Component<RichHighlighter>,
+        /// Format applied to the next insertion (at an empty selection)
+        typing: Option<RichFormat>,
+        /// Range awaiting a [`SetLink`] reply
+        link_range: Option<Range<usize>>,
+        /// The last revision number assigned (used as the editor's undo tag)
+        revision: u64,
+        /// Snapshots by revision, restored on undo and redo
+        history: VecDeque<(u64, RichText)>,
+        on_edit: Option<Box<dyn Fn(&mut EventCx, &RichText) + Send>>,
+    }
+
+    impl Deref for Self {
+        type Target = Editor;
+        fn deref(&self) -> &Self::Target {
+            &self.editor.0
+        }
+    }
+
+    impl Layout for Self {
+        fn size_rules(&mut self, cx: &mut SizeCx, axis: AxisInfo) -> SizeRules {
+            let (min, mut ideal): (i32, i32);
+            if axis.is_horizontal() {
+                let dpem = cx.dpem(TextClass::Editor);
+                min = (self.width.0 * dpem).cast_ceil();
+                ideal = (self.width.1 * dpem).cast_ceil();
+            } else if let Some(width) = axis.other() {
+                let height = self
+                    .editor
+                    .measure_height(width.cast(), std::num::NonZero::new(1));
+                min = (self.lines.0 * height).cast_ceil();
+                ideal = (self.lines.1 * height).cast_ceil();
+            } else {
+                unreachable!()
+            };
+
+            let rules = self.editor.size_rules(cx, axis);
+            ideal = ideal.max(rules.ideal_size());
+
+            SizeRules::new(min, ideal, Stretch::High).with_margins(cx.text_margins().extract(axis))
+        }
+    }
+
+    impl Viewport for Self {
+        #[inline]
+        fn content_size(&self) -> Size {
+            self.editor.part().content_size()
+        }
+
+        #[inline]
+        fn draw_with_offset(&self, draw: DrawCx, rect: Rect, offset: Offset) {
+            self.editor.draw_with_offset(draw, rect, offset);
+        }
+    }
+
+    impl Tile for Self {
+        fn navigable(&self) -> bool {
+            true
+        }
+
+        fn role(&self, cx: &mut dyn RoleCx) -> Role<'_> {
+            cx.set_text_runs(self.text_runs());
+            Role::TextInput {
+                text: self.as_str(),
+                multi_line: true,
+                protected: false,
+                cursor: self.cursor_range(),
+            }
+        }
+    }
+
+    impl Events for Self {
+        const REDRAW_ON_MOUSE_OVER: bool = true;
+
+        type Data = ();
+
+        fn probe(&self, _: Coord) -> Id {
+            self.id()
+        }
+
+        #[inline]
+        fn mouse_over_icon(&self) -> Option<CursorIcon> {
+            Some(CursorIcon::Text)
+        }
+
+        fn configure(&mut self, cx: &mut ConfigCx) {
+            self.editor.configure(cx, self.id());
+        }
+
+        fn update(&mut self, _: &mut ConfigCx, _: &()) {
+            self.editor.prepare();
+        }
+
+        fn handle_event(&mut self, cx: &mut EventCx, _: &(), event: Event) -> IsUsed {
+            let cmd = match &event {
+                Event::Command(cmd, _) => Some(*cmd),
+                Event::Key(key, false) if key.state == ElementState::Pressed => cx
+                    .config()
+                    .shortcuts()
+                    .try_match_event(cx.modifiers(), key),
+                _ => None,
+            };
+
+            let mut paste = None;
+            match cmd {
+                Some(Command::Bold) => return self.toggle_format(cx, RichAttr::Bold),
+                Some(Command::Italic) => return self.toggle_format(cx, RichAttr::Italic),
+                Some(Command::Underline) => return self.toggle_format(cx, RichAttr::Underline),
+                Some(Command::Link) => return self.link(cx),
+                Some(Command::Copy | Command::Cut) => {
+                    let range = self.cursor_range().range();
+                    if !range.is_empty() {
+                        cx.set_rich_clipboard(self.editor.highlighter().text.slice(range));
+                    }
+                }
+                Some(Command::Paste) => {
+                    paste = cx.get_rich_clipboard();
+                }
+                _ => (),
+            }
+            let undo = matches!(cmd, Some(Command::Undo | Command::Redo));
+
+            match self.editor.handle_event(cx, event) {
+                EventAction::Unused => Unused,
+                EventAction::Cursor => {
+                    self.typing = None;
+                    Used
+                }
+                EventAction::FocusLost => {
+                    self.typing = None;
+                    Used
+                }
+                EventAction::Used
+                | EventAction::FocusGained
+                | EventAction::Activate(_)
+                | EventAction::Preedit
+                | EventAction::Fold => Used,
+                EventAction::Edit => {
+                    self.sync(cx, paste, undo);
+                    Used
+                }
+            }
+        }
+
+        fn handle_messages(&mut self, cx: &mut EventCx, _: &()) {
+            if let Some(SetTextSelection(range)) = cx.try_pop() {
+                if self.is_valid_cursor_range(range) {
+                    self.editor.0.set_cursor_range(range);
+                    cx.redraw();
+                }
+                return;
+            }
+
+            if self.is_read_only() {
+                return;
+            }
+
+            if let Some(SetLink(target)) = cx.try_pop() {
+                let range = match self.link_range.take() {
+                    Some(range) => range,
+                    None => self.cursor_range().range(),
+                };
+                if range.is_empty() || range.end > self.as_str().len() {
+                    return;
+                }
+
+                let link = target.map(Into::into);
+                self.edit_format(cx, |text| text.update_format(range, |f| f.link = link.clone()));
+            } else if let Some(SetValueText(string)) = cx.try_pop() {
+                self.editor.0.pre_commit();
+                self.editor.0.set_string(cx, string);
+                self.sync(cx, None, false);
+            } else if let Some(ReplaceSelectedText(text)) = cx.try_pop() {
+                self.editor.0.pre_commit();
+                self.editor.0.replace_selected_text(cx, &text);
+                self.sync(cx, None, false);
+            }
+        }
+    }
+
+    impl Default for Self {
+        #[inline]
+        fn default() -> Self {
+            Self::new(RichText::default())
+        }
+    }
+
+    impl Self {
+        /// Construct with the given initial `text`
+        pub fn new(text: RichText) -> Self {
+            let mut editor = Component::new(true).with_text(text.as_str());
+            editor.highlighter_mut().text = text.clone();
+            RichEditField {
+                core: Default::default(),
+                width: (8.0, 16.0),
+                lines: (4.0, 7.0),
+                editor,
+                typing: None,
+                link_range: None,
+                revision: 0,
+                history: VecDeque::from([(0, text)]),
+                on_edit: None,
+            }
+        }
+
+        /// Call `f` on each edit (inline)
+        ///
+        /// The closure `f` is called after each change to text or formatting,
+        /// with the new text. The resulting message is pushed.
+        #[inline]
+        #[must_use]
+        pub fn with_msg<M>(mut self, f: impl Fn(&RichText) -> M + Send + 'static) -> Self
+        where
+            M: std::fmt::Debug + 'static,
+        {
+            self.on_edit = Some(Box::new(move |cx, text| cx.push(f(text))));
+            self
+        }
+
+        /// Set whether this widget is read-only (inline)
+        #[inline]
+        #[must_use]
+        pub fn with_read_only(mut self, read_only: bool) -> Self {
+            self.editor.0.set_read_only(read_only);
+            self
+        }
+
+        /// Adjust the height allocation (inline)
+        #[inline]
+        #[must_use]
+        pub fn with_lines(mut self, min_lines: f32, ideal_lines: f32) -> Self {
+            self.lines = (min_lines, ideal_lines);
+            self
+        }
+
+        /// Adjust the width allocation (inline)
+        #[inline]
+        #[must_use]
+        pub fn with_width_em(mut self, min_em: f32, ideal_em: f32) -> Self {
+            self.width = (min_em, ideal_em);
+            self
+        }
+
+        /// Access the rich text
+        #[inline]
+        pub fn rich_text(&self) -> &RichText {
+            self.editor.highlighter().text()
+        }
+
+        /// Replace the rich text
+        ///
+        /// This clears formatting snapshots (thus undo of earlier edits will
+        /// not restore their formatting) but does not otherwise interact with
+        /// undo history.
+        pub fn set_rich_text(&mut self, cx: &mut EventState, text: RichText) {
+            self.editor.0.set_string(cx, text.as_str().to_string());
+            self.editor.highlighter_mut().text = text;
+            self.typing = None;
+            self.link_range = None;
+            self.history.clear();
+            self.push_history();
+        }
+
+        /// Toggle formatting attribute `attr` over the selection
+        fn toggle_format(&mut self, cx: &mut EventCx, attr: RichAttr) -> IsUsed {
+            if self.is_read_only() {
+                return Unused;
+            }
+
+            let range = self.cursor_range().range();
+            if range.is_empty() {
+                let text = &self.editor.highlighter().text;
+                let mut format = self
+                    .typing
+                    .take()
+                    .unwrap_or_else(|| text.insertion_format(range.start));
+                let value = !format.has(&attr);
+                format.set(&attr, value);
+                self.typing = Some(format);
+            } else {
+                self.edit_format(cx, |text| {
+                    text.toggle(range, &attr);
+                });
+            }
+            Used
+        }
+
+        /// Handle [`Command::Link`]
+        fn link(&mut self, cx: &mut EventCx) -> IsUsed {
+            if self.is_read_only() {
+                return Unused;
+            }
+
+            let range = self.cursor_range().range();
+            if range.is_empty() {
+                return Used;
+            }
+
+            let link = RichAttr::Link("".into());
+            if self.editor.highlighter().text.range_has(range.clone(), &link) {
+                self.edit_format(cx, |text| text.update_format(range, |f| f.link = None));
+            } else {
+                let text = self.as_str()[range.clone()].to_string();
+                self.link_range = Some(range);
+                cx.push(RequestLink {
+                    id: self.id(),
+                    text,
+                });
+            }
+            Used
+        }
+
+        /// Apply a formatting change
+        ///
+        /// This is recorded as a distinct step in the undo history.
+        fn edit_format(&mut self, cx: &mut EventCx, f: impl FnOnce(&mut RichText)) {
+            self.editor.0.pre_commit();
+            f(&mut self.editor.highlighter_mut().text);
+            self.push_history();
+            self.finish_edit(cx);
+        }
+
+        /// Update the rich text following an edit to the plain text
+        ///
+        /// If `paste` is provided and matches the inserted text, it is used as
+        /// the replacement. If `undo`, formatting is restored from history.
+        fn sync(&mut self, cx: &mut EventCx, paste: Option<RichText>, undo: bool) {
+            self.link_range = None;
+            let typing = self.typing.take();
+            let new = self.editor.0.as_str();
+            let old = &self.editor.highlighter().text;
+
+            if undo {
+                let revision = self.editor.0.undo_tag();
+                if let Some((_, text)) = self.history.iter().find(|(r, _)| *r == revision)
+                    && text.as_str() == new
+                {
+                    if text != old {
+                        self.editor.highlighter_mut().text = text.clone();
+                        self.finish_edit(cx);
+                    }
+                    return;
+                }
+            }
+
+            if old.as_str() == new {
+                return;
+            }
+
+            let (range, inserted) = diff(old.as_str(), new);
+            let replacement = match paste {
+                Some(rt) if rt.as_str() == &new[inserted.clone()] => rt,
+                _ => {
+                    let format = typing.unwrap_or_else(|| old.insertion_format(range.start));
+                    RichText::with_format(&new[inserted], format)
+                }
+            };
+            let mut text = old.clone();
+            text.replace_range(range, &replacement);
+
+            self.editor.highlighter_mut().text = text;
+            self.push_history();
+            self.finish_edit(cx);
+        }
+
+        /// Record the current text as a new revision
+        fn push_history(&mut self) {
+            self.revision += 1;
+            self.editor.0.set_undo_tag(self.revision);
+            let text = self.editor.highlighter().text.clone();
+            self.history.push_back((self.revision, text));
+            if self.history.len() > HISTORY_LEN {
+                self.history.pop_front();
+            }
+        }
+
+        fn finish_edit(&mut self, cx: &mut EventCx) {
+            self.editor.prepare_and_scroll(cx);
+            if let Some(ref f) = self.on_edit {
+                f(cx, self.editor.highlighter().text());
+            }
+        }
+    }
+}
+
+#[impl_self]
+mod RichEditBox {
+    /// A rich-text editor
+    ///
+    /// This is a [`RichEditField`] within a frame and a scrollable region. See
+    /// [`RichEditField`] for documentation of behaviour.
+    #[widget]
+    #[layout(frame!(self.inner).with_style(FrameStyle::EditBox))]
+    pub struct RichEditBox {
+        core: widget_core!(),
+        #[widget]
+        inner: ScrollRegion<RichEditField>,
+    }
+
+    impl Events for Self {
+        type Data = ();
+
+        fn handle_messages(&mut self, cx: &mut EventCx, _: &()) {
+            if let Some(SetLink(target)) = cx.try_pop() {
+                cx.send(self.inner.inner().id(), SetLink(target));
+            }
+        }
+    }
+
+    impl Default for Self {
+        #[inline]
+        fn default() -> Self {
+            Self::new(RichText::default())
+        }
+    }
+
+    impl Self {
+        /// Construct with the given initial `text`
+        #[inline]
+        pub fn new(text: RichText) -> Self {
+            Self::from(RichEditField::new(text))
+        }
+
+        /// Access the inner field
+        #[inline]
+        pub fn field(&self) -> &RichEditField {
+            self.inner.inner()
+        }
+
+        /// Access the inner field mutably
+        #[inline]
+        pub fn field_mut(&mut self) -> &mut RichEditField {
+            self.inner.inner_mut()
+        }
+
+        /// Access the rich text
+        #[inline]
+        pub fn rich_text(&self) -> &RichText {
+            self.field().rich_text()
+        }
+    }
+
+    impl From<RichEditField> for Self {
+        #[inline]
+        fn from(field: RichEditField) -> Self {
+            RichEditBox {
+                core: Default::default(),
+                inner: ScrollRegion::new_viewport(field),
+            }
+        }
+    }
+}
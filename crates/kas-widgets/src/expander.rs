@@ -0,0 +1,659 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Expander and accordion containers
+
+use crate::{AccessLabel, Mark};
+use kas::event::{Command, TimerHandle};
+use kas::layout::{DynRowStorage, RowSetter, RowSolver, RulesSetter, RulesSolver};
+use kas::messages::{Activate, Collapse, Expand};
+use kas::prelude::*;
+use kas::theme::{FrameStyle, MarkStyle};
+use std::time::{Duration, Instant};
+
+/// Duration of the show/hide animation
+const ANIM_DURATION: Duration = Duration::from_millis(150);
+
+const TIMER_ANIM: TimerHandle = TimerHandle::new(0, true);
+
+/// Message: the expanded state of an [`Expander`] was changed
+///
+/// This is pushed when the user expands or collapses an [`Expander`], but not
+/// when [`Expander::set_expanded`] is called.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExpanderMsg {
+    /// The content was shown
+    Expanded,
+    /// The content was hidden
+    Collapsed,
+}
+
+/// Toggle the expanded state
+#[derive(Debug)]
+struct Toggle;
+
+fn mark_style(expanded: bool) -> MarkStyle {
+    let dir = match expanded {
+        false => Direction::Right,
+        true => Direction::Down,
+    };
+    MarkStyle::Chevron(dir)
+}
+
+#[impl_self]
+mod Header {
+    /// The header of an [`Expander`]
+    #[widget]
+    #[layout(
+        frame!(row![self.mark, self.label])
+            .with_style(FrameStyle::InvisibleButton)
+    )]
+    struct Header {
+        core: widget_core!(),
+        #[widget]
+        mark: Mark,
+        #[widget]
+        label: AccessLabel,
+        expanded: bool,
+    }
+
+    impl Self {
+        fn new(label: AccessLabel, expanded: bool) -> Self {
+            Header {
+                core: Default::default(),
+                mark: Mark::new(mark_style(expanded), "Expand"),
+                label,
+                expanded,
+            }
+        }
+
+        fn set_expanded(&mut self, expanded: bool) {
+            self.expanded = expanded;
+            self.mark.set_mark(mark_style(expanded));
+        }
+    }
+
+    impl Tile for Self {
+        fn navigable(&self) -> bool {
+            true
+        }
+
+        fn role(&self, _: &mut dyn RoleCx) -> Role<'_> {
+            Role::Disclosure {
+                expanded: self.expanded,
+            }
+        }
+    }
+
+    impl Events for Self {
+        const REDRAW_ON_MOUSE_OVER: bool = true;
+
+        type Data = ();
+
+        fn probe(&self, _: Coord) -> Id {
+            self.id()
+        }
+
+        fn handle_event(&mut self, cx: &mut EventCx, _: &(), event: Event) -> IsUsed {
+            match event {
+                Event::Command(Command::Left, _) if self.expanded => {
+                    cx.push(Collapse);
+                    Used
+                }
+                Event::Command(Command::Right, _) if !self.expanded => {
+                    cx.push(Expand);
+                    Used
+                }
+                event => event.on_click(cx, self.id(), |cx| cx.push(Toggle)),
+            }
+        }
+
+        fn handle_messages(&mut self, cx: &mut EventCx, _: &()) {
+            if let Some(Activate(code)) = cx.try_pop() {
+                cx.push(Toggle);
+                cx.depress_with_key(&self, code);
+            }
+        }
+    }
+}
+
+#[impl_self]
+mod Expander {
+    /// A container with a header which shows or hides its content
+    ///
+    /// The header consists of a disclosure mark and a label. Clicking the
+    /// header or activating it via the keyboard toggles visibility of the
+    /// `inner` content; <kbd>Left</kbd> and <kbd>Right</kbd> arrow keys may
+    /// also be used to collapse and expand.
+    ///
+    /// By default, showing and hiding the content is animated (the content is
+    /// revealed or hidden progressively from the top); this may be disabled
+    /// with [`Self::with_animation`]. Space for the content is allocated when
+    /// expanding starts and released when collapsing completes.
+    ///
+    /// ### Messages
+    ///
+    /// [`kas::messages::Expand`] and [`kas::messages::Collapse`] may be used to
+    /// show and hide the content.
+    ///
+    /// [`ExpanderMsg`] is pushed when the user changes the expanded state.
+    #[widget]
+    pub struct Expander<W: Widget> {
+        core: widget_core!(),
+        #[widget(&())]
+        header: Header,
+        /// The inner widget
+        #[widget]
+        pub inner: W,
+        expanded: bool,
+        animate: bool,
+        anim_start: Option<Instant>,
+        // Animation progress (0 to 1) as of the last frame
+        anim_progress: f32,
+        header_rules: SizeRules,
+        body_rules: SizeRules,
+        body_rect: Rect,
+    }
+
+    impl Self {
+        /// Construct with a `label` and `inner` content
+        ///
+        /// The expander is initially collapsed.
+        #[inline]
+        pub fn new(label: impl Into<AccessString>, inner: W) -> Self {
+            Expander {
+                core: Default::default(),
+                header: Header::new(AccessLabel::new(label), false),
+                inner,
+                expanded: false,
+                animate: true,
+                anim_start: None,
+                anim_progress: 1.0,
+                header_rules: SizeRules::EMPTY,
+                body_rules: SizeRules::EMPTY,
+                body_rect: Rect::ZERO,
+            }
+        }
+
+        /// Set the initial expanded state (inline)
+        #[inline]
+        #[must_use]
+        pub fn with_expanded(mut self, expanded: bool) -> Self {
+            self.expanded = expanded;
+            self.header.set_expanded(expanded);
+            self
+        }
+
+        /// Enable or disable animation (inline)
+        ///
+        /// By default this is enabled.
+        #[inline]
+        #[must_use]
+        pub fn with_animation(mut self, animate: bool) -> Self {
+            self.animate = animate;
+            self
+        }
+
+        /// Get whether the content is shown
+        #[inline]
+        pub fn is_expanded(&self) -> bool {
+            self.expanded
+        }
+
+        /// Show or hide the content
+        ///
+        /// This does not push [`ExpanderMsg`].
+        pub fn set_expanded(&mut self, cx: &mut ConfigCx, expanded: bool) {
+            if expanded == self.expanded {
+                return;
+            }
+
+            self.start_expand(cx.now(), expanded);
+            if self.animate {
+                cx.request_frame_timer(self.id(), TIMER_ANIM);
+            }
+            if expanded || !self.animate {
+                cx.resize();
+            } else {
+                // Space is released when the animation completes
+                cx.redraw();
+            }
+        }
+
+        /// Set the expanded state and start the animation (if enabled) at `now`
+        fn start_expand(&mut self, now: Instant, expanded: bool) {
+            let fraction = self.fraction();
+            self.expanded = expanded;
+            self.header.set_expanded(expanded);
+
+            if self.animate {
+                // Start from the current fraction when reversing an animation
+                let t = if expanded { fraction } else { 1.0 - fraction };
+                self.anim_start = Some(now - ANIM_DURATION.mul_f32(t));
+                self.anim_progress = t;
+            }
+        }
+
+        /// Advance the animation to `now`
+        ///
+        /// Returns true while the animation is in progress.
+        fn step_anim(&mut self, now: Instant) -> bool {
+            let Some(start) = self.anim_start else {
+                return false;
+            };
+            let elapsed = now.saturating_duration_since(start);
+            self.anim_progress = (elapsed.as_secs_f32() / ANIM_DURATION.as_secs_f32()).min(1.0);
+            if self.anim_progress < 1.0 {
+                true
+            } else {
+                self.anim_start = None;
+                false
+            }
+        }
+
+        /// Fraction of the content shown, from 0 to 1
+        fn fraction(&self) -> f32 {
+            let t = match self.anim_start {
+                Some(_) => self.anim_progress,
+                None => 1.0,
+            };
+            if self.expanded { t } else { 1.0 - t }
+        }
+
+        /// True if space is allocated for the content
+        fn has_body(&self) -> bool {
+            self.expanded || self.anim_start.is_some()
+        }
+
+        /// The visible part of the content area
+        fn body_clip(&self) -> Rect {
+            let mut rect = self.body_rect;
+            rect.size.1 = (f32::conv(rect.size.1) * self.fraction()).cast_nearest();
+            rect
+        }
+    }
+
+    impl Layout for Self {
+        fn size_rules(&mut self, cx: &mut SizeCx, axis: AxisInfo) -> SizeRules {
+            let mut rules = self.header.size_rules(cx, axis);
+            let body = self.inner.size_rules(cx, axis);
+
+            if axis.is_horizontal() {
+                rules.max_with(body);
+                return rules;
+            }
+
+            self.header_rules = rules;
+            self.body_rules = body;
+            if self.has_body() { rules.appended(body) } else { rules }
+        }
+
+        fn set_rect(&mut self, cx: &mut SizeCx, rect: Rect, hints: AlignHints) {
+            self.core.set_rect(rect);
+
+            let mut header_rect = rect;
+            header_rect.size.1 = self.header_rules.ideal_size().min(rect.size.1);
+            self.header.set_rect(cx, header_rect, hints);
+
+            if self.has_body() {
+                let gap = self
+                    .header_rules
+                    .margins_i32()
+                    .1
+                    .max(self.body_rules.margins_i32().0);
+                let y = header_rect.pos.1 + header_rect.size.1 + gap;
+                let height = (rect.pos.1 + rect.size.1 - y).max(0);
+                self.body_rect = Rect::new(Coord(rect.pos.0, y), Size(rect.size.0, height));
+                self.inner.set_rect(cx, self.body_rect, hints);
+            }
+        }
+
+        fn draw(&self, mut draw: DrawCx) {
+            self.header.draw(draw.re());
+
+            let fraction = self.fraction();
+            if fraction >= 1.0 {
+                self.inner.draw(draw.re());
+            } else if fraction > 0.0 {
+                draw.with_clip_region(self.body_clip(), Offset::ZERO, |draw| self.inner.draw(draw));
+            }
+        }
+    }
+
+    impl Tile for Self {
+        fn role(&self, _: &mut dyn RoleCx) -> Role<'_> {
+            Role::None
+        }
+
+        #[inline]
+        fn child_indices(&self) -> ChildIndices {
+            let n = if self.fraction() > 0.0 { 2 } else { 1 };
+            ChildIndices::range(0..n)
+        }
+
+        fn nav_next(&self, reverse: bool, from: Option<usize>) -> Option<usize> {
+            let last = usize::from(self.expanded);
+            match (reverse, from) {
+                (false, None) => Some(0),
+                (false, Some(0)) if last > 0 => Some(1),
+                (true, None) => Some(last),
+                (true, Some(1)) => Some(0),
+                _ => None,
+            }
+        }
+    }
+
+    impl Events for Self {
+        type Data = W::Data;
+
+        fn probe(&self, coord: Coord) -> Id {
+            if self.fraction() > 0.0
+                && self.body_clip().contains(coord)
+                && let Some(id) = self.inner.try_probe(coord)
+            {
+                return id;
+            }
+            self.header.try_probe(coord).unwrap_or_else(|| self.id())
+        }
+
+        #[inline]
+        fn recurse_indices(&self) -> ChildIndices {
+            ChildIndices::range(0..2)
+        }
+
+        fn handle_event(&mut self, cx: &mut EventCx, _: &W::Data, event: Event) -> IsUsed {
+            match event {
+                Event::Timer(TIMER_ANIM) => {
+                    // Only the clip region is animated; no re-layout is needed
+                    // until collapsing completes
+                    if self.step_anim(cx.now()) {
+                        cx.request_frame_timer(self.id(), TIMER_ANIM);
+                    } else if !self.expanded {
+                        cx.resize();
+                    }
+                    cx.redraw();
+                    Used
+                }
+                _ => Unused,
+            }
+        }
+
+        fn handle_messages(&mut self, cx: &mut EventCx, _: &W::Data) {
+            let expanded = if let Some(Toggle) = cx.try_pop() {
+                !self.expanded
+            } else if let Some(Expand) = cx.try_pop() {
+                true
+            } else if let Some(Collapse) = cx.try_pop() {
+                false
+            } else {
+                return;
+            };
+
+            if expanded != self.expanded {
+                self.set_expanded(cx, expanded);
+                cx.push(match expanded {
+                    false => ExpanderMsg::Collapsed,
+                    true => ExpanderMsg::Expanded,
+                });
+            }
+        }
+    }
+}
+
+#[impl_self]
+mod Accordion {
+    /// A column of [`Expander`] sections of which at most one is expanded
+    ///
+    /// Expanding a section (by user action) collapses all other sections.
+    ///
+    /// ### Messages
+    ///
+    /// [`kas::messages::SetIndex`] may be used to expand a section.
+    #[widget]
+    pub struct Accordion<A> {
+        core: widget_core!(),
+        layout: DynRowStorage,
+        sections: Vec<Expander<Box<dyn Widget<Data = A>>>>,
+    }
+
+    impl Default for Self {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl Self {
+        /// Construct a new, empty instance
+        #[inline]
+        pub fn new() -> Self {
+            Accordion {
+                core: Default::default(),
+                layout: Default::default(),
+                sections: vec![],
+            }
+        }
+
+        /// Add a section (inline)
+        ///
+        /// The section is initially collapsed.
+        #[must_use]
+        pub fn with_section(
+            mut self,
+            label: impl Into<AccessString>,
+            widget: impl Widget<Data = A> + 'static,
+        ) -> Self {
+            let widget: Box<dyn Widget<Data = A>> = Box::new(widget);
+            self.sections.push(Expander::new(label, widget));
+            self
+        }
+
+        /// Set the initially expanded section (inline)
+        #[must_use]
+        pub fn with_expanded(mut self, index: usize) -> Self {
+            for (i, section) in self.sections.iter_mut().enumerate() {
+                section.expanded = i == index;
+                section.header.set_expanded(i == index);
+            }
+            self
+        }
+
+        /// Enable or disable animation (inline)
+        ///
+        /// This applies to existing sections. By default this is enabled.
+        #[must_use]
+        pub fn with_animation(mut self, animate: bool) -> Self {
+            for section in self.sections.iter_mut() {
+                section.animate = animate;
+            }
+            self
+        }
+
+        /// True if there are no sections
+        #[inline]
+        pub fn is_empty(&self) -> bool {
+            self.sections.is_empty()
+        }
+
+        /// Returns the number of sections
+        #[inline]
+        pub fn len(&self) -> usize {
+            self.sections.len()
+        }
+
+        /// Get the index of the expanded section, if any
+        pub fn expanded(&self) -> Option<usize> {
+            self.sections.iter().position(|s| s.is_expanded())
+        }
+
+        /// Expand the section at `index`, or collapse all if `None`
+        pub fn set_expanded(&mut self, cx: &mut ConfigCx, index: Option<usize>) {
+            for (i, section) in self.sections.iter_mut().enumerate() {
+                section.set_expanded(cx, Some(i) == index);
+            }
+        }
+    }
+
+    impl Layout for Self {
+        fn size_rules(&mut self, cx: &mut SizeCx, axis: AxisInfo) -> SizeRules {
+            let dim = (Direction::Down, self.sections.len(), true);
+            let mut solver = RowSolver::new(axis, dim, &mut self.layout);
+            for (n, child) in self.sections.iter_mut().enumerate() {
+                solver.for_child(&mut self.layout, n, |axis| child.size_rules(cx, axis));
+            }
+            solver.finish(&mut self.layout)
+        }
+
+        fn set_rect(&mut self, cx: &mut SizeCx, rect: Rect, hints: AlignHints) {
+            self.core.set_rect(rect);
+            let dim = (Direction::Down, self.sections.len(), true);
+            let mut setter = RowSetter::<Direction, Vec<i32>, _>::new(rect, dim, &mut self.layout);
+            for (n, child) in self.sections.iter_mut().enumerate() {
+                let child_rect = setter.child_rect(&mut self.layout, n);
+                child.set_rect(cx, child_rect, hints);
+            }
+        }
+
+        fn draw(&self, mut draw: DrawCx) {
+            for child in &self.sections {
+                child.draw(draw.re());
+            }
+        }
+    }
+
+    impl Tile for Self {
+        fn role(&self, _: &mut dyn RoleCx) -> Role<'_> {
+            Role::None
+        }
+
+        #[inline]
+        fn child_indices(&self) -> ChildIndices {
+            ChildIndices::range(0..self.sections.len())
+        }
+        fn get_child(&self, index: usize) -> Option<&dyn Tile> {
+            self.sections.get(index).map(|w| w.as_tile())
+        }
+    }
+
+    impl Events for Self {
+        fn probe(&self, coord: Coord) -> Id {
+            for child in &self.sections {
+                if let Some(id) = child.try_probe(coord) {
+                    return id;
+                }
+            }
+            self.id()
+        }
+
+        fn handle_messages(&mut self, cx: &mut EventCx, _: &A) {
+            if let Some(kas::messages::SetIndex(index)) = cx.try_pop() {
+                self.set_expanded(cx, Some(index));
+            } else if let Some(ExpanderMsg::Expanded) = cx.try_peek()
+                && let Some(index) = cx.last_child()
+            {
+                for (i, section) in self.sections.iter_mut().enumerate() {
+                    if i != index {
+                        section.set_expanded(cx, false);
+                    }
+                }
+            }
+        }
+    }
+
+    impl Widget for Self {
+        type Data = A;
+
+        fn child_node<'n>(&'n mut self, data: &'n A, index: usize) -> Option<Node<'n>> {
+            self.sections.get_mut(index).map(|w| w.as_node(data))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Filler;
+
+    fn expander() -> Expander<Filler> {
+        let mut expander = Expander::new("Section", Filler::new());
+        expander.body_rect = Rect::new(Coord(0, 20), Size(100, 80));
+        expander
+    }
+
+    #[test]
+    fn expand() {
+        let mut expander = expander();
+        assert!(!expander.is_expanded());
+        assert!(!expander.has_body());
+        assert_eq!(expander.fraction(), 0.0);
+
+        let start = Instant::now();
+        expander.start_expand(start, true);
+        assert!(expander.is_expanded());
+        assert!(expander.has_body());
+        assert_eq!(expander.body_clip().size.1, 0);
+
+        // The first step may occur without time passing
+        assert!(expander.step_anim(start));
+        assert_eq!(expander.fraction(), 0.0);
+
+        assert!(expander.step_anim(start + ANIM_DURATION / 2));
+        assert_eq!(expander.body_clip(), Rect::new(Coord(0, 20), Size(100, 40)));
+
+        assert!(!expander.step_anim(start + ANIM_DURATION));
+        assert_eq!(expander.fraction(), 1.0);
+        assert_eq!(expander.body_clip(), expander.body_rect);
+    }
+
+    #[test]
+    fn collapse() {
+        let mut expander = expander().with_expanded(true);
+        assert!(expander.has_body());
+        assert_eq!(expander.fraction(), 1.0);
+
+        let start = Instant::now();
+        expander.start_expand(start, false);
+        assert!(!expander.is_expanded());
+        assert!(expander.step_anim(start));
+        assert_eq!(expander.fraction(), 1.0);
+
+        assert!(expander.step_anim(start + ANIM_DURATION / 4));
+        assert_eq!(expander.body_clip().size.1, 60);
+        // Space is retained until the animation completes
+        assert!(expander.has_body());
+
+        assert!(!expander.step_anim(start + ANIM_DURATION));
+        assert!(!expander.has_body());
+        assert_eq!(expander.fraction(), 0.0);
+    }
+
+    #[test]
+    fn reverse() {
+        let mut expander = expander();
+        let start = Instant::now();
+        expander.start_expand(start, true);
+        expander.step_anim(start + ANIM_DURATION / 4);
+
+        // Collapsing starts from the fraction already shown
+        let now = start + ANIM_DURATION / 4;
+        expander.start_expand(now, false);
+        assert_eq!(expander.body_clip().size.1, 20);
+        assert!(expander.step_anim(now + ANIM_DURATION / 8));
+        assert_eq!(expander.body_clip().size.1, 10);
+        assert!(!expander.step_anim(now + ANIM_DURATION / 4 + Duration::from_millis(1)));
+        assert!(!expander.has_body());
+    }
+
+    #[test]
+    fn no_animation() {
+        let mut expander = expander().with_animation(false);
+        expander.start_expand(Instant::now(), true);
+        assert!(!expander.step_anim(Instant::now()));
+        assert_eq!(expander.body_clip(), expander.body_rect);
+
+        expander.start_expand(Instant::now(), false);
+        assert!(!expander.has_body());
+        assert_eq!(expander.fraction(), 0.0);
+    }
+}
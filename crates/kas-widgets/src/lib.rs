@@ -15,8 +15,12 @@
 //!
 //! -   [`adapt`] provides [`Adapt`], [`AdaptWidget`], [`AdaptWidgetAny`] and supporting items
 //!     (the items mentioned are re-export here).
+//! -   [`date`] provides date and time types along with the [`Calendar`], [`DatePicker`] and
+//!     [`TimePicker`] widgets (re-export here)
 //! -   [`dialog`] provides [`MessageBox`](dialog::MessageBox), ...
 //! -   [`edit`] provides text-editing functionality; the [`EditBox`] and [`EditBoxCore`] widgets are re-export here
+//!     while [`RichEditBox`](edit::RichEditBox) supports formatted text and [`PasswordBox`](edit::PasswordBox)
+//!     secret entry
 //! -   [`menu`] provides a [`MenuBar`](menu::MenuBar), [`SubMenu`](menu::SubMenu), ...
 //!
 //! ## Container widgets
@@ -26,7 +30,11 @@
 //! -   [`Stack`], [`TabStack`]: a stack of widgets in the same rect
 //! -   [`List`]: a row / column of children
 //! -   [`Splitter`]: like [`List`] but with resizing handles
+//! -   [`Dock`]: dockable panels around a central widget
+//! -   [`Expander`], [`Accordion`]: collapsible sections
 //! -   [`Grid`]: a container using grid layout
+//! -   [`ToolBar`]: a row of buttons and other controls with an overflow menu
+//! -   [`StatusBar`]: places a status bar with transient messages below content
 //!
 //! ## Controls
 //!
@@ -37,7 +45,10 @@
 //! -   [`ScrollBar`]: a scroll bar
 //!     bars around an inner widget
 //! -   [`Slider`]: a slider
+//! -   [`RangeSlider`]: a slider selecting a range
 //! -   [`SpinBox`]: numeric entry
+//! -   [`DatePicker`], [`TimePicker`]: date and time entry
+//! -   [`ColorPicker`], [`ColorButton`]: colour selection
 //!
 //! ## Displays
 //!
@@ -47,10 +58,13 @@
 //! -   [`Mark`]: a small mark
 //! -   [`ScrollLabel`]: static text label supporting scrolling and selection
 //! -   [`ScrollText`]: dynamic text label supporting scrolling and selection
+//! -   [`Link`]: a hyperlink
 //! -   [`Separator`]: a visible bar to separate things
 //! -   [`format_text`] and [`format_label`] are constructors for [`Text`],
 //!     displaying a text label derived from input data
 //! -   [`ProgressBar`]: show completion level
+//! -   [`Spinner`]: a busy indicator
+//! -   [`StepProgress`]: show completion of a number of steps
 //!
 //! ## Components
 //!
@@ -66,20 +80,27 @@ pub use adapt::{Adapt, AdaptWidget, AdaptWidgetAny};
 mod access_label;
 mod button;
 mod check_box;
+mod color_picker;
 mod combobox;
+pub mod date;
 pub mod dialog;
+mod dock;
 pub mod edit;
 mod event_config;
+mod expander;
 mod filler;
 mod float;
 mod flow;
 mod frame;
 mod grid;
 mod grip;
+mod link;
 mod list;
 pub mod menu;
+mod optional_mark;
 mod progress;
 mod radio_box;
+mod range_slider;
 mod scroll;
 mod scroll_bar;
 mod scroll_label;
@@ -88,26 +109,34 @@ mod slider;
 mod spin_box;
 mod splitter;
 mod stack;
+mod status_bar;
 mod tab_stack;
 mod text;
+mod toolbar;
 
 #[doc(inline)] pub use kas::widgets::*;
 
 pub use access_label::AccessLabel;
 pub use button::Button;
 pub use check_box::{CheckBox, CheckButton};
+pub use color_picker::{ColorButton, ColorPicker};
 pub use combobox::ComboBox;
+pub use date::{Calendar, DatePicker, TimePicker};
+pub use dock::{Dock, DockArea, DockAreaLayout, DockGroupLayout, DockLayout};
 pub use edit::{EditBox, EditBoxCore};
 pub use event_config::EventConfig;
+pub use expander::{Accordion, Expander, ExpanderMsg};
 pub use filler::Filler;
 pub use float::Float;
 pub use flow::Flow;
 pub use frame::Frame;
 pub use grid::Grid;
 pub use grip::{GripMsg, GripPart};
+pub use link::{FollowLink, Link};
 pub use list::*;
-pub use progress::ProgressBar;
+pub use progress::{ProgressBar, Spinner, StepProgress};
 pub use radio_box::{RadioBox, RadioButton};
+pub use range_slider::RangeSlider;
 pub use scroll::{ClipRegion, ScrollRegion};
 pub use scroll_bar::{ScrollBar, ScrollBarMode, ScrollBarMsg};
 pub use scroll_label::{ScrollLabel, ScrollText, ScrollTextCore};
@@ -116,5 +145,7 @@ pub use slider::{Slider, SliderValue};
 pub use spin_box::{SpinBox, SpinValue};
 pub use splitter::Splitter;
 pub use stack::{Page, Stack};
+pub use status_bar::{StatusBar, StatusMessage, StatusProgress};
 pub use tab_stack::{BoxTabStack, Tab, TabStack};
 pub use text::Text;
+pub use toolbar::ToolBar;
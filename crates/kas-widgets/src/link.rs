@@ -0,0 +1,94 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Hyperlinks
+
+use super::Label;
+use kas::prelude::*;
+use kas::text::format::{RichFormat, RichText};
+use kas::theme::FrameStyle;
+use std::sync::Arc;
+
+/// Message: follow a link
+///
+/// This message is emitted by a [`Link`] on activation. The handler should
+/// open the link target (e.g. in a web browser).
+#[derive(Clone, Debug)]
+pub struct FollowLink(pub Arc<str>);
+
+#[impl_self]
+mod Link {
+    /// A hyperlink
+    ///
+    /// Displays its text in link style. On activation (by click or by
+    /// keyboard) this emits [`FollowLink`] with the link target.
+    ///
+    /// ### Messages
+    ///
+    /// [`kas::messages::Activate`] may be used to follow the link.
+    #[widget]
+    #[layout(
+        frame!(self.label)
+            .with_style(FrameStyle::InvisibleButton)
+    )]
+    pub struct Link {
+        core: widget_core!(),
+        #[widget]
+        label: Label<RichText>,
+        url: Arc<str>,
+    }
+
+    impl Self {
+        /// Construct with the given `text` and target `url`
+        pub fn new(text: impl ToString, url: impl Into<Arc<str>>) -> Self {
+            let url = url.into();
+            let format = RichFormat {
+                link: Some(url.clone()),
+                ..Default::default()
+            };
+            Link {
+                core: Default::default(),
+                label: Label::new(RichText::with_format(text, format)),
+                url,
+            }
+        }
+
+        /// Get the link target
+        #[inline]
+        pub fn url(&self) -> &str {
+            &self.url
+        }
+    }
+
+    impl Tile for Self {
+        fn navigable(&self) -> bool {
+            true
+        }
+
+        fn role(&self, cx: &mut dyn RoleCx) -> Role<'_> {
+            cx.set_label(self.label.id());
+            Role::Link {
+                url: Some(&self.url),
+            }
+        }
+    }
+
+    impl Events for Self {
+        const REDRAW_ON_MOUSE_OVER: bool = true;
+
+        type Data = ();
+
+        fn handle_event(&mut self, cx: &mut EventCx, _: &(), event: Event) -> IsUsed {
+            event.on_click(cx, self.id(), |cx| cx.push(FollowLink(self.url.clone())))
+        }
+
+        fn handle_messages(&mut self, cx: &mut EventCx, _: &()) {
+            if let Some(kas::messages::Activate(code)) = cx.try_pop() {
+                cx.push(FollowLink(self.url.clone()));
+                cx.depress_with_key(&self, code);
+            }
+        }
+    }
+}
@@ -579,6 +579,23 @@ mod List {
             w
         }
 
+        /// Moves the child widget at position `from` to position `to`
+        ///
+        /// Panics if either index is out of bounds.
+        ///
+        /// The child is not re-configured and retains its identifier.
+        /// Triggers a resize.
+        pub fn move_child(&mut self, cx: &mut ConfigCx, from: usize, to: usize) {
+            let w = self.widgets.remove(from);
+            self.widgets.insert(to, w);
+
+            for v in self.id_map.values_mut() {
+                *v = moved_index(*v, from, to);
+            }
+
+            cx.resize();
+        }
+
         /// Removes all children at positions ≥ `len`
         ///
         /// Does nothing if `self.len() < len`.
@@ -756,3 +773,47 @@ impl<'a, W: Widget> ExactSizeIterator for ListIterMut<'a, W> {
         self.list.len()
     }
 }
+
+/// Get the new position of the item at `index` after moving an item from
+/// `from` to `to`
+pub(crate) fn moved_index(index: usize, from: usize, to: usize) -> usize {
+    if index == from {
+        to
+    } else if from < index && index <= to {
+        index - 1
+    } else if to <= index && index < from {
+        index + 1
+    } else {
+        index
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn moved_index_forward() {
+        // Move 1 to 3: [0, 1, 2, 3, 4] -> [0, 2, 3, 1, 4]
+        let order = [0, 2, 3, 1, 4];
+        for (pos, index) in order.into_iter().enumerate() {
+            assert_eq!(moved_index(index, 1, 3), pos);
+        }
+    }
+
+    #[test]
+    fn moved_index_backward() {
+        // Move 3 to 0: [0, 1, 2, 3, 4] -> [3, 0, 1, 2, 4]
+        let order = [3, 0, 1, 2, 4];
+        for (pos, index) in order.into_iter().enumerate() {
+            assert_eq!(moved_index(index, 3, 0), pos);
+        }
+    }
+
+    #[test]
+    fn moved_index_in_place() {
+        for index in 0..3 {
+            assert_eq!(moved_index(index, 1, 1), index);
+        }
+    }
+}
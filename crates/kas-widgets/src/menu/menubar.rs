@@ -6,6 +6,7 @@
 //! Menubar
 
 use super::{Menu, SubMenu, SubMenuBuilder};
+use kas::Relation;
 use kas::event::{FocusSource, TimerHandle};
 use kas::layout::{self, RowPositionSolver, RowSetter, RowSolver, RulesSetter, RulesSolver};
 use kas::prelude::*;
@@ -113,6 +114,13 @@ mod MenuBar {
             Role::MenuBar
         }
 
+        fn role_child_properties(&self, cx: &mut dyn RoleCx, index: usize) {
+            // An open menu's pop-up is not within the menu in the widget tree
+            if let Some(id) = self.widgets.get(index).and_then(|w| w.popup_id()) {
+                cx.add_relation(Relation::Owns, id);
+            }
+        }
+
         #[inline]
         fn child_indices(&self) -> ChildIndices {
             ChildIndices::range(0..self.widgets.len())
@@ -256,7 +264,7 @@ mod MenuBar {
     }
 
     impl Self {
-        fn set_menu_path(
+        pub(crate) fn set_menu_path(
             &mut self,
             cx: &mut EventCx,
             data: &Data,
@@ -7,6 +7,7 @@
 
 use super::{BoxedMenu, Menu, SubItems};
 use crate::{AccessLabel, Mark};
+use kas::Relation;
 use kas::event::FocusSource;
 use kas::layout::{self, RulesSetter, RulesSolver};
 use kas::messages::{Activate, Collapse, Expand};
@@ -63,6 +64,11 @@ mod SubMenu {
             }
         }
 
+        /// Get the identifier of the pop-up's contents, if open
+        pub(super) fn popup_id(&self) -> Option<Id> {
+            self.popup.is_open().then(|| self.popup.inner.id())
+        }
+
         fn open_menu(&mut self, cx: &mut EventCx, data: &Data, set_focus: bool) {
             if self.popup.open(cx, data, self.id(), true) {
                 if set_focus {
@@ -121,9 +127,13 @@ mod SubMenu {
             !TOP_LEVEL
         }
 
-        fn role(&self, _: &mut dyn RoleCx) -> Role<'_> {
+        fn role(&self, cx: &mut dyn RoleCx) -> Role<'_> {
+            let popup = self.popup_id();
+            if let Some(id) = popup.clone() {
+                cx.add_relation(Relation::Controls, id);
+            }
             Role::Menu {
-                expanded: self.popup.is_open(),
+                expanded: popup.is_some(),
             }
         }
 
@@ -0,0 +1,100 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! A mark button which may be hidden
+
+use kas::prelude::*;
+use kas::theme::MarkStyle;
+use std::fmt::Debug;
+
+#[impl_self]
+mod OptionalMark {
+    /// A mark button which may be hidden
+    ///
+    /// This is similar to [`MarkButton`](crate::MarkButton), but for
+    /// use within a parent widget (e.g. the close button of a tab). When not
+    /// visible, this has zero size and is not interactive.
+    ///
+    /// A clone of the message is pushed on click.
+    #[widget]
+    pub(crate) struct OptionalMark<M: Clone + Debug + 'static> {
+        core: widget_core!(),
+        visible: bool,
+        style: MarkStyle,
+        label: &'static str,
+        msg: M,
+    }
+
+    impl Self {
+        /// Construct
+        pub(crate) fn new(visible: bool, style: MarkStyle, label: &'static str, msg: M) -> Self {
+            OptionalMark {
+                core: Default::default(),
+                visible,
+                style,
+                label,
+                msg,
+            }
+        }
+
+        /// True if visible
+        #[inline]
+        pub(crate) fn is_visible(&self) -> bool {
+            self.visible
+        }
+
+        /// Set visibility
+        ///
+        /// The caller is responsible for resizing if required.
+        #[inline]
+        pub(crate) fn set_visible(&mut self, visible: bool) {
+            self.visible = visible;
+        }
+    }
+
+    impl Layout for Self {
+        fn size_rules(&mut self, cx: &mut SizeCx, axis: AxisInfo) -> SizeRules {
+            if self.visible {
+                cx.feature(self.style.into(), axis)
+            } else {
+                SizeRules::EMPTY
+            }
+        }
+
+        fn draw(&self, mut draw: DrawCx) {
+            if self.visible {
+                draw.mark(self.rect(), self.style);
+            }
+        }
+    }
+
+    impl Tile for Self {
+        fn tooltip(&self) -> Option<&str> {
+            self.visible.then_some(self.label)
+        }
+
+        fn role(&self, cx: &mut dyn RoleCx) -> Role<'_> {
+            if self.visible {
+                cx.set_label(self.label);
+                Role::Button
+            } else {
+                Role::None
+            }
+        }
+    }
+
+    impl Events for Self {
+        const REDRAW_ON_MOUSE_OVER: bool = true;
+
+        type Data = ();
+
+        fn handle_event(&mut self, cx: &mut EventCx, _: &(), event: Event) -> IsUsed {
+            if !self.visible {
+                return Unused;
+            }
+            event.on_click(cx, self.id(), |cx| cx.push(self.msg.clone()))
+        }
+    }
+}
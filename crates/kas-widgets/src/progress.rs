@@ -3,23 +3,43 @@
 // You may obtain a copy of the License in the LICENSE-APACHE file or at:
 //     https://www.apache.org/licenses/LICENSE-2.0
 
-//! Progress bar
+//! Progress indicators
 
+use kas::event::TimerHandle;
 use kas::prelude::*;
 use kas::theme::Feature;
+use std::time::{Duration, Instant};
+
+/// Period of the indeterminate progress bar animation
+const BAR_PERIOD: Duration = Duration::from_millis(1500);
+/// Period of the spinner animation
+const SPINNER_PERIOD: Duration = Duration::from_millis(1000);
+
+const TIMER_ANIM: TimerHandle = TimerHandle::new(0, true);
+
+/// Get the animation phase (from 0 to 1) of an animation started at `start`
+fn phase(start: Instant, now: Instant, period: Duration) -> f32 {
+    let elapsed = now.saturating_duration_since(start);
+    (elapsed.as_secs_f32() / period.as_secs_f32()).fract()
+}
 
 #[impl_self]
 mod ProgressBar {
     /// A progress bar
     ///
     /// The "progress" value may range from 0.0 to 1.0.
-    #[autoimpl(Debug ignore self.value_fn)]
+    ///
+    /// Where progress is unknown the bar may instead be shown in an animated
+    /// *indeterminate* mode; see [`Self::with_indeterminate`].
+    #[autoimpl(Debug ignore self.value_fn, self.indeterminate_fn)]
     #[widget]
     pub struct ProgressBar<A, D: Directional = kas::dir::Right> {
         core: widget_core!(),
         direction: D,
         value: f32,
         value_fn: Box<dyn Fn(&ConfigCx, &A) -> f32 + Send>,
+        indeterminate: Option<Instant>,
+        indeterminate_fn: Option<Box<dyn Fn(&ConfigCx, &A) -> bool + Send>>,
     }
 
     impl Self
@@ -61,14 +81,36 @@ mod ProgressBar {
                 direction,
                 value: 0.0,
                 value_fn: Box::new(value_fn),
+                indeterminate: None,
+                indeterminate_fn: None,
             }
         }
 
+        /// Enable indeterminate mode (inline)
+        ///
+        /// While closure `f` returns `true`, the bar is animated to indicate
+        /// activity of unknown duration and the value is ignored.
+        #[inline]
+        #[must_use]
+        pub fn with_indeterminate(
+            mut self,
+            f: impl Fn(&ConfigCx, &A) -> bool + Send + 'static,
+        ) -> Self {
+            self.indeterminate_fn = Some(Box::new(f));
+            self
+        }
+
         /// Get the progress bar's direction
         #[inline]
         pub fn direction(&self) -> Direction {
             self.direction.as_direction()
         }
+
+        /// True if the bar is in indeterminate mode
+        #[inline]
+        pub fn is_indeterminate(&self) -> bool {
+            self.indeterminate.is_some()
+        }
     }
 
     impl Layout for Self {
@@ -87,14 +129,19 @@ mod ProgressBar {
 
         fn draw(&self, mut draw: DrawCx) {
             let dir = self.direction.as_direction();
-            draw.progress_bar(self.rect(), dir, self.value);
+            if let Some(start) = self.indeterminate {
+                let phase = phase(start, draw.ev_state().now(), BAR_PERIOD);
+                draw.progress_bar_indeterminate(self.rect(), dir, phase);
+            } else {
+                draw.progress_bar(self.rect(), dir, self.value);
+            }
         }
     }
 
     impl Tile for Self {
         fn role(&self, _: &mut dyn RoleCx) -> Role<'_> {
             Role::ProgressBar {
-                fraction: self.value,
+                fraction: self.indeterminate.is_none().then_some(self.value),
                 direction: self.direction.as_direction(),
             }
         }
@@ -106,6 +153,232 @@ mod ProgressBar {
         fn update(&mut self, cx: &mut ConfigCx, data: &A) {
             let value = (self.value_fn)(cx, data);
             self.value = value.clamp(0.0, 1.0);
+
+            let indeterminate = self.indeterminate_fn.as_ref().is_some_and(|f| f(cx, data));
+            if indeterminate != self.indeterminate.is_some() {
+                self.indeterminate = indeterminate.then(|| cx.now());
+                if indeterminate {
+                    cx.request_frame_timer(self.id(), TIMER_ANIM);
+                }
+                cx.redraw();
+            }
+        }
+
+        fn handle_event(&mut self, cx: &mut EventCx, _: &A, event: Event) -> IsUsed {
+            match event {
+                Event::Timer(TIMER_ANIM) => {
+                    if self.indeterminate.is_some() {
+                        cx.request_frame_timer(self.id(), TIMER_ANIM);
+                        cx.redraw();
+                    }
+                    Used
+                }
+                _ => Unused,
+            }
+        }
+    }
+}
+
+#[impl_self]
+mod Spinner {
+    /// A circular busy indicator
+    ///
+    /// The spinner is animated while `active_fn` returns `true` and is
+    /// otherwise invisible (but still occupies space).
+    #[autoimpl(Debug ignore self.active_fn)]
+    #[widget]
+    pub struct Spinner<A> {
+        core: widget_core!(),
+        active: Option<Instant>,
+        active_fn: Box<dyn Fn(&ConfigCx, &A) -> bool + Send>,
+    }
+
+    impl Self {
+        /// Construct a spinner
+        ///
+        /// Closure `active_fn` returns whether the spinner is animated.
+        #[inline]
+        pub fn new(active_fn: impl Fn(&ConfigCx, &A) -> bool + Send + 'static) -> Self {
+            Spinner {
+                core: Default::default(),
+                active: None,
+                active_fn: Box::new(active_fn),
+            }
+        }
+
+        /// True if the spinner is active
+        #[inline]
+        pub fn is_active(&self) -> bool {
+            self.active.is_some()
+        }
+    }
+
+    impl Layout for Self {
+        fn size_rules(&mut self, cx: &mut SizeCx, axis: AxisInfo) -> SizeRules {
+            cx.feature(Feature::Spinner, axis)
+        }
+
+        fn set_rect(&mut self, cx: &mut SizeCx, rect: Rect, hints: AlignHints) {
+            let rect = cx.align_feature(Feature::Spinner, rect, hints.complete_center());
+            self.core.set_rect(rect);
+        }
+
+        fn draw(&self, mut draw: DrawCx) {
+            if let Some(start) = self.active {
+                let phase = phase(start, draw.ev_state().now(), SPINNER_PERIOD);
+                draw.spinner(self.rect(), phase);
+            }
+        }
+    }
+
+    impl Tile for Self {
+        fn role(&self, _: &mut dyn RoleCx) -> Role<'_> {
+            Role::Indicator
+        }
+    }
+
+    impl Events for Self {
+        type Data = A;
+
+        fn update(&mut self, cx: &mut ConfigCx, data: &A) {
+            let active = (self.active_fn)(cx, data);
+            if active != self.active.is_some() {
+                self.active = active.then(|| cx.now());
+                if active {
+                    cx.request_frame_timer(self.id(), TIMER_ANIM);
+                }
+                cx.redraw();
+            }
+        }
+
+        fn handle_event(&mut self, cx: &mut EventCx, _: &A, event: Event) -> IsUsed {
+            match event {
+                Event::Timer(TIMER_ANIM) => {
+                    if self.active.is_some() {
+                        cx.request_frame_timer(self.id(), TIMER_ANIM);
+                        cx.redraw();
+                    }
+                    Used
+                }
+                _ => Unused,
+            }
+        }
+    }
+}
+
+#[impl_self]
+mod StepProgress {
+    /// A segmented progress indicator
+    ///
+    /// This displays a fixed number of steps (for example the stages of a
+    /// wizard). Steps before the current step are shown as complete.
+    #[autoimpl(Debug ignore self.current_fn)]
+    #[widget]
+    pub struct StepProgress<A, D: Directional = kas::dir::Right> {
+        core: widget_core!(),
+        direction: D,
+        steps: usize,
+        current: usize,
+        current_fn: Box<dyn Fn(&ConfigCx, &A) -> usize + Send>,
+    }
+
+    impl Self
+    where
+        D: Default,
+    {
+        /// Construct with the given number of `steps`
+        ///
+        /// Closure `current_fn` returns the index of the current step. A value
+        /// of `steps` (or greater) indicates that all steps are complete.
+        #[inline]
+        pub fn new(
+            steps: usize,
+            current_fn: impl Fn(&ConfigCx, &A) -> usize + Send + 'static,
+        ) -> Self {
+            Self::new_dir(steps, current_fn, D::default())
+        }
+    }
+
+    impl Self {
+        /// Construct with the given number of `steps` and `direction`
+        ///
+        /// Closure `current_fn` returns the index of the current step. A value
+        /// of `steps` (or greater) indicates that all steps are complete.
+        #[inline]
+        pub fn new_dir<V>(steps: usize, current_fn: V, direction: D) -> Self
+        where
+            V: Fn(&ConfigCx, &A) -> usize + Send + 'static,
+        {
+            StepProgress {
+                core: Default::default(),
+                direction,
+                steps,
+                current: 0,
+                current_fn: Box::new(current_fn),
+            }
+        }
+
+        /// Get the direction
+        #[inline]
+        pub fn direction(&self) -> Direction {
+            self.direction.as_direction()
+        }
+
+        /// Get the number of steps
+        #[inline]
+        pub fn steps(&self) -> usize {
+            self.steps
+        }
+
+        /// Get the index of the current step
+        #[inline]
+        pub fn current(&self) -> usize {
+            self.current
+        }
+    }
+
+    impl Layout for Self {
+        fn size_rules(&mut self, cx: &mut SizeCx, axis: AxisInfo) -> SizeRules {
+            cx.feature(Feature::ProgressBar(self.direction()), axis)
+        }
+
+        fn set_rect(&mut self, cx: &mut SizeCx, rect: Rect, hints: AlignHints) {
+            let align = match self.direction.is_vertical() {
+                false => AlignPair::new(Align::Stretch, hints.vert.unwrap_or(Align::Center)),
+                true => AlignPair::new(hints.horiz.unwrap_or(Align::Center), Align::Stretch),
+            };
+            let rect = cx.align_feature(Feature::ProgressBar(self.direction()), rect, align);
+            self.core.set_rect(rect);
+        }
+
+        fn draw(&self, mut draw: DrawCx) {
+            let dir = self.direction.as_direction();
+            draw.progress_steps(self.rect(), dir, self.steps, self.current);
+        }
+    }
+
+    impl Tile for Self {
+        fn role(&self, _: &mut dyn RoleCx) -> Role<'_> {
+            let fraction = match self.steps {
+                0 => 1.0,
+                n => f32::conv(self.current.min(n)) / f32::conv(n),
+            };
+            Role::ProgressBar {
+                fraction: Some(fraction),
+                direction: self.direction.as_direction(),
+            }
+        }
+    }
+
+    impl Events for Self {
+        type Data = A;
+
+        fn update(&mut self, cx: &mut ConfigCx, data: &A) {
+            let current = (self.current_fn)(cx, data);
+            if current != self.current {
+                self.current = current;
+                cx.redraw();
+            }
         }
     }
 }
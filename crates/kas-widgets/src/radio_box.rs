@@ -65,7 +65,7 @@ mod RadioBox {
             let new_state = (self.state_fn)(cx, data);
             if self.state != new_state {
                 self.state = new_state;
-                self.last_change = Some(Instant::now());
+                self.last_change = Some(cx.now());
                 cx.redraw();
             }
         }
@@ -140,7 +140,7 @@ mod RadioBox {
                 f(cx, data);
             }
 
-            self.last_change = Some(Instant::now());
+            self.last_change = Some(cx.now());
             cx.redraw();
         }
     }
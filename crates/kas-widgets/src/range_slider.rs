@@ -0,0 +1,662 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! `RangeSlider` control
+
+use super::{GripMsg, GripPart, SliderValue};
+use kas::event::FocusSource;
+use kas::messages::{DecrementStep, IncrementStep, SetValueF64};
+use kas::prelude::*;
+use kas::theme::Feature;
+use std::ops::RangeInclusive;
+
+/// Add `step` to `value`, saturating at `max`
+///
+/// This avoids overflow where `max` is close to the limit of the type.
+fn step_up<T: SliderValue>(value: T, step: T, max: T) -> T {
+    if value < max && step < max - value {
+        value + step
+    } else {
+        max
+    }
+}
+
+/// Subtract `step` from `value`, saturating at `min`
+fn step_down<T: SliderValue>(value: T, step: T, min: T) -> T {
+    if min < value && step < value - min {
+        value - step
+    } else {
+        min
+    }
+}
+
+#[impl_self]
+mod Handle {
+    /// A handle of a [`RangeSlider`]
+    ///
+    /// This wraps a [`GripPart`], adding navigation focus and accessibility
+    /// information. The parent handles all events.
+    #[widget]
+    struct Handle {
+        core: widget_core!(),
+        #[widget(&())]
+        grip: GripPart,
+        min: f64,
+        max: f64,
+        step: f64,
+        value: f64,
+        direction: Direction,
+    }
+
+    /// As for [`GripPart`], the rect is that of the grip.
+    impl Layout for Self {
+        fn rect(&self) -> Rect {
+            self.grip.rect()
+        }
+
+        fn size_rules(&mut self, cx: &mut SizeCx, axis: AxisInfo) -> SizeRules {
+            self.grip.size_rules(cx, axis)
+        }
+
+        fn set_rect(&mut self, cx: &mut SizeCx, rect: Rect, hints: AlignHints) {
+            self.grip.set_rect(cx, rect, hints);
+        }
+
+        fn draw(&self, _: DrawCx) {}
+    }
+
+    impl Tile for Self {
+        fn navigable(&self) -> bool {
+            true
+        }
+
+        fn role(&self, _: &mut dyn RoleCx) -> Role<'_> {
+            Role::Slider {
+                min: self.min,
+                max: self.max,
+                step: self.step,
+                value: self.value,
+                direction: self.direction,
+            }
+        }
+    }
+
+    impl Events for Self {
+        type Data = ();
+
+        fn probe(&self, coord: Coord) -> Id {
+            self.grip.try_probe(coord).unwrap_or_else(|| self.id())
+        }
+    }
+
+    impl Self {
+        fn new(direction: Direction) -> Self {
+            Handle {
+                core: Default::default(),
+                grip: GripPart::new(),
+                min: 0.0,
+                max: 0.0,
+                step: 0.0,
+                value: 0.0,
+                direction,
+            }
+        }
+    }
+}
+
+#[impl_self]
+mod RangeSlider {
+    /// A slider selecting a range
+    ///
+    /// Range sliders allow user input of a sub-range of a fixed range. The
+    /// slider has two handles, each of which may be dragged or receive
+    /// keyboard focus. Handles may not cross: the lower handle is bounded
+    /// above by the upper handle and vice-versa.
+    ///
+    /// ### Messages
+    ///
+    /// [`SetValueF64`], [`IncrementStep`] and [`DecrementStep`] may be sent to
+    /// either handle to adjust its value.
+    #[autoimpl(Debug ignore self.state_fn, self.on_move)]
+    #[widget]
+    pub struct RangeSlider<A, T: SliderValue, D: Directional = Direction> {
+        core: widget_core!(),
+        direction: D,
+        range: (T, T),
+        step: T,
+        value: [T; 2],
+        #[widget(&())]
+        lower: Handle,
+        #[widget(&())]
+        upper: Handle,
+        state_fn: Box<dyn Fn(&ConfigCx, &A) -> RangeInclusive<T> + Send>,
+        on_move: Option<Box<dyn Fn(&mut EventCx, &A, RangeInclusive<T>) + Send>>,
+    }
+
+    impl Self
+    where
+        D: Default,
+    {
+        /// Construct a range slider
+        ///
+        /// Values vary within the given `range`, increasing in the given
+        /// `direction`. The default step size is
+        /// 1 for common types (see [`SliderValue::default_step`]).
+        ///
+        /// The selected sub-range is set by `state_fn` on update.
+        ///
+        /// To make the slider interactive, assign an event handler with
+        /// [`Self::with`] or [`Self::with_msg`].
+        #[inline]
+        pub fn new(
+            range: RangeInclusive<T>,
+            state_fn: impl Fn(&ConfigCx, &A) -> RangeInclusive<T> + Send + 'static,
+        ) -> Self {
+            RangeSlider::new_dir(range, state_fn, D::default())
+        }
+    }
+    impl<A, T: SliderValue> RangeSlider<A, T, kas::dir::Left> {
+        /// Construct with fixed direction
+        #[inline]
+        pub fn left(
+            range: RangeInclusive<T>,
+            state_fn: impl Fn(&ConfigCx, &A) -> RangeInclusive<T> + Send + 'static,
+        ) -> Self {
+            RangeSlider::new(range, state_fn)
+        }
+    }
+    impl<A, T: SliderValue> RangeSlider<A, T, kas::dir::Right> {
+        /// Construct with fixed direction
+        #[inline]
+        pub fn right(
+            range: RangeInclusive<T>,
+            state_fn: impl Fn(&ConfigCx, &A) -> RangeInclusive<T> + Send + 'static,
+        ) -> Self {
+            RangeSlider::new(range, state_fn)
+        }
+    }
+    impl<A, T: SliderValue> RangeSlider<A, T, kas::dir::Up> {
+        /// Construct with fixed direction
+        #[inline]
+        pub fn up(
+            range: RangeInclusive<T>,
+            state_fn: impl Fn(&ConfigCx, &A) -> RangeInclusive<T> + Send + 'static,
+        ) -> Self {
+            RangeSlider::new(range, state_fn)
+        }
+    }
+    impl<A, T: SliderValue> RangeSlider<A, T, kas::dir::Down> {
+        /// Construct with fixed direction
+        #[inline]
+        pub fn down(
+            range: RangeInclusive<T>,
+            state_fn: impl Fn(&ConfigCx, &A) -> RangeInclusive<T> + Send + 'static,
+        ) -> Self {
+            RangeSlider::new(range, state_fn)
+        }
+    }
+
+    impl Self {
+        /// Construct a range slider with given direction
+        ///
+        /// Values vary within the given `range`, increasing in the given
+        /// `direction`. The default step size is
+        /// 1 for common types (see [`SliderValue::default_step`]).
+        ///
+        /// The selected sub-range is set by `state_fn` on update.
+        ///
+        /// To make the slider interactive, assign an event handler with
+        /// [`Self::with`] or [`Self::with_msg`].
+        #[inline]
+        pub fn new_dir(
+            range: RangeInclusive<T>,
+            state_fn: impl Fn(&ConfigCx, &A) -> RangeInclusive<T> + Send + 'static,
+            direction: D,
+        ) -> Self {
+            assert!(!range.is_empty());
+            let range = range.into_inner();
+            let step = T::default_step();
+            let dir = direction.as_direction();
+            let mut slider = RangeSlider {
+                core: Default::default(),
+                direction,
+                range,
+                step,
+                value: [range.0, range.1],
+                lower: Handle::new(dir),
+                upper: Handle::new(dir),
+                state_fn: Box::new(state_fn),
+                on_move: None,
+            };
+            slider.update_handles();
+            slider
+        }
+
+        /// Send the message generated by `f` on movement
+        #[inline]
+        #[must_use]
+        pub fn with_msg<M>(self, f: impl Fn(RangeInclusive<T>) -> M + Send + 'static) -> Self
+        where
+            M: std::fmt::Debug + 'static,
+        {
+            self.with(move |cx, _, range| cx.push(f(range)))
+        }
+
+        /// Call the handler `f` on movement
+        #[inline]
+        #[must_use]
+        pub fn with(
+            mut self,
+            f: impl Fn(&mut EventCx, &A, RangeInclusive<T>) + Send + 'static,
+        ) -> Self {
+            debug_assert!(self.on_move.is_none());
+            self.on_move = Some(Box::new(f));
+            self
+        }
+
+        /// Get the slider's direction
+        #[inline]
+        pub fn direction(&self) -> Direction {
+            self.direction.as_direction()
+        }
+
+        /// Set the step size
+        #[inline]
+        #[must_use]
+        pub fn with_step(mut self, step: T) -> Self {
+            self.step = step;
+            self.update_handles();
+            self
+        }
+
+        /// Get the selected sub-range
+        #[inline]
+        pub fn value(&self) -> RangeInclusive<T> {
+            self.value[0]..=self.value[1]
+        }
+
+        fn handle(&self, index: usize) -> &Handle {
+            match index {
+                0 => &self.lower,
+                _ => &self.upper,
+            }
+        }
+
+        fn handle_mut(&mut self, index: usize) -> &mut Handle {
+            match index {
+                0 => &mut self.lower,
+                _ => &mut self.upper,
+            }
+        }
+
+        // The range within which handle `index` may move
+        fn bounds(&self, index: usize) -> (T, T) {
+            match index {
+                0 => (self.range.0, self.value[1]),
+                _ => (self.value[0], self.range.1),
+            }
+        }
+
+        // Step size for PageUp/PageDown: 16 steps, limited to the span of the range
+        #[allow(clippy::neg_cmp_op_on_partial_ord)]
+        fn page_step(&self) -> T {
+            let span = self.range.1 - self.range.0;
+            let mut x = self.step;
+            for _ in 0..4 {
+                if !(x <= span) || !(x <= span - x) {
+                    return span;
+                }
+                x = x + x;
+            }
+            x
+        }
+
+        // The new value of handle `index` on keyboard command `cmd`, if any
+        //
+        // The result is within the bounds of the handle.
+        fn command_value(&self, index: usize, cmd: Command) -> Option<T> {
+            let current = self.value[index];
+            let (min, max) = self.bounds(index);
+            let rev = self.direction.is_reversed();
+            let (up, step) = match cmd {
+                Command::Left | Command::Up => (rev, self.step),
+                Command::Right | Command::Down => (!rev, self.step),
+                Command::PageUp | Command::PageDown => {
+                    (rev != (cmd == Command::PageDown), self.page_step())
+                }
+                Command::Home => return Some(min),
+                Command::End => return Some(max),
+                _ => return None,
+            };
+            Some(match up {
+                true => step_up(current, step, max),
+                false => step_down(current, step, min),
+            })
+        }
+
+        // Update accessibility information of handles
+        fn update_handles(&mut self) {
+            let (min, max) = (self.range.0.cast(), self.range.1.cast());
+            let step = self.step.cast();
+            for index in 0..2 {
+                let value = self.value[index].cast();
+                let handle = self.handle_mut(index);
+                (handle.min, handle.max, handle.step) = (min, max, step);
+                handle.value = value;
+            }
+        }
+
+        /// Set both values and update grips
+        ///
+        /// Values are clamped to the supported range and ordered.
+        /// Returns `true` if any value changed.
+        #[allow(clippy::neg_cmp_op_on_partial_ord)]
+        fn set_values(&mut self, cx: &mut EventState, values: [T; 2]) -> bool {
+            let [mut lower, mut upper] = values.map(|value| {
+                if !(value >= self.range.0) {
+                    self.range.0
+                } else if !(value <= self.range.1) {
+                    self.range.1
+                } else {
+                    value
+                }
+            });
+            if !(lower <= upper) {
+                (lower, upper) = (upper, lower);
+            }
+
+            if [lower, upper] == self.value {
+                return false;
+            }
+
+            self.value = [lower, upper];
+            self.update_handles();
+            for index in 0..2 {
+                let offset = self.offset(self.value[index]);
+                self.handle_mut(index).grip.set_offset(cx, offset);
+            }
+            true
+        }
+
+        /// Set value of handle `index` and update its grip
+        ///
+        /// The value is clamped to [`Self::bounds`].
+        /// Returns `true` if the value changed.
+        #[allow(clippy::neg_cmp_op_on_partial_ord)]
+        fn set_value(&mut self, cx: &mut EventState, index: usize, value: T) -> bool {
+            let (min, max) = self.bounds(index);
+            let value = if !(value >= min) {
+                min
+            } else if !(value <= max) {
+                max
+            } else {
+                value
+            };
+
+            let mut values = self.value;
+            values[index] = value;
+            self.set_values(cx, values)
+        }
+
+        // translate value to offset in local coordinates
+        fn offset(&self, value: T) -> Offset {
+            let a = value - self.range.0;
+            let b = self.range.1 - self.range.0;
+            let max_offset = self.lower.grip.max_offset();
+            let mut frac = a.cast() / b.cast();
+            assert!((0.0..=1.0).contains(&frac));
+            if self.direction.is_reversed() {
+                frac = 1.0 - frac;
+            }
+            match self.direction.is_vertical() {
+                false => Offset((max_offset.0 as f64 * frac).cast_floor(), 0),
+                true => Offset(0, (max_offset.1 as f64 * frac).cast_floor()),
+            }
+        }
+
+        fn apply_grip_offset(&mut self, cx: &mut EventCx, data: &A, index: usize, offset: Offset) {
+            let b = self.range.1 - self.range.0;
+            let max_offset = self.lower.grip.max_offset();
+            let (offset, max) = match self.direction.is_vertical() {
+                false => (offset.0, max_offset.0),
+                true => (offset.1, max_offset.1),
+            };
+            let step = self.step.cast();
+            let a: f64 = b.cast() * (offset as f64 / (max as f64 * step));
+            let a: f64 = a.round() * step;
+            let mut a: T = a.cast_approx();
+            if self.direction.is_reversed() {
+                a = b - a;
+            }
+            self.set_and_notify(cx, data, index, a + self.range.0);
+        }
+
+        fn set_and_notify(&mut self, cx: &mut EventCx, data: &A, index: usize, value: T) {
+            if self.set_value(cx, index, value)
+                && let Some(ref f) = self.on_move
+            {
+                f(cx, data, self.value());
+            }
+        }
+
+        // Index of the handle last sending a message, if any
+        fn message_source(&self, cx: &EventCx) -> Option<usize> {
+            match cx.last_child() {
+                Some(i) if i == widget_index![self.lower] => Some(0),
+                Some(i) if i == widget_index![self.upper] => Some(1),
+                _ => None,
+            }
+        }
+    }
+
+    impl Layout for Self {
+        fn size_rules(&mut self, cx: &mut SizeCx, axis: AxisInfo) -> SizeRules {
+            let _ = self.lower.size_rules(cx, axis);
+            let _ = self.upper.size_rules(cx, axis);
+            cx.feature(Feature::Slider(self.direction()), axis)
+        }
+
+        fn set_rect(&mut self, cx: &mut SizeCx, rect: Rect, hints: AlignHints) {
+            let align = match self.direction.is_vertical() {
+                false => AlignPair::new(Align::Stretch, hints.vert.unwrap_or(Align::Center)),
+                true => AlignPair::new(hints.horiz.unwrap_or(Align::Center), Align::Stretch),
+            };
+            let mut rect = cx.align_feature(Feature::Slider(self.direction()), rect, align);
+            self.core.set_rect(rect);
+            let track = rect;
+            rect.size.set_component(self.direction, cx.grip_len());
+            for index in 0..2 {
+                let offset = self.offset(self.value[index]);
+                let handle = self.handle_mut(index);
+                handle.grip.set_track(track);
+                handle.set_rect(cx, rect, AlignHints::NONE);
+                handle.grip.set_offset(cx, offset);
+            }
+        }
+
+        fn draw(&self, mut draw: DrawCx) {
+            let dir = self.direction.as_direction();
+            let handles = [
+                (&self.lower, &self.lower.grip),
+                (&self.upper, &self.upper.grip),
+            ];
+            draw.range_slider(self.rect(), handles, dir);
+        }
+    }
+
+    impl Events for Self {
+        type Data = A;
+
+        fn probe(&self, coord: Coord) -> Id {
+            if self.on_move.is_some() {
+                // The upper handle is drawn on top, but if both handles are at
+                // the maximum only the lower may move.
+                let order = match self.value[0] == self.range.1 {
+                    false => [&self.upper, &self.lower],
+                    true => [&self.lower, &self.upper],
+                };
+                for handle in order {
+                    if let Some(id) = handle.try_probe(coord) {
+                        return id;
+                    }
+                }
+            }
+            self.id()
+        }
+
+        fn update(&mut self, cx: &mut ConfigCx, data: &A) {
+            let range = (self.state_fn)(cx, data);
+            self.set_values(cx, range.into_inner().into());
+        }
+
+        fn handle_event(&mut self, cx: &mut EventCx, data: &A, event: Event) -> IsUsed {
+            if self.on_move.is_none() {
+                return Unused;
+            }
+
+            match event {
+                Event::Command(cmd, code) => {
+                    let index = if cx.has_nav_focus(self.lower.id_ref()) {
+                        0
+                    } else if cx.has_nav_focus(self.upper.id_ref()) {
+                        1
+                    } else {
+                        return Unused;
+                    };
+
+                    let Some(value) = self.command_value(index, cmd) else {
+                        return Unused;
+                    };
+
+                    cx.depress_with_key(self.handle(index).id(), code);
+                    self.set_and_notify(cx, data, index, value);
+                }
+                Event::PressStart(press) => {
+                    // Move the nearest handle
+                    let coord = press.coord();
+                    let [lower, upper] = [&self.lower, &self.upper].map(|handle| {
+                        let rect = handle.rect();
+                        (coord - (rect.pos + rect.size / 2)).extract(self.direction)
+                    });
+                    let index = if lower.abs() != upper.abs() {
+                        usize::from(upper.abs() < lower.abs())
+                    } else {
+                        // Handles coincide: choose by direction of movement
+                        usize::from((upper > 0) != self.direction.is_reversed())
+                    };
+
+                    let id = self.handle(index).id();
+                    cx.request_nav_focus(id, FocusSource::Pointer);
+                    let offset = self
+                        .handle_mut(index)
+                        .grip
+                        .handle_press_on_track(cx, &press);
+                    self.apply_grip_offset(cx, data, index, offset);
+                }
+                _ => return Unused,
+            }
+            Used
+        }
+
+        fn handle_messages(&mut self, cx: &mut EventCx, data: &A) {
+            if self.on_move.is_none() {
+                return;
+            }
+            let Some(index) = self.message_source(cx) else {
+                return;
+            };
+
+            match cx.try_pop() {
+                Some(GripMsg::PressStart) => {
+                    let id = self.handle(index).id();
+                    cx.request_nav_focus(id, FocusSource::Synthetic)
+                }
+                Some(GripMsg::PressMove(pos)) => {
+                    self.apply_grip_offset(cx, data, index, pos);
+                }
+                Some(GripMsg::PressEnd(_)) => (),
+                None => {
+                    let current = self.value[index];
+                    let mut new_value = None;
+                    if let Some(SetValueF64(v)) = cx.try_pop() {
+                        new_value = v
+                            .try_cast_approx()
+                            .map_err(|err| {
+                                log::warn!("RangeSlider failed to handle SetValueF64: {err}")
+                            })
+                            .ok();
+                    } else if let Some(IncrementStep) = cx.try_pop() {
+                        new_value = Some(step_up(current, self.step, self.bounds(index).1));
+                    } else if let Some(DecrementStep) = cx.try_pop() {
+                        new_value = Some(step_down(current, self.step, self.bounds(index).0));
+                    }
+
+                    if let Some(value) = new_value {
+                        self.set_and_notify(cx, data, index, value);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn slider(range: RangeInclusive<u8>, value: [u8; 2]) -> RangeSlider<(), u8> {
+        let mut slider = RangeSlider::new(range, |_, _| 0..=0);
+        slider.value = value;
+        slider
+    }
+
+    #[test]
+    fn step_saturates() {
+        assert_eq!(step_up(250u8, 10, 255), 255);
+        assert_eq!(step_up(255u8, 1, 255), 255);
+        assert_eq!(step_down(5u8, 10, 0), 0);
+        assert_eq!(step_down(0u8, 1, 0), 0);
+        assert_eq!(step_down(-126i8, 5, -128), -128);
+
+        let s = slider(0..=255, [2, 253]);
+        assert_eq!(s.command_value(0, Command::Left), Some(1));
+        assert_eq!(s.command_value(0, Command::PageUp), Some(0));
+        assert_eq!(s.command_value(1, Command::PageDown), Some(255));
+
+        let s = slider(0..=255, [0, 255]);
+        assert_eq!(s.command_value(0, Command::Left), Some(0));
+        assert_eq!(s.command_value(1, Command::Right), Some(255));
+    }
+
+    #[test]
+    fn page_step() {
+        assert_eq!(slider(0..=255, [0, 0]).page_step(), 16);
+        assert_eq!(slider(0..=20, [0, 0]).page_step(), 16);
+        assert_eq!(slider(0..=10, [0, 0]).page_step(), 10);
+        assert_eq!(slider(0..=255, [0, 0]).with_step(100).page_step(), 255);
+    }
+
+    #[test]
+    fn handles_do_not_cross() {
+        let mut s = slider(0..=255, [10, 12]);
+        for _ in 0..4 {
+            let value = s.command_value(0, Command::Right).unwrap();
+            assert!(value <= s.value[1]);
+            s.value[0] = value;
+        }
+        assert_eq!(s.value, [12, 12]);
+        assert_eq!(s.command_value(0, Command::PageDown), Some(12));
+        assert_eq!(s.command_value(0, Command::End), Some(12));
+
+        s.value = [10, 12];
+        for _ in 0..4 {
+            let value = s.command_value(1, Command::Left).unwrap();
+            assert!(value >= s.value[0]);
+            s.value[1] = value;
+        }
+        assert_eq!(s.value, [10, 10]);
+        assert_eq!(s.command_value(1, Command::Home), Some(10));
+    }
+}
@@ -69,7 +69,7 @@ mod Slider {
     /// [`SetValueF64`] may be used to set the input value.
     ///
     /// [`IncrementStep`] and [`DecrementStep`] change the value by one step.
-    #[autoimpl(Debug ignore self.state_fn, self.on_move)]
+    #[autoimpl(Debug ignore self.state_fn, self.on_move, self.on_release)]
     #[widget]
     pub struct Slider<A, T: SliderValue, D: Directional = Direction> {
         core: widget_core!(),
@@ -82,6 +82,7 @@ mod Slider {
         grip: GripPart,
         state_fn: Box<dyn Fn(&ConfigCx, &A) -> T + Send>,
         on_move: Option<Box<dyn Fn(&mut EventCx, &A, T) + Send>>,
+        on_release: Option<Box<dyn Fn(&mut EventCx, &A, T) + Send>>,
     }
 
     impl Self
@@ -176,6 +177,7 @@ mod Slider {
                 grip: GripPart::new(),
                 state_fn: Box::new(state_fn),
                 on_move: None,
+                on_release: None,
             }
         }
 
@@ -198,6 +200,20 @@ mod Slider {
             self
         }
 
+        /// Call the handler `f` on completion of an adjustment
+        ///
+        /// This is called on release of the grip and after each keyboard
+        /// adjustment, thus once per user action (unlike [`Self::with`],
+        /// which is called for each movement). It has no effect unless a
+        /// movement handler is also assigned.
+        #[inline]
+        #[must_use]
+        pub fn with_release(mut self, f: impl Fn(&mut EventCx, &A, T) + Send + 'static) -> Self {
+            debug_assert!(self.on_release.is_none());
+            self.on_release = Some(Box::new(f));
+            self
+        }
+
         /// Get the slider's direction
         #[inline]
         pub fn direction(&self) -> Direction {
@@ -376,6 +392,9 @@ mod Slider {
                         if let Some(ref f) = self.on_move {
                             f(cx, data, self.value);
                         }
+                        if let Some(ref f) = self.on_release {
+                            f(cx, data, self.value);
+                        }
                     }
                 }
                 Event::PressStart(press) => {
@@ -399,7 +418,11 @@ mod Slider {
                 Some(GripMsg::PressMove(pos)) => {
                     self.apply_grip_offset(cx, data, pos);
                 }
-                Some(GripMsg::PressEnd(_)) => (),
+                Some(GripMsg::PressEnd(_)) => {
+                    if let Some(ref f) = self.on_release {
+                        f(cx, data, self.value);
+                    }
+                }
                 None => {
                     let mut new_value = None;
                     if let Some(SetValueF64(v)) = cx.try_pop() {
@@ -468,6 +468,19 @@ impl<A> Stack<A> {
         w.0
     }
 
+    /// Moves the page at position `from` to position `to`
+    ///
+    /// Panics if either index is out of bounds.
+    ///
+    /// The page is not re-configured and retains its identifier. The active
+    /// page does not change (the index of the active page may change instead).
+    pub fn move_page(&mut self, from: usize, to: usize) {
+        let entry = self.widgets.remove(from);
+        self.widgets.insert(to, entry);
+
+        self.active = crate::list::moved_index(self.active, from, to);
+    }
+
     /// Replace the child at `index`
     ///
     /// Panics if `index` is out of bounds.
@@ -584,3 +597,62 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Label;
+
+    fn stack(len: usize, active: usize) -> Stack<()> {
+        let pages = (0..len).map(|i| Page::new(Label::new(i.to_string())));
+        Stack::from(pages).with_active(active)
+    }
+
+    /// Identify each page by the address of its (boxed) widget
+    fn pages(stack: &Stack<()>) -> Vec<*const ()> {
+        (0..stack.len())
+            .map(|i| &*stack[i].inner as *const dyn Widget<Data = ()> as *const ())
+            .collect()
+    }
+
+    #[test]
+    fn move_page_order() {
+        let mut s = stack(4, 0);
+        let p = pages(&s);
+        s.move_page(0, 2);
+        assert_eq!(pages(&s), [p[1], p[2], p[0], p[3]]);
+        s.move_page(3, 0);
+        assert_eq!(pages(&s), [p[3], p[1], p[2], p[0]]);
+    }
+
+    #[test]
+    fn move_active_page() {
+        let mut s = stack(4, 1);
+        s.move_page(1, 3);
+        assert_eq!(s.active(), 3);
+        s.move_page(3, 0);
+        assert_eq!(s.active(), 0);
+    }
+
+    #[test]
+    fn move_page_before_active() {
+        // A page moved from before to after the active page
+        let mut s = stack(4, 2);
+        s.move_page(0, 3);
+        assert_eq!(s.active(), 1);
+
+        // A page moved from after to before the active page
+        s.move_page(3, 0);
+        assert_eq!(s.active(), 2);
+    }
+
+    #[test]
+    fn move_page_after_active() {
+        // Pages moved entirely before or after the active page
+        let mut s = stack(5, 2);
+        s.move_page(3, 4);
+        assert_eq!(s.active(), 2);
+        s.move_page(1, 0);
+        assert_eq!(s.active(), 2);
+    }
+}
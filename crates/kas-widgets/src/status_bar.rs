@@ -0,0 +1,393 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Status bar
+
+use crate::{Label, ProgressBar};
+use kas::Live;
+use kas::event::TimerHandle;
+use kas::layout::{DynRowStorage, RowSetter, RowSolver, RulesSetter, RulesSolver};
+use kas::prelude::*;
+use std::time::{Duration, Instant};
+
+/// Default timeout of a [`StatusMessage`]
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+
+const TIMER_CLEAR: TimerHandle = TimerHandle::new(0, false);
+
+/// Message: show a transient message on a [`StatusBar`]
+///
+/// This may be pushed by any descendant of the [`StatusBar`]'s content or
+/// sent to the [`StatusBar`] directly.
+#[derive(Clone, Debug)]
+pub struct StatusMessage {
+    text: String,
+    timeout: Option<Duration>,
+}
+
+impl StatusMessage {
+    /// Construct with the given `text`
+    ///
+    /// By default the message is cleared after five seconds.
+    pub fn new(text: impl ToString) -> Self {
+        StatusMessage {
+            text: text.to_string(),
+            timeout: Some(DEFAULT_TIMEOUT),
+        }
+    }
+
+    /// Set the timeout (inline)
+    ///
+    /// If `None`, the message is shown until replaced.
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+}
+
+/// Message: set the progress shown on a [`StatusBar`]
+///
+/// The value should be between 0.0 and 1.0. `None` hides the progress bar.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StatusProgress(pub Option<f32>);
+
+#[impl_self]
+mod StatusRow {
+    /// The row of a [`StatusBar`]
+    ///
+    /// Children: index 0 is the message, then segments, then the progress bar.
+    /// The progress bar is always configured but only visible while a value is
+    /// set.
+    #[widget]
+    struct StatusRow<A> {
+        core: widget_core!(),
+        layout: DynRowStorage,
+        message: Label<String>,
+        deadline: Option<Instant>,
+        progress: ProgressBar<f32>,
+        value: Option<f32>,
+        segments: Vec<(Box<dyn Widget<Data = A>>, Stretch)>,
+    }
+
+    impl Layout for Self {
+        fn size_rules(&mut self, cx: &mut SizeCx, axis: AxisInfo) -> SizeRules {
+            let len = self.len();
+            let dim = (Direction::Right, len, true);
+            let mut solver = RowSolver::new(axis, dim, &mut self.layout);
+            let is_horiz = axis.is_horizontal();
+
+            solver.for_child(&mut self.layout, 0, |axis| {
+                let mut rules = self.message.size_rules(cx, axis);
+                if is_horiz {
+                    rules.set_stretch(Stretch::High);
+                }
+                rules
+            });
+            for (n, (child, stretch)) in self.segments.iter_mut().enumerate() {
+                solver.for_child(&mut self.layout, n + 1, |axis| {
+                    let mut rules = child.size_rules(cx, axis);
+                    if is_horiz {
+                        rules.set_stretch(*stretch);
+                    }
+                    rules
+                });
+            }
+            if self.value.is_some() {
+                solver.for_child(&mut self.layout, len - 1, |axis| {
+                    self.progress.size_rules(cx, axis)
+                });
+            }
+            solver.finish(&mut self.layout)
+        }
+
+        fn set_rect(&mut self, cx: &mut SizeCx, rect: Rect, _: AlignHints) {
+            self.core.set_rect(rect);
+            let len = self.len();
+            let dim = (Direction::Right, len, true);
+            let mut setter = RowSetter::<Direction, Vec<i32>, _>::new(rect, dim, &mut self.layout);
+            let hints = AlignHints::NONE;
+
+            let child_rect = setter.child_rect(&mut self.layout, 0);
+            self.message.set_rect(cx, child_rect, hints);
+            for (n, (child, _)) in self.segments.iter_mut().enumerate() {
+                let child_rect = setter.child_rect(&mut self.layout, n + 1);
+                child.set_rect(cx, child_rect, hints);
+            }
+            if self.value.is_some() {
+                let child_rect = setter.child_rect(&mut self.layout, len - 1);
+                self.progress.set_rect(cx, child_rect, hints);
+            }
+        }
+
+        fn draw(&self, mut draw: DrawCx) {
+            self.message.draw(draw.re());
+            for (child, _) in &self.segments {
+                child.draw(draw.re());
+            }
+            if self.value.is_some() {
+                self.progress.draw(draw.re());
+            }
+        }
+    }
+
+    impl Tile for Self {
+        fn role(&self, _: &mut dyn RoleCx) -> Role<'_> {
+            Role::StatusBar
+        }
+
+        fn role_child_properties(&self, cx: &mut dyn RoleCx, index: usize) {
+            if index == 0 {
+                cx.set_live(Live::Polite);
+            }
+        }
+
+        #[inline]
+        fn child_indices(&self) -> ChildIndices {
+            ChildIndices::range(0..self.len())
+        }
+        fn get_child(&self, index: usize) -> Option<&dyn Tile> {
+            let progress = self.segments.len() + 1;
+            match index {
+                0 => Some(self.message.as_tile()),
+                i if i == progress => Some(self.progress.as_tile()),
+                _ => self.segments.get(index - 1).map(|w| w.0.as_tile()),
+            }
+        }
+    }
+
+    impl Events for Self {
+        fn probe(&self, coord: Coord) -> Id {
+            for (child, _) in &self.segments {
+                if let Some(id) = child.try_probe(coord) {
+                    return id;
+                }
+            }
+            self.id()
+        }
+
+        #[inline]
+        fn recurse_indices(&self) -> ChildIndices {
+            // Configure the progress bar even while hidden
+            ChildIndices::range(0..self.segments.len() + 2)
+        }
+
+        fn handle_event(&mut self, cx: &mut EventCx, _: &A, event: Event) -> IsUsed {
+            match event {
+                Event::Timer(TIMER_CLEAR) => {
+                    let now = cx.now();
+                    if self.is_expired(now) {
+                        self.deadline = None;
+                        self.message.set_text(cx, String::new());
+                    } else if let Some(deadline) = self.deadline {
+                        cx.request_timer(self.id(), TIMER_CLEAR, deadline - now);
+                    }
+                    Used
+                }
+                _ => Unused,
+            }
+        }
+    }
+
+    impl Widget for Self {
+        type Data = A;
+
+        fn child_node<'n>(&'n mut self, data: &'n A, index: usize) -> Option<Node<'n>> {
+            let progress = self.segments.len() + 1;
+            match index {
+                0 => Some(self.message.as_node(&())),
+                i if i == progress => {
+                    Some(self.progress.as_node(self.value.as_ref().unwrap_or(&0.0)))
+                }
+                _ => self.segments.get_mut(index - 1).map(|w| w.0.as_node(data)),
+            }
+        }
+    }
+
+    impl Self {
+        fn new(segments: Vec<(Box<dyn Widget<Data = A>>, Stretch)>) -> Self {
+            StatusRow {
+                core: Default::default(),
+                layout: Default::default(),
+                message: Label::new(String::new()).with_wrap(false),
+                deadline: None,
+                progress: ProgressBar::new(|_, value: &f32| *value),
+                value: None,
+                segments,
+            }
+        }
+
+        // Number of children in the layout
+        fn len(&self) -> usize {
+            1 + self.segments.len() + usize::from(self.value.is_some())
+        }
+
+        fn set_message(&mut self, cx: &mut EventCx, msg: StatusMessage) {
+            self.message.set_text(cx, msg.text);
+            self.set_deadline(cx.now(), msg.timeout);
+            if let Some(timeout) = msg.timeout {
+                cx.request_timer(self.id(), TIMER_CLEAR, timeout);
+            }
+        }
+
+        // Set the deadline of a message shown at `now`
+        //
+        // This replaces the deadline of any previous message.
+        fn set_deadline(&mut self, now: Instant, timeout: Option<Duration>) {
+            self.deadline = timeout.map(|timeout| now + timeout);
+        }
+
+        // True if the current message has expired at `now`
+        fn is_expired(&self, now: Instant) -> bool {
+            self.deadline.is_some_and(|deadline| deadline <= now)
+        }
+
+        fn set_progress(&mut self, cx: &mut EventCx, value: Option<f32>) {
+            if value.is_some() != self.value.is_some() {
+                cx.resize();
+            }
+            self.value = value;
+            if let Some(value) = value.as_ref() {
+                cx.update(self.progress.as_node(value));
+                cx.redraw();
+            }
+        }
+    }
+}
+
+#[impl_self]
+mod StatusBar {
+    /// A status bar
+    ///
+    /// This widget places a status bar below some `inner` content (usually the
+    /// content of a window). The status bar consists of a message area followed
+    /// by user-defined segments and an optional progress bar.
+    ///
+    /// # Messages
+    ///
+    /// [`StatusMessage`] may be used to show a transient message. Since
+    /// messages bubble up to ancestor widgets, any widget within `inner` may
+    /// push this message.
+    ///
+    /// [`StatusProgress`] may be used to show (or hide) the progress bar, for
+    /// example to report progress on a background task.
+    #[widget]
+    #[layout(column![self.inner, self.row])]
+    pub struct StatusBar<A> {
+        core: widget_core!(),
+        #[widget]
+        inner: Box<dyn Widget<Data = A>>,
+        #[widget]
+        row: StatusRow<A>,
+    }
+
+    impl Self {
+        /// Construct over `inner` content
+        pub fn new(inner: impl Widget<Data = A> + 'static) -> Self {
+            StatusBar {
+                core: Default::default(),
+                inner: Box::new(inner),
+                row: StatusRow::new(vec![]),
+            }
+        }
+
+        /// Add a segment (inline)
+        ///
+        /// Segments are placed after the message area (which has stretch
+        /// policy [`Stretch::High`]) in order of addition. The `stretch`
+        /// policy determines how much extra space is given to the segment.
+        #[must_use]
+        pub fn with_segment(
+            mut self,
+            segment: impl Widget<Data = A> + 'static,
+            stretch: Stretch,
+        ) -> Self {
+            self.row.segments.push((Box::new(segment), stretch));
+            self
+        }
+
+        /// Show a transient message
+        pub fn set_message(&mut self, cx: &mut EventCx, msg: StatusMessage) {
+            self.row.set_message(cx, msg);
+        }
+
+        /// Set the progress value
+        ///
+        /// The value should be between 0.0 and 1.0. `None` hides the progress bar.
+        pub fn set_progress(&mut self, cx: &mut EventCx, value: Option<f32>) {
+            self.row.set_progress(cx, value);
+        }
+
+        /// Get the current message text
+        pub fn message(&self) -> &str {
+            self.row.message.as_str()
+        }
+    }
+
+    impl Events for Self {
+        type Data = A;
+
+        fn handle_messages(&mut self, cx: &mut EventCx, _: &A) {
+            if let Some(msg) = cx.try_pop::<StatusMessage>() {
+                self.row.set_message(cx, msg);
+            } else if let Some(StatusProgress(value)) = cx.try_pop() {
+                self.row.set_progress(cx, value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Filler;
+
+    fn indices(indices: ChildIndices) -> Vec<usize> {
+        indices.into_iter().collect()
+    }
+
+    #[test]
+    fn sections() {
+        let mut bar = StatusBar::new(Filler::new())
+            .with_segment(Label::new("A"), Stretch::None)
+            .with_segment(Label::new("B"), Stretch::Low);
+        let row = &bar.row;
+        assert_eq!(row.len(), 3);
+        assert_eq!(indices(row.child_indices()), [0, 1, 2]);
+        // The hidden progress bar is still configured
+        assert_eq!(indices(row.recurse_indices()), [0, 1, 2, 3]);
+
+        // Message, then segments in order of addition, then progress
+        let child = |index| row.get_child(index).unwrap();
+        assert!(std::ptr::addr_eq(child(0), row.message.as_tile()));
+        assert!(std::ptr::addr_eq(child(1), row.segments[0].0.as_tile()));
+        assert!(std::ptr::addr_eq(child(2), row.segments[1].0.as_tile()));
+        assert!(std::ptr::addr_eq(child(3), row.progress.as_tile()));
+
+        bar.row.value = Some(0.5);
+        assert_eq!(bar.row.len(), 4);
+        assert_eq!(indices(bar.row.child_indices()), [0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn message_replacement() {
+        let mut row = StatusRow::<()>::new(vec![]);
+        let t0 = Instant::now();
+        let secs = Duration::from_secs;
+
+        row.set_deadline(t0, Some(secs(5)));
+        assert!(!row.is_expired(t0 + secs(4)));
+        assert!(row.is_expired(t0 + secs(5)));
+
+        // A replacement message is not cleared by the earlier deadline
+        row.set_deadline(t0 + secs(3), Some(secs(5)));
+        assert!(!row.is_expired(t0 + secs(5)));
+        assert!(row.is_expired(t0 + secs(8)));
+
+        // A message without timeout is shown until replaced
+        row.set_deadline(t0 + secs(4), None);
+        assert!(!row.is_expired(t0 + secs(3600)));
+    }
+}
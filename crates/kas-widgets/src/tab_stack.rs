@@ -6,10 +6,28 @@
 //! A tabbed stack
 
 use crate::adapt::{AdaptEvents, AdaptWidget};
-use crate::{AccessLabel, Page, Row, Stack};
+use crate::optional_mark::OptionalMark;
+use crate::{AccessLabel, ClipRegion, Page, Row, ScrollRegion, Stack};
+use kas::event::Scroll;
 use kas::messages::{Select, SetIndex};
 use kas::prelude::*;
-use kas::theme::FrameStyle;
+use kas::theme::{FrameStyle, MarkStyle};
+
+/// Messages from a [`Tab`] to its [`TabStack`]
+#[derive(Clone, Copy, Debug)]
+enum TabMsg {
+    /// Request to close the tab
+    Close,
+    /// Tab is dragged to the given coordinate
+    Drag(Coord),
+}
+
+#[derive(Clone, Copy, Debug)]
+struct TabEvent(usize, TabMsg);
+
+/// Request a tab to scroll itself into view
+#[derive(Debug)]
+struct ScrollIntoView;
 
 #[impl_self]
 mod Tab {
@@ -17,15 +35,23 @@ mod Tab {
     ///
     /// This is a special variant of `Button` which sends a [`Select`] on press.
     ///
+    /// A tab may optionally show a close button (see [`Self::set_closable`]);
+    /// a middle-click also closes a closable tab. Tabs may be dragged to
+    /// reorder them within a [`TabStack`].
+    ///
     /// # Messages
     ///
     /// [`kas::messages::Activate`] may be used to open this tab.
     #[widget]
-    #[layout(frame!(self.label).with_style(FrameStyle::Tab))]
+    #[layout(frame!(row![self.label, self.close]).with_style(FrameStyle::Tab))]
     pub struct Tab {
         core: widget_core!(),
         #[widget]
         label: AccessLabel,
+        #[widget]
+        close: OptionalMark<TabMsg>,
+        // Press start coordinate and whether a drag is in progress
+        press: Option<(Coord, bool)>,
     }
 
     impl Self {
@@ -35,6 +61,8 @@ mod Tab {
             Tab {
                 core: Default::default(),
                 label: AccessLabel::new(label),
+                close: OptionalMark::new(false, MarkStyle::X, "Close tab", TabMsg::Close),
+                press: None,
             }
         }
 
@@ -42,6 +70,30 @@ mod Tab {
         pub fn as_str(&self) -> &str {
             self.label.as_str()
         }
+
+        /// Set whether the tab shows a close button (inline)
+        ///
+        /// By default, tabs are not closable.
+        #[inline]
+        #[must_use]
+        pub fn with_closable(mut self, closable: bool) -> Self {
+            self.close.set_visible(closable);
+            self
+        }
+
+        /// True if the tab shows a close button
+        #[inline]
+        pub fn is_closable(&self) -> bool {
+            self.close.is_visible()
+        }
+
+        /// Set whether the tab shows a close button
+        pub fn set_closable(&mut self, cx: &mut ConfigCx, closable: bool) {
+            if closable != self.close.is_visible() {
+                self.close.set_visible(closable);
+                cx.resize();
+            }
+        }
     }
 
     impl Tile for Self {
@@ -60,18 +112,59 @@ mod Tab {
 
         type Data = ();
 
-        fn probe(&self, _: Coord) -> Id {
-            self.id()
+        fn probe(&self, coord: Coord) -> Id {
+            self.close.try_probe(coord).unwrap_or_else(|| self.id())
         }
 
         fn handle_event(&mut self, cx: &mut EventCx, _: &(), event: Event) -> IsUsed {
-            event.on_click(cx, self.id(), |cx| cx.push(Select))
+            match event {
+                Event::Command(cmd, code) if cmd.is_activate() => {
+                    cx.depress_with_key(self.id(), code);
+                    cx.push(Select);
+                    Used
+                }
+                Event::PressStart(press) if press.is_tertiary() && self.close.is_visible() => {
+                    press.grab_click(self.id()).complete(cx)
+                }
+                Event::PressStart(press) if press.is_primary() => {
+                    self.press = Some((press.coord(), false));
+                    press.grab_move(self.id()).complete(cx)
+                }
+                Event::PressMove { press, .. } => {
+                    if let Some((start, ref mut dragging)) = self.press {
+                        if !*dragging && cx.config_test_pan_thresh(press.coord - start) {
+                            *dragging = true;
+                        }
+                        if *dragging {
+                            cx.push(TabMsg::Drag(press.coord));
+                        }
+                    }
+                    Used
+                }
+                Event::PressEnd { press, success } => {
+                    let on_self = success && self.id() == press.id;
+                    if press.is_tertiary() {
+                        if on_self {
+                            cx.push(TabMsg::Close);
+                        }
+                    } else if let Some((_, dragging)) = self.press.take()
+                        && !dragging
+                        && on_self
+                    {
+                        cx.push(Select);
+                    }
+                    Used
+                }
+                _ => Unused,
+            }
         }
 
         fn handle_messages(&mut self, cx: &mut EventCx, _: &()) {
             if let Some(kas::messages::Activate(code)) = cx.try_pop() {
                 cx.push(Select);
                 cx.depress_with_key(&self, code);
+            } else if let Some(ScrollIntoView) = cx.try_pop() {
+                cx.set_scroll(Scroll::Rect(self.rect()));
             }
         }
     }
@@ -88,6 +181,16 @@ mod Tab {
 /// This is a parametrisation of [`TabStack`].
 pub type BoxTabStack<Data> = TabStack<Box<dyn Widget<Data = Data>>>;
 
+/// The scrollable tab bar
+type TabBar = ScrollRegion<ClipRegion<AdaptEvents<Row<Vec<Tab>>>>>;
+
+fn tab_bar(tabs: Vec<Tab>) -> TabBar {
+    let row = Row::new(tabs)
+        .map_message(|index, Select| SetIndex(index))
+        .map_message(|index, msg: TabMsg| TabEvent(index, msg));
+    ScrollRegion::new_clip(row).with_invisible_bars(true, false)
+}
+
 #[impl_self]
 mod TabStack {
     /// A tabbed stack of widgets
@@ -100,12 +203,27 @@ mod TabStack {
     /// default value is [`Direction::Up`]: tabs are positioned above the stack.
     /// Within the bar, items are always left-to-right
     /// (TODO: support for vertical bars).
+    /// Where tabs exceed the available width, the bar may be scrolled.
+    ///
+    /// Tabs may be reordered by dragging (see also [`Self::with_move`]). Tabs
+    /// may optionally be closed (see [`Self::with_closable`]).
     ///
     /// This may only be parametrised with a single widget type, thus usually
     /// it will be necessary to box children (this is what [`BoxTabStack`] is).
     ///
     /// See also the main implementing widget: [`Stack`].
     ///
+    /// # Commands
+    ///
+    /// When a tab or page has navigation focus, the following commands are
+    /// handled:
+    ///
+    /// -   [`Command::TabNext`] and [`Command::TabPrevious`] change the page
+    /// -   [`Command::TabNew`] calls the handler set by [`Self::with_new_tab`]
+    ///     (if any)
+    /// -   [`Command::Close`] closes the active page when a tab has navigation
+    ///     focus, if closable (otherwise the command closes the window)
+    ///
     /// # Messages
     ///
     /// [`kas::messages::SetIndex`] may be used to change the page.
@@ -115,11 +233,15 @@ mod TabStack {
     pub struct TabStack<A> {
         core: widget_core!(),
         direction: Direction,
+        closable: bool,
         #[widget(&())]
-        tabs: AdaptEvents<Row<Vec<Tab>>>, // TODO: want a TabBar widget for scrolling support?
+        tabs: TabBar,
         #[widget]
         stack: Stack<A>,
         on_change: Option<Box<dyn Fn(&mut EventCx, &A, usize, &str) + Send>>,
+        on_close: Option<Box<dyn Fn(&mut EventCx, &A, usize) + Send>>,
+        on_new: Option<Box<dyn Fn(&mut EventCx, &A) + Send>>,
+        on_move: Option<Box<dyn Fn(&mut EventCx, &A, usize, usize) + Send>>,
     }
 
     impl Self {
@@ -130,9 +252,13 @@ mod TabStack {
             Self {
                 core: Default::default(),
                 direction: Direction::Up,
+                closable: false,
                 stack: Stack::new(),
-                tabs: Row::new(vec![]).map_message(|index, Select| SetIndex(index)),
+                tabs: tab_bar(vec![]),
                 on_change: None,
+                on_close: None,
+                on_new: None,
+                on_move: None,
             }
         }
 
@@ -170,6 +296,175 @@ mod TabStack {
         {
             self.with(move |cx, _, index, title| cx.push(f(index, title)))
         }
+
+        /// Set whether tabs show a close button (inline)
+        ///
+        /// This applies to all tabs, including those added later.
+        ///
+        /// By default, a closed tab and its page are removed immediately. Use
+        /// [`Self::with_close`] to handle close requests instead.
+        #[inline]
+        #[must_use]
+        pub fn with_closable(mut self, closable: bool) -> Self {
+            self.closable = closable;
+            for tab in self.tab_row_mut().iter_mut() {
+                tab.close.set_visible(closable);
+            }
+            self
+        }
+
+        /// Call the handler `f` on a request to close a tab
+        ///
+        /// `f` receives as parameters input data and page index.
+        ///
+        /// When this handler is set, the tab is *not* removed automatically;
+        /// the handler (or a parent receiving a message from it) is expected to
+        /// call [`Self::remove`] where appropriate. This allows closing to be
+        /// vetoed, e.g. to confirm discarding unsaved changes.
+        #[inline]
+        #[must_use]
+        pub fn with_close(mut self, f: impl Fn(&mut EventCx, &A, usize) + Send + 'static) -> Self {
+            debug_assert!(self.on_close.is_none());
+            self.on_close = Some(Box::new(f));
+            self
+        }
+
+        /// Send the message generated by `f` on a request to close a tab
+        ///
+        /// `f` receives the page index. See [`Self::with_close`].
+        #[inline]
+        #[must_use]
+        pub fn with_close_msg<M>(self, f: impl Fn(usize) -> M + Send + 'static) -> Self
+        where
+            M: std::fmt::Debug + 'static,
+        {
+            self.with_close(move |cx, _, index| cx.push(f(index)))
+        }
+
+        /// Call the handler `f` on [`Command::TabNew`]
+        ///
+        /// The handler is expected to add a new page.
+        #[inline]
+        #[must_use]
+        pub fn with_new_tab(mut self, f: impl Fn(&mut EventCx, &A) + Send + 'static) -> Self {
+            debug_assert!(self.on_new.is_none());
+            self.on_new = Some(Box::new(f));
+            self
+        }
+
+        /// Send the message generated by `f` on [`Command::TabNew`]
+        ///
+        /// See [`Self::with_new_tab`].
+        #[inline]
+        #[must_use]
+        pub fn with_new_tab_msg<M>(self, f: impl Fn() -> M + Send + 'static) -> Self
+        where
+            M: std::fmt::Debug + 'static,
+        {
+            self.with_new_tab(move |cx, _| cx.push(f()))
+        }
+
+        /// Call the handler `f` when a tab is moved by dragging
+        ///
+        /// `f` receives as parameters input data and the page's old and new
+        /// indices. The page has already been moved when `f` is called.
+        #[inline]
+        #[must_use]
+        pub fn with_move(
+            mut self,
+            f: impl Fn(&mut EventCx, &A, usize, usize) + Send + 'static,
+        ) -> Self {
+            debug_assert!(self.on_move.is_none());
+            self.on_move = Some(Box::new(f));
+            self
+        }
+
+        /// Send the message generated by `f` when a tab is moved by dragging
+        ///
+        /// `f` receives the page's old and new indices. See [`Self::with_move`].
+        #[inline]
+        #[must_use]
+        pub fn with_move_msg<M>(self, f: impl Fn(usize, usize) -> M + Send + 'static) -> Self
+        where
+            M: std::fmt::Debug + 'static,
+        {
+            self.with_move(move |cx, _, from, to| cx.push(f(from, to)))
+        }
+
+        fn tab_row(&self) -> &Row<Vec<Tab>> {
+            &self.tabs.inner().inner().inner
+        }
+
+        fn tab_row_mut(&mut self) -> &mut Row<Vec<Tab>> {
+            &mut self.tabs.inner_mut().inner_mut().inner
+        }
+
+        fn notify_change(&mut self, cx: &mut EventCx, data: &A, index: usize) {
+            if let Some(tab) = self.tab_row().get(index) {
+                cx.send(tab.id(), ScrollIntoView);
+                if let Some(ref f) = self.on_change {
+                    f(cx, data, index, tab.as_str());
+                }
+            }
+        }
+
+        // Move to the next (or previous) page, wrapping around
+        fn step_active(&mut self, cx: &mut EventCx, data: &A, reverse: bool) -> IsUsed {
+            let len = self.len();
+            if len == 0 {
+                return Unused;
+            }
+
+            let index = step_index(self.active(), len, reverse);
+            if index != self.active() {
+                self.set_active(cx, data, index);
+                self.notify_change(cx, data, index);
+            }
+            Used
+        }
+
+        fn close_tab(&mut self, cx: &mut EventCx, data: &A, index: usize) {
+            if !self.closable || index >= self.len() {
+                return;
+            }
+            if let Some(ref f) = self.on_close {
+                f(cx, data, index);
+                return;
+            }
+
+            let active = self.active();
+            let _ = self.remove(cx, index);
+            let Some(new_active) = active_after_close(active, index, self.len()) else {
+                return;
+            };
+            self.set_active(cx, data, new_active);
+            if active == index {
+                self.notify_change(cx, data, new_active);
+            }
+        }
+
+        fn drag_tab(&mut self, cx: &mut EventCx, data: &A, index: usize, coord: Coord) {
+            let row = self.tab_row();
+            let width = row[index].rect().size.0;
+            let Some(target) = row.iter().position(|tab| {
+                let rect = tab.rect();
+                rect.pos.0 <= coord.0 && coord.0 < rect.pos.0 + rect.size.0
+            }) else {
+                return;
+            };
+
+            // Only move once the dragged tab would remain under the pointer
+            // (avoiding oscillation between tabs of different widths).
+            let rect = row[target].rect();
+            if (target < index && coord.0 < rect.pos.0 + width)
+                || (target > index && coord.0 >= rect.pos.0 + rect.size.0 - width)
+            {
+                self.move_page(cx, index, target);
+                if let Some(ref f) = self.on_move {
+                    f(cx, data, index, target);
+                }
+            }
+        }
     }
 
     impl Tile for Self {
@@ -182,12 +477,42 @@ mod TabStack {
     impl Events for Self {
         type Data = A;
 
+        fn handle_event(&mut self, cx: &mut EventCx, data: &A, event: Event) -> IsUsed {
+            match event {
+                Event::Command(Command::TabNext, _) => self.step_active(cx, data, false),
+                Event::Command(Command::TabPrevious, _) => self.step_active(cx, data, true),
+                Event::Command(Command::TabNew, _) => match self.on_new {
+                    Some(ref f) => {
+                        f(cx, data);
+                        Used
+                    }
+                    None => Unused,
+                },
+                Event::Command(Command::Close, _)
+                    if self.closable
+                        && cx
+                            .nav_focus()
+                            .is_some_and(|id| self.tabs.id_ref().is_ancestor_of(id)) =>
+                {
+                    let index = self.active();
+                    if index >= self.len() {
+                        return Unused;
+                    }
+                    self.close_tab(cx, data, index);
+                    Used
+                }
+                _ => Unused,
+            }
+        }
+
         fn handle_messages(&mut self, cx: &mut EventCx, data: &A) {
             if let Some(SetIndex(index)) = cx.try_pop() {
                 self.set_active(cx, data, index);
-                if let Some(ref f) = self.on_change {
-                    let title = self.tabs.inner[index].as_str();
-                    f(cx, data, index, title);
+                self.notify_change(cx, data, index);
+            } else if let Some(TabEvent(index, msg)) = cx.try_pop() {
+                match msg {
+                    TabMsg::Close => self.close_tab(cx, data, index),
+                    TabMsg::Drag(coord) => self.drag_tab(cx, data, index, coord),
                 }
             }
         }
@@ -259,7 +584,7 @@ impl<A> TabStack<A> {
     /// This does not change the active page index.
     pub fn clear(&mut self) {
         self.stack.clear();
-        self.tabs.inner.clear();
+        self.tab_row_mut().clear();
     }
 
     /// Get a page
@@ -274,12 +599,12 @@ impl<A> TabStack<A> {
 
     /// Get a tab
     pub fn get_tab(&self, index: usize) -> Option<&Tab> {
-        self.tabs.inner.get(index)
+        self.tab_row().get(index)
     }
 
     /// Get a tab
     pub fn get_tab_mut(&mut self, index: usize) -> Option<&mut Tab> {
-        self.tabs.inner.get_mut(index)
+        self.tab_row_mut().get_mut(index)
     }
 
     /// Append a page
@@ -289,7 +614,8 @@ impl<A> TabStack<A> {
     ///
     /// Returns the new page's index.
     pub fn push(&mut self, cx: &mut ConfigCx, data: &A, tab: Tab, page: Page<A>) -> usize {
-        let ti = self.tabs.inner.push(cx, &(), tab);
+        let tab = tab.with_closable(self.closable);
+        let ti = self.tab_row_mut().push(cx, &(), tab);
         let si = self.stack.push(cx, data, page);
         debug_assert_eq!(ti, si);
         si
@@ -300,7 +626,7 @@ impl<A> TabStack<A> {
     /// If this page was active then no page will be left active.
     /// Consider also calling [`Self::set_active`].
     pub fn pop(&mut self, cx: &mut ConfigCx) -> Option<(Tab, Page<A>)> {
-        let tab = self.tabs.inner.pop(cx);
+        let tab = self.tab_row_mut().pop(cx);
         let w = self.stack.pop(cx);
         debug_assert_eq!(tab.is_some(), w.is_some());
         tab.zip(w)
@@ -312,7 +638,8 @@ impl<A> TabStack<A> {
     ///
     /// The active page does not change (the index of the active page may change instead).
     pub fn insert(&mut self, cx: &mut ConfigCx, data: &A, index: usize, tab: Tab, page: Page<A>) {
-        self.tabs.inner.insert(cx, &(), index, tab);
+        let tab = tab.with_closable(self.closable);
+        self.tab_row_mut().insert(cx, &(), index, tab);
         self.stack.insert(cx, data, index, page);
     }
 
@@ -323,11 +650,21 @@ impl<A> TabStack<A> {
     /// If this page was active then no page will be left active.
     /// Consider also calling [`Self::set_active`].
     pub fn remove(&mut self, cx: &mut ConfigCx, index: usize) -> (Tab, Page<A>) {
-        let tab = self.tabs.inner.remove(cx, index);
+        let tab = self.tab_row_mut().remove(cx, index);
         let stack = self.stack.remove(cx, index);
         (tab, stack)
     }
 
+    /// Move the page at `from` (and its tab) to position `to`
+    ///
+    /// Panics if either index is out of bounds.
+    ///
+    /// The active page does not change (the index of the active page may change instead).
+    pub fn move_page(&mut self, cx: &mut ConfigCx, from: usize, to: usize) {
+        self.tab_row_mut().move_child(cx, from, to);
+        self.stack.move_page(from, to);
+    }
+
     /// Replace the child at `index`
     ///
     /// Panics if `index` is out of bounds.
@@ -352,7 +689,8 @@ impl<A> TabStack<A> {
         // self.tabs.reserve(min_len);
         // self.stack.reserve(min_len);
         for (tab, w) in iter {
-            self.tabs.inner.push(cx, &(), tab);
+            let tab = tab.with_closable(self.closable);
+            self.tab_row_mut().push(cx, &(), tab);
             self.stack.push(cx, data, w);
         }
     }
@@ -375,8 +713,76 @@ where
         }
         Self {
             stack: Stack::from(stack),
-            tabs: Row::new(tabs).map_message(|index, Select| SetIndex(index)),
+            tabs: tab_bar(tabs),
             ..Default::default()
         }
     }
 }
+
+/// Get the page after (or before) `active`, wrapping around
+///
+/// Requires `len > 0`.
+fn step_index(active: usize, len: usize, reverse: bool) -> usize {
+    let active = active.min(len - 1);
+    match reverse {
+        false => (active + 1) % len,
+        true => (active + len - 1) % len,
+    }
+}
+
+/// Get the new active page after closing page `index`
+///
+/// Here, `len` is the number of pages remaining. Returns `None` if no pages
+/// remain.
+fn active_after_close(active: usize, index: usize, len: usize) -> Option<usize> {
+    if len == 0 {
+        return None;
+    }
+    Some(match active {
+        a if a > index => a - 1,
+        a => a.min(len - 1),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn step_index_wraps() {
+        assert_eq!(step_index(0, 3, false), 1);
+        assert_eq!(step_index(2, 3, false), 0);
+        assert_eq!(step_index(1, 3, true), 0);
+        assert_eq!(step_index(0, 3, true), 2);
+        assert_eq!(step_index(0, 1, false), 0);
+        assert_eq!(step_index(0, 1, true), 0);
+    }
+
+    #[test]
+    fn step_index_clamps_active() {
+        // No active page (usize::MAX) or an out-of-range index
+        assert_eq!(step_index(usize::MAX, 3, false), 0);
+        assert_eq!(step_index(usize::MAX, 3, true), 1);
+        assert_eq!(step_index(5, 3, false), 0);
+    }
+
+    #[test]
+    fn close_active_tab() {
+        // The next tab becomes active
+        assert_eq!(active_after_close(1, 1, 3), Some(1));
+        // ... or the previous tab if the last was closed
+        assert_eq!(active_after_close(3, 3, 3), Some(2));
+    }
+
+    #[test]
+    fn close_other_tab() {
+        assert_eq!(active_after_close(2, 0, 3), Some(1));
+        assert_eq!(active_after_close(1, 2, 3), Some(1));
+        assert_eq!(active_after_close(1, 3, 3), Some(1));
+    }
+
+    #[test]
+    fn close_last_tab() {
+        assert_eq!(active_after_close(0, 0, 0), None);
+    }
+}
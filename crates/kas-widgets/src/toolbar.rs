@@ -0,0 +1,595 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Tool bar
+
+use crate::adapt::MapAny;
+use crate::menu::{BoxedMenu, MenuBar, MenuEntry, MenuToggle, SubMenu};
+use crate::{AccessLabel, Button, CheckBox, ComboBox, Separator};
+use kas::prelude::*;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+/// Label of the overflow menu
+const OVERFLOW_LABEL: &str = "»";
+
+/// Message: the number of visible items changed
+#[derive(Debug)]
+struct Overflowed;
+
+type MenuFn<A> = Box<dyn Fn() -> BoxedMenu<A>>;
+
+#[impl_self]
+mod ToolItem {
+    /// An item of a [`ToolBar`]
+    ///
+    /// This adds a tooltip to the `inner` widget, and remembers how to
+    /// construct an equivalent menu item for the overflow menu.
+    ///
+    /// Since this shares the `inner` widget's identifier, the tooltip is shown
+    /// where `inner` is the target of mouse hover (see [`Events::probe`]).
+    #[derive_widget]
+    struct ToolItem<W: Widget> {
+        #[widget]
+        inner: W,
+        text: String,
+        command: Option<Command>,
+        tooltip: Option<String>,
+        menu_fn: MenuFn<W::Data>,
+    }
+
+    impl Tile for Self {
+        fn tooltip(&self) -> Option<&str> {
+            self.tooltip.as_deref()
+        }
+    }
+}
+
+#[impl_self]
+mod ToolToggle {
+    /// A toggle of a [`ToolBar`]
+    ///
+    /// This is similar to [`CheckButton`](crate::CheckButton), except that
+    /// the toggle itself (not the inner [`CheckBox`]) is the target of mouse
+    /// input, thus the item's tooltip is shown over both box and label.
+    #[widget]
+    #[layout(row![self.inner, self.label])]
+    struct ToolToggle<A> {
+        core: widget_core!(),
+        #[widget]
+        inner: CheckBox<A>,
+        #[widget(&())]
+        label: AccessLabel,
+    }
+
+    impl Tile for Self {
+        fn role(&self, _: &mut dyn RoleCx) -> Role<'_> {
+            Role::None
+        }
+
+        fn role_child_properties(&self, cx: &mut dyn RoleCx, index: usize) {
+            if index == widget_index!(self.inner) {
+                cx.set_label(self.label.id());
+            }
+        }
+
+        fn nav_next(&self, _: bool, from: Option<usize>) -> Option<usize> {
+            from.xor(Some(widget_index!(self.inner)))
+        }
+    }
+
+    impl Events for Self {
+        type Data = A;
+
+        fn probe(&self, _: Coord) -> Id {
+            self.id()
+        }
+
+        fn post_configure(&mut self, _: &mut ConfigCx) {
+            self.label.set_target(self.inner.id());
+        }
+
+        fn handle_event(&mut self, cx: &mut EventCx, data: &A, event: Event) -> IsUsed {
+            event.on_click(cx, self.id(), |cx| self.inner.toggle(cx, data))
+        }
+
+        fn handle_messages(&mut self, cx: &mut EventCx, data: &A) {
+            if let Some(kas::messages::Activate(code)) = cx.try_pop() {
+                self.inner.toggle(cx, data);
+                cx.depress_with_key(self.inner.id(), code);
+            }
+        }
+    }
+}
+
+#[impl_self]
+mod ToolBar {
+    /// A tool bar
+    ///
+    /// A row (or column) of buttons, toggles, separators and combo boxes.
+    /// Items which do not fit are moved to an overflow menu shown at the end
+    /// of the bar.
+    ///
+    /// Items may have a tooltip (see [`Self::with_tooltip`]). Where an item is
+    /// associated with a [`Command`] (see [`Self::with_shortcut`]), the key
+    /// binding for this command (from [`Shortcuts`](kas::config::Shortcuts))
+    /// is appended to the tooltip.
+    ///
+    /// The bar's orientation is set by the [`Directional`] type `D`.
+    #[widget]
+    pub struct ToolBar<A: 'static, D: Directional = kas::dir::Right> {
+        core: widget_core!(),
+        direction: D,
+        overflow: MenuBar<A, D>,
+        items: Vec<ToolItem<Box<dyn Widget<Data = A>>>>,
+        // Size rules of items along the main axis
+        rules: Vec<SizeRules>,
+        overflow_rules: SizeRules,
+        // Number of items shown on the bar
+        visible: usize,
+    }
+
+    impl Self
+    where
+        D: Default,
+    {
+        /// Construct an empty tool bar
+        #[inline]
+        pub fn new() -> Self {
+            ToolBar::new_dir(D::default())
+        }
+    }
+
+    impl Self {
+        /// Construct an empty tool bar with explicit direction
+        pub fn new_dir(direction: D) -> Self {
+            ToolBar {
+                core: Default::default(),
+                direction,
+                overflow: Self::overflow_menu(direction, vec![]),
+                items: vec![],
+                rules: vec![],
+                overflow_rules: SizeRules::EMPTY,
+                visible: 0,
+            }
+        }
+
+        fn overflow_menu(direction: D, list: Vec<BoxedMenu<A>>) -> MenuBar<A, D> {
+            let dir = direction.as_direction().flipped();
+            let menu = SubMenu::new(OVERFLOW_LABEL, list, dir);
+            MenuBar::new_dir(vec![menu], direction)
+        }
+
+        fn push_item(
+            &mut self,
+            inner: Box<dyn Widget<Data = A>>,
+            text: String,
+            menu_fn: MenuFn<A>,
+        ) {
+            self.items.push(ToolItem {
+                inner,
+                text,
+                command: None,
+                tooltip: None,
+                menu_fn,
+            });
+            self.visible = self.items.len();
+        }
+
+        /// Add a button (inline)
+        ///
+        /// When clicked, the button pushes a clone of `msg`.
+        #[must_use]
+        pub fn with_button<M>(mut self, label: impl Into<AccessString>, msg: M) -> Self
+        where
+            M: Clone + Debug + Send + 'static,
+        {
+            let label = label.into();
+            let text = label.text().to_string();
+            let button = Button::label_msg(label.clone(), msg.clone());
+            let menu_fn = move || -> BoxedMenu<A> {
+                Box::new(MapAny::new(MenuEntry::new_msg(label.clone(), msg.clone())))
+            };
+            self.push_item(Box::new(MapAny::new(button)), text, Box::new(menu_fn));
+            self
+        }
+
+        /// Add a toggle (inline)
+        ///
+        /// -   `state_fn` extracts the current state from input data
+        /// -   A message generated by `msg_fn` is emitted when toggled
+        #[must_use]
+        pub fn with_toggle<M: Debug + 'static>(
+            mut self,
+            label: impl Into<AccessString>,
+            state_fn: impl Fn(&ConfigCx, &A) -> bool + Send + Sync + 'static,
+            msg_fn: impl Fn(bool) -> M + Send + Sync + 'static,
+        ) -> Self {
+            let label = label.into();
+            let text = label.text().to_string();
+            let state_fn = Arc::new(state_fn);
+            let msg_fn = Arc::new(msg_fn);
+
+            let (s, m) = (state_fn.clone(), msg_fn.clone());
+            let toggle = ToolToggle {
+                core: Default::default(),
+                inner: CheckBox::new_msg(move |cx, data| s(cx, data), move |state| m(state)),
+                label: AccessLabel::new(label.clone()),
+            };
+            let menu_fn = move || -> BoxedMenu<A> {
+                let (s, m) = (state_fn.clone(), msg_fn.clone());
+                Box::new(MenuToggle::new_msg(
+                    label.clone(),
+                    move |cx, data| s(cx, data),
+                    move |state| m(state),
+                ))
+            };
+            self.push_item(Box::new(toggle), text, Box::new(menu_fn));
+            self
+        }
+
+        /// Add a combo box (inline)
+        ///
+        /// -   `label` is used for the tooltip and the overflow menu
+        /// -   `iter` yields the choices: a label and value for each
+        /// -   `state_fn` selects the active choice from input data
+        /// -   A message generated by `msg_fn` is emitted on selection
+        #[must_use]
+        pub fn with_combo_box<T, I, V, M>(
+            mut self,
+            label: impl Into<AccessString>,
+            iter: I,
+            state_fn: impl Fn(&ConfigCx, &A) -> V + Send + 'static,
+            msg_fn: impl Fn(V) -> M + Send + Sync + 'static,
+        ) -> Self
+        where
+            T: Into<AccessString>,
+            I: IntoIterator<Item = (T, V)>,
+            V: Clone + Debug + Eq + 'static,
+            M: Clone + Debug + 'static,
+        {
+            let label = label.into();
+            let text = label.text().to_string();
+            let choices: Vec<(AccessString, V)> = iter
+                .into_iter()
+                .map(|(label, value)| (label.into(), value))
+                .collect();
+            let msg_fn = Arc::new(msg_fn);
+
+            let m = msg_fn.clone();
+            let combo = ComboBox::new_msg(choices.clone(), state_fn, move |value| m(value));
+            let menu_fn = move || -> BoxedMenu<A> {
+                let list = choices
+                    .iter()
+                    .map(|(label, value)| -> BoxedMenu<A> {
+                        let msg = msg_fn(value.clone());
+                        Box::new(MapAny::new(MenuEntry::new_msg(label.clone(), msg)))
+                    })
+                    .collect();
+                Box::new(SubMenu::<false, A>::right(label.clone(), list))
+            };
+            self.push_item(Box::new(combo), text, Box::new(menu_fn));
+            self
+        }
+
+        /// Add a separator (inline)
+        #[must_use]
+        pub fn with_separator(mut self) -> Self {
+            let menu_fn = || -> BoxedMenu<A> { Box::new(Separator::new()) };
+            self.push_item(Box::new(Separator::new()), String::new(), Box::new(menu_fn));
+            self
+        }
+
+        /// Set the tooltip of the last item (inline)
+        ///
+        /// By default, the tooltip of buttons, toggles and combo boxes is
+        /// their label.
+        #[must_use]
+        pub fn with_tooltip(mut self, text: impl ToString) -> Self {
+            if let Some(item) = self.items.last_mut() {
+                item.text = text.to_string();
+            }
+            self
+        }
+
+        /// Associate the last item with a `command` (inline)
+        ///
+        /// The key binding for this command, if any, is shown in the item's
+        /// tooltip. This does not bind the key; the app should handle the
+        /// command itself (usually on the window root).
+        #[must_use]
+        pub fn with_shortcut(mut self, command: Command) -> Self {
+            if let Some(item) = self.items.last_mut() {
+                item.command = Some(command);
+            }
+            self
+        }
+
+        /// Get the number of items
+        #[inline]
+        pub fn len(&self) -> usize {
+            self.items.len()
+        }
+
+        /// True if there are no items
+        #[inline]
+        pub fn is_empty(&self) -> bool {
+            self.items.is_empty()
+        }
+
+        /// True if some items are shown in the overflow menu
+        #[inline]
+        pub fn is_overflowing(&self) -> bool {
+            self.visible < self.items.len()
+        }
+
+        // Number of items which fit within length `len`
+        fn fit(&self, len: i32) -> usize {
+            let mut used = 0;
+            let mut margin = 0;
+            for (n, rules) in self.rules.iter().enumerate() {
+                let (m0, m1) = rules.margins_i32();
+                if n > 0 {
+                    used += margin.max(m0);
+                }
+                used += rules.ideal_size();
+                if used > len {
+                    return n;
+                }
+                margin = m1;
+            }
+            self.rules.len()
+        }
+
+        // Partition items for bar length `len`
+        //
+        // Returns the number of items shown on the bar and the length of the
+        // overflow menu (zero if all items fit).
+        fn partition(&self, len: i32) -> (usize, i32) {
+            let visible = self.fit(len);
+            if visible < self.rules.len() {
+                let (m0, _) = self.overflow_rules.margins_i32();
+                let overflow_len = self.overflow_rules.min_size();
+                (self.fit(len - overflow_len - m0), overflow_len)
+            } else {
+                (visible, 0)
+            }
+        }
+
+        // Get the rect of an element at `pos` with `size` along the main axis
+        fn child_rect(&self, pos: i32, size: i32) -> Rect {
+            let rect = self.rect();
+            let mut child = rect;
+            if self.direction.is_horizontal() {
+                child.size.0 = size;
+                child.pos.0 += if self.direction.is_reversed() {
+                    rect.size.0 - pos - size
+                } else {
+                    pos
+                };
+            } else {
+                child.size.1 = size;
+                child.pos.1 += if self.direction.is_reversed() {
+                    rect.size.1 - pos - size
+                } else {
+                    pos
+                };
+            }
+            child
+        }
+    }
+
+    impl Layout for Self {
+        fn size_rules(&mut self, cx: &mut SizeCx, axis: AxisInfo) -> SizeRules {
+            let is_main = axis.is_horizontal() == self.direction.is_horizontal();
+            let overflow = self.overflow.size_rules(cx, axis);
+
+            if is_main {
+                self.rules.clear();
+                let mut total: Option<SizeRules> = None;
+                for item in self.items.iter_mut() {
+                    let rules = item.size_rules(cx, axis);
+                    self.rules.push(rules);
+                    total = Some(match total {
+                        Some(total) => total.appended(rules),
+                        None => rules,
+                    });
+                }
+                self.overflow_rules = overflow;
+
+                // We require at least enough space for the overflow menu
+                let total = total.unwrap_or(overflow);
+                let ideal = total.ideal_size().max(overflow.min_size());
+                SizeRules::new(overflow.min_size(), ideal, Stretch::Low)
+                    .with_margins(total.margins())
+            } else {
+                let mut rules = overflow;
+                for item in self.items.iter_mut() {
+                    rules.max_with(item.size_rules(cx, axis));
+                }
+                rules
+            }
+        }
+
+        fn set_rect(&mut self, cx: &mut SizeCx, rect: Rect, _: AlignHints) {
+            self.core.set_rect(rect);
+            let hints = AlignHints::CENTER;
+            let len = rect.size.extract(self.direction);
+
+            let (visible, overflow_len) = self.partition(len);
+
+            let mut pos = 0;
+            let mut margin = 0;
+            for n in 0..visible {
+                let rules = self.rules[n];
+                let (m0, m1) = rules.margins_i32();
+                if n > 0 {
+                    pos += margin.max(m0);
+                }
+                let size = rules.ideal_size();
+                let child_rect = self.child_rect(pos, size);
+                self.items[n].set_rect(cx, child_rect, hints);
+                pos += size;
+                margin = m1;
+            }
+
+            let overflow_rect = self.child_rect(len - overflow_len, overflow_len);
+            self.overflow.set_rect(cx, overflow_rect, hints);
+
+            if visible != self.visible {
+                self.visible = visible;
+                cx.send(self.id(), Overflowed);
+            }
+        }
+
+        fn draw(&self, mut draw: DrawCx) {
+            for item in &self.items[..self.visible] {
+                item.draw(draw.re());
+            }
+            if self.is_overflowing() {
+                self.overflow.draw(draw.re());
+            }
+        }
+    }
+
+    impl Tile for Self {
+        fn role(&self, _: &mut dyn RoleCx) -> Role<'_> {
+            Role::ToolBar
+        }
+
+        // Index 0 is the overflow menu; items follow
+        #[inline]
+        fn child_indices(&self) -> ChildIndices {
+            let start = if self.is_overflowing() { 0 } else { 1 };
+            ChildIndices::range(start..self.visible + 1)
+        }
+        fn get_child(&self, index: usize) -> Option<&dyn Tile> {
+            if index == 0 {
+                Some(self.overflow.as_tile())
+            } else {
+                self.items.get(index - 1).map(|w| w.as_tile())
+            }
+        }
+
+        fn nav_next(&self, reverse: bool, from: Option<usize>) -> Option<usize> {
+            // Navigate items first, then the overflow menu
+            let overflow = self.is_overflowing().then_some(0);
+            let order: Vec<usize> = (1..self.visible + 1).chain(overflow).collect();
+            match from {
+                None if reverse => order.last().cloned(),
+                None => order.first().cloned(),
+                Some(index) => {
+                    let pos = order.iter().position(|i| *i == index)?;
+                    if reverse {
+                        pos.checked_sub(1).map(|pos| order[pos])
+                    } else {
+                        order.get(pos + 1).cloned()
+                    }
+                }
+            }
+        }
+    }
+
+    impl Events for Self {
+        fn probe(&self, coord: Coord) -> Id {
+            if self.is_overflowing()
+                && let Some(id) = self.overflow.try_probe(coord)
+            {
+                return id;
+            }
+            for item in &self.items[..self.visible] {
+                if let Some(id) = item.try_probe(coord) {
+                    return id;
+                }
+            }
+            self.id()
+        }
+
+        fn configure(&mut self, cx: &mut ConfigCx) {
+            let shortcuts = cx.config().shortcuts();
+            for item in self.items.iter_mut() {
+                let binding = item.command.and_then(|cmd| shortcuts.describe_binding(cmd));
+                item.tooltip = match binding {
+                    Some(binding) if item.text.is_empty() => Some(binding),
+                    Some(binding) => Some(format!("{} ({binding})", item.text)),
+                    None if item.text.is_empty() => None,
+                    None => Some(item.text.clone()),
+                };
+            }
+        }
+
+        #[inline]
+        fn recurse_indices(&self) -> ChildIndices {
+            ChildIndices::range(0..self.items.len() + 1)
+        }
+
+        fn handle_messages(&mut self, cx: &mut EventCx, data: &A) {
+            if let Some(Overflowed) = cx.try_pop() {
+                self.overflow.set_menu_path(cx, data, None, false);
+                let list = self.items[self.visible..]
+                    .iter()
+                    .map(|item| (item.menu_fn)())
+                    .collect();
+                self.overflow = Self::overflow_menu(self.direction, list);
+                let id = self.make_child_id(0);
+                cx.configure(self.overflow.as_node(data), id);
+                // This message is sent from set_rect: the new menu must be sized
+                cx.resize();
+            }
+        }
+    }
+
+    impl Widget for Self {
+        type Data = A;
+
+        fn child_node<'n>(&'n mut self, data: &'n A, index: usize) -> Option<Node<'n>> {
+            if index == 0 {
+                Some(self.overflow.as_node(data))
+            } else {
+                self.items.get_mut(index - 1).map(|w| w.as_node(data))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn bar(sizes: &[i32]) -> ToolBar<()> {
+        let mut bar = ToolBar::new();
+        bar.rules = sizes
+            .iter()
+            .map(|size| SizeRules::fixed(*size).with_margins((4, 4)))
+            .collect();
+        bar.overflow_rules = SizeRules::fixed(20).with_margins((4, 4));
+        bar
+    }
+
+    #[test]
+    fn partition() {
+        // Items end at 30, 64 and 98
+        let bar = bar(&[30, 30, 30]);
+        assert_eq!(bar.partition(120), (3, 0));
+        assert_eq!(bar.partition(98), (3, 0));
+        // Space is reserved for the overflow menu and its margin
+        assert_eq!(bar.partition(97), (2, 20));
+        assert_eq!(bar.partition(88), (2, 20));
+        assert_eq!(bar.partition(87), (1, 20));
+        assert_eq!(bar.partition(20), (0, 20));
+        assert_eq!(bar.partition(0), (0, 20));
+    }
+
+    #[test]
+    fn partition_margins() {
+        // Margins between items are the larger of adjacent margins
+        let mut bar = bar(&[10, 10]);
+        bar.rules[1] = SizeRules::fixed(10).with_margins((8, 0));
+        assert_eq!(bar.partition(28), (2, 0));
+        assert_eq!(bar.partition(27), (0, 20));
+
+        assert_eq!(ToolBar::<()>::new().partition(0), (0, 0));
+    }
+}
@@ -17,6 +17,8 @@ enum EditorAction {
     Open,
     Save,
     SaveAs,
+    FoldAll,
+    UnfoldAll,
 }
 
 #[derive(Debug)]
@@ -37,6 +39,8 @@ fn menus() -> impl Widget<Data = ()> {
         Button::label_msg("&Open", EditorAction::Open),
         Button::label_msg("&Save", EditorAction::Save),
         Button::label_msg("Save&As", EditorAction::SaveAs),
+        Button::label_msg("&Fold all", EditorAction::FoldAll),
+        Button::label_msg("&Unfold all", EditorAction::UnfoldAll),
         Filler::low()
     ]
 }
@@ -164,6 +168,7 @@ mod Editor {
                 editor: EditBox::new(Guard::default())
                     .with_highlighter(SyntectHighlighter::new_plain())
                     .with_multi_line(true)
+                    .with_folding(true)
                     .with_lines(5.0, 20.0)
                     .with_width_em(10.0, 30.0),
                 pending: None,
@@ -204,6 +209,8 @@ mod Editor {
                         cx.send_async(self.id(), async { SaveFile(picker.save_file().await) });
                     }
                 }
+                EditorAction::FoldAll => cx.send(self.editor.id(), edit::FoldAll),
+                EditorAction::UnfoldAll => cx.send(self.editor.id(), edit::UnfoldAll),
             }
         }
     }
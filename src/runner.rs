@@ -12,7 +12,9 @@ use crate::config::{AutoFactory, Config, ConfigFactory};
 use crate::draw::DrawSharedImpl;
 use crate::theme::Theme;
 use crate::window::{Window, WindowId};
-pub use kas_core::runner::{AppData, ClosedError, Error, Platform, Proxy, ReadMessage, Result};
+pub use kas_core::runner::{
+    AppData, Clock, ClosedError, Error, Platform, Proxy, ReadMessage, Result,
+};
 use kas_core::runner::{GraphicsInstance, PreLaunchState};
 #[allow(unused)]
 use kas_core::theme::{FlatTheme, SimpleTheme};
@@ -322,6 +324,22 @@ where
         self.state.create_proxy()
     }
 
+    /// Access the clock
+    #[inline]
+    pub fn clock(&self) -> &Clock {
+        self.state.clock()
+    }
+
+    /// Replace the clock, inline
+    ///
+    /// By default the runner uses the system time. Tests may supply a manual
+    /// clock ([`Clock::new_manual`]), retaining a clone to advance time.
+    #[inline]
+    pub fn with_clock(mut self, clock: Clock) -> Self {
+        self.state.set_clock(clock);
+        self
+    }
+
     /// Run the main loop.
     #[inline]
     pub fn run(self) -> Result<()> {